@@ -24,6 +24,8 @@ use reth_primitives::{
     EthereumHardfork, Hardforks, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader, StaticFileSegment,
 };
+#[cfg(feature = "telos")]
+use reth_provider::TelosExtraFieldsWriter;
 use reth_provider::{providers::ProviderNodeTypes, BlockExecutionWriter, BlockNumReader, BlockReader, BlockWriter, CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications, ChainSpecProvider, ChainSplit, ChainSplitTarget, DisplayBlocksChain, HeaderProvider, ProviderError, StaticFileProviderFactory};
 use reth_stages_api::{MetricEvent, MetricEventsSender};
 #[cfg(not(feature = "telos"))]
@@ -37,6 +39,8 @@ use std::{
     collections::{btree_map::Entry, BTreeMap, HashSet},
     sync::Arc,
 };
+#[cfg(feature = "telos")]
+use std::collections::HashMap;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
@@ -78,6 +82,12 @@ pub struct BlockchainTree<N: NodeTypesWithDB, E> {
     sync_metrics_tx: Option<MetricEventsSender>,
     /// Metrics for the blockchain tree.
     metrics: TreeMetrics,
+    /// Telos engine API extra fields of the non-finalized blocks, by block hash.
+    ///
+    /// Kept until the block is finalized so that buffered and re-executed blocks can be executed
+    /// with them, and written to the database once their block is committed.
+    #[cfg(feature = "telos")]
+    telos_extra_fields: HashMap<BlockHash, (BlockNumber, TelosEngineAPIExtraFields)>,
 }
 
 impl<N: NodeTypesWithDB, E> BlockchainTree<N, E> {
@@ -147,6 +157,8 @@ where
             canon_state_notification_sender,
             sync_metrics_tx: None,
             metrics: Default::default(),
+            #[cfg(feature = "telos")]
+            telos_extra_fields: Default::default(),
         })
     }
 
@@ -330,6 +342,15 @@ where
     ) -> Result<BlockStatus, InsertBlockErrorKind> {
         debug_assert!(self.validate_block(&block).is_ok(), "Block must be validated");
 
+        #[cfg(feature = "telos")]
+        let telos_extra_fields = match telos_extra_fields {
+            Some(fields) => {
+                self.telos_extra_fields.insert(block.hash(), (block.number, fields.clone()));
+                Some(fields)
+            }
+            None => self.telos_extra_fields.get(&block.hash()).map(|(_, fields)| fields.clone()),
+        };
+
         let parent = block.parent_num_hash();
 
         // check if block parent can be found in any side chain.
@@ -521,6 +542,8 @@ where
                 canonical_fork,
                 &self.externals,
                 block_validation_kind,
+                #[cfg(feature = "telos")]
+                telos_extra_fields,
             )?;
             self.insert_chain(chain);
             BlockAttachment::HistoricalFork
@@ -870,6 +893,8 @@ where
         }
         // clean block buffer.
         self.remove_old_blocks(finalized_block);
+        #[cfg(feature = "telos")]
+        self.telos_extra_fields.retain(|_, (number, _)| *number > finalized_block);
 
         // save finalized block in db.
         self.externals.save_finalized_block_number(finalized_block)?;
@@ -1343,6 +1368,15 @@ where
             )
             .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
 
+        #[cfg(feature = "telos")]
+        for (_, hash) in &block_hash_numbers {
+            if let Some((_, fields)) = self.telos_extra_fields.get(hash) {
+                provider_rw
+                    .insert_telos_extra_fields(*hash, fields)
+                    .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
+            }
+        }
+
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
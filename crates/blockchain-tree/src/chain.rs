@@ -117,6 +117,8 @@ impl AppendableChain {
         canonical_fork: ForkBlock,
         externals: &TreeExternals<N, E>,
         block_validation_kind: BlockValidationKind,
+        #[cfg(feature = "telos")]
+        telos_extra_fields: Option<TelosEngineAPIExtraFields>,
     ) -> Result<Self, InsertBlockErrorKind>
     where
         N: ProviderNodeTypes,
@@ -147,7 +149,8 @@ impl AppendableChain {
             externals,
             BlockAttachment::HistoricalFork,
             block_validation_kind,
-            #[cfg(feature = "telos")] None,
+            #[cfg(feature = "telos")]
+            telos_extra_fields,
         )?;
         // extending will also optimize few things, mostly related to selfdestruct and wiping of
         // storage.
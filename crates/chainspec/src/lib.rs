@@ -35,6 +35,8 @@ pub use spec::{
 };
 #[cfg(feature = "telos")]
 pub use spec::{TEVMMAINNET, TEVMTESTNET, TEVMMAINNET_BASE, TEVMTESTNET_BASE};
+#[cfg(feature = "telos")]
+pub use spec::{TELOS_FORK_HASH_SALT, TELOS_MAINNET_CHAIN_ID, TELOS_TESTNET_CHAIN_ID};
 
 /// Simple utility to create a `OnceCell` with a value set.
 pub fn once_cell_set<T>(value: T) -> once_cell::sync::OnceCell<T> {
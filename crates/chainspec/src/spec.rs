@@ -3,6 +3,8 @@ pub use alloy_eips::eip1559::BaseFeeParams;
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_chains::{Chain, NamedChain};
 use alloy_genesis::Genesis;
+#[cfg(feature = "telos")]
+use alloy_primitives::keccak256;
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
 use alloy_trie::EMPTY_ROOT_HASH;
 use derive_more::From;
@@ -16,6 +18,8 @@ use reth_network_peers::{
     sepolia_nodes, NodeRecord,
 };
 #[cfg(feature = "telos")]
+use reth_network_peers::{tevmmainnet_nodes, tevmtestnet_nodes};
+#[cfg(feature = "telos")]
 use reth_primitives_traits::constants::{
     TEVMMAINNET_GENESIS_HASH, TEVMTESTNET_GENESIS_HASH, TEVMMAINNET_BASE_GENESIS_HASH, TEVMTESTNET_BASE_GENESIS_HASH
 };
@@ -30,6 +34,19 @@ use reth_trie_common::root::state_root_ref_unhashed;
 
 use crate::{constants::MAINNET_DEPOSIT_CONTRACT, once_cell_set, EthChainSpec};
 
+/// Chain id of the Telos EVM mainnet.
+#[cfg(feature = "telos")]
+pub const TELOS_MAINNET_CHAIN_ID: u64 = 40;
+
+/// Chain id of the Telos EVM testnet.
+#[cfg(feature = "telos")]
+pub const TELOS_TESTNET_CHAIN_ID: u64 = 41;
+
+/// Salt mixed into the genesis hash of Telos chains before deriving fork hashes, see
+/// [`ChainSpec::fork_filter`].
+#[cfg(feature = "telos")]
+pub const TELOS_FORK_HASH_SALT: &[u8] = b"telos-evm";
+
 /// The Ethereum mainnet spec
 pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
     let mut spec = ChainSpec {
@@ -591,12 +608,30 @@ impl ChainSpec {
             })
         });
 
-        ForkFilter::new(head, self.genesis_hash(), self.genesis_timestamp(), forks)
+        ForkFilter::new(head, self.fork_hash_genesis(), self.genesis_timestamp(), forks)
+    }
+
+    /// Returns the hash the fork hashes of this chain are derived from.
+    ///
+    /// This is the genesis hash, mixed with [`TELOS_FORK_HASH_SALT`] on Telos chains: Telos peers
+    /// send headers along with their Telos block extension and must only pair with each other.
+    fn fork_hash_genesis(&self) -> B256 {
+        #[cfg(feature = "telos")]
+        if self.is_telos() {
+            return keccak256([self.genesis_hash().as_slice(), TELOS_FORK_HASH_SALT].concat())
+        }
+        self.genesis_hash()
+    }
+
+    /// Returns `true` if this is a Telos EVM chain.
+    #[cfg(feature = "telos")]
+    pub fn is_telos(&self) -> bool {
+        matches!(self.chain.id(), TELOS_MAINNET_CHAIN_ID | TELOS_TESTNET_CHAIN_ID)
     }
 
     /// Compute the [`ForkId`] for the given [`Head`] following eip-6122 spec
     pub fn fork_id(&self, head: &Head) -> ForkId {
-        let mut forkhash = ForkHash::from(self.genesis_hash());
+        let mut forkhash = ForkHash::from(self.fork_hash_genesis());
         let mut current_applied = 0;
 
         // handle all block forks before handling timestamp based forks. see: https://eips.ethereum.org/EIPS/eip-6122
@@ -708,6 +743,12 @@ impl ChainSpec {
     pub fn bootnodes(&self) -> Option<Vec<NodeRecord>> {
         use NamedChain as C;
         let chain = self.chain;
+        #[cfg(feature = "telos")]
+        match chain.id() {
+            TELOS_MAINNET_CHAIN_ID => return Some(tevmmainnet_nodes()),
+            TELOS_TESTNET_CHAIN_ID => return Some(tevmtestnet_nodes()),
+            _ => {}
+        }
         match chain.try_into().ok()? {
             C::Mainnet => Some(mainnet_nodes()),
             C::Sepolia => Some(sepolia_nodes()),
@@ -1828,6 +1869,19 @@ Post-merge hard forks (timestamp based):
         test_fork_ids(&TEVMTESTNET, &[]);
     }
 
+    #[test]
+    #[cfg(feature = "telos")]
+    fn tevm_fork_hash_is_salted() {
+        for spec in [&TEVMMAINNET_BASE, &TEVMTESTNET_BASE] {
+            let head =
+                Head { number: spec.genesis.number.unwrap_or_default(), ..Default::default() };
+            let fork_id = spec.fork_id(&head);
+            let unsalted = ChainSpec { chain: Chain::mainnet(), ..(***spec).clone() };
+            assert_ne!(fork_id.hash, unsalted.fork_id(&head).hash);
+            assert!(spec.fork_filter(head).validate(fork_id).is_ok());
+        }
+    }
+
     #[test]
     #[cfg(feature = "telos")]
     fn tevmmainnet_base_forkids() {
@@ -79,11 +79,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Environmen
             config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
         }
 
+        config.static_files.compression.validate().map_err(|err| eyre::eyre!(err))?;
+
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let (db, sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
+                StaticFileProvider::read_write(sf_path)?
+                    .with_compression(config.static_files.compression.clone()),
             ),
             AccessRights::RO => (
                 Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
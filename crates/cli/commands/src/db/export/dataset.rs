@@ -0,0 +1,348 @@
+use super::writer::{Column, ColumnType, Row, Value};
+use alloy_primitives::{BlockNumber, TxNumber};
+use reth_chainspec::EthereumHardforks;
+use reth_db_api::{
+    models::{BlockNumberAddress, StoredBlockBodyIndices},
+    transaction::DbTx,
+};
+use reth_provider::{
+    BlockReader, DatabaseProvider, HeaderProvider, ProviderError, ProviderResult, ReceiptProvider,
+    TransactionsProvider,
+};
+use std::ops::{Range, RangeInclusive};
+
+/// Dataset that can be exported by `reth db export`.
+///
+/// Every dataset has a fixed set of columns, see [`Dataset::columns`], so that files produced by
+/// different runs can be read with the same schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Dataset {
+    /// Block headers together with their hashes.
+    Headers,
+    /// Transactions with their recovered senders.
+    Transactions,
+    /// Transaction receipts.
+    Receipts,
+    /// Logs emitted by transactions, one row per log.
+    Logs,
+    /// Account state before each block that changed it.
+    AccountChangesets,
+    /// Storage slot values before each block that changed them.
+    StorageChangesets,
+}
+
+const HEADERS_COLUMNS: &[Column] = &[
+    Column::new("number", ColumnType::UInt64),
+    Column::new("hash", ColumnType::Binary),
+    Column::new("parent_hash", ColumnType::Binary),
+    Column::new("beneficiary", ColumnType::Binary),
+    Column::new("state_root", ColumnType::Binary),
+    Column::new("transactions_root", ColumnType::Binary),
+    Column::new("receipts_root", ColumnType::Binary),
+    Column::new("timestamp", ColumnType::UInt64),
+    Column::new("gas_limit", ColumnType::UInt64),
+    Column::new("gas_used", ColumnType::UInt64),
+    Column::nullable("base_fee_per_gas", ColumnType::UInt64),
+    Column::new("difficulty", ColumnType::Utf8),
+    Column::new("extra_data", ColumnType::Binary),
+];
+
+const TRANSACTIONS_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnType::UInt64),
+    Column::new("transaction_index", ColumnType::UInt64),
+    Column::new("tx_number", ColumnType::UInt64),
+    Column::new("hash", ColumnType::Binary),
+    Column::new("type", ColumnType::UInt64),
+    Column::new("from", ColumnType::Binary),
+    Column::nullable("to", ColumnType::Binary),
+    Column::new("nonce", ColumnType::UInt64),
+    Column::new("value", ColumnType::Utf8),
+    Column::new("gas_limit", ColumnType::UInt64),
+    Column::new("input", ColumnType::Binary),
+];
+
+const RECEIPTS_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnType::UInt64),
+    Column::new("transaction_index", ColumnType::UInt64),
+    Column::new("tx_number", ColumnType::UInt64),
+    Column::new("type", ColumnType::UInt64),
+    Column::new("success", ColumnType::Bool),
+    Column::new("cumulative_gas_used", ColumnType::UInt64),
+    Column::new("logs_count", ColumnType::UInt64),
+];
+
+const LOGS_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnType::UInt64),
+    Column::new("transaction_index", ColumnType::UInt64),
+    Column::new("tx_number", ColumnType::UInt64),
+    Column::new("log_index", ColumnType::UInt64),
+    Column::new("address", ColumnType::Binary),
+    Column::nullable("topic0", ColumnType::Binary),
+    Column::nullable("topic1", ColumnType::Binary),
+    Column::nullable("topic2", ColumnType::Binary),
+    Column::nullable("topic3", ColumnType::Binary),
+    Column::new("data", ColumnType::Binary),
+];
+
+const ACCOUNT_CHANGESETS_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnType::UInt64),
+    Column::new("address", ColumnType::Binary),
+    Column::nullable("nonce", ColumnType::UInt64),
+    Column::nullable("balance", ColumnType::Utf8),
+    Column::nullable("bytecode_hash", ColumnType::Binary),
+];
+
+const STORAGE_CHANGESETS_COLUMNS: &[Column] = &[
+    Column::new("block_number", ColumnType::UInt64),
+    Column::new("address", ColumnType::Binary),
+    Column::new("key", ColumnType::Binary),
+    Column::new("value", ColumnType::Utf8),
+];
+
+impl Dataset {
+    /// Returns the name of the dataset, used for output directories and file names.
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Headers => "headers",
+            Self::Transactions => "transactions",
+            Self::Receipts => "receipts",
+            Self::Logs => "logs",
+            Self::AccountChangesets => "account-changesets",
+            Self::StorageChangesets => "storage-changesets",
+        }
+    }
+
+    /// Returns the columns of the dataset.
+    pub(crate) const fn columns(&self) -> &'static [Column] {
+        match self {
+            Self::Headers => HEADERS_COLUMNS,
+            Self::Transactions => TRANSACTIONS_COLUMNS,
+            Self::Receipts => RECEIPTS_COLUMNS,
+            Self::Logs => LOGS_COLUMNS,
+            Self::AccountChangesets => ACCOUNT_CHANGESETS_COLUMNS,
+            Self::StorageChangesets => STORAGE_CHANGESETS_COLUMNS,
+        }
+    }
+
+    /// Reads the rows of the dataset for the given block range.
+    ///
+    /// Static files and the database are both read through the [`DatabaseProvider`], so the
+    /// result doesn't depend on where the data currently lives.
+    pub(crate) fn read_rows<TX: DbTx, Spec: Send + Sync + EthereumHardforks>(
+        &self,
+        provider: &DatabaseProvider<TX, Spec>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Row>> {
+        match self {
+            Self::Headers => header_rows(provider, range),
+            Self::Transactions => transaction_rows(provider, range),
+            Self::Receipts | Self::Logs => receipt_rows(provider, range, *self == Self::Logs),
+            Self::AccountChangesets => Ok(provider
+                .account_changesets_range(range)?
+                .into_iter()
+                .map(|(block_number, account_before)| {
+                    let info = account_before.info;
+                    vec![
+                        Value::UInt64(block_number),
+                        Value::Binary(account_before.address.to_vec()),
+                        info.map_or(Value::Null, |info| Value::UInt64(info.nonce)),
+                        info.map_or(Value::Null, |info| Value::Utf8(info.balance.to_string())),
+                        info.and_then(|info| info.bytecode_hash)
+                            .map_or(Value::Null, |hash| Value::Binary(hash.to_vec())),
+                    ]
+                })
+                .collect()),
+            Self::StorageChangesets => Ok(provider
+                .storage_changesets_range(BlockNumberAddress::range(range))?
+                .into_iter()
+                .map(|(BlockNumberAddress((block_number, address)), entry)| {
+                    vec![
+                        Value::UInt64(block_number),
+                        Value::Binary(address.to_vec()),
+                        Value::Binary(entry.key.to_vec()),
+                        Value::Utf8(entry.value.to_string()),
+                    ]
+                })
+                .collect()),
+        }
+    }
+}
+
+impl std::fmt::Display for Dataset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn header_rows<TX: DbTx, Spec: Send + Sync + EthereumHardforks>(
+    provider: &DatabaseProvider<TX, Spec>,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<Row>> {
+    Ok(provider
+        .sealed_headers_range(range)?
+        .into_iter()
+        .map(|header| {
+            vec![
+                Value::UInt64(header.number),
+                Value::Binary(header.hash().to_vec()),
+                Value::Binary(header.parent_hash.to_vec()),
+                Value::Binary(header.beneficiary.to_vec()),
+                Value::Binary(header.state_root.to_vec()),
+                Value::Binary(header.transactions_root.to_vec()),
+                Value::Binary(header.receipts_root.to_vec()),
+                Value::UInt64(header.timestamp),
+                Value::UInt64(header.gas_limit),
+                Value::UInt64(header.gas_used),
+                header.base_fee_per_gas.map_or(Value::Null, Value::UInt64),
+                Value::Utf8(header.difficulty.to_string()),
+                Value::Binary(header.extra_data.to_vec()),
+            ]
+        })
+        .collect())
+}
+
+fn transaction_rows<TX: DbTx, Spec: Send + Sync + EthereumHardforks>(
+    provider: &DatabaseProvider<TX, Spec>,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<Row>> {
+    let body_indices = block_body_indices(provider, range)?;
+    let Some(tx_range) = tx_range(&body_indices) else { return Ok(Vec::new()) };
+
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    // Senders are missing if they were pruned, in which case they're recovered from signatures.
+    let senders = provider.senders_by_tx_range(tx_range)?;
+    let senders = (senders.len() == transactions.len()).then_some(senders);
+
+    transaction_positions(&body_indices)
+        .zip(transactions)
+        .enumerate()
+        .map(|(index, ((block_number, transaction_index, tx_number), transaction))| {
+            let transaction = transaction.with_hash();
+            let sender = match &senders {
+                Some(senders) => senders[index],
+                None => transaction
+                    .recover_signer_unchecked()
+                    .ok_or(ProviderError::SenderRecoveryError)?,
+            };
+
+            Ok(vec![
+                Value::UInt64(block_number),
+                Value::UInt64(transaction_index),
+                Value::UInt64(tx_number),
+                Value::Binary(transaction.hash().to_vec()),
+                Value::UInt64(u8::from(transaction.tx_type()).into()),
+                Value::Binary(sender.to_vec()),
+                transaction.to().map_or(Value::Null, |to| Value::Binary(to.to_vec())),
+                Value::UInt64(transaction.nonce()),
+                Value::Utf8(transaction.value().to_string()),
+                Value::UInt64(transaction.gas_limit()),
+                Value::Binary(transaction.input().to_vec()),
+            ])
+        })
+        .collect()
+}
+
+fn receipt_rows<TX: DbTx, Spec: Send + Sync + EthereumHardforks>(
+    provider: &DatabaseProvider<TX, Spec>,
+    range: RangeInclusive<BlockNumber>,
+    logs: bool,
+) -> ProviderResult<Vec<Row>> {
+    let body_indices = block_body_indices(provider, range)?;
+    let Some(tx_range) = tx_range(&body_indices) else { return Ok(Vec::new()) };
+
+    let receipts = provider.receipts_by_tx_range(tx_range)?;
+
+    let mut rows = Vec::with_capacity(receipts.len());
+    for ((block_number, transaction_index, tx_number), receipt) in
+        transaction_positions(&body_indices).zip(receipts)
+    {
+        let position = [
+            Value::UInt64(block_number),
+            Value::UInt64(transaction_index),
+            Value::UInt64(tx_number),
+        ];
+
+        if !logs {
+            rows.push(
+                position
+                    .into_iter()
+                    .chain([
+                        Value::UInt64(u8::from(receipt.tx_type).into()),
+                        Value::Bool(receipt.success),
+                        Value::UInt64(receipt.cumulative_gas_used),
+                        Value::UInt64(receipt.logs.len() as u64),
+                    ])
+                    .collect(),
+            );
+            continue
+        }
+
+        for (log_index, log) in receipt.logs.into_iter().enumerate() {
+            let topics = log.data.topics();
+            rows.push(
+                position
+                    .iter()
+                    .cloned()
+                    .chain([Value::UInt64(log_index as u64), Value::Binary(log.address.to_vec())])
+                    .chain((0..4).map(|index| {
+                        topics.get(index).map_or(Value::Null, |topic| Value::Binary(topic.to_vec()))
+                    }))
+                    .chain([Value::Binary(log.data.data.to_vec())])
+                    .collect(),
+            );
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Returns the body indices of all blocks in the range.
+fn block_body_indices<TX: DbTx, Spec: Send + Sync + EthereumHardforks>(
+    provider: &DatabaseProvider<TX, Spec>,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+    range
+        .map(|block_number| {
+            let body_indices = provider
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+            Ok((block_number, body_indices))
+        })
+        .collect()
+}
+
+/// Returns the range of transactions spanned by the blocks, or `None` if there are none.
+fn tx_range(body_indices: &[(BlockNumber, StoredBlockBodyIndices)]) -> Option<Range<TxNumber>> {
+    let (_, first) = body_indices.first()?;
+    let (_, last) = body_indices.last()?;
+    let range = first.first_tx_num..last.next_tx_num();
+    (!range.is_empty()).then_some(range)
+}
+
+/// Returns the block number, index in the block and transaction number of every transaction in
+/// the blocks.
+fn transaction_positions(
+    body_indices: &[(BlockNumber, StoredBlockBodyIndices)],
+) -> impl Iterator<Item = (BlockNumber, u64, TxNumber)> + '_ {
+    body_indices.iter().flat_map(|(block_number, body_indices)| {
+        body_indices
+            .tx_num_range()
+            .map(move |tx_number| (*block_number, tx_number - body_indices.first_tx_num, tx_number))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+    use std::collections::HashSet;
+
+    #[test]
+    fn column_names_are_unique() {
+        for dataset in Dataset::value_variants() {
+            let columns = dataset.columns();
+            let names = columns.iter().map(|column| column.name).collect::<HashSet<_>>();
+            assert_eq!(names.len(), columns.len(), "{dataset}");
+        }
+    }
+}
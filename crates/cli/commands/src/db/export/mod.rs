@@ -0,0 +1,179 @@
+//! `reth db export` command for bulk extracts of chain data.
+//!
+//! Every [`Dataset`] is written into its own subdirectory of the output directory, split into
+//! files of `--blocks-per-file` blocks named `<dataset>_<first block>_<last block>.<extension>`.
+//! Files are produced in parallel, each by a reader opening a fresh read-only transaction for
+//! every batch of blocks.
+
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use rayon::prelude::*;
+use reth_db_common::DbTool;
+use reth_provider::{providers::ProviderNodeTypes, BlockNumReader, ProviderFactory};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tracing::info;
+
+mod dataset;
+pub use dataset::Dataset;
+
+mod writer;
+pub use writer::ExportFormat;
+use writer::ExportWriter;
+
+/// Default number of blocks written to a single file.
+const DEFAULT_BLOCKS_PER_FILE: u64 = 100_000;
+
+/// Default number of blocks read from the database at once.
+const DEFAULT_BATCH_SIZE: u64 = 1_000;
+
+/// The arguments for the `reth db export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The datasets to export.
+    #[arg(value_enum, required = true, num_args = 1..)]
+    datasets: Vec<Dataset>,
+
+    /// The directory to write the exported files to.
+    #[arg(long, short, value_name = "DIR")]
+    output: PathBuf,
+
+    /// The format of the exported files.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Parquet)]
+    format: ExportFormat,
+
+    /// The first block to export.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export. Defaults to the highest fully synced block.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The number of blocks written to a single file.
+    #[arg(long, default_value_t = DEFAULT_BLOCKS_PER_FILE)]
+    blocks_per_file: u64,
+
+    /// The number of blocks read from the database at once.
+    #[arg(long, default_value_t = DEFAULT_BATCH_SIZE)]
+    batch_size: u64,
+
+    /// The number of files written in parallel. Defaults to the number of CPUs.
+    #[arg(long)]
+    jobs: Option<usize>,
+}
+
+impl Command {
+    /// Execute `db export` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        eyre::ensure!(self.blocks_per_file > 0, "--blocks-per-file must be greater than zero");
+        eyre::ensure!(self.batch_size > 0, "--batch-size must be greater than zero");
+
+        let to = match self.to {
+            Some(to) => to,
+            None => tool.provider_factory.provider()?.best_block_number()?,
+        };
+        eyre::ensure!(self.from <= to, "Block range {}..={to} is empty", self.from);
+
+        for dataset in &self.datasets {
+            reth_fs_util::create_dir_all(self.output.join(dataset.as_str()))?;
+        }
+
+        let files = self
+            .datasets
+            .iter()
+            .flat_map(|dataset| {
+                file_ranges(self.from..=to, self.blocks_per_file).map(|range| (*dataset, range))
+            })
+            .collect::<Vec<_>>();
+
+        info!(
+            target: "reth::cli",
+            datasets = ?self.datasets,
+            from = self.from,
+            %to,
+            files = files.len(),
+            format = ?self.format,
+            "Exporting"
+        );
+        let start = Instant::now();
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(self.jobs.unwrap_or(0)).build()?;
+        let rows = pool.install(|| {
+            files
+                .into_par_iter()
+                .map(|(dataset, range)| {
+                    let path = self.output.join(dataset.as_str()).join(format!(
+                        "{dataset}_{}_{}.{}",
+                        range.start(),
+                        range.end(),
+                        self.format.extension()
+                    ));
+                    self.export_file(&tool.provider_factory, dataset, range, &path)
+                })
+                .try_reduce(|| 0, |a, b| Ok(a + b))
+        })?;
+
+        info!(
+            target: "reth::cli",
+            rows,
+            elapsed = ?start.elapsed(),
+            output = %self.output.display(),
+            "Export finished"
+        );
+
+        Ok(())
+    }
+
+    /// Exports a single file of the dataset, returning the number of written rows.
+    fn export_file<N: ProviderNodeTypes>(
+        &self,
+        provider_factory: &ProviderFactory<N>,
+        dataset: Dataset,
+        range: RangeInclusive<BlockNumber>,
+        path: &Path,
+    ) -> eyre::Result<usize> {
+        let mut writer = ExportWriter::new(self.format, path, dataset.columns())?;
+        let mut rows = 0;
+
+        for batch in file_ranges(range, self.batch_size) {
+            // Open a new read-only transaction for every batch to prevent long-lived transactions
+            let provider = provider_factory.provider()?;
+            let batch_rows = dataset.read_rows(&provider, batch)?;
+            rows += batch_rows.len();
+            writer.write_rows(&batch_rows)?;
+        }
+
+        writer.finish()?;
+        info!(target: "reth::cli", %dataset, rows, path = %path.display(), "Exported file");
+
+        Ok(rows)
+    }
+}
+
+/// Splits the block range into consecutive ranges of at most `size` blocks.
+fn file_ranges(
+    range: RangeInclusive<BlockNumber>,
+    size: u64,
+) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    let end = *range.end();
+    range.step_by(size as usize).map(move |start| start..=start.saturating_add(size - 1).min(end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_file_ranges() {
+        assert_eq!(file_ranges(0..=9, 4).collect::<Vec<_>>(), vec![0..=3, 4..=7, 8..=9]);
+        assert_eq!(file_ranges(5..=5, 4).collect::<Vec<_>>(), vec![5..=5]);
+        assert_eq!(
+            file_ranges(0..=u64::MAX, u64::MAX).take(2).collect::<Vec<_>>(),
+            vec![0..=u64::MAX - 1, u64::MAX..=u64::MAX]
+        );
+    }
+}
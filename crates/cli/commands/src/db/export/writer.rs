@@ -0,0 +1,312 @@
+use alloy_primitives::hex;
+use arrow::{
+    array::{ArrayRef, BinaryArray, BooleanArray, StringArray, UInt64Array},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+/// Output file format of `reth db export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Apache Parquet, compressed with zstd.
+    Parquet,
+    /// Newline-delimited JSON, one object per row.
+    Jsonl,
+}
+
+impl ExportFormat {
+    /// Returns the file extension for the format.
+    pub(crate) const fn extension(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// Type of an exported column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnType {
+    /// Unsigned 64-bit integer.
+    UInt64,
+    /// Boolean.
+    Bool,
+    /// Raw bytes. Written as `0x`-prefixed hex strings to JSONL.
+    Binary,
+    /// UTF-8 string. Used for 256-bit integers, which are written in decimal.
+    Utf8,
+}
+
+/// A column of an exported dataset.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Column {
+    pub(crate) name: &'static str,
+    pub(crate) ty: ColumnType,
+    pub(crate) nullable: bool,
+}
+
+impl Column {
+    pub(crate) const fn new(name: &'static str, ty: ColumnType) -> Self {
+        Self { name, ty, nullable: false }
+    }
+
+    pub(crate) const fn nullable(name: &'static str, ty: ColumnType) -> Self {
+        Self { name, ty, nullable: true }
+    }
+}
+
+/// A single value of an exported row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value {
+    Null,
+    UInt64(u64),
+    Bool(bool),
+    Binary(Vec<u8>),
+    Utf8(String),
+}
+
+impl Value {
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::UInt64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn as_binary(&self) -> Option<&[u8]> {
+        match self {
+            Self::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Utf8(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Null => serde_json::Value::Null,
+            Self::UInt64(value) => (*value).into(),
+            Self::Bool(value) => (*value).into(),
+            Self::Binary(value) => hex::encode_prefixed(value).into(),
+            Self::Utf8(value) => value.clone().into(),
+        }
+    }
+}
+
+/// A row of an exported dataset, with one value per column.
+pub(crate) type Row = Vec<Value>;
+
+/// Writes rows of a single dataset into a file of the chosen [`ExportFormat`].
+#[derive(Debug)]
+pub(crate) enum ExportWriter {
+    Parquet { writer: ArrowWriter<File>, schema: SchemaRef, columns: &'static [Column] },
+    Jsonl { writer: BufWriter<File>, columns: &'static [Column] },
+}
+
+impl ExportWriter {
+    /// Creates a new file at `path` for the dataset with the given columns.
+    pub(crate) fn new(
+        format: ExportFormat,
+        path: &Path,
+        columns: &'static [Column],
+    ) -> eyre::Result<Self> {
+        let file = File::create(path)?;
+        Ok(match format {
+            ExportFormat::Parquet => {
+                let schema = Arc::new(schema(columns));
+                let properties = WriterProperties::builder()
+                    .set_compression(Compression::ZSTD(ZstdLevel::default()))
+                    .build();
+                let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))?;
+                Self::Parquet { writer, schema, columns }
+            }
+            ExportFormat::Jsonl => Self::Jsonl { writer: BufWriter::new(file), columns },
+        })
+    }
+
+    /// Appends rows to the file.
+    pub(crate) fn write_rows(&mut self, rows: &[Row]) -> eyre::Result<()> {
+        if rows.is_empty() {
+            return Ok(())
+        }
+
+        match self {
+            Self::Parquet { writer, schema, columns } => {
+                let arrays = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| column_array(column.ty, rows, index))
+                    .collect();
+                writer.write(&RecordBatch::try_new(schema.clone(), arrays)?)?;
+            }
+            Self::Jsonl { writer, columns } => {
+                for row in rows {
+                    serde_json::to_writer(&mut *writer, &JsonRow { columns, row })?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flushes all buffered rows and closes the file.
+    pub(crate) fn finish(self) -> eyre::Result<()> {
+        match self {
+            Self::Parquet { writer, .. } => {
+                writer.close()?;
+            }
+            Self::Jsonl { mut writer, .. } => writer.flush()?,
+        }
+
+        Ok(())
+    }
+}
+
+/// JSON object of a row, with keys in column order.
+struct JsonRow<'a> {
+    columns: &'a [Column],
+    row: &'a Row,
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (column, value) in self.columns.iter().zip(self.row) {
+            map.serialize_entry(column.name, &value.to_json())?;
+        }
+        map.end()
+    }
+}
+
+/// Returns the Arrow schema of the dataset with the given columns.
+pub(crate) fn schema(columns: &[Column]) -> Schema {
+    Schema::new(
+        columns
+            .iter()
+            .map(|column| {
+                let data_type = match column.ty {
+                    ColumnType::UInt64 => DataType::UInt64,
+                    ColumnType::Bool => DataType::Boolean,
+                    ColumnType::Binary => DataType::Binary,
+                    ColumnType::Utf8 => DataType::Utf8,
+                };
+                Field::new(column.name, data_type, column.nullable)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Collects the values at `index` of all rows into an Arrow array.
+fn column_array(ty: ColumnType, rows: &[Row], index: usize) -> ArrayRef {
+    match ty {
+        ColumnType::UInt64 => {
+            Arc::new(rows.iter().map(|row| row[index].as_u64()).collect::<UInt64Array>())
+        }
+        ColumnType::Bool => {
+            Arc::new(rows.iter().map(|row| row[index].as_bool()).collect::<BooleanArray>())
+        }
+        ColumnType::Binary => {
+            Arc::new(rows.iter().map(|row| row[index].as_binary()).collect::<BinaryArray>())
+        }
+        ColumnType::Utf8 => {
+            Arc::new(rows.iter().map(|row| row[index].as_str()).collect::<StringArray>())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::AsArray;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    const COLUMNS: &[Column] = &[
+        Column::new("number", ColumnType::UInt64),
+        Column::nullable("hash", ColumnType::Binary),
+        Column::new("success", ColumnType::Bool),
+        Column::new("value", ColumnType::Utf8),
+    ];
+
+    fn rows() -> Vec<Row> {
+        vec![
+            vec![
+                Value::UInt64(1),
+                Value::Binary(vec![0xab, 0xcd]),
+                Value::Bool(true),
+                Value::Utf8("10".to_string()),
+            ],
+            vec![Value::UInt64(2), Value::Null, Value::Bool(false), Value::Utf8("20".to_string())],
+        ]
+    }
+
+    #[test]
+    fn write_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.jsonl");
+
+        let mut writer = ExportWriter::new(ExportFormat::Jsonl, &path, COLUMNS).unwrap();
+        writer.write_rows(&rows()).unwrap();
+        writer.finish().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"number":1,"hash":"0xabcd","success":true,"value":"10"}"#,
+                r#"{"number":2,"hash":null,"success":false,"value":"20"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn write_parquet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.parquet");
+
+        let mut writer = ExportWriter::new(ExportFormat::Parquet, &path, COLUMNS).unwrap();
+        writer.write_rows(&rows()).unwrap();
+        writer.finish().unwrap();
+
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap().build();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        assert_eq!(batch.schema().as_ref(), &schema(COLUMNS));
+        assert_eq!(
+            batch.column(0).as_primitive::<arrow::datatypes::UInt64Type>().values().to_vec(),
+            vec![1, 2]
+        );
+        let hashes = batch.column(1).as_binary::<i32>();
+        assert_eq!(hashes.value(0), &[0xab, 0xcd]);
+        assert!(hashes.is_null(1));
+        assert_eq!(batch.column(3).as_string::<i32>().value(1), "20");
+    }
+}
@@ -10,11 +10,15 @@ use std::io::{self, Write};
 mod checksum;
 mod clear;
 mod diff;
+mod export;
 mod get;
 mod list;
+mod restore;
+mod snapshot;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -39,6 +43,17 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports headers, transactions, receipts, logs and changesets over a block range to Parquet
+    /// or JSONL files
+    Export(export::Command),
+    /// Decodes all tables and static files and verifies invariants across them, writing the
+    /// findings to a JSON report
+    Verify(verify::Command),
+    /// Takes a consistent snapshot of the database and static files, e.g. for backups
+    Snapshot(snapshot::Command),
+    /// Restores a snapshot taken with `reth db snapshot` or the `admin_snapshot` RPC into an
+    /// empty datadir
+    Restore(restore::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -72,6 +87,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
 
+        // restoring a snapshot creates the database
+        if let Subcommands::Restore(command) = self.command {
+            return command.execute(&data_dir, self.env.chain.chain().id())
+        }
+
         // ensure the provided datadir exist
         eyre::ensure!(
             data_dir.data_dir().is_dir(),
@@ -109,6 +129,22 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Export(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Verify(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(data_dir, &tool)?;
+                });
+            }
+            Subcommands::Snapshot(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Restore(_) => unreachable!("snapshots are restored without a database"),
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
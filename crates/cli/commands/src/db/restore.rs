@@ -0,0 +1,44 @@
+use clap::Parser;
+use reth_db_common::snapshot::{restore_snapshot, RestoreConfig};
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `reth db restore` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory of the snapshot to restore.
+    #[arg(value_name = "DIR")]
+    dir: PathBuf,
+
+    /// Skip verifying the sizes and checksums of the snapshot files.
+    #[arg(long)]
+    skip_verify: bool,
+
+    /// Move the files out of the snapshot instead of copying them, leaving the snapshot unusable.
+    #[arg(long)]
+    move_files: bool,
+}
+
+impl Command {
+    /// Execute `db restore` command
+    pub fn execute(self, data_dir: &ChainPath<DataDirPath>, chain_id: u64) -> eyre::Result<()> {
+        let config = RestoreConfig { verify: !self.skip_verify, move_files: self.move_files };
+        let manifest = restore_snapshot(
+            &self.dir,
+            chain_id,
+            &data_dir.db(),
+            &data_dir.static_files(),
+            config,
+        )?;
+
+        info!(
+            target: "reth::cli",
+            datadir = %data_dir,
+            tip_number = manifest.tip_number,
+            tip_hash = %manifest.tip_hash,
+            "Snapshot restored"
+        );
+        Ok(())
+    }
+}
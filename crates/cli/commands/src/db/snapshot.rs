@@ -0,0 +1,52 @@
+use clap::Parser;
+use reth_db_common::{
+    snapshot::{create_snapshot, SnapshotConfig},
+    DbTool,
+};
+use reth_provider::providers::ProviderNodeTypes;
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `reth db snapshot` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the snapshot to. Must not exist or be empty.
+    #[arg(value_name = "DIR")]
+    dir: PathBuf,
+
+    /// Omit free pages from the database copy, which makes it smaller but slower to take.
+    #[arg(long)]
+    compact: bool,
+
+    /// Copy all static files instead of hard-linking the ones of finalized blocks.
+    ///
+    /// Hard links share their contents with the datadir. Static files of non-finalized blocks can
+    /// be modified by an unwind and are always copied.
+    #[arg(long)]
+    copy_static_files: bool,
+}
+
+impl Command {
+    /// Execute `db snapshot` command
+    ///
+    /// The node must not be running, use the `admin_snapshot` RPC to snapshot a running node.
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let config = SnapshotConfig {
+            compact: self.compact,
+            copy_static_files: self.copy_static_files,
+            pause_persistence: false,
+        };
+        let manifest = create_snapshot(&tool.provider_factory, &self.dir, config)?;
+
+        info!(
+            target: "reth::cli",
+            dir = %self.dir.display(),
+            tip_number = manifest.tip_number,
+            tip_hash = %manifest.tip_hash,
+            files = manifest.files.len(),
+            size = manifest.size(),
+            "Snapshot written"
+        );
+        Ok(())
+    }
+}
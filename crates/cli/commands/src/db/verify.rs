@@ -0,0 +1,96 @@
+use clap::Parser;
+use reth_db::Tables;
+use reth_db_common::{
+    verify::{DbVerifier, VerifyConfig, DEFAULT_MAX_FINDINGS},
+    DbTool,
+};
+use reth_fs_util as fs;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::providers::ProviderNodeTypes;
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `reth db verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The tables to decode. Defaults to all tables.
+    #[arg(long, value_delimiter = ',')]
+    tables: Vec<Tables>,
+
+    /// Skip decoding static files.
+    #[arg(long)]
+    skip_static_files: bool,
+
+    /// Skip verifying invariants across tables and static files.
+    #[arg(long)]
+    skip_invariants: bool,
+
+    /// Maximum number of entries, static file rows and blocks read per second.
+    #[arg(long, value_name = "ENTRIES_PER_SEC", value_parser = clap::value_parser!(u64).range(1..))]
+    rate_limit: Option<u64>,
+
+    /// Path of the JSON report. Defaults to `<DATADIR>/db-verify/report.json`.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Path of the file the progress is persisted to, so an interrupted run can be resumed.
+    /// Defaults to `<DATADIR>/db-verify/progress.json`.
+    #[arg(long, value_name = "PATH")]
+    progress: Option<PathBuf>,
+
+    /// Discard the progress of an interrupted run and start over.
+    #[arg(long)]
+    restart: bool,
+
+    /// Maximum number of findings included in the report.
+    #[arg(long, default_value_t = DEFAULT_MAX_FINDINGS)]
+    max_findings: usize,
+}
+
+impl Command {
+    /// Execute `db verify` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        data_dir: ChainPath<DataDirPath>,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<()> {
+        let report_path = self.report.unwrap_or_else(|| data_dir.db_verify().join("report.json"));
+        let config = VerifyConfig {
+            tables: (!self.tables.is_empty()).then_some(self.tables),
+            static_files: !self.skip_static_files,
+            invariants: !self.skip_invariants,
+            rate_limit: self.rate_limit,
+            progress_path: Some(
+                self.progress.unwrap_or_else(|| data_dir.db_verify().join("progress.json")),
+            ),
+            max_findings: self.max_findings,
+        };
+
+        let verifier = DbVerifier::new(tool.provider_factory.clone(), config);
+        if self.restart {
+            verifier.clear_progress()?;
+        }
+        let report = verifier.run()?;
+
+        if let Some(parent) = report_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write_json_file(&report_path, &report)?;
+
+        info!(
+            target: "reth::cli",
+            entries = report.entries.values().sum::<u64>(),
+            findings = report.total_findings,
+            report = %report_path.display(),
+            "Database verified"
+        );
+        eyre::ensure!(
+            report.is_ok(),
+            "Found {} inconsistencies, see {}",
+            report.total_findings,
+            report_path.display()
+        );
+
+        Ok(())
+    }
+}
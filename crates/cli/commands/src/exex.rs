@@ -0,0 +1,105 @@
+//! Execution extension utilities.
+
+use crate::common::EnvironmentArgs;
+use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_exex::Wal;
+use tracing::info;
+
+/// `reth exex` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth exex` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// ExEx write-ahead log utilities
+    #[command(subcommand)]
+    Wal(WalCommand<C>),
+}
+
+/// `reth exex wal` subcommands
+///
+/// The node must not be running while these commands are used.
+#[derive(Subcommand, Debug)]
+pub enum WalCommand<C: ChainSpecParser> {
+    /// Lists the WAL segments with their notifications, sizes and block ranges
+    Inspect {
+        #[command(flatten)]
+        env: EnvironmentArgs<C>,
+    },
+    /// Removes notifications whose highest block is at or below the given block
+    Prune {
+        #[command(flatten)]
+        env: EnvironmentArgs<C>,
+
+        /// The block to prune the WAL up to, inclusive.
+        #[arg(long, value_name = "BLOCK_NUMBER")]
+        to_block: u64,
+    },
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Wal(WalCommand::Inspect { env }) => {
+                let wal = open_wal(&env)?;
+
+                let mut table = ComfyTable::new();
+                table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+                table.set_header(["Segment", "Notifications", "Removed", "Block Range", "Size"]);
+
+                let (mut notifications, mut size) = (0, 0);
+                for segment in wal.segments() {
+                    notifications += segment.notifications;
+                    size += segment.size_bytes;
+
+                    let mut row = Row::new();
+                    row.add_cell(Cell::new(segment.first_id))
+                        .add_cell(Cell::new(segment.notifications))
+                        .add_cell(Cell::new(segment.written as usize - segment.notifications))
+                        .add_cell(Cell::new(
+                            segment
+                                .block_range
+                                .map(|range| format!("{}..={}", range.start(), range.end()))
+                                .unwrap_or_default(),
+                        ))
+                        .add_cell(Cell::new(human_bytes(segment.size_bytes as f64)));
+                    table.add_row(row);
+                }
+
+                let mut row = Row::new();
+                row.add_cell(Cell::new("Total"))
+                    .add_cell(Cell::new(notifications))
+                    .add_cell(Cell::new(""))
+                    .add_cell(Cell::new(""))
+                    .add_cell(Cell::new(human_bytes(size as f64)));
+                table.add_row(row);
+
+                println!("{table}");
+            }
+            Subcommands::Wal(WalCommand::Prune { env, to_block }) => {
+                let wal = open_wal(&env)?;
+                wal.prune(to_block)?;
+                info!(target: "reth::cli", ?to_block, "Pruned ExEx WAL");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn open_wal<C: ChainSpecParser<ChainSpec: EthChainSpec>>(
+    env: &EnvironmentArgs<C>,
+) -> eyre::Result<Wal> {
+    let path = env.datadir.clone().resolve_datadir(env.chain.chain()).exex_wal();
+    info!(target: "reth::cli", ?path, "Opening ExEx WAL");
+    Wal::new(path)
+}
@@ -2,13 +2,23 @@
 
 use crate::common::{AccessRights, Environment, EnvironmentArgs};
 use alloy_primitives::B256;
-use clap::Parser;
+#[cfg(feature = "telos")]
+use alloy_primitives::U256;
+#[cfg(feature = "telos")]
+use clap::Args;
+use clap::{Parser, ValueEnum};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_config::config::EtlConfig;
 use reth_db_common::init::init_from_state_dump;
+#[cfg(feature = "telos")]
+use reth_db_common::init::init_from_telos_snapshot;
 use reth_node_builder::NodeTypesWithEngine;
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+#[cfg(feature = "telos")]
+use reth_provider::{writer::UnifiedStorageWriter, StaticFileProviderFactory};
+#[cfg(feature = "telos")]
+use reth_telos_primitives_traits::TelosBlockExtension;
 
 use std::{fs::File, io::BufReader, path::PathBuf};
 use tracing::info;
@@ -36,8 +46,58 @@ pub struct InitStateCommand<C: ChainSpecParser> {
     ///
     /// Allows init at a non-genesis block. Caution! Blocks must be manually imported up until
     /// and including the non-genesis block to init chain at. See 'import' command.
+    ///
+    /// With '--format telos-snapshot' this is a directory with the 'account.jsonl',
+    /// 'accountstate.jsonl' and, if the contract stores code separately, 'accountcode.jsonl'
+    /// dumps of the 'eosio.evm' tables instead.
     #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
     pub state: PathBuf,
+
+    /// Format of the state dump.
+    #[arg(long, value_enum, default_value_t = StateDumpFormat::Jsonl)]
+    pub format: StateDumpFormat,
+
+    #[cfg(feature = "telos")]
+    #[command(flatten)]
+    pub telos: TelosSnapshotArgs,
+}
+
+/// Format of the state dump passed to [`InitStateCommand`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StateDumpFormat {
+    /// JSONL file with the state root followed by one account per line.
+    #[default]
+    Jsonl,
+    /// Directory with JSONL dumps of the `eosio.evm` `account` and `accountstate` tables.
+    #[cfg(feature = "telos")]
+    TelosSnapshot,
+}
+
+/// Parameters of a telos snapshot import, see [`StateDumpFormat::TelosSnapshot`].
+#[cfg(feature = "telos")]
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Telos snapshot")]
+pub struct TelosSnapshotArgs {
+    /// Gas price of the `eosio.evm` config at the base block.
+    #[arg(
+        long = "telos.gas-price",
+        value_name = "WEI",
+        required_if_eq("format", "telos-snapshot")
+    )]
+    pub gas_price: Option<U256>,
+
+    /// Revision of the `eosio.evm` config at the base block.
+    #[arg(long = "telos.revision", value_name = "REVISION", default_value_t = 0)]
+    pub revision: u64,
+
+    /// Expected state root of the base block, the import fails if the computed state root
+    /// doesn't match.
+    #[arg(
+        long = "telos.state-root",
+        value_name = "STATE_ROOT",
+        required_if_eq("format", "telos-snapshot")
+    )]
+    pub state_root: Option<B256>,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateCommand<C> {
@@ -51,7 +111,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
 
         info!(target: "reth::cli", "Initiating state dump");
 
-        let hash = init_at_state(self.state, provider_factory, config.stages.etl)?;
+        let hash = match self.format {
+            StateDumpFormat::Jsonl => {
+                init_at_state(self.state, provider_factory, config.stages.etl)?
+            }
+            #[cfg(feature = "telos")]
+            StateDumpFormat::TelosSnapshot => {
+                init_at_telos_snapshot(self.state, self.telos, provider_factory, config.stages.etl)?
+            }
+        };
 
         info!(target: "reth::cli", hash = ?hash, "Genesis block written");
         Ok(())
@@ -77,3 +145,48 @@ pub fn init_at_state<N: ProviderNodeTypes>(
 
     Ok(hash)
 }
+
+/// Initialize chain with state at the base block, from a directory with `eosio.evm` table dumps.
+#[cfg(feature = "telos")]
+pub fn init_at_telos_snapshot<N: ProviderNodeTypes>(
+    snapshot_dir: PathBuf,
+    args: TelosSnapshotArgs,
+    factory: ProviderFactory<N>,
+    etl_config: EtlConfig,
+) -> eyre::Result<B256> {
+    info!(target: "reth::cli",
+        path=?snapshot_dir,
+        "Opening telos snapshot");
+
+    let accounts = BufReader::new(File::open(snapshot_dir.join("account.jsonl"))?);
+    let account_states = BufReader::new(File::open(snapshot_dir.join("accountstate.jsonl"))?);
+    let account_codes_path = snapshot_dir.join("accountcode.jsonl");
+    let account_codes = account_codes_path
+        .exists()
+        .then(|| File::open(account_codes_path).map(BufReader::new))
+        .transpose()?;
+    let expected_state_root = args
+        .state_root
+        .ok_or_else(|| eyre::eyre!("missing expected state root of telos snapshot"))?;
+    let block_extension = TelosBlockExtension {
+        starting_gas_price: args.gas_price.unwrap_or_default(),
+        starting_revision_number: args.revision,
+        gas_price_change: None,
+        revision_change: None,
+    };
+
+    let provider_rw = factory.provider_rw()?;
+    let hash = init_from_telos_snapshot(
+        accounts,
+        account_states,
+        account_codes,
+        block_extension,
+        expected_state_root,
+        &provider_rw.0,
+        etl_config,
+    )?;
+    // the base block header is rewritten in static files
+    UnifiedStorageWriter::commit(provider_rw, factory.static_file_provider())?;
+
+    Ok(hash)
+}
@@ -9,8 +9,9 @@ use reth_db::{init_db, DatabaseEnv};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::{
-        utils::EthereumChainSpecParser, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        utils::EthereumChainSpecParser, DatabaseArgs, DatabaseVerifyArgs, DatadirArgs, DebugArgs,
+        DevArgs, ExExArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, SyncArgs,
+        TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -99,6 +100,10 @@ pub struct NodeCommand<
     #[command(flatten)]
     pub db: DatabaseArgs,
 
+    /// All background database verification related arguments with --db.verify prefix
+    #[command(flatten)]
+    pub db_verify: DatabaseVerifyArgs,
+
     /// All dev related arguments with --dev prefix
     #[command(flatten)]
     pub dev: DevArgs,
@@ -107,6 +112,14 @@ pub struct NodeCommand<
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All sync related arguments with --sync prefix
+    #[command(flatten)]
+    pub sync: SyncArgs,
+
+    /// All execution extension related arguments with --exex prefix
+    #[command(flatten)]
+    pub exex: ExExArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -157,8 +170,11 @@ impl<
             builder,
             debug,
             db,
+            db_verify,
             dev,
             pruning,
+            sync,
+            exex,
             ext,
         } = self;
 
@@ -175,8 +191,11 @@ impl<
             builder,
             debug,
             db,
+            db_verify,
             dev,
             pruning,
+            sync,
+            exex,
         };
 
         // Register the prometheus recorder before creating the database,
@@ -40,8 +40,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogsStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageError, StageExt, UnwindInput,
     UnwindOutput,
@@ -303,6 +303,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => {
+                    (Box::new(IndexLogsStage::new(config.stages.index_logs, etl_config)), None)
+                }
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -0,0 +1,267 @@
+//! Static file utilities.
+
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand, ValueEnum};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::{eyre, WrapErr};
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use rand::Rng;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_nippy_jar::{compression::ColumnCompression, ColumnResult, NippyJar, NippyJarCursor};
+use reth_node_builder::NodeTypesWithEngine;
+use reth_provider::StaticFileProviderFactory;
+use reth_static_file_types::{Compression, SegmentHeader, StaticFileSegment};
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// `reth static-file` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth static-file` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Benchmarks compression configurations on the rows of an existing static file, reporting
+    /// the size and random-access latency of each
+    Bench(BenchCommand<C>),
+}
+
+/// `reth static-file bench` command
+#[derive(Debug, Parser)]
+pub struct BenchCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The static file segment to benchmark.
+    #[arg(long, value_enum, default_value_t = StaticFileSegment::Transactions)]
+    segment: StaticFileSegment,
+
+    /// A block contained in the static file to benchmark. Defaults to the highest static file
+    /// of the segment.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    block: Option<u64>,
+
+    /// Maximum number of rows of the static file to benchmark, spread evenly over the file.
+    #[arg(long, default_value_t = 100_000)]
+    rows: usize,
+
+    /// Number of random row reads used to measure the access latency.
+    #[arg(long, default_value_t = 10_000)]
+    reads: usize,
+
+    /// A compression configuration to benchmark. Either a comma-separated list with the
+    /// compression of each column, or a single compression for all columns. Can be repeated.
+    ///
+    /// Defaults to the configured compression of the segment, and `uncompressed`, `lz4`, `zstd`
+    /// and `zstd-dict` for all columns.
+    #[arg(long = "compression", value_name = "COLUMNS")]
+    compressions: Vec<String>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-file` command
+    pub async fn execute<N: NodeTypesWithEngine<ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Bench(command) => command.execute::<N>(),
+        }
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> BenchCommand<C> {
+    /// Execute `static-file bench` command
+    fn execute<N: NodeTypesWithEngine<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { config, provider_factory, data_dir } =
+            self.env.init::<N>(AccessRights::RO)?;
+        let static_file_provider = provider_factory.static_file_provider();
+        let compression = &config.static_files.compression;
+
+        let block = match self.block {
+            Some(block) => block,
+            None => static_file_provider
+                .get_highest_static_file_block(self.segment)
+                .ok_or_else(|| eyre!("no static files found for segment {}", self.segment))?,
+        };
+        let block_range = static_file_provider.find_fixed_range(block);
+        let path = static_file_provider.directory().join(self.segment.filename(&block_range));
+        let jar = NippyJar::<SegmentHeader>::load(&path)
+            .wrap_err_with(|| format!("failed to load static file {}", path.display()))?;
+
+        info!(target: "reth::cli", ?path, rows = self.rows, "Sampling static file rows");
+        let rows = jar.sample_columns(self.rows)?;
+        let num_rows = rows.first().map_or(0, Vec::len);
+        if num_rows == 0 {
+            return Err(eyre!("static file {} has no rows", path.display()))
+        }
+        let raw_size = rows.iter().flatten().map(Vec::len).sum::<usize>();
+
+        // Dictionaries are trained on the previous static file like the node does, if there's
+        // one. Otherwise, they're trained on the benchmarked rows themselves.
+        let previous_path = block_range.start().checked_sub(1).map(|block| {
+            static_file_provider
+                .directory()
+                .join(self.segment.filename(&static_file_provider.find_fixed_range(block)))
+        });
+        let training_samples = match previous_path.filter(|path| path.exists()) {
+            Some(previous_path) => NippyJar::<SegmentHeader>::load(&previous_path)?
+                .sample_columns(compression.dictionary_samples)?,
+            None => rows.clone(),
+        };
+
+        let mut configurations: Vec<Vec<Compression>> = Vec::new();
+        let candidates = if self.compressions.is_empty() {
+            std::iter::once(compression.columns(self.segment))
+                .chain(
+                    [
+                        Compression::Uncompressed,
+                        Compression::Lz4,
+                        Compression::Zstd,
+                        Compression::ZstdWithDictionary,
+                    ]
+                    .map(|compression| vec![compression; self.segment.columns()]),
+                )
+                .collect()
+        } else {
+            self.compressions
+                .iter()
+                .map(|spec| parse_columns(spec, self.segment))
+                .collect::<eyre::Result<Vec<_>>>()?
+        };
+        for columns in candidates {
+            if !configurations.contains(&columns) {
+                configurations.push(columns);
+            }
+        }
+
+        let bench_dir = data_dir.data_dir().join("static-file-bench");
+        fs::create_dir_all(&bench_dir)?;
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Compression", "Size", "Ratio", "Mean", "p50", "p99"]);
+
+        for columns in configurations {
+            let name = columns.iter().map(AsRef::as_ref).join(",");
+            info!(target: "reth::cli", compression = %name, "Benchmarking compression");
+
+            let jar_path = bench_dir.join(&name);
+            let result = bench_compression(
+                &jar_path,
+                columns
+                    .into_iter()
+                    .map(|column| match column {
+                        Compression::Lz4 => ColumnCompression::Lz4,
+                        Compression::Zstd => ColumnCompression::Zstd { level: 0 },
+                        Compression::ZstdWithDictionary => ColumnCompression::ZstdDictionary {
+                            level: 0,
+                            max_size: compression.dictionary_max_size,
+                            dictionary: None,
+                        },
+                        Compression::Uncompressed => ColumnCompression::None,
+                    })
+                    .collect(),
+                &rows,
+                &training_samples,
+                self.reads,
+            );
+            let _ = fs::remove_dir_all(&jar_path);
+            let (size, mut latencies) = result?;
+
+            latencies.sort_unstable();
+            let mean = latencies.iter().sum::<Duration>() / latencies.len().max(1) as u32;
+            let percentile =
+                |p: usize| latencies.get(latencies.len() * p / 100).copied().unwrap_or_default();
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name))
+                .add_cell(Cell::new(human_bytes(size as f64)))
+                .add_cell(Cell::new(format!("{:.3}", size as f64 / raw_size as f64)))
+                .add_cell(Cell::new(format!("{mean:?}")))
+                .add_cell(Cell::new(format!("{:?}", percentile(50))))
+                .add_cell(Cell::new(format!("{:?}", percentile(99))));
+            table.add_row(row);
+        }
+
+        let _ = fs::remove_dir_all(&bench_dir);
+
+        println!(
+            "Static file {} ({num_rows} rows, {} uncompressed)",
+            path.display(),
+            human_bytes(raw_size as f64)
+        );
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+/// Parses a comma-separated list with the compression of each column of the segment. A single
+/// compression applies to all columns.
+fn parse_columns(spec: &str, segment: StaticFileSegment) -> eyre::Result<Vec<Compression>> {
+    let columns = spec
+        .split(',')
+        .map(|column| Compression::from_str(column.trim(), true).map_err(|err| eyre!(err)))
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    match columns.len() {
+        1 => Ok(vec![columns[0]; segment.columns()]),
+        len if len == segment.columns() => Ok(columns),
+        len => Err(eyre!(
+            "static file segment {segment} has {} columns, but compression of {len} columns is \
+             given in {spec:?}",
+            segment.columns()
+        )),
+    }
+}
+
+/// Writes the rows to a new jar with the given column compression, and returns its size on disk
+/// alongside the latency of `reads` random row reads.
+fn bench_compression(
+    path: &Path,
+    columns: Vec<ColumnCompression>,
+    rows: &[Vec<Vec<u8>>],
+    training_samples: &[Vec<Vec<u8>>],
+    reads: usize,
+) -> eyre::Result<(u64, Vec<Duration>)> {
+    fs::create_dir_all(path)?;
+    let data_path = path.join("data");
+    let num_rows = rows.first().map_or(0, Vec::len);
+
+    let mut jar =
+        NippyJar::new_without_header(rows.len(), &data_path).with_column_compression(columns);
+    jar.train_column_compression(training_samples)?;
+    let jar = jar.freeze(
+        rows.iter()
+            .map(|column| column.iter().map(|value| ColumnResult::Ok(value.clone())))
+            .collect(),
+        num_rows as u64,
+    )?;
+
+    let size = [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()]
+        .iter()
+        .map(|path| fs::metadata(path).map(|metadata| metadata.len()))
+        .sum::<Result<u64, _>>()?;
+
+    let jar = NippyJar::<()>::load_without_header(&data_path)?;
+    let mut cursor = NippyJarCursor::new(&jar)?;
+    let mut rng = rand::thread_rng();
+    let mut latencies = Vec::with_capacity(reads);
+    for _ in 0..reads {
+        let row = rng.gen_range(0..num_rows);
+        let start = Instant::now();
+        cursor.row_by_number(row)?.ok_or_else(|| eyre!("missing row {row}"))?;
+        latencies.push(start.elapsed());
+    }
+
+    Ok((size, latencies))
+}
@@ -4,6 +4,7 @@ use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
+use reth_static_file_types::CompressionConfig;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
@@ -28,6 +29,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for static files.
+    pub static_files: StaticFilesConfig,
 }
 
 impl Config {
@@ -117,6 +120,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Snap Sync stage configuration.
+    pub snap_sync: SnapSyncConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -370,6 +377,40 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether the log index stage is part of the pipeline.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Snap sync stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct SnapSyncConfig {
+    /// The number of blocks behind the tip of the pivot block whose state is downloaded.
+    pub pivot_distance: u64,
+    /// The maximum number of concurrent requests to peers.
+    pub max_concurrent_requests: usize,
+    /// The soft limit of the response size in bytes requested from peers.
+    pub response_bytes: u64,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self { pivot_distance: 64, max_concurrent_requests: 16, response_bytes: 512 * 1024 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -413,6 +454,7 @@ impl PruneConfig {
             self.segments.account_history.or(other.segments.account_history);
         self.segments.storage_history =
             self.segments.storage_history.or(other.segments.storage_history);
+        self.segments.bodies = self.segments.bodies.or(other.segments.bodies);
 
         if self.segments.receipts_log_filter.0.is_empty() &&
             !other.segments.receipts_log_filter.0.is_empty()
@@ -422,6 +464,14 @@ impl PruneConfig {
     }
 }
 
+/// Static files configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct StaticFilesConfig {
+    /// Compression of each column of newly created static files, per segment.
+    pub compression: CompressionConfig,
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -447,6 +497,7 @@ mod tests {
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
+    use reth_static_file_types::{Compression, StaticFileSegment};
     use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -934,6 +985,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                bodies: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -949,6 +1001,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                bodies: Some(PruneMode::Distance(20000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -967,6 +1020,7 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.bodies, Some(PruneMode::Distance(20000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -987,6 +1041,36 @@ connect_trusted_nodes_only = true
         assert!(conf.peers.trusted_nodes_only);
     }
 
+    #[test]
+    fn test_static_files_compression() {
+        let reth_toml = r#"
+    [static_files.compression]
+    headers = ["lz4", "lz4", "uncompressed"]
+    transactions = ["zstd-dict"]
+    dictionary_max_size = 50000
+    "#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let compression = &conf.static_files.compression;
+        assert_eq!(
+            compression.columns(StaticFileSegment::Headers),
+            vec![Compression::Lz4, Compression::Lz4, Compression::Uncompressed]
+        );
+        assert_eq!(
+            compression.columns(StaticFileSegment::Transactions),
+            vec![Compression::ZstdWithDictionary]
+        );
+        assert_eq!(
+            compression.columns(StaticFileSegment::Receipts),
+            StaticFileSegment::Receipts.default_compression()
+        );
+        assert_eq!(compression.dictionary_max_size, 50000);
+        assert!(compression.validate().is_ok());
+
+        let serialized = toml::to_string_pretty(&conf).unwrap();
+        assert_eq!(toml::from_str::<Config>(&serialized).unwrap(), conf);
+    }
+
     #[test]
     fn test_can_support_dns_in_trusted_nodes() {
         let reth_toml = r#"
@@ -156,9 +156,16 @@ where
         #[cfg(feature = "telos")]
         let mut tx_index = 0;
         #[cfg(feature = "telos")]
-        let unwrapped_telos_extra_fields = telos_extra_fields.unwrap_or_default();
+        let unwrapped_telos_extra_fields = telos_extra_fields.ok_or_else(|| {
+            BlockExecutionError::msg(format!("missing telos extra fields for block {}", block.number))
+        })?;
         #[cfg(feature = "telos")]
-        let mut new_addresses_using_create_iter = unwrapped_telos_extra_fields.new_addresses_using_create.as_ref().unwrap().into_iter().peekable();
+        let mut new_addresses_using_create_iter = unwrapped_telos_extra_fields
+            .new_addresses_using_create
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .peekable();
         // #[cfg(feature = "telos")]
         // let parent_telos_ext;
         // #[cfg(feature = "telos")]
@@ -489,7 +496,11 @@ where
     type Output = ExecutionOutcome;
     type Error = BlockExecutionError;
 
-    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
+    fn execute_and_verify_one(
+        &mut self,
+        input: Self::Input<'_>,
+        #[cfg(feature = "telos")] telos_extra_fields: Option<TelosEngineAPIExtraFields>,
+    ) -> Result<(), Self::Error> {
         let BlockExecutionInput { block, total_difficulty } = input;
 
         if self.batch_record.first_block().is_none() {
@@ -497,7 +508,7 @@ where
         }
 
         let EthExecuteOutput { receipts, requests, gas_used: _ } =
-            self.executor.execute_without_verification(block, total_difficulty, #[cfg(feature = "telos")] None)?;
+            self.executor.execute_without_verification(block, total_difficulty, #[cfg(feature = "telos")] telos_extra_fields)?;
 
         validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
 
@@ -26,8 +26,9 @@ use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, ConfiguredOrdering, EthPooledTransaction, EthTransactionPool,
+    EthTransactionValidator, Pool, TransactionOrdering, TransactionPool,
+    TransactionValidationTaskExecutor, TransactionValidator,
 };
 
 use crate::{EthEngineTypes, EthEvmConfig};
@@ -141,14 +142,23 @@ pub struct EthereumPoolBuilder {
     // TODO add options for txpool args
 }
 
-impl<Types, Node> PoolBuilder<Node> for EthereumPoolBuilder
-where
-    Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
-    Node: FullNodeTypes<Types = Types>,
-{
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
-
-    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+impl EthereumPoolBuilder {
+    /// Builds a transaction pool with the Ethereum validator wrapped by `map_validator` and the
+    /// given ordering of pending transactions, and spawns its maintenance tasks.
+    ///
+    /// This allows nodes that only change how transactions are validated or ordered to reuse the
+    /// Ethereum pool.
+    pub async fn build_pool_with<Node, F, V, T>(
+        ctx: &BuilderContext<Node>,
+        map_validator: F,
+        ordering: T,
+    ) -> eyre::Result<Pool<TransactionValidationTaskExecutor<V>, T, DiskFileBlobStore>>
+    where
+        Node: FullNodeTypes<Types: NodeTypesWithEngine<ChainSpec = ChainSpec>>,
+        F: FnOnce(EthTransactionValidator<Node::Provider, EthPooledTransaction>) -> V,
+        V: TransactionValidator<Transaction = EthPooledTransaction> + Clone + 'static,
+        T: TransactionOrdering<Transaction = EthPooledTransaction>,
+    {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
@@ -161,31 +171,20 @@ where
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
                 blob_store.clone(),
-            );
+            )
+            .map(map_validator);
 
         let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+            reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config);
         info!(target: "reth::cli", "Transaction pool initialized");
-        let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
         {
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
-
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
-                "local transactions backup task",
-                |shutdown| {
-                    reth_transaction_pool::maintain::backup_local_transactions_task(
-                        shutdown,
-                        pool.clone(),
-                        transactions_backup_config,
-                    )
-                },
-            );
+
+            ctx.spawn_transaction_pool_persistence(pool.clone());
 
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
@@ -205,6 +204,23 @@ where
     }
 }
 
+impl<Types, Node> PoolBuilder<Node> for EthereumPoolBuilder
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
+    Node: FullNodeTypes<Types = Types>,
+{
+    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+
+    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+        Self::build_pool_with(
+            ctx,
+            |validator| validator,
+            ConfiguredOrdering::new(ctx.transaction_ordering()),
+        )
+        .await
+    }
+}
+
 /// A basic ethereum payload service.
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
@@ -113,10 +113,22 @@ where
     type Output = ExecutionOutcome;
     type Error = BlockExecutionError;
 
-    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
+    fn execute_and_verify_one(
+        &mut self,
+        input: Self::Input<'_>,
+        #[cfg(feature = "telos")] telos_extra_fields: Option<TelosEngineAPIExtraFields>,
+    ) -> Result<(), Self::Error> {
         match self {
-            Self::Left(a) => a.execute_and_verify_one(input),
-            Self::Right(b) => b.execute_and_verify_one(input),
+            Self::Left(a) => a.execute_and_verify_one(
+                input,
+                #[cfg(feature = "telos")]
+                telos_extra_fields,
+            ),
+            Self::Right(b) => b.execute_and_verify_one(
+                input,
+                #[cfg(feature = "telos")]
+                telos_extra_fields,
+            ),
         }
     }
 
@@ -60,19 +60,30 @@ pub trait BatchExecutor<DB> {
     type Error;
 
     /// Executes the next block in the batch, verifies the output and updates the state internally.
-    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error>;
+    fn execute_and_verify_one(
+        &mut self,
+        input: Self::Input<'_>,
+        #[cfg(feature = "telos")] telos_extra_fields: Option<TelosEngineAPIExtraFields>,
+    ) -> Result<(), Self::Error>;
 
     /// Executes multiple inputs in the batch, verifies the output, and updates the state
     /// internally.
     ///
     /// This method is a convenience function for calling [`BatchExecutor::execute_and_verify_one`]
     /// for each input.
+    ///
+    /// Telos blocks are executed without their extra fields, use
+    /// [`BatchExecutor::execute_and_verify_one`] to execute them.
     fn execute_and_verify_many<'a, I>(&mut self, inputs: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Self::Input<'a>>,
     {
         for input in inputs {
-            self.execute_and_verify_one(input)?;
+            self.execute_and_verify_one(
+                input,
+                #[cfg(feature = "telos")]
+                None,
+            )?;
         }
         Ok(())
     }
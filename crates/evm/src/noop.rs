@@ -69,7 +69,11 @@ impl<DB> BatchExecutor<DB> for NoopBlockExecutorProvider {
     type Output = ExecutionOutcome;
     type Error = BlockExecutionError;
 
-    fn execute_and_verify_one(&mut self, _: Self::Input<'_>) -> Result<(), Self::Error> {
+    fn execute_and_verify_one(
+        &mut self,
+        _: Self::Input<'_>,
+        #[cfg(feature = "telos")] _telos_extra_fields: Option<TelosEngineAPIExtraFields>,
+    ) -> Result<(), Self::Error> {
         Err(BlockExecutionError::msg(UNAVAILABLE_FOR_NOOP))
     }
 
@@ -114,7 +114,11 @@ where
             }
             .with_senders_unchecked(senders);
 
-            executor.execute_and_verify_one((&block, td).into())?;
+            executor.execute_and_verify_one(
+                (&block, td).into(),
+                #[cfg(feature = "telos")]
+                None,
+            )?;
             execution_duration += execute_start.elapsed();
 
             // TODO(alexey): report gas metrics using `block.header.gas_used`
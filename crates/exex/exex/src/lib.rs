@@ -49,6 +49,11 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+#[cfg(unix)]
+mod remote;
+#[cfg(unix)]
+pub use remote::*;
+
 mod wal;
 pub use wal::*;
 
@@ -44,6 +44,11 @@ impl<P, E> ExExNotifications<P, E> {
         Self { node_head, provider, executor, notifications, wal_handle }
     }
 
+    /// Returns the handle to the WAL that backs these notifications.
+    pub const fn wal_handle(&self) -> &WalHandle {
+        &self.wal_handle
+    }
+
     /// Receives the next value for this receiver.
     ///
     /// This method returns `None` if the channel has been closed and there are
@@ -0,0 +1,301 @@
+//! An ExEx that bridges notifications to an out-of-process consumer over a Unix socket.
+//!
+//! See [`reth_exex_types::remote`] for the wire protocol.
+
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+use alloy_primitives::B256;
+use bytes::Bytes;
+use futures::{SinkExt, Stream, StreamExt};
+use reth_exex_types::remote::{encode_notification, RemoteExExRequest, MAX_FRAME_LENGTH};
+use reth_node_api::FullNodeComponents;
+use reth_tracing::tracing::{debug, info, warn};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::mpsc::UnboundedSender,
+};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+use crate::{ExExContext, ExExEvent, ExExHead, ExExNotification, ExExNotifications, WalHandle};
+
+/// An ExEx that serves [`ExExNotification`]s to a single out-of-process client connected to a
+/// Unix socket, and forwards the client's `FinishedHeight` acknowledgements to the node.
+///
+/// While no client is connected, live notifications are dropped: they are already committed to
+/// the WAL, and since the node does not receive any acknowledgements in the meantime, the WAL
+/// keeps them until a client reconnects and replays them from its last processed block.
+#[derive(Debug)]
+pub struct RemoteExEx<S> {
+    socket_path: PathBuf,
+    notifications: S,
+    wal_handle: WalHandle,
+    events: UnboundedSender<ExExEvent>,
+}
+
+impl<P, E> RemoteExEx<ExExNotifications<P, E>> {
+    /// Creates a new [`RemoteExEx`] from the given context, listening on `socket_path`.
+    pub fn from_context<Node>(ctx: ExExContext<Node>, socket_path: impl Into<PathBuf>) -> Self
+    where
+        Node: FullNodeComponents<Provider = P, Executor = E>,
+    {
+        let wal_handle = ctx.notifications.wal_handle().clone();
+        Self::new(socket_path, ctx.notifications, wal_handle, ctx.events)
+    }
+}
+
+impl<S> RemoteExEx<S> {
+    /// Creates a new [`RemoteExEx`].
+    pub fn new(
+        socket_path: impl Into<PathBuf>,
+        notifications: S,
+        wal_handle: WalHandle,
+        events: UnboundedSender<ExExEvent>,
+    ) -> Self {
+        Self { socket_path: socket_path.into(), notifications, wal_handle, events }
+    }
+}
+
+impl<S> RemoteExEx<S>
+where
+    S: Stream<Item = ExExNotification> + Unpin,
+{
+    /// Binds the socket and serves clients until the notification stream ends.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let listener = bind(&self.socket_path)?;
+        info!(target: "exex::remote", path = ?self.socket_path, "Listening for remote ExEx");
+
+        let mut session = None::<Session>;
+        loop {
+            tokio::select! {
+                conn = listener.accept() => {
+                    let (stream, _) = conn?;
+                    if session.is_some() {
+                        warn!(target: "exex::remote", "Remote ExEx already connected, rejecting");
+                        continue
+                    }
+                    debug!(target: "exex::remote", "Remote ExEx connected");
+                    session = Some(Session::new(stream));
+                }
+                notification = self.notifications.next() => {
+                    let Some(notification) = notification else { return Ok(()) };
+                    let Some(active) = session.as_mut() else { continue };
+                    if let Err(err) = active.on_live_notification(&notification).await {
+                        warn!(target: "exex::remote", %err, "Failed to notify remote ExEx");
+                        session = None;
+                    }
+                }
+                request = next_request(&mut session) => {
+                    let Some(request) = request else {
+                        info!(target: "exex::remote", "Remote ExEx disconnected");
+                        session = None;
+                        continue
+                    };
+                    let active = session.as_mut().expect("request is only received with a session");
+                    let result = match request {
+                        Ok(request) => self.on_request(active, request).await,
+                        Err(err) => Err(err),
+                    };
+                    if let Err(err) = result {
+                        warn!(target: "exex::remote", %err, "Remote ExEx session failed");
+                        session = None;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn on_request(
+        &self,
+        session: &mut Session,
+        request: RemoteExExRequest,
+    ) -> eyre::Result<()> {
+        match request {
+            RemoteExExRequest::Subscribe { head } => {
+                if session.subscribed {
+                    eyre::bail!("remote ExEx is already subscribed")
+                }
+                info!(target: "exex::remote", ?head, "Remote ExEx subscribed");
+                session.subscribed = true;
+
+                if let Some(head) = head {
+                    self.replay(session, head).await?;
+                }
+            }
+            RemoteExExRequest::FinishedHeight(height) => {
+                if !session.subscribed {
+                    eyre::bail!("remote ExEx sent a finished height before subscribing")
+                }
+                debug!(target: "exex::remote", ?height, "Remote ExEx finished height");
+                self.events.send(ExExEvent::FinishedHeight(height))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends all notifications from the WAL that touch blocks above the given head.
+    ///
+    /// Notifications overlapping the head are replayed in full, so the client may see blocks it
+    /// has already processed.
+    async fn replay(&self, session: &mut Session, head: ExExHead) -> eyre::Result<()> {
+        let mut replayed = 0;
+        for notification in self.wal_handle.iter_notifications()? {
+            let notification = notification?;
+            if highest_block(&notification) <= head.block.number {
+                continue
+            }
+
+            session.send(&notification).await?;
+            session.replayed.insert(notification_key(&notification));
+            replayed += 1;
+        }
+        debug!(target: "exex::remote", ?replayed, "Replayed notifications from the WAL");
+
+        Ok(())
+    }
+}
+
+/// A connected client.
+#[derive(Debug)]
+struct Session {
+    framed: Framed<UnixStream, LengthDelimitedCodec>,
+    /// Whether the client has sent [`RemoteExExRequest::Subscribe`].
+    subscribed: bool,
+    /// Keys of notifications replayed from the WAL. Notifications are committed to the WAL
+    /// before they are sent to ExExes, so the first live notifications after a replay may
+    /// duplicate replayed ones and are skipped.
+    replayed: HashSet<(Option<B256>, Option<B256>)>,
+}
+
+impl Session {
+    fn new(stream: UnixStream) -> Self {
+        let codec = LengthDelimitedCodec::builder().max_frame_length(MAX_FRAME_LENGTH).new_codec();
+        Self { framed: Framed::new(stream, codec), subscribed: false, replayed: HashSet::new() }
+    }
+
+    async fn on_live_notification(&mut self, notification: &ExExNotification) -> eyre::Result<()> {
+        if !self.subscribed {
+            return Ok(())
+        }
+
+        if !self.replayed.is_empty() {
+            if self.replayed.remove(&notification_key(notification)) {
+                return Ok(())
+            }
+            self.replayed.clear();
+        }
+
+        self.send(notification).await
+    }
+
+    async fn send(&mut self, notification: &ExExNotification) -> eyre::Result<()> {
+        self.framed.send(Bytes::from(encode_notification(notification)?)).await?;
+        Ok(())
+    }
+}
+
+/// Waits for the next request of the connected client, or forever if there is none.
+///
+/// Returns `None` if the client disconnected.
+async fn next_request(session: &mut Option<Session>) -> Option<eyre::Result<RemoteExExRequest>> {
+    let Some(session) = session else { return std::future::pending().await };
+    Some(match session.framed.next().await? {
+        Ok(frame) => RemoteExExRequest::decode(&frame).map_err(eyre::Report::from),
+        Err(err) => Err(err.into()),
+    })
+}
+
+/// Removes a stale socket left over from a previous run and binds a new one.
+fn bind(path: &Path) -> eyre::Result<UnixListener> {
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(reth_fs_util::FsPathError::remove_file(err, path).into()),
+    }
+    Ok(UnixListener::bind(path)?)
+}
+
+fn highest_block(notification: &ExExNotification) -> u64 {
+    let committed = notification.committed_chain().map(|chain| chain.tip().number);
+    let reverted = notification.reverted_chain().map(|chain| chain.tip().number);
+    committed.max(reverted).unwrap_or_default()
+}
+
+fn notification_key(notification: &ExExNotification) -> (Option<B256>, Option<B256>) {
+    (
+        notification.reverted_chain().map(|chain| chain.tip().hash()),
+        notification.committed_chain().map(|chain| chain.tip().hash()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use eyre::OptionExt;
+    use futures::StreamExt;
+    use reth_exex_remote_client::RemoteExExClient;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::Wal;
+
+    #[tokio::test]
+    async fn replays_wal_and_forwards_finished_height() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let wal = Wal::new(temp_dir.path().join("wal"))?;
+        let socket_path = temp_dir.path().join("exex.sock");
+
+        let blocks = random_block_range(&mut rng, 0..=3, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| block.seal_with_senders().ok_or_eyre("failed to recover senders"))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let notifications = blocks
+            .iter()
+            .map(|block| ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block.clone()], Default::default(), None)),
+            })
+            .collect::<Vec<_>>();
+
+        // The first three notifications are in the WAL before the client connects
+        for notification in &notifications[..3] {
+            wal.commit(notification)?;
+        }
+
+        let (notifications_tx, notifications_rx) = futures::channel::mpsc::unbounded();
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let remote = RemoteExEx::new(&socket_path, notifications_rx, wal.handle(), events_tx);
+        tokio::spawn(remote.run());
+
+        let head = ExExHead { block: blocks[0].num_hash() };
+        let mut client = loop {
+            match RemoteExExClient::connect(&socket_path, Some(head)).await {
+                Ok(client) => break client,
+                Err(_) => tokio::task::yield_now().await,
+            }
+        };
+
+        // Notifications above the head are replayed from the WAL
+        assert_eq!(client.next().await.transpose()?.as_ref(), Some(&notifications[1]));
+        assert_eq!(client.next().await.transpose()?.as_ref(), Some(&notifications[2]));
+
+        // A live notification that was already replayed is skipped
+        notifications_tx.unbounded_send(notifications[2].clone())?;
+        wal.commit(&notifications[3])?;
+        notifications_tx.unbounded_send(notifications[3].clone())?;
+        assert_eq!(client.next().await.transpose()?.as_ref(), Some(&notifications[3]));
+
+        client.send_finished_height(blocks[3].num_hash()).await?;
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(blocks[3].num_hash())));
+
+        Ok(())
+    }
+}
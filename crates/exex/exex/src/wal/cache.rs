@@ -85,31 +85,24 @@ impl BlockCache {
         self.committed_blocks.get(block_hash).map(|entry| entry.0)
     }
 
-    /// Inserts the blocks from the notification into the cache with the given file ID.
-    pub(super) fn insert_notification_blocks_with_file_id(
+    /// Inserts the highest block and the committed blocks of a notification into the cache with
+    /// the given file ID.
+    pub(super) fn insert_blocks_with_file_id(
         &mut self,
         file_id: u32,
-        notification: &ExExNotification,
+        max_block: Option<BlockNumber>,
+        committed_blocks: &[CachedBlock],
     ) {
-        let reverted_chain = notification.reverted_chain();
-        let committed_chain = notification.committed_chain();
-
-        let max_block =
-            reverted_chain.iter().chain(&committed_chain).map(|chain| chain.tip().number).max();
         if let Some(max_block) = max_block {
             self.notification_max_blocks.push(Reverse((max_block, file_id)));
         }
 
-        if let Some(committed_chain) = &committed_chain {
-            for block in committed_chain.blocks().values() {
-                let cached_block = CachedBlock {
-                    block: (block.number, block.hash()).into(),
-                    parent_hash: block.parent_hash,
-                };
-                self.committed_blocks.insert(block.hash(), (file_id, cached_block));
-            }
+        for cached_block in committed_blocks {
+            self.committed_blocks.insert(cached_block.block.hash, (file_id, *cached_block));
+        }
 
-            self.highest_committed_block_height = Some(committed_chain.tip().number);
+        if let Some(tip) = committed_blocks.iter().map(|block| block.block.number).max() {
+            self.highest_committed_block_height = Some(tip);
         }
     }
 
@@ -135,6 +128,28 @@ impl BlockCache {
     }
 }
 
+/// Returns the highest block of the notification, chosen between the committed and reverted
+/// chains, and the committed blocks of the notification.
+pub(super) fn notification_blocks(
+    notification: &ExExNotification,
+) -> (Option<BlockNumber>, Vec<CachedBlock>) {
+    let reverted_chain = notification.reverted_chain();
+    let committed_chain = notification.committed_chain();
+
+    let max_block =
+        reverted_chain.iter().chain(&committed_chain).map(|chain| chain.tip().number).max();
+    let committed_blocks = committed_chain
+        .iter()
+        .flat_map(|chain| chain.blocks().values())
+        .map(|block| CachedBlock {
+            block: (block.number, block.hash()).into(),
+            parent_hash: block.parent_hash,
+        })
+        .collect();
+
+    (max_block, committed_blocks)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) struct CachedBlock {
     /// The block number and hash of the block.
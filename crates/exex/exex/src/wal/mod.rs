@@ -3,7 +3,7 @@
 mod cache;
 pub use cache::BlockCache;
 mod storage;
-pub use storage::Storage;
+pub use storage::{Storage, WalSegment};
 mod metrics;
 use metrics::Metrics;
 
@@ -16,14 +16,14 @@ use std::{
 };
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
 use reth_tracing::tracing::{debug, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
 ///
-/// WAL is backed by a directory of binary files represented by [`Storage`] and a block cache
+/// WAL is backed by a directory of segment files represented by [`Storage`] and a block cache
 /// represented by [`BlockCache`]. The role of the block cache is to avoid walking the WAL directory
 /// and decoding notifications every time we want to iterate or finalize the WAL.
 ///
@@ -57,9 +57,20 @@ impl Wal {
     /// The caller should check that all ExExes are on the canonical chain and will not need any
     /// blocks from the WAL below the provided block, inclusive.
     pub fn finalize(&self, to_block: BlockNumHash) -> eyre::Result<()> {
+        self.inner.finalize(to_block.number)
+    }
+
+    /// Removes all notifications with the highest block less than or equal to the given block
+    /// number, without checking whether the block is canonical.
+    pub fn prune(&self, to_block: BlockNumber) -> eyre::Result<()> {
         self.inner.finalize(to_block)
     }
 
+    /// Returns the summary of all segments of the WAL storage.
+    pub fn segments(&self) -> Vec<WalSegment> {
+        self.inner.storage.segments()
+    }
+
     /// Returns an iterator over all notifications in the WAL.
     pub fn iter_notifications(
         &self,
@@ -95,32 +106,31 @@ impl WalInner {
         self.block_cache.read()
     }
 
-    /// Fills the block cache with the notifications from the storage.
+    /// Fills the block cache with the notifications from the storage index.
     #[instrument(skip(self))]
     fn fill_block_cache(&mut self) -> eyre::Result<()> {
-        let Some(files_range) = self.storage.files_range()? else { return Ok(()) };
-        self.next_file_id.store(files_range.end() + 1, Ordering::Relaxed);
+        let Some(ids_range) = self.storage.ids_range()? else { return Ok(()) };
+        self.next_file_id.store(ids_range.end() + 1, Ordering::Relaxed);
 
         let mut block_cache = self.block_cache.write();
         let mut notifications_size = 0;
 
-        for entry in self.storage.iter_notifications(files_range) {
-            let (file_id, size, notification) = entry?;
-
-            notifications_size += size;
-
-            let committed_chain = notification.committed_chain();
-            let reverted_chain = notification.reverted_chain();
+        for entry in self.storage.iter_index() {
+            notifications_size += entry.len as u64;
 
             debug!(
                 target: "exex::wal",
-                ?file_id,
-                reverted_block_range = ?reverted_chain.as_ref().map(|chain| chain.range()),
-                committed_block_range = ?committed_chain.as_ref().map(|chain| chain.range()),
+                file_id = ?entry.id,
+                max_block = ?entry.max_block,
+                committed_blocks = ?entry.committed_blocks.len(),
                 "Inserting block cache entries"
             );
 
-            block_cache.insert_notification_blocks_with_file_id(file_id, &notification);
+            block_cache.insert_blocks_with_file_id(
+                entry.id,
+                entry.max_block,
+                &entry.committed_blocks,
+            );
         }
 
         self.update_metrics(&block_cache, notifications_size as i64);
@@ -136,20 +146,20 @@ impl WalInner {
         let mut block_cache = self.block_cache.write();
 
         let file_id = self.next_file_id.fetch_add(1, Ordering::Relaxed);
-        let size = self.storage.write_notification(file_id, notification)?;
+        let entry = self.storage.write_notification(file_id, notification)?;
 
         debug!(target: "exex::wal", ?file_id, "Inserting notification blocks into the block cache");
-        block_cache.insert_notification_blocks_with_file_id(file_id, notification);
+        block_cache.insert_blocks_with_file_id(file_id, entry.max_block, &entry.committed_blocks);
 
-        self.update_metrics(&block_cache, size as i64);
+        self.update_metrics(&block_cache, entry.len as i64);
 
         Ok(())
     }
 
     #[instrument(skip(self))]
-    fn finalize(&self, to_block: BlockNumHash) -> eyre::Result<()> {
+    fn finalize(&self, to_block: BlockNumber) -> eyre::Result<()> {
         let mut block_cache = self.block_cache.write();
-        let file_ids = block_cache.remove_before(to_block.number);
+        let file_ids = block_cache.remove_before(to_block);
 
         // Remove notifications from the storage.
         if file_ids.is_empty() {
@@ -183,7 +193,7 @@ impl WalInner {
     fn iter_notifications(
         &self,
     ) -> eyre::Result<Box<dyn Iterator<Item = eyre::Result<ExExNotification>> + '_>> {
-        let Some(range) = self.storage.files_range()? else {
+        let Some(range) = self.storage.ids_range()? else {
             return Ok(Box::new(std::iter::empty()))
         };
 
@@ -192,7 +202,7 @@ impl WalInner {
 }
 
 /// A read-only handle to the WAL that can be shared.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WalHandle {
     wal: Arc<WalInner>,
 }
@@ -213,6 +223,13 @@ impl WalHandle {
             .read_notification(file_id)
             .map(|entry| entry.map(|(notification, _)| notification))
     }
+
+    /// Returns an iterator over all notifications in the WAL, in the order they were committed.
+    pub fn iter_notifications(
+        &self,
+    ) -> eyre::Result<Box<dyn Iterator<Item = eyre::Result<ExExNotification>> + '_>> {
+        self.wal.iter_notifications()
+    }
 }
 
 #[cfg(test)]
@@ -231,11 +248,11 @@ mod tests {
     use crate::wal::{cache::CachedBlock, Wal};
 
     fn read_notifications(wal: &Wal) -> eyre::Result<Vec<ExExNotification>> {
-        let Some(files_range) = wal.inner.storage.files_range()? else { return Ok(Vec::new()) };
+        let Some(ids_range) = wal.inner.storage.ids_range()? else { return Ok(Vec::new()) };
 
         wal.inner
             .storage
-            .iter_notifications(files_range)
+            .iter_notifications(ids_range)
             .map(|entry| Ok(entry?.2))
             .collect::<eyre::Result<_>>()
     }
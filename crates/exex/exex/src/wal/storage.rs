@@ -1,101 +1,306 @@
 use std::{
-    fs::File,
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
+use alloy_primitives::{BlockNumber, B256};
+use bytes::{Buf, BufMut};
+use crc::{Crc, CRC_32_ISO_HDLC};
 use eyre::OptionExt;
+use parking_lot::RwLock;
 use reth_exex_types::ExExNotification;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use tracing::instrument;
 
-/// The underlying WAL storage backed by a directory of files.
+use super::cache::{notification_blocks, CachedBlock};
+
+/// Extension of the segment data files.
+const SEGMENT_EXTENSION: &str = "seg";
+/// Extension of the segment index files.
+const INDEX_EXTENSION: &str = "idx";
+/// Extension of the files written by the previous one-file-per-notification storage.
+const LEGACY_EXTENSION: &str = "wal";
+
+/// Maximum number of notifications written to a segment before a new one is started.
+const MAX_SEGMENT_NOTIFICATIONS: u32 = 4096;
+/// Maximum size of the segment data file in bytes before a new segment is started.
+const MAX_SEGMENT_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Zstd compression level of notifications.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Length of the frame header: payload length and checksum, both `u32`.
+const FRAME_HEADER_SIZE: usize = 8;
+
+const CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// The underlying WAL storage backed by a directory of segments.
+///
+/// Each segment consists of two append-only files named after the ID of the first notification
+/// in the segment:
+/// - `{id}.seg` contains zstd-compressed MessagePack-encoded notifications.
+/// - `{id}.idx` contains an index record per written notification with its offset in the data file
+///   and its blocks, and a record per removed notification.
+///
+/// Both data and index records are framed with their length and a CRC32 checksum, so a torn
+/// write at the end of a segment is detected and discarded when the storage is opened. The
+/// block cache of the WAL is rebuilt from the index without decoding any notifications.
 ///
-/// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
-#[derive(Debug, Clone)]
+/// Segment files are deleted once all notifications in them are removed.
+#[derive(Debug)]
 pub struct Storage {
-    /// The path to the WAL file.
+    /// The path to the WAL directory.
     path: PathBuf,
+    /// Segments by the ID of their first notification.
+    segments: RwLock<BTreeMap<u32, Segment>>,
 }
 
 impl Storage {
-    /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
-    /// it doesn't exist.
+    /// Creates a new instance of [`Storage`] backed by the directory at the given path and
+    /// creates it if it doesn't exist.
+    ///
+    /// Notifications written by the previous one-file-per-notification storage are moved into
+    /// segments.
     pub(super) fn new(path: impl AsRef<Path>) -> eyre::Result<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf() })
+        let storage =
+            Self { path: path.as_ref().to_path_buf(), segments: RwLock::new(BTreeMap::new()) };
+        let legacy_ids = storage.load()?;
+        storage.migrate_legacy(legacy_ids)?;
+
+        Ok(storage)
+    }
+
+    fn data_path(&self, first_id: u32) -> PathBuf {
+        self.path.join(format!("{first_id}.{SEGMENT_EXTENSION}"))
     }
 
-    fn file_path(&self, id: u32) -> PathBuf {
-        self.path.join(format!("{id}.wal"))
+    fn index_path(&self, first_id: u32) -> PathBuf {
+        self.path.join(format!("{first_id}.{INDEX_EXTENSION}"))
     }
 
-    fn parse_filename(filename: &str) -> eyre::Result<u32> {
+    fn legacy_path(&self, id: u32) -> PathBuf {
+        self.path.join(format!("{id}.{LEGACY_EXTENSION}"))
+    }
+
+    fn parse_filename(filename: &str) -> eyre::Result<(u32, &str)> {
         filename
-            .strip_suffix(".wal")
-            .and_then(|s| s.parse().ok())
+            .split_once('.')
+            .and_then(|(id, extension)| Some((id.parse().ok()?, extension)))
+            .filter(|(_, extension)| {
+                [SEGMENT_EXTENSION, INDEX_EXTENSION, LEGACY_EXTENSION].contains(extension)
+            })
             .ok_or_eyre(format!("failed to parse file name: {filename}"))
     }
 
-    /// Removes notification for the given file ID from the storage.
+    /// Loads all segments from the directory.
     ///
     /// # Returns
     ///
-    /// The size of the file that was removed in bytes, if any.
-    #[instrument(skip(self))]
-    fn remove_notification(&self, file_id: u32) -> Option<u64> {
-        let path = self.file_path(file_id);
-        let size = path.metadata().ok()?.len();
-
-        match reth_fs_util::remove_file(self.file_path(file_id)) {
-            Ok(()) => {
-                debug!(target: "exex::wal::storage", "Notification was removed from the storage");
-                Some(size)
+    /// Sorted IDs of the notifications written by the previous storage.
+    fn load(&self) -> eyre::Result<Vec<u32>> {
+        let mut first_ids = Vec::new();
+        let mut legacy_ids = Vec::new();
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            match Self::parse_filename(&file_name.to_string_lossy())? {
+                (id, LEGACY_EXTENSION) => legacy_ids.push(id),
+                (first_id, _) => first_ids.push(first_id),
             }
-            Err(err) => {
-                debug!(target: "exex::wal::storage", ?err, "Failed to remove notification from the storage");
-                None
+        }
+        first_ids.sort_unstable();
+        first_ids.dedup();
+        legacy_ids.sort_unstable();
+
+        let mut segments = self.segments.write();
+        for first_id in first_ids {
+            let segment = self.load_segment(first_id)?;
+            if segment.entries.is_empty() {
+                debug!(target: "exex::wal::storage", ?first_id, "Removing empty segment");
+                self.remove_segment_files(first_id)?;
+                continue
             }
+            segments.insert(first_id, segment);
         }
+
+        Ok(legacy_ids)
     }
 
-    /// Returns the range of file IDs in the storage.
+    /// Loads the segment by replaying its index.
     ///
-    /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> eyre::Result<Option<RangeInclusive<u32>>> {
-        let mut min_id = None;
-        let mut max_id = None;
+    /// Torn index records and data that was written without an index record are truncated.
+    #[instrument(skip(self))]
+    fn load_segment(&self, first_id: u32) -> eyre::Result<Segment> {
+        let data_path = self.data_path(first_id);
+        let index_path = self.index_path(first_id);
+
+        let data_len =
+            if data_path.exists() { reth_fs_util::metadata(&data_path)?.len() } else { 0 };
+        let index = if index_path.exists() { reth_fs_util::read(&index_path)? } else { Vec::new() };
+
+        let mut segment = Segment::new(first_id);
+        let mut index_offset = 0;
+        while index_offset < index.len() {
+            let Some((payload, frame_len)) = decode_frame(&index[index_offset..]) else { break };
+            match IndexRecord::decode(payload)? {
+                IndexRecord::Insert(entry) => {
+                    if entry.end() > data_len {
+                        break
+                    }
+                    segment.insert(entry);
+                }
+                IndexRecord::Remove(id) => {
+                    segment.entries.remove(&id);
+                }
+            }
+            index_offset += frame_len;
+        }
 
-        for entry in reth_fs_util::read_dir(&self.path)? {
-            let entry = entry?;
-            let file_name = entry.file_name();
-            let file_id = Self::parse_filename(&file_name.to_string_lossy())?;
+        if index_offset < index.len() {
+            warn!(
+                target: "exex::wal::storage",
+                ?index_path,
+                valid_len = ?index_offset,
+                len = ?index.len(),
+                "Truncating torn segment index"
+            );
+            truncate(&index_path, index_offset as u64)?;
+        }
+
+        // Data after the last indexed notification was never committed
+        if data_len > segment.data_len {
+            warn!(
+                target: "exex::wal::storage",
+                ?data_path,
+                valid_len = ?segment.data_len,
+                len = ?data_len,
+                "Truncating unindexed segment data"
+            );
+            truncate(&data_path, segment.data_len)?;
+        }
+
+        Ok(segment)
+    }
 
-            min_id = min_id.map_or(Some(file_id), |min_id: u32| Some(min_id.min(file_id)));
-            max_id = max_id.map_or(Some(file_id), |max_id: u32| Some(max_id.max(file_id)));
+    /// Moves notifications written by the previous storage into segments.
+    fn migrate_legacy(&self, ids: Vec<u32>) -> eyre::Result<()> {
+        if ids.is_empty() {
+            return Ok(())
         }
+        debug!(target: "exex::wal::storage", count = ?ids.len(), "Migrating notifications to segments");
+
+        // Notifications up to the last ID in the segments were migrated before, but their legacy
+        // files weren't removed
+        let last_id = self.ids_range()?.map(|range| *range.end());
+
+        for id in ids {
+            let legacy_path = self.legacy_path(id);
+
+            if last_id.map_or(true, |last_id| id > last_id) {
+                let mut file = File::open(&legacy_path)
+                    .map_err(|err| reth_fs_util::FsPathError::open(err, &legacy_path))?;
+                let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
+                    rmp_serde::decode::from_read(&mut file).map_err(|err| {
+                        eyre::eyre!("failed to decode notification from {legacy_path:?}: {err:?}")
+                    })?;
+                self.write_notification(id, &notification.into())?;
+            }
+
+            reth_fs_util::remove_file(&legacy_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_segment_files(&self, first_id: u32) -> eyre::Result<()> {
+        for path in [self.index_path(first_id), self.data_path(first_id)] {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(reth_fs_util::FsPathError::remove_file(err, path).into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ID of the segment and the index entry of the notification with the given ID.
+    fn segment_entry(&self, id: u32) -> Option<(u32, IndexEntry)> {
+        let segments = self.segments.read();
+        let (first_id, segment) = segments.range(..=id).next_back()?;
+        Some((*first_id, segment.entries.get(&id)?.clone()))
+    }
+
+    /// Returns the range of notification IDs in the storage.
+    ///
+    /// The range starts at the lowest stored notification and ends at the highest notification
+    /// ever written to a segment that is still present, so the next notification must have a
+    /// higher ID. Not all IDs in the range are necessarily stored.
+    ///
+    /// If there are no notifications in the storage, returns `None`.
+    pub(super) fn ids_range(&self) -> eyre::Result<Option<RangeInclusive<u32>>> {
+        let segments = self.segments.read();
+
+        let min_id = segments.values().find_map(|segment| segment.entries.keys().next().copied());
+        let max_id = segments.values().filter_map(|segment| segment.last_id).max();
 
         Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
     }
 
-    /// Removes notifications from the storage according to the given list of file IDs.
+    /// Returns the index entries of all notifications in the storage, ordered by their IDs.
+    pub(super) fn iter_index(&self) -> Vec<IndexEntry> {
+        self.segments
+            .read()
+            .values()
+            .flat_map(|segment| segment.entries.values().cloned())
+            .collect()
+    }
+
+    /// Returns the summary of all segments in the storage.
+    pub(super) fn segments(&self) -> Vec<WalSegment> {
+        self.segments.read().values().map(Segment::summary).collect()
+    }
+
+    /// Removes notifications from the storage according to the given list of IDs.
     ///
     /// # Returns
     ///
-    /// Number of removed notifications and the total size of the removed files in bytes.
+    /// Number of removed notifications and the total size of their frames in bytes.
     pub(super) fn remove_notifications(
         &self,
-        file_ids: impl IntoIterator<Item = u32>,
+        ids: impl IntoIterator<Item = u32>,
     ) -> eyre::Result<(usize, u64)> {
+        let mut segments = self.segments.write();
+
+        let mut removed_records = HashMap::<u32, Vec<u8>>::new();
         let mut deleted_total = 0;
         let mut deleted_size = 0;
 
-        for id in file_ids {
-            if let Some(size) = self.remove_notification(id) {
-                deleted_total += 1;
-                deleted_size += size;
+        for id in ids {
+            let Some((first_id, segment)) = segments.range_mut(..=id).next_back() else { continue };
+            let Some(entry) = segment.entries.remove(&id) else { continue };
+
+            encode_frame(
+                &IndexRecord::Remove(id).encode(),
+                removed_records.entry(*first_id).or_default(),
+            );
+            deleted_total += 1;
+            deleted_size += entry.len as u64;
+        }
+
+        for (first_id, records) in removed_records {
+            if segments.get(&first_id).is_some_and(|segment| segment.entries.is_empty()) {
+                debug!(target: "exex::wal::storage", ?first_id, "Removing segment");
+                segments.remove(&first_id);
+                self.remove_segment_files(first_id)?;
+            } else {
+                append(&self.index_path(first_id), &records)?;
             }
         }
 
@@ -106,68 +311,333 @@ impl Storage {
         &self,
         range: RangeInclusive<u32>,
     ) -> impl Iterator<Item = eyre::Result<(u32, u64, ExExNotification)>> + '_ {
-        range.map(move |id| {
+        let ids = self
+            .segments
+            .read()
+            .values()
+            .flat_map(|segment| segment.entries.range(range.clone()).map(|(id, _)| *id))
+            .collect::<Vec<_>>();
+
+        ids.into_iter().map(move |id| {
             let (notification, size) =
-                self.read_notification(id)?.ok_or_eyre("notification {id} not found")?;
+                self.read_notification(id)?.ok_or_eyre(format!("notification {id} not found"))?;
 
             Ok((id, size, notification))
         })
     }
 
-    /// Reads the notification from the file with the given ID.
+    /// Reads the notification with the given ID.
     #[instrument(skip(self))]
     pub(super) fn read_notification(
         &self,
-        file_id: u32,
+        id: u32,
     ) -> eyre::Result<Option<(ExExNotification, u64)>> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
+        let Some((first_id, entry)) = self.segment_entry(id) else { return Ok(None) };
 
-        let mut file = match File::open(&file_path) {
+        let data_path = self.data_path(first_id);
+        debug!(target: "exex::wal::storage", ?data_path, offset = ?entry.offset, "Reading notification from WAL");
+
+        let mut file = match File::open(&data_path) {
             Ok(file) => file,
+            // The segment was removed concurrently
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &data_path).into()),
         };
-        let size = file.metadata()?.len();
+        let mut frame = vec![0; entry.len as usize];
+        file.seek(SeekFrom::Start(entry.offset))?;
+        file.read_exact(&mut frame)?;
+
+        let (payload, _) = decode_frame(&frame).ok_or_else(|| {
+            eyre::eyre!("checksum mismatch of notification {id} in {data_path:?}")
+        })?;
+        let payload = zstd::stream::decode_all(payload)?;
 
         // Deserialize using the bincode- and msgpack-compatible serde wrapper
         let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
-            rmp_serde::decode::from_read(&mut file).map_err(|err| {
-                eyre::eyre!("failed to decode notification from {file_path:?}: {err:?}")
+            rmp_serde::decode::from_slice(&payload).map_err(|err| {
+                eyre::eyre!("failed to decode notification {id} from {data_path:?}: {err:?}")
             })?;
 
-        Ok(Some((notification.into(), size)))
+        Ok(Some((notification.into(), entry.len as u64)))
     }
 
-    /// Writes the notification to the file with the given ID.
+    /// Appends the notification with the given ID to the last segment, starting a new one if
+    /// it's full.
+    ///
+    /// The ID must be higher than the IDs of all notifications in the storage.
     ///
     /// # Returns
     ///
-    /// The size of the file that was written in bytes.
+    /// The index entry of the written notification.
     #[instrument(skip(self, notification))]
     pub(super) fn write_notification(
         &self,
-        file_id: u32,
+        id: u32,
         notification: &ExExNotification,
-    ) -> eyre::Result<u64> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
-
+    ) -> eyre::Result<IndexEntry> {
         // Serialize using the bincode- and msgpack-compatible serde wrapper
-        let notification =
+        let notification_compat =
             reth_exex_types::serde_bincode_compat::ExExNotification::from(notification);
+        let payload = zstd::stream::encode_all(
+            rmp_serde::encode::to_vec(&notification_compat)?.as_slice(),
+            COMPRESSION_LEVEL,
+        )?;
+        let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+        encode_frame(&payload, &mut frame);
+
+        let mut segments = self.segments.write();
+        if let Some(last_id) = segments.values().filter_map(|segment| segment.last_id).max() {
+            eyre::ensure!(id > last_id, "notification {id} is not higher than {last_id}");
+        }
 
-        reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
-        })?;
+        let start_segment = segments.last_key_value().map_or(true, |(_, segment)| {
+            segment.written >= MAX_SEGMENT_NOTIFICATIONS || segment.data_len >= MAX_SEGMENT_SIZE
+        });
+        if start_segment {
+            debug!(target: "exex::wal::storage", first_id = ?id, "Starting new segment");
+            segments.insert(id, Segment::new(id));
+        }
+        let (first_id, segment) = segments.last_key_value().expect("segment exists");
+        let first_id = *first_id;
+
+        let (max_block, committed_blocks) = notification_blocks(notification);
+        let entry = IndexEntry {
+            id,
+            offset: segment.data_len,
+            len: frame.len() as u32,
+            max_block,
+            committed_blocks,
+        };
+
+        let data_path = self.data_path(first_id);
+        debug!(target: "exex::wal::storage", ?data_path, offset = ?entry.offset, "Writing notification to WAL");
+
+        // The data is synced before the index record, so an indexed notification is always
+        // complete
+        append(&data_path, &frame)?;
+        let mut record = Vec::new();
+        encode_frame(&IndexRecord::Insert(entry.clone()).encode(), &mut record);
+        append(&self.index_path(first_id), &record)?;
+
+        segments.get_mut(&first_id).expect("segment exists").insert(entry.clone());
+
+        Ok(entry)
+    }
+}
+
+/// Summary of a WAL segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalSegment {
+    /// ID of the first notification written to the segment.
+    pub first_id: u32,
+    /// Number of notifications in the segment.
+    pub notifications: usize,
+    /// Number of notifications written to the segment, including removed ones.
+    pub written: u32,
+    /// Size of the segment data file in bytes.
+    pub size_bytes: u64,
+    /// Range of blocks touched by the notifications in the segment.
+    pub block_range: Option<RangeInclusive<BlockNumber>>,
+}
+
+/// In-memory state of a segment, rebuilt from its index.
+#[derive(Debug)]
+struct Segment {
+    first_id: u32,
+    /// Notifications in the segment that weren't removed.
+    entries: BTreeMap<u32, IndexEntry>,
+    /// ID of the last notification written to the segment.
+    last_id: Option<u32>,
+    /// Number of notifications written to the segment, including removed ones.
+    written: u32,
+    /// Length of the data file in bytes.
+    data_len: u64,
+}
+
+impl Segment {
+    const fn new(first_id: u32) -> Self {
+        Self { first_id, entries: BTreeMap::new(), last_id: None, written: 0, data_len: 0 }
+    }
+
+    fn insert(&mut self, entry: IndexEntry) {
+        self.last_id = Some(entry.id);
+        self.written += 1;
+        self.data_len = self.data_len.max(entry.end());
+        self.entries.insert(entry.id, entry);
+    }
+
+    fn summary(&self) -> WalSegment {
+        let lowest_block = self
+            .entries
+            .values()
+            .filter_map(|entry| {
+                entry.committed_blocks.first().map(|block| block.block.number).or(entry.max_block)
+            })
+            .min();
+        let highest_block = self.entries.values().filter_map(|entry| entry.max_block).max();
+
+        WalSegment {
+            first_id: self.first_id,
+            notifications: self.entries.len(),
+            written: self.written,
+            size_bytes: self.data_len,
+            block_range: lowest_block.zip(highest_block).map(|(low, high)| low..=high),
+        }
+    }
+}
+
+/// Index entry of a notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct IndexEntry {
+    /// ID of the notification.
+    pub(super) id: u32,
+    /// Offset of the notification frame in the segment data file.
+    offset: u64,
+    /// Length of the notification frame in bytes.
+    pub(super) len: u32,
+    /// The highest block of the notification, chosen between the committed and reverted chains.
+    pub(super) max_block: Option<BlockNumber>,
+    /// The committed blocks of the notification.
+    pub(super) committed_blocks: Vec<CachedBlock>,
+}
+
+impl IndexEntry {
+    const fn end(&self) -> u64 {
+        self.offset + self.len as u64
+    }
+}
+
+/// A record of the segment index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IndexRecord {
+    /// The notification was written to the segment.
+    Insert(IndexEntry),
+    /// The notification with the given ID was removed.
+    Remove(u32),
+}
+
+impl IndexRecord {
+    const INSERT: u8 = 0;
+    const REMOVE: u8 = 1;
+
+    /// Size of an encoded committed block: number, hash and parent hash.
+    const COMMITTED_BLOCK_SIZE: usize = 8 + 32 + 32;
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::Insert(entry) => {
+                buf.put_u8(Self::INSERT);
+                buf.put_u32_le(entry.id);
+                buf.put_u64_le(entry.offset);
+                buf.put_u32_le(entry.len);
+                buf.put_u8(entry.max_block.is_some() as u8);
+                buf.put_u64_le(entry.max_block.unwrap_or_default());
+                buf.put_u32_le(entry.committed_blocks.len() as u32);
+                for block in &entry.committed_blocks {
+                    buf.put_u64_le(block.block.number);
+                    buf.put_slice(block.block.hash.as_slice());
+                    buf.put_slice(block.parent_hash.as_slice());
+                }
+            }
+            Self::Remove(id) => {
+                buf.put_u8(Self::REMOVE);
+                buf.put_u32_le(*id);
+            }
+        }
+        buf
+    }
+
+    fn decode(mut buf: &[u8]) -> eyre::Result<Self> {
+        eyre::ensure!(buf.remaining() >= 5, "index record too short");
+        let kind = buf.get_u8();
+        let id = buf.get_u32_le();
+
+        match kind {
+            Self::INSERT => {
+                eyre::ensure!(buf.remaining() >= 25, "index record too short");
+                let offset = buf.get_u64_le();
+                let len = buf.get_u32_le();
+                let has_max_block = buf.get_u8() != 0;
+                let max_block = buf.get_u64_le();
+                let count = buf.get_u32_le() as usize;
+                eyre::ensure!(
+                    buf.remaining() == count * Self::COMMITTED_BLOCK_SIZE,
+                    "index record length mismatch"
+                );
+
+                let committed_blocks = (0..count)
+                    .map(|_| {
+                        let number = buf.get_u64_le();
+                        let hash = B256::from_slice(&buf[..32]);
+                        let parent_hash = B256::from_slice(&buf[32..64]);
+                        buf.advance(64);
+                        CachedBlock { block: (number, hash).into(), parent_hash }
+                    })
+                    .collect();
+
+                Ok(Self::Insert(IndexEntry {
+                    id,
+                    offset,
+                    len,
+                    max_block: has_max_block.then_some(max_block),
+                    committed_blocks,
+                }))
+            }
+            Self::REMOVE => Ok(Self::Remove(id)),
+            kind => eyre::bail!("unknown index record kind {kind}"),
+        }
+    }
+}
+
+/// Appends a frame with the given payload to the buffer.
+fn encode_frame(payload: &[u8], buf: &mut Vec<u8>) {
+    buf.put_u32_le(payload.len() as u32);
+    buf.put_u32_le(CRC32.checksum(payload));
+    buf.put_slice(payload);
+}
 
-        Ok(file_path.metadata()?.len())
+/// Decodes the frame at the start of the buffer.
+///
+/// Returns the payload and the length of the whole frame, or `None` if the frame is incomplete
+/// or its checksum doesn't match.
+fn decode_frame(mut buf: &[u8]) -> Option<(&[u8], usize)> {
+    if buf.len() < FRAME_HEADER_SIZE {
+        return None
     }
+    let len = buf.get_u32_le() as usize;
+    let checksum = buf.get_u32_le();
+
+    let payload = buf.get(..len)?;
+    (CRC32.checksum(payload) == checksum).then_some((payload, FRAME_HEADER_SIZE + len))
+}
+
+/// Appends the bytes to the file, creating it if it doesn't exist, and syncs it to disk.
+fn append(path: &Path, bytes: &[u8]) -> eyre::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| reth_fs_util::FsPathError::open(err, path))?;
+    file.write_all(bytes).map_err(|err| reth_fs_util::FsPathError::write(err, path))?;
+    file.sync_data().map_err(|err| reth_fs_util::FsPathError::write(err, path))?;
+    Ok(())
+}
+
+/// Truncates the file to the given length.
+fn truncate(path: &Path, len: u64) -> eyre::Result<()> {
+    let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(|err| reth_fs_util::FsPathError::open(err, path))?;
+    file.set_len(len).map_err(|err| reth_fs_util::FsPathError::write(err, path))?;
+    file.sync_all().map_err(|err| reth_fs_util::FsPathError::write(err, path))?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{fs::OpenOptions, io::Write, sync::Arc};
 
     use eyre::OptionExt;
     use reth_exex_types::ExExNotification;
@@ -206,4 +676,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_torn_write_and_removal() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage = Storage::new(&temp_dir)?;
+
+        let notifications = (0..3)
+            .map(|number| {
+                let block = random_block(&mut rng, number, Default::default())
+                    .seal_with_senders()
+                    .ok_or_eyre("failed to recover senders")?;
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        for (id, notification) in notifications.iter().enumerate() {
+            storage.write_notification(id as u32, notification)?;
+        }
+        storage.remove_notifications([0])?;
+
+        // Simulate a crash in the middle of writing the next notification
+        for path in [storage.data_path(0), storage.index_path(0)] {
+            OpenOptions::new().append(true).open(path)?.write_all(&[0xff; 16])?;
+        }
+
+        let storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.ids_range()?, Some(1..=2));
+        assert_eq!(
+            storage
+                .iter_notifications(0..=2)
+                .map(|entry| Ok(entry?.2))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            notifications[1..]
+        );
+
+        // Removing all notifications deletes the segment
+        storage.remove_notifications([1, 2])?;
+        assert_eq!(storage.ids_range()?, None);
+        assert!(!storage.data_path(0).exists());
+        assert!(!storage.index_path(0).exists());
+
+        Ok(())
+    }
 }
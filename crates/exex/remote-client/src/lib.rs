@@ -0,0 +1,95 @@
+//! Client for out-of-process ExExes.
+//!
+//! Connects to the Unix socket of a node running the remote ExEx bridge, receives
+//! [`ExExNotification`]s and acknowledges processed blocks back to the node.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use reth_exex_remote_client::RemoteExExClient;
+//!
+//! # async fn run() -> eyre::Result<()> {
+//! let mut client = RemoteExExClient::connect("/tmp/reth-exex.sock", None).await?;
+//! while let Some(notification) = client.next().await {
+//!     let notification = notification?;
+//!     if let Some(committed_chain) = notification.committed_chain() {
+//!         // Process the chain, then let the node know it can prune it.
+//!         client.send_finished_height(committed_chain.tip().num_hash()).await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use std::{
+    path::Path,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use alloy_eips::BlockNumHash;
+use bytes::Bytes;
+use futures::{SinkExt, Stream, StreamExt};
+use reth_exex_types::remote::{decode_notification, RemoteExExRequest, MAX_FRAME_LENGTH};
+use tokio::net::UnixStream;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+pub use reth_exex_types::{ExExHead, ExExNotification};
+
+/// A connection to the remote ExEx bridge of a node.
+///
+/// The client is a [`Stream`] of [`ExExNotification`]s. It ends when the node closes the
+/// connection, after which a new client can be connected with the last processed block as the
+/// head to resume from the node's WAL.
+#[derive(Debug)]
+pub struct RemoteExExClient {
+    framed: Framed<UnixStream, LengthDelimitedCodec>,
+}
+
+impl RemoteExExClient {
+    /// Connects to the node's socket and subscribes to notifications.
+    ///
+    /// If `head` is set, the node first replays notifications for blocks above it that are still
+    /// in its WAL. Otherwise only notifications committed after subscribing are received.
+    pub async fn connect(path: impl AsRef<Path>, head: Option<ExExHead>) -> eyre::Result<Self> {
+        let stream = UnixStream::connect(path).await?;
+        let codec = LengthDelimitedCodec::builder().max_frame_length(MAX_FRAME_LENGTH).new_codec();
+        let mut client = Self { framed: Framed::new(stream, codec) };
+
+        client.send(RemoteExExRequest::Subscribe { head }).await?;
+
+        Ok(client)
+    }
+
+    /// Notifies the node that all blocks up to and including `height` have been processed and
+    /// are safe to prune.
+    pub async fn send_finished_height(&mut self, height: BlockNumHash) -> eyre::Result<()> {
+        self.send(RemoteExExRequest::FinishedHeight(height)).await
+    }
+
+    async fn send(&mut self, request: RemoteExExRequest) -> eyre::Result<()> {
+        self.framed.send(Bytes::from(request.encode()?)).await?;
+        Ok(())
+    }
+}
+
+impl Stream for RemoteExExClient {
+    type Item = eyre::Result<ExExNotification>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(frame) = ready!(self.get_mut().framed.poll_next_unpin(cx)) else {
+            return Poll::Ready(None)
+        };
+
+        Poll::Ready(Some(
+            frame.map_err(eyre::Report::from).and_then(|frame| Ok(decode_notification(&frame)?)),
+        ))
+    }
+}
@@ -11,6 +11,8 @@
 mod finished_height;
 mod head;
 mod notification;
+#[cfg(feature = "remote")]
+pub mod remote;
 
 pub use finished_height::FinishedExExHeight;
 pub use head::ExExHead;
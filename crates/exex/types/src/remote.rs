@@ -0,0 +1,81 @@
+//! Wire protocol spoken between the node and out-of-process ExExes.
+//!
+//! The node listens on a local socket and every message in both directions is sent as a single
+//! length-delimited frame (a big-endian `u32` length prefix followed by the payload). Payloads
+//! are MessagePack-encoded, the same encoding the ExEx WAL uses on disk.
+//!
+//! A session looks as follows:
+//! 1. The client sends [`RemoteExExRequest::Subscribe`] with the last block it has processed.
+//! 2. The node replays notifications above that block from the WAL and then streams live
+//!    [`ExExNotification`]s.
+//! 3. The client acknowledges processed blocks with [`RemoteExExRequest::FinishedHeight`], which
+//!    the node forwards to the ExEx manager so the WAL and the pruner can advance.
+
+use alloy_eips::BlockNumHash;
+
+use crate::{ExExHead, ExExNotification};
+
+/// Maximum length of a single frame. Notifications carry full blocks and their execution
+/// outcome, so this is deliberately generous.
+pub const MAX_FRAME_LENGTH: usize = 256 * 1024 * 1024;
+
+/// A message sent from an out-of-process ExEx to the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RemoteExExRequest {
+    /// Starts streaming notifications. Must be the first message of a session.
+    ///
+    /// If `head` is set, notifications for blocks above it that are still in the WAL are
+    /// replayed before live notifications. Otherwise only live notifications are sent.
+    Subscribe {
+        /// The last block the ExEx has fully processed.
+        head: Option<ExExHead>,
+    },
+    /// The ExEx has processed all blocks up to and including the given one.
+    FinishedHeight(BlockNumHash),
+}
+
+impl RemoteExExRequest {
+    /// Encodes the request into a frame payload.
+    pub fn encode(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
+
+    /// Decodes the request from a frame payload.
+    pub fn decode(buf: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(buf)
+    }
+}
+
+/// Encodes the notification into a frame payload.
+pub fn encode_notification(
+    notification: &ExExNotification,
+) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    rmp_serde::to_vec(&crate::serde_bincode_compat::ExExNotification::from(notification))
+}
+
+/// Decodes the notification from a frame payload.
+pub fn decode_notification(buf: &[u8]) -> Result<ExExNotification, rmp_serde::decode::Error> {
+    rmp_serde::from_slice::<crate::serde_bincode_compat::ExExNotification<'_>>(buf).map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn request_roundtrip() {
+        let requests = [
+            RemoteExExRequest::Subscribe { head: None },
+            RemoteExExRequest::Subscribe {
+                head: Some(ExExHead { block: BlockNumHash::new(10, B256::repeat_byte(0x11)) }),
+            },
+            RemoteExExRequest::FinishedHeight(BlockNumHash::new(11, B256::repeat_byte(0x11))),
+        ];
+
+        for request in requests {
+            let encoded = request.encode().unwrap();
+            assert_eq!(RemoteExExRequest::decode(&encoded).unwrap(), request);
+        }
+    }
+}
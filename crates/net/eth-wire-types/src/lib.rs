@@ -40,3 +40,6 @@ pub use disconnect_reason::*;
 
 pub mod capability;
 pub use capability::*;
+
+#[cfg(feature = "telos")]
+pub mod telos;
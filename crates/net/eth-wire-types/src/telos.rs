@@ -0,0 +1,131 @@
+//! Telos wire encoding of block headers.
+//!
+//! The `TelosBlockExtension` of a header is not part of the header RLP, because the RLP determines
+//! the block hash. Telos peers send every header of a [`BlockHeaders`] response as a list of the
+//! header and its extension instead, so downloaded headers can be executed and stored as they are.
+
+use crate::BlockHeaders;
+use alloy_rlp::{length_of_length, Decodable, Encodable, Header as RlpHeader};
+use reth_primitives::Header;
+
+/// Returns the payload length of a header with its extension.
+fn wire_header_payload_length(header: &Header) -> usize {
+    header.length() + header.telos_block_extension.length()
+}
+
+/// Returns the encoded length of a header with its extension.
+fn wire_header_length(header: &Header) -> usize {
+    let payload_length = wire_header_payload_length(header);
+    payload_length + length_of_length(payload_length)
+}
+
+impl Encodable for BlockHeaders {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        let payload_length = self.0.iter().map(wire_header_length).sum();
+        RlpHeader { list: true, payload_length }.encode(out);
+        for header in &self.0 {
+            RlpHeader { list: true, payload_length: wire_header_payload_length(header) }
+                .encode(out);
+            header.encode(out);
+            header.telos_block_extension.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.0.iter().map(wire_header_length).sum::<usize>();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for BlockHeaders {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = RlpHeader::decode_bytes(buf, true)?;
+
+        let mut headers = Vec::new();
+        while !payload.is_empty() {
+            let mut item = RlpHeader::decode_bytes(&mut payload, true)?;
+            let mut header = Header::decode(&mut item)?;
+            header.telos_block_extension = Decodable::decode(&mut item)?;
+            if !item.is_empty() {
+                return Err(alloy_rlp::Error::UnexpectedLength)
+            }
+            headers.push(header);
+        }
+
+        Ok(Self(headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::RequestPair;
+    use alloy_primitives::{hex, B256, U256};
+
+    #[test]
+    fn block_headers_carry_extension() {
+        let mut header = Header { number: 180698824, ..Default::default() };
+        header.telos_block_extension.starting_gas_price = U256::from(500_000_000_000u64);
+        header.telos_block_extension.starting_revision_number = 1;
+        let headers = BlockHeaders(vec![header.clone(), Header::default()]);
+
+        let mut buf = Vec::new();
+        headers.encode(&mut buf);
+        assert_eq!(buf.len(), headers.length());
+
+        let decoded = BlockHeaders::decode(&mut buf.as_slice()).unwrap();
+        assert_eq!(decoded, headers);
+        // the extension doesn't change the block hash
+        assert_eq!(
+            decoded.0[0].hash_slow(),
+            Header { number: 180698824, ..Default::default() }.hash_slow()
+        );
+    }
+
+    // The header of the EIP-2481 test vector in `blocks.rs`, with a Telos extension
+    fn eip_2481_header() -> Header {
+        let mut header = Header {
+            ommers_hash: B256::ZERO,
+            state_root: B256::ZERO,
+            transactions_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            difficulty: U256::from(0x8aeu64),
+            number: 0xd05u64,
+            gas_limit: 0x115c,
+            gas_used: 0x15b3,
+            timestamp: 0x1a0au64,
+            extra_data: hex!("7788")[..].into(),
+            ..Default::default()
+        };
+        header.telos_block_extension.starting_gas_price = U256::from(500_000_000_000u64);
+        header.telos_block_extension.starting_revision_number = 1;
+        header
+    }
+
+    // [ 0x0457 = 1111, [ [ header, [ 500 gwei, 1, [], [] ] ] ] ]
+    const EIP_2481_TELOS_BLOCK_HEADERS: [u8; 530] = hex!("f9020f820457f90209f90206f901f9a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000940000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008208ae820d0582115c8215b3821a0a827788a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c985746a52880001c0c0");
+
+    #[test]
+    fn encode_block_header() {
+        let mut data = vec![];
+        RequestPair { request_id: 1111, message: BlockHeaders(vec![eip_2481_header()]) }
+            .encode(&mut data);
+        assert_eq!(data, EIP_2481_TELOS_BLOCK_HEADERS);
+    }
+
+    #[test]
+    fn decode_block_header() {
+        let result = RequestPair::<BlockHeaders>::decode(&mut &EIP_2481_TELOS_BLOCK_HEADERS[..]);
+        assert_eq!(
+            result.unwrap(),
+            RequestPair { request_id: 1111, message: BlockHeaders(vec![eip_2481_header()]) }
+        );
+    }
+
+    #[test]
+    fn decode_block_header_without_extension() {
+        // the plain EIP-2481 vector, whose header isn't wrapped with an extension
+        let data = hex!("f90202820457f901fcf901f9a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000940000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008208ae820d0582115c8215b3821a0a827788a00000000000000000000000000000000000000000000000000000000000000000880000000000000000");
+        assert!(RequestPair::<BlockHeaders>::decode(&mut &data[..]).is_err());
+    }
+}
@@ -129,14 +129,16 @@ impl TransactionsHandle {
 
     /// Manually propagate the transaction hash to a specific peer.
     ///
-    /// Note: this only propagates if the pool contains the transaction.
+    /// Note: this only propagates if the pool contains the transaction and it may be propagated,
+    /// see [`ValidPoolTransaction::propagate`].
     pub fn propagate_hash_to(&self, hash: TxHash, peer: PeerId) {
         self.propagate_hashes_to(Some(hash), peer)
     }
 
     /// Manually propagate the transaction hashes to a specific peer.
     ///
-    /// Note: this only propagates the transactions that are known to the pool.
+    /// Note: this only propagates the transactions that are known to the pool and may be
+    /// propagated.
     pub fn propagate_hashes_to(&self, hash: impl IntoIterator<Item = TxHash>, peer: PeerId) {
         self.send(TransactionsCommand::PropagateHashesTo(hash.into_iter().collect(), peer))
     }
@@ -149,6 +151,9 @@ impl TransactionsHandle {
     }
 
     /// Manually propagate full transactions to a specific peer.
+    ///
+    /// Note: this only propagates the transactions that are known to the pool and may be
+    /// propagated.
     pub fn propagate_transactions_to(&self, transactions: Vec<TxHash>, peer: PeerId) {
         self.send(TransactionsCommand::PropagateTransactionsTo(transactions, peer))
     }
@@ -402,7 +407,12 @@ where
         // This fetches all transaction from the pool, including the 4844 blob transactions but
         // __without__ their sidecar, because 4844 transactions are only ever announced as hashes.
         let propagated = self.propagate_transactions(
-            self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect(),
+            self.pool
+                .get_all(hashes)
+                .into_iter()
+                .filter(|tx| tx.propagate)
+                .map(PropagateTransaction::new)
+                .collect(),
         );
 
         // notify pool so events get fired
@@ -511,7 +521,12 @@ where
         // filter all transactions unknown to the peer
         let mut full_transactions = FullTransactionsBuilder::new(peer.version);
 
-        let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::new);
+        let to_propagate = self
+            .pool
+            .get_all(txs)
+            .into_iter()
+            .filter(|tx| tx.propagate)
+            .map(PropagateTransaction::new);
 
         // Iterate through the transactions to propagate and fill the hashes and full transaction
         for tx in to_propagate {
@@ -569,8 +584,13 @@ where
                 return
             };
 
-            let to_propagate: Vec<PropagateTransaction> =
-                self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect();
+            let to_propagate: Vec<PropagateTransaction> = self
+                .pool
+                .get_all(hashes)
+                .into_iter()
+                .filter(|tx| tx.propagate)
+                .map(PropagateTransaction::new)
+                .collect();
 
             let mut propagated = PropagatedTransactions::default();
 
@@ -0,0 +1,221 @@
+//! Requesting state from peers over `snap/1`.
+
+use crate::message::{
+    AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+    StorageRanges, TrieNodes,
+};
+use futures::{future::BoxFuture, FutureExt};
+use parking_lot::RwLock;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError, RequestResult},
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::trace;
+
+/// Time after which a request to a peer fails with [`RequestError::Timeout`].
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The future of a `snap/1` request, which resolves to the response and the peer that sent it.
+pub type SnapResponseFut<T> = BoxFuture<'static, PeerRequestResult<T>>;
+
+/// A client requesting state from peers over `snap/1`.
+///
+/// The request IDs are assigned by the client, any ID set in the request is overwritten.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: DownloadClient {
+    /// Requests a range of accounts.
+    fn get_account_range(&self, request: GetAccountRange) -> SnapResponseFut<AccountRange>;
+
+    /// Requests the storage ranges of accounts.
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapResponseFut<StorageRanges>;
+
+    /// Requests contract bytecodes.
+    fn get_byte_codes(&self, request: GetByteCodes) -> SnapResponseFut<ByteCodes>;
+
+    /// Requests trie nodes.
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapResponseFut<TrieNodes>;
+}
+
+/// A request to a peer, sent to its [`SnapConnection`](crate::SnapConnection).
+#[derive(Debug)]
+pub(crate) enum SnapPeerRequest {
+    /// Request for a range of accounts.
+    GetAccountRange {
+        /// The request.
+        request: GetAccountRange,
+        /// The channel sender for the response.
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request for storage ranges.
+    GetStorageRanges {
+        /// The request.
+        request: GetStorageRanges,
+        /// The channel sender for the response.
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request for bytecodes.
+    GetByteCodes {
+        /// The request.
+        request: GetByteCodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    },
+    /// Request for trie nodes.
+    GetTrieNodes {
+        /// The request.
+        request: GetTrieNodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    },
+}
+
+/// The connected peers supporting `snap/1`, which implements [`SnapClient`].
+///
+/// The [`SnapConnection`](crate::SnapConnection)s register themselves if the
+/// [`SnapProtocolHandler`](crate::SnapProtocolHandler) was created with
+/// [`SnapProtocolHandler::with_peers`](crate::SnapProtocolHandler::with_peers). Requests are sent
+/// to the peers in turn, peers reported for bad messages are skipped for the rest of their
+/// connection.
+#[derive(Debug, Clone, Default)]
+pub struct SnapPeers {
+    inner: Arc<SnapPeersInner>,
+}
+
+#[derive(Debug, Default)]
+struct SnapPeersInner {
+    /// Connected peers and the channels to their connections.
+    peers: RwLock<Vec<SnapPeer>>,
+    /// Index of the peer to send the next request to.
+    next_peer: AtomicUsize,
+    /// ID of the next request.
+    next_request_id: AtomicU64,
+}
+
+#[derive(Debug)]
+struct SnapPeer {
+    peer_id: PeerId,
+    to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    /// Whether the peer sent a bad message.
+    bad: bool,
+}
+
+impl SnapPeers {
+    /// Registers the connection of a peer, replacing a previous connection of the peer.
+    pub(crate) fn register(
+        &self,
+        peer_id: PeerId,
+        to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    ) {
+        let mut peers = self.inner.peers.write();
+        peers.retain(|peer| peer.peer_id != peer_id);
+        peers.push(SnapPeer { peer_id, to_connection, bad: false });
+    }
+
+    /// Removes the connection of a peer, if it wasn't replaced by a newer one.
+    pub(crate) fn unregister(
+        &self,
+        peer_id: PeerId,
+        to_connection: &mpsc::UnboundedSender<SnapPeerRequest>,
+    ) {
+        self.inner.peers.write().retain(|peer| {
+            peer.peer_id != peer_id || !peer.to_connection.same_channel(to_connection)
+        });
+    }
+
+    /// Returns the next request ID.
+    fn next_request_id(&self) -> u64 {
+        self.inner.next_request_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Sends the request to the next peer that isn't known to be bad, and returns the future of
+    /// the response.
+    fn send_request<T: Send + 'static>(
+        &self,
+        mut request: impl FnMut(oneshot::Sender<RequestResult<T>>) -> SnapPeerRequest,
+    ) -> SnapResponseFut<T> {
+        let peers = self.inner.peers.read();
+        let start = self.inner.next_peer.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..peers.len() {
+            let peer = &peers[(start + offset) % peers.len()];
+            if peer.bad {
+                continue
+            }
+
+            let (tx, rx) = oneshot::channel();
+            if peer.to_connection.send(request(tx)).is_err() {
+                // the connection is closing
+                continue
+            }
+            let peer_id = peer.peer_id;
+            return async move {
+                match tokio::time::timeout(SNAP_REQUEST_TIMEOUT, rx).await {
+                    Ok(Ok(response)) => response.map(|response| WithPeerId::new(peer_id, response)),
+                    Ok(Err(_)) => Err(RequestError::ConnectionDropped),
+                    Err(_) => Err(RequestError::Timeout),
+                }
+            }
+            .boxed()
+        }
+
+        trace!(target: "net::snap", "No peer to send the snap request to");
+        futures::future::ready(Err(RequestError::UnsupportedCapability)).boxed()
+    }
+}
+
+impl DownloadClient for SnapPeers {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        if let Some(peer) =
+            self.inner.peers.write().iter_mut().find(|peer| peer.peer_id == peer_id)
+        {
+            trace!(target: "net::snap", %peer_id, "Skipping peer after bad snap response");
+            peer.bad = true;
+        }
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.inner.peers.read().iter().filter(|peer| !peer.bad).count()
+    }
+}
+
+impl SnapClient for SnapPeers {
+    fn get_account_range(&self, mut request: GetAccountRange) -> SnapResponseFut<AccountRange> {
+        request.request_id = self.next_request_id();
+        self.send_request(|response| SnapPeerRequest::GetAccountRange {
+            request: request.clone(),
+            response,
+        })
+    }
+
+    fn get_storage_ranges(&self, mut request: GetStorageRanges) -> SnapResponseFut<StorageRanges> {
+        request.request_id = self.next_request_id();
+        self.send_request(|response| SnapPeerRequest::GetStorageRanges {
+            request: request.clone(),
+            response,
+        })
+    }
+
+    fn get_byte_codes(&self, mut request: GetByteCodes) -> SnapResponseFut<ByteCodes> {
+        request.request_id = self.next_request_id();
+        self.send_request(|response| SnapPeerRequest::GetByteCodes {
+            request: request.clone(),
+            response,
+        })
+    }
+
+    fn get_trie_nodes(&self, mut request: GetTrieNodes) -> SnapResponseFut<TrieNodes> {
+        request.request_id = self.next_request_id();
+        self.send_request(|response| SnapPeerRequest::GetTrieNodes {
+            request: request.clone(),
+            response,
+        })
+    }
+}
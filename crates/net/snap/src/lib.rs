@@ -0,0 +1,54 @@
+//! Server and client of the `snap/1` state sync protocol.
+//!
+//! Serves account ranges, storage ranges, bytecodes and trie nodes of recent state roots to peers,
+//! from the hashed state and trie tables, so they can snap sync from the node.
+//!
+//! The [`SnapProtocolHandler`] is installed as an additional `RLPx` sub-protocol of the network and
+//! forwards the requests of peers to the [`SnapRequestHandler`], which answers them from the
+//! database:
+//!
+//! ```ignore
+//! let (tx, rx) = tokio::sync::mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+//! network.add_rlpx_sub_protocol(SnapProtocolHandler::new(tx).into_rlpx_sub_protocol());
+//! let request_handler = SnapRequestHandler::new(provider, rx);
+//! task_executor.spawn_critical_blocking("snap request handler", request_handler);
+//! ```
+//!
+//! To request state from peers, the connections are registered with [`SnapPeers`], which
+//! implements the [`SnapClient`]. The responses can be checked with [`verify_range_proof`]:
+//!
+//! ```ignore
+//! let peers = SnapPeers::default();
+//! network.add_rlpx_sub_protocol(
+//!     SnapProtocolHandler::default().with_peers(peers.clone()).into_rlpx_sub_protocol(),
+//! );
+//! let range = peers.get_account_range(request).await?;
+//! ```
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub mod message;
+mod protocol;
+mod server;
+pub mod trie;
+mod verify;
+
+pub use client::{SnapClient, SnapPeers, SnapResponseFut, SNAP_REQUEST_TIMEOUT};
+pub use message::{snap_capability, snap_protocol, SnapMessage, SnapMessageId};
+pub use protocol::{SnapConnection, SnapConnectionHandler, SnapProtocolHandler};
+pub use server::{IncomingSnapRequest, SnapRequestHandler, SNAP_SERVE_RECENT_BLOCKS};
+pub use verify::{verify_range_proof, RangeProofError};
+
+/// Capacity of the channel between the [`SnapProtocolHandler`] and the [`SnapRequestHandler`].
+///
+/// Requests are dropped while the channel is full.
+pub const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
@@ -0,0 +1,410 @@
+//! Messages of the `snap/1` protocol.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use alloy_primitives::{
+    bytes::{BufMut, BytesMut},
+    Bytes, B256, U256,
+};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_eth_wire::{protocol::Protocol, Capability};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, Account, KECCAK_EMPTY};
+
+/// Returns the `snap/1` capability.
+pub const fn snap_capability() -> Capability {
+    Capability::new_static("snap", 1)
+}
+
+/// Returns the `snap/1` protocol, which has 8 messages.
+pub const fn snap_protocol() -> Protocol {
+    Protocol::new(snap_capability(), 8)
+}
+
+/// Message IDs of the `snap/1` protocol, relative to the protocol's offset.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapMessageId {
+    /// Requests a range of accounts.
+    GetAccountRange = 0x00,
+    /// Response to [`SnapMessageId::GetAccountRange`].
+    AccountRange = 0x01,
+    /// Requests the storage ranges of accounts.
+    GetStorageRanges = 0x02,
+    /// Response to [`SnapMessageId::GetStorageRanges`].
+    StorageRanges = 0x03,
+    /// Requests contract bytecodes.
+    GetByteCodes = 0x04,
+    /// Response to [`SnapMessageId::GetByteCodes`].
+    ByteCodes = 0x05,
+    /// Requests trie nodes.
+    GetTrieNodes = 0x06,
+    /// Response to [`SnapMessageId::GetTrieNodes`].
+    TrieNodes = 0x07,
+}
+
+impl TryFrom<u8> for SnapMessageId {
+    type Error = u8;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Ok(match id {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return Err(id),
+        })
+    }
+}
+
+/// Requests the accounts of the state trie with the given root, starting at `starting_hash`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetAccountRange {
+    /// ID of the request, echoed in the response.
+    pub request_id: u64,
+    /// Root of the state trie to serve.
+    pub root_hash: B256,
+    /// Hash of the first account to return.
+    pub starting_hash: B256,
+    /// Hash of the account after which to stop.
+    pub limit_hash: B256,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// Consecutive accounts of the state trie, along with the proofs of the requested origin and the
+/// last returned account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountRange {
+    /// ID of the request.
+    pub request_id: u64,
+    /// Accounts, ordered by hash.
+    pub accounts: Vec<AccountData>,
+    /// Trie nodes proving the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account of an [`AccountRange`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountData {
+    /// Hash of the account address.
+    pub hash: B256,
+    /// The account.
+    pub account: SlimAccount,
+}
+
+/// An account in the slim format of the protocol, where the empty storage root and the empty code
+/// hash are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct SlimAccount {
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Balance of the account.
+    pub balance: U256,
+    /// Storage root, empty if the account has no storage.
+    pub storage_root: Bytes,
+    /// Code hash, empty if the account has no code.
+    pub code_hash: Bytes,
+}
+
+impl SlimAccount {
+    /// Creates the slim account of the account with the given storage root.
+    pub fn new(account: Account, storage_root: B256) -> Self {
+        let code_hash = account.get_bytecode_hash();
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root: if storage_root == EMPTY_ROOT_HASH {
+                Bytes::new()
+            } else {
+                storage_root.into()
+            },
+            code_hash: if code_hash == KECCAK_EMPTY { Bytes::new() } else { code_hash.into() },
+        }
+    }
+
+    /// Returns the storage root of the account.
+    pub fn storage_root(&self) -> B256 {
+        if self.storage_root.is_empty() {
+            EMPTY_ROOT_HASH
+        } else {
+            B256::left_padding_from(&self.storage_root)
+        }
+    }
+
+    /// Returns the code hash of the account.
+    pub fn code_hash(&self) -> B256 {
+        if self.code_hash.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            B256::left_padding_from(&self.code_hash)
+        }
+    }
+}
+
+/// Requests the storage slots of the given accounts.
+///
+/// `starting_hash` and `limit_hash` only apply to the first account, and are empty to request the
+/// whole storage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetStorageRanges {
+    /// ID of the request, echoed in the response.
+    pub request_id: u64,
+    /// Root of the state trie to serve.
+    pub root_hash: B256,
+    /// Hashes of the accounts whose storage to return.
+    pub account_hashes: Vec<B256>,
+    /// Hash of the first storage slot to return.
+    pub starting_hash: Bytes,
+    /// Hash of the storage slot after which to stop.
+    pub limit_hash: Bytes,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// Storage slots of consecutive accounts, along with the proof of the last range if it's
+/// incomplete or doesn't start at the beginning of the storage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageRanges {
+    /// ID of the request.
+    pub request_id: u64,
+    /// Storage slots of each served account, ordered by hash.
+    pub slots: Vec<Vec<StorageData>>,
+    /// Trie nodes proving the last range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot of [`StorageRanges`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageData {
+    /// Hash of the storage slot.
+    pub hash: B256,
+    /// RLP encoded value of the storage slot.
+    pub data: Bytes,
+}
+
+/// Requests contract bytecodes by hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetByteCodes {
+    /// ID of the request, echoed in the response.
+    pub request_id: u64,
+    /// Hashes of the requested bytecodes.
+    pub hashes: Vec<B256>,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The requested bytecodes, in request order with unavailable ones skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ByteCodes {
+    /// ID of the request.
+    pub request_id: u64,
+    /// The bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests trie nodes by path.
+///
+/// Each path set is either a single compact encoded path into the state trie, or the hash of an
+/// account followed by compact encoded paths into its storage trie.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetTrieNodes {
+    /// ID of the request, echoed in the response.
+    pub request_id: u64,
+    /// Root of the state trie to serve.
+    pub root_hash: B256,
+    /// The requested path sets.
+    pub paths: Vec<Vec<Bytes>>,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The requested trie nodes, in request order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct TrieNodes {
+    /// ID of the request.
+    pub request_id: u64,
+    /// RLP encoded trie nodes, empty for unavailable nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// A message of the `snap/1` protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapMessage {
+    /// Request for a range of accounts.
+    GetAccountRange(GetAccountRange),
+    /// Response with a range of accounts.
+    AccountRange(AccountRange),
+    /// Request for storage ranges.
+    GetStorageRanges(GetStorageRanges),
+    /// Response with storage ranges.
+    StorageRanges(StorageRanges),
+    /// Request for bytecodes.
+    GetByteCodes(GetByteCodes),
+    /// Response with bytecodes.
+    ByteCodes(ByteCodes),
+    /// Request for trie nodes.
+    GetTrieNodes(GetTrieNodes),
+    /// Response with trie nodes.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the ID of the message.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the ID of the request or response.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns the message ID followed by the RLP encoded message.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from its message ID followed by the RLP encoded message.
+    pub fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        *buf = rest;
+        let id = SnapMessageId::try_from(id)
+            .map_err(|_| alloy_rlp::Error::Custom("unknown snap message id"))?;
+        Ok(match id {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn message_roundtrip() {
+        let messages = [
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::with_last_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::with_last_byte(2),
+                    account: SlimAccount::new(
+                        Account { nonce: 1, balance: U256::from(10), bytecode_hash: None },
+                        EMPTY_ROOT_HASH,
+                    ),
+                }],
+                proof: vec![Bytes::from_static(&[0xc0])],
+            }),
+            SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 2,
+                root_hash: B256::with_last_byte(1),
+                account_hashes: vec![B256::with_last_byte(2)],
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: 1024,
+            }),
+            SnapMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![vec![StorageData {
+                    hash: B256::with_last_byte(3),
+                    data: Bytes::from_static(&[0x2a]),
+                }]],
+                proof: Vec::new(),
+            }),
+            SnapMessage::GetByteCodes(GetByteCodes {
+                request_id: 3,
+                hashes: vec![B256::with_last_byte(4)],
+                response_bytes: 1024,
+            }),
+            SnapMessage::ByteCodes(ByteCodes {
+                request_id: 3,
+                codes: vec![Bytes::from_static(&hex!("6000"))],
+            }),
+            SnapMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 4,
+                root_hash: B256::with_last_byte(1),
+                paths: vec![vec![Bytes::from_static(&[0x00])]],
+                response_bytes: 1024,
+            }),
+            SnapMessage::TrieNodes(TrieNodes {
+                request_id: 4,
+                nodes: vec![Bytes::from_static(&[0xc0])],
+            }),
+        ];
+
+        for (id, message) in messages.into_iter().enumerate() {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], id as u8);
+            assert_eq!(SnapMessage::decode(&mut &encoded[..]).unwrap(), message);
+        }
+
+        assert!(SnapMessage::decode(&mut &[0x08, 0xc0][..]).is_err());
+        assert!(SnapMessage::decode(&mut &[][..]).is_err());
+    }
+
+    #[test]
+    fn slim_account() {
+        let account = SlimAccount::new(Account::default(), EMPTY_ROOT_HASH);
+        assert!(account.storage_root.is_empty());
+        assert!(account.code_hash.is_empty());
+        assert_eq!(account.storage_root(), EMPTY_ROOT_HASH);
+        assert_eq!(account.code_hash(), KECCAK_EMPTY);
+
+        let code_hash = B256::with_last_byte(1);
+        let storage_root = B256::with_last_byte(2);
+        let account = SlimAccount::new(
+            Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            storage_root,
+        );
+        assert_eq!(account.storage_root(), storage_root);
+        assert_eq!(account.code_hash(), code_hash);
+    }
+}
@@ -0,0 +1,379 @@
+//! The `snap/1` `RLPx` sub-protocol.
+
+use crate::{
+    client::{SnapPeerRequest, SnapPeers},
+    message::{
+        snap_protocol, AccountRange, ByteCodes, SnapMessage, StorageRanges, TrieNodes,
+    },
+    server::IncomingSnapRequest,
+};
+use alloy_primitives::bytes::BytesMut;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::error::{RequestError, RequestResult};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, trace};
+
+/// Maximum number of requests of a peer that are served concurrently.
+///
+/// Further requests are dropped until a response was sent.
+const MAX_CONCURRENT_REQUESTS_PER_PEER: usize = 8;
+
+/// Announces the `snap/1` protocol on all connections, forwards the requests of peers to the
+/// [`SnapRequestHandler`](crate::SnapRequestHandler) and sends the requests of the
+/// [`SnapPeers`] client.
+///
+/// Without a request handler, requests of peers are answered with empty responses.
+#[derive(Debug, Clone, Default)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// The client to register the connections with.
+    peers: Option<SnapPeers>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler forwarding requests to the given channel.
+    pub const fn new(to_request_handler: mpsc::Sender<IncomingSnapRequest>) -> Self {
+        Self { to_request_handler: Some(to_request_handler), peers: None }
+    }
+
+    /// Registers all connections with the given client, so it can request state from the peers.
+    pub fn with_peers(mut self, peers: SnapPeers) -> Self {
+        self.peers = Some(peers);
+        self
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler {
+        SnapConnectionHandler {
+            to_request_handler: self.to_request_handler.clone(),
+            peers: self.peers.clone(),
+        }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// Establishes the `snap/1` protocol on a connection.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    peers: Option<SnapPeers>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        snap_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let requests = self.peers.map(|peers| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            peers.register(peer_id, tx.clone());
+            OutgoingRequests {
+                peer_id,
+                peers,
+                to_connection: tx,
+                requests: rx,
+                inflight: HashMap::new(),
+            }
+        });
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: FuturesUnordered::new(),
+            requests,
+        }
+    }
+}
+
+/// A `snap/1` connection with a peer, which serves the requests of the peer and sends our
+/// requests to it.
+///
+/// The connection is closed if the peer sends an invalid message.
+pub struct SnapConnection {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// Messages received from the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Responses to requests of the peer that are being served.
+    pending_responses: FuturesUnordered<BoxFuture<'static, Option<SnapMessage>>>,
+    /// Our requests to the peer, if the connection is registered with [`SnapPeers`].
+    requests: Option<OutgoingRequests>,
+}
+
+/// Our requests to a peer.
+#[derive(Debug)]
+struct OutgoingRequests {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// The client the connection is registered with.
+    peers: SnapPeers,
+    /// The sender half of `requests`, to unregister the connection.
+    to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    /// Requests to send to the peer.
+    requests: mpsc::UnboundedReceiver<SnapPeerRequest>,
+    /// Requests sent to the peer, by request ID.
+    inflight: HashMap<u64, InflightRequest>,
+}
+
+/// The channel for the response of a request sent to a peer.
+#[derive(Debug)]
+enum InflightRequest {
+    AccountRange(oneshot::Sender<RequestResult<AccountRange>>),
+    StorageRanges(oneshot::Sender<RequestResult<StorageRanges>>),
+    ByteCodes(oneshot::Sender<RequestResult<ByteCodes>>),
+    TrieNodes(oneshot::Sender<RequestResult<TrieNodes>>),
+}
+
+impl OutgoingRequests {
+    /// Tracks the request and returns the message to send to the peer.
+    fn on_request(&mut self, request: SnapPeerRequest) -> SnapMessage {
+        let (message, inflight) = match request {
+            SnapPeerRequest::GetAccountRange { request, response } => {
+                (SnapMessage::GetAccountRange(request), InflightRequest::AccountRange(response))
+            }
+            SnapPeerRequest::GetStorageRanges { request, response } => {
+                (SnapMessage::GetStorageRanges(request), InflightRequest::StorageRanges(response))
+            }
+            SnapPeerRequest::GetByteCodes { request, response } => {
+                (SnapMessage::GetByteCodes(request), InflightRequest::ByteCodes(response))
+            }
+            SnapPeerRequest::GetTrieNodes { request, response } => {
+                (SnapMessage::GetTrieNodes(request), InflightRequest::TrieNodes(response))
+            }
+        };
+        // requests of timed out futures are never answered
+        self.inflight.retain(|_, inflight| !inflight.is_closed());
+        self.inflight.insert(message.request_id(), inflight);
+        message
+    }
+
+    /// Sends the response to the request it answers.
+    fn on_response(&mut self, message: SnapMessage) {
+        let Some(inflight) = self.inflight.remove(&message.request_id()) else {
+            trace!(target: "net::snap", peer_id=%self.peer_id, id=?message.message_id(), "Ignoring unrequested snap response");
+            return
+        };
+        match (inflight, message) {
+            (InflightRequest::AccountRange(tx), SnapMessage::AccountRange(response)) => {
+                let _ = tx.send(Ok(response));
+            }
+            (InflightRequest::StorageRanges(tx), SnapMessage::StorageRanges(response)) => {
+                let _ = tx.send(Ok(response));
+            }
+            (InflightRequest::ByteCodes(tx), SnapMessage::ByteCodes(response)) => {
+                let _ = tx.send(Ok(response));
+            }
+            (InflightRequest::TrieNodes(tx), SnapMessage::TrieNodes(response)) => {
+                let _ = tx.send(Ok(response));
+            }
+            (inflight, _) => inflight.send_err(RequestError::BadResponse),
+        }
+    }
+}
+
+impl InflightRequest {
+    /// Returns whether the request was abandoned.
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::AccountRange(tx) => tx.is_closed(),
+            Self::StorageRanges(tx) => tx.is_closed(),
+            Self::ByteCodes(tx) => tx.is_closed(),
+            Self::TrieNodes(tx) => tx.is_closed(),
+        }
+    }
+
+    /// Fails the request with the given error.
+    fn send_err(self, err: RequestError) {
+        match self {
+            Self::AccountRange(tx) => drop(tx.send(Err(err))),
+            Self::StorageRanges(tx) => drop(tx.send(Err(err))),
+            Self::ByteCodes(tx) => drop(tx.send(Err(err))),
+            Self::TrieNodes(tx) => drop(tx.send(Err(err))),
+        }
+    }
+}
+
+impl Drop for OutgoingRequests {
+    fn drop(&mut self) {
+        self.peers.unregister(self.peer_id, &self.to_connection);
+    }
+}
+
+impl SnapConnection {
+    /// Handles a message of the peer.
+    fn on_message(&mut self, message: SnapMessage) {
+        match message {
+            SnapMessage::AccountRange(_) |
+            SnapMessage::StorageRanges(_) |
+            SnapMessage::ByteCodes(_) |
+            SnapMessage::TrieNodes(_) => {
+                if let Some(requests) = &mut self.requests {
+                    requests.on_response(message);
+                } else {
+                    trace!(target: "net::snap", peer_id=%self.peer_id, id=?message.message_id(), "Ignoring unrequested snap response");
+                }
+            }
+            request => self.on_request(request),
+        }
+    }
+
+    /// Forwards a request of the peer to the request handler.
+    fn on_request(&mut self, message: SnapMessage) {
+        if self.pending_responses.len() >= MAX_CONCURRENT_REQUESTS_PER_PEER {
+            trace!(target: "net::snap", peer_id=%self.peer_id, "Dropping snap request, too many pending requests");
+            return
+        }
+
+        let Some(to_request_handler) = &self.to_request_handler else {
+            // not serving, so there is nothing to respond with
+            let request_id = message.request_id();
+            let response = match message {
+                SnapMessage::GetAccountRange(_) => {
+                    SnapMessage::AccountRange(AccountRange { request_id, ..Default::default() })
+                }
+                SnapMessage::GetStorageRanges(_) => {
+                    SnapMessage::StorageRanges(StorageRanges { request_id, ..Default::default() })
+                }
+                SnapMessage::GetByteCodes(_) => {
+                    SnapMessage::ByteCodes(ByteCodes { request_id, ..Default::default() })
+                }
+                SnapMessage::GetTrieNodes(_) => {
+                    SnapMessage::TrieNodes(TrieNodes { request_id, ..Default::default() })
+                }
+                _ => return,
+            };
+            self.pending_responses.push(futures::future::ready(Some(response)).boxed());
+            return
+        };
+
+        let peer_id = self.peer_id;
+        let (request, response) = match message {
+            SnapMessage::GetAccountRange(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                    rx.map(|res| res.ok().map(SnapMessage::AccountRange)).boxed(),
+                )
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                    rx.map(|res| res.ok().map(SnapMessage::StorageRanges)).boxed(),
+                )
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                    rx.map(|res| res.ok().map(SnapMessage::ByteCodes)).boxed(),
+                )
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                    rx.map(|res| res.ok().map(SnapMessage::TrieNodes)).boxed(),
+                )
+            }
+            SnapMessage::AccountRange(_) |
+            SnapMessage::StorageRanges(_) |
+            SnapMessage::ByteCodes(_) |
+            SnapMessage::TrieNodes(_) => return,
+        };
+
+        if to_request_handler.try_send(request).is_err() {
+            trace!(target: "net::snap", peer_id=%self.peer_id, "Dropping snap request, request handler is busy");
+            return
+        }
+        self.pending_responses.push(response);
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(response.encoded()))
+                }
+                continue
+            }
+
+            if let Some(requests) = &mut this.requests {
+                // the channel can't close, the connection holds a sender itself
+                if let Poll::Ready(Some(request)) = requests.requests.poll_recv(cx) {
+                    return Poll::Ready(Some(requests.on_request(request).encoded()))
+                }
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+            match SnapMessage::decode(&mut &msg[..]) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    debug!(target: "net::snap", %err, peer_id=%this.peer_id, "Invalid snap message, closing the connection");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .field("inflight_requests", &self.requests.as_ref().map(|r| r.inflight.len()))
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,908 @@
+//! Serving `snap/1` requests from the hashed state and trie tables.
+
+use crate::{
+    message::{
+        AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+        GetTrieNodes, SlimAccount, StorageData, StorageRanges, TrieNodes,
+    },
+    trie::{decode_compact_path, nodes_at, path_key},
+};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    BlockNumber, Bytes, B256,
+};
+use futures::StreamExt;
+use reth_db::tables;
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_network_api::PeerId;
+use reth_provider::{HistoricalStateProviderRef, LowestAvailableBlocks, StaticFileProviderFactory};
+use reth_prune_types::PruneSegment;
+use reth_stages_types::StageId;
+use reth_storage_api::{DBProvider, DatabaseProviderFactory, HeaderProvider};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    prefix_set::TriePrefixSetsMut,
+    proof::{Proof, StorageProof},
+    HashedPostState, HashedPostStateSorted, Nibbles, StorageRoot,
+};
+use reth_trie_common::proof::ProofNodes;
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseProof, DatabaseStorageRoot, DatabaseTrieCursorFactory,
+};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, trace};
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.14.11/eth/protocols/snap/handler.go#L36-L60>
+
+/// Maximum size of replies to data retrievals.
+const SOFT_RESPONSE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Number of recent blocks whose state can be served.
+///
+/// The state of older blocks is reverted from the changesets in the database and in static files.
+/// State roots whose changesets were already pruned are not served.
+pub const SNAP_SERVE_RECENT_BLOCKS: u64 = 128;
+
+/// Serves `snap/1` requests of peers from the hashed state and trie tables.
+///
+/// The latest persisted state is served directly, the state of the preceding
+/// [`SNAP_SERVE_RECENT_BLOCKS`] blocks by reverting the changesets on top of it. Requests for
+/// other state roots are answered with empty responses.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<F> {
+    /// The factory for database providers.
+    provider_factory: F,
+    /// Incoming requests from the [`SnapConnection`](crate::SnapConnection)s.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// The state of the most recently requested state root.
+    served_state: Option<ServedState>,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<F> SnapRequestHandler<F> {
+    /// Create a new instance
+    pub fn new(provider_factory: F, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            provider_factory,
+            incoming_requests: ReceiverStream::new(incoming),
+            served_state: None,
+        }
+    }
+}
+
+impl<F> SnapRequestHandler<F>
+where
+    F: DatabaseProviderFactory + StaticFileProviderFactory + HeaderProvider,
+{
+    /// Returns the state with the given root, if it's the latest persisted state or the state of
+    /// one of the [`SNAP_SERVE_RECENT_BLOCKS`] blocks before.
+    fn served_state<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        root: B256,
+    ) -> ProviderResult<Option<&ServedState>> {
+        let Some(tip) = tx
+            .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+            .map(|checkpoint| checkpoint.block_number)
+        else {
+            return Ok(None)
+        };
+        if self.served_state.as_ref().is_some_and(|state| state.tip == tip && state.root == root) {
+            return Ok(self.served_state.as_ref())
+        }
+
+        let Some(header) = self
+            .provider_factory
+            .headers_range(tip.saturating_sub(SNAP_SERVE_RECENT_BLOCKS - 1)..=tip)?
+            .into_iter()
+            .rev()
+            .find(|header| header.state_root == root)
+        else {
+            return Ok(None)
+        };
+
+        let hashed_state = if header.number == tip {
+            HashedPostState::default()
+        } else {
+            let lowest_available_blocks = LowestAvailableBlocks {
+                account_history_block_number: lowest_available_block(
+                    tx,
+                    PruneSegment::AccountHistory,
+                )?,
+                storage_history_block_number: lowest_available_block(
+                    tx,
+                    PruneSegment::StorageHistory,
+                )?,
+            };
+            let revert_state = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+                tx,
+                header.number + 1,
+                lowest_available_blocks,
+                self.provider_factory.static_file_provider(),
+            )
+            .revert_state();
+            match revert_state {
+                Ok(revert_state) => revert_state,
+                Err(ProviderError::StateAtBlockPruned(_)) => {
+                    debug!(
+                        target: "net::snap",
+                        %root,
+                        number = header.number,
+                        "Not serving state with pruned changesets"
+                    );
+                    return Ok(None)
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        trace!(target: "net::snap", %root, number = header.number, tip, "Serving state");
+        self.served_state = Some(ServedState {
+            tip,
+            root,
+            prefix_sets: hashed_state.construct_prefix_sets(),
+            hashed_state: hashed_state.into_sorted(),
+        });
+        Ok(self.served_state.as_ref())
+    }
+
+    /// Returns the accounts of the requested range.
+    fn get_account_range(&mut self, request: GetAccountRange) -> ProviderResult<AccountRange> {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            request;
+        let mut response = AccountRange { request_id, ..Default::default() };
+
+        let provider = self.provider_factory.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(state) = self.served_state(tx, root_hash)? else { return Ok(response) };
+
+        let byte_limit = response_bytes.min(SOFT_RESPONSE_LIMIT);
+        let mut size = 0;
+        let mut cursor = state.hashed_cursor_factory(tx).hashed_account_cursor()?;
+        let mut entry = cursor.seek(starting_hash)?;
+        while let Some((hash, account)) = entry {
+            let account = SlimAccount::new(account, state.storage_root(tx, hash)?);
+            size += (B256::len_bytes() + alloy_rlp::Encodable::length(&account)) as u64;
+            response.accounts.push(AccountData { hash, account });
+
+            // the first account past the limit proves that there are no more accounts in range
+            if hash >= limit_hash || size >= byte_limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        let mut targets = vec![starting_hash];
+        targets.extend(response.accounts.last().map(|account| account.hash));
+        response.proof = state.account_proof(tx, &targets)?;
+        Ok(response)
+    }
+
+    /// Returns the storage slots of the requested accounts.
+    fn get_storage_ranges(&mut self, request: GetStorageRanges) -> ProviderResult<StorageRanges> {
+        let GetStorageRanges {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+        let mut response = StorageRanges { request_id, ..Default::default() };
+
+        let (Some(mut origin), Some(mut limit)) =
+            (hash_or(&starting_hash, B256::ZERO), hash_or(&limit_hash, B256::repeat_byte(0xff)))
+        else {
+            return Ok(response)
+        };
+
+        let provider = self.provider_factory.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(state) = self.served_state(tx, root_hash)? else { return Ok(response) };
+
+        let soft_limit = response_bytes.min(SOFT_RESPONSE_LIMIT);
+        // allow the last range to exceed the limit a bit, to avoid proving nearly complete ranges
+        let hard_limit = soft_limit + soft_limit / 10;
+        let mut size = 0;
+        let hashed_cursor_factory = state.hashed_cursor_factory(tx);
+        for hashed_address in account_hashes {
+            if size >= soft_limit {
+                break
+            }
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+            let mut entry = cursor.seek(origin)?;
+            while let Some((hash, value)) = entry {
+                if size >= hard_limit {
+                    aborted = true;
+                    break
+                }
+                let data = Bytes::from(alloy_rlp::encode(value));
+                size += (B256::len_bytes() + data.len()) as u64;
+                slots.push(StorageData { hash, data });
+
+                if hash >= limit {
+                    break
+                }
+                entry = cursor.next()?;
+            }
+
+            // only ranges that don't cover the whole storage need to be proven
+            let proven = origin != B256::ZERO || aborted;
+            let last = slots.last().map(|slot| slot.hash);
+            if !(aborted && slots.is_empty()) {
+                response.slots.push(slots);
+            }
+            if proven {
+                let mut targets = vec![origin];
+                targets.extend(last);
+                response.proof = state.storage_proof(tx, hashed_address, &targets)?;
+                break
+            }
+
+            // the requested bounds only apply to the first account
+            origin = B256::ZERO;
+            limit = B256::repeat_byte(0xff);
+        }
+        Ok(response)
+    }
+
+    /// Returns the requested bytecodes.
+    fn get_byte_codes(&self, request: GetByteCodes) -> ProviderResult<ByteCodes> {
+        let GetByteCodes { request_id, hashes, response_bytes } = request;
+        let mut response = ByteCodes { request_id, ..Default::default() };
+
+        let provider = self.provider_factory.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let byte_limit = response_bytes.min(SOFT_RESPONSE_LIMIT);
+        let mut size = 0;
+        for hash in hashes.into_iter().take(MAX_CODE_LOOKUPS) {
+            let Some(bytecode) = tx.get::<tables::Bytecodes>(hash)? else { continue };
+            let code = bytecode.original_bytes();
+            size += code.len() as u64;
+            response.codes.push(code);
+            if size >= byte_limit {
+                break
+            }
+        }
+        Ok(response)
+    }
+
+    /// Returns the requested trie nodes.
+    fn get_trie_nodes(&mut self, request: GetTrieNodes) -> ProviderResult<TrieNodes> {
+        let GetTrieNodes { request_id, root_hash, paths, response_bytes } = request;
+        let mut response = TrieNodes { request_id, ..Default::default() };
+
+        let provider = self.provider_factory.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(state) = self.served_state(tx, root_hash)? else { return Ok(response) };
+
+        // decode the requested paths, until the first invalid path set
+        let mut requested = Vec::new();
+        let mut lookups = 0;
+        for path_set in &paths {
+            let node_paths = match path_set.as_slice() {
+                [] => break,
+                [path] => {
+                    let Some(path) = decode_compact_path(path) else { break };
+                    TrieNodePaths::Account(path)
+                }
+                [account, slot_paths @ ..] => {
+                    let Ok(hashed_address) = B256::try_from(&account[..]) else { break };
+                    let Some(slot_paths) =
+                        slot_paths.iter().map(|path| decode_compact_path(path)).collect()
+                    else {
+                        break
+                    };
+                    TrieNodePaths::Storage(hashed_address, slot_paths)
+                }
+            };
+            lookups += node_paths.len();
+            requested.push(node_paths);
+            if lookups >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+        }
+
+        // resolve the nodes of each trie at once
+        let account_paths = requested
+            .iter()
+            .filter_map(|paths| match paths {
+                TrieNodePaths::Account(path) => Some(path.clone()),
+                TrieNodePaths::Storage(..) => None,
+            })
+            .collect::<Vec<_>>();
+        let mut account_nodes = state.account_trie_nodes(tx, &account_paths)?;
+
+        let byte_limit = response_bytes.min(SOFT_RESPONSE_LIMIT);
+        let mut size = 0;
+        'paths: for paths in requested {
+            let nodes = match paths {
+                TrieNodePaths::Account(path) => {
+                    vec![account_nodes.remove(&path).unwrap_or_default()]
+                }
+                TrieNodePaths::Storage(hashed_address, paths) => {
+                    let mut nodes = state.storage_trie_nodes(tx, hashed_address, &paths)?;
+                    paths.iter().map(|path| nodes.remove(path).unwrap_or_default()).collect()
+                }
+            };
+            for node in nodes {
+                size += node.len() as u64;
+                response.nodes.push(node);
+                if size >= byte_limit {
+                    break 'paths
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    /// Answers the request, or responds with an empty response if it can't be served.
+    fn on_request(&mut self, request: IncomingSnapRequest) {
+        match request {
+            IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                let request_id = request.request_id;
+                let range = self.get_account_range(request).unwrap_or_else(|err| {
+                    debug!(target: "net::snap", %err, %peer_id, "Failed to serve account range");
+                    AccountRange { request_id, ..Default::default() }
+                });
+                let _ = response.send(range);
+            }
+            IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                let request_id = request.request_id;
+                let ranges = self.get_storage_ranges(request).unwrap_or_else(|err| {
+                    debug!(target: "net::snap", %err, %peer_id, "Failed to serve storage ranges");
+                    StorageRanges { request_id, ..Default::default() }
+                });
+                let _ = response.send(ranges);
+            }
+            IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                let request_id = request.request_id;
+                let codes = self.get_byte_codes(request).unwrap_or_else(|err| {
+                    debug!(target: "net::snap", %err, %peer_id, "Failed to serve bytecodes");
+                    ByteCodes { request_id, ..Default::default() }
+                });
+                let _ = response.send(codes);
+            }
+            IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                let request_id = request.request_id;
+                let nodes = self.get_trie_nodes(request).unwrap_or_else(|err| {
+                    debug!(target: "net::snap", %err, %peer_id, "Failed to serve trie nodes");
+                    TrieNodes { request_id, ..Default::default() }
+                });
+                let _ = response.send(nodes);
+            }
+        }
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<F> Future for SnapRequestHandler<F>
+where
+    F: DatabaseProviderFactory + StaticFileProviderFactory + HeaderProvider + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while let Poll::Ready(request) = this.incoming_requests.poll_next_unpin(cx) {
+            let Some(request) = request else {
+                // all connections and the protocol handler are gone
+                return Poll::Ready(())
+            };
+            this.on_request(request);
+        }
+
+        Poll::Pending
+    }
+}
+
+/// All `snap` requests delegated by the [`SnapConnection`](crate::SnapConnection)s.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request for a range of accounts.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The request.
+        request: GetAccountRange,
+        /// The channel sender for the response.
+        response: oneshot::Sender<AccountRange>,
+    },
+    /// Request for storage ranges.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The request.
+        request: GetStorageRanges,
+        /// The channel sender for the response.
+        response: oneshot::Sender<StorageRanges>,
+    },
+    /// Request for bytecodes.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The request.
+        request: GetByteCodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<ByteCodes>,
+    },
+    /// Request for trie nodes.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The request.
+        request: GetTrieNodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<TrieNodes>,
+    },
+}
+
+/// A served state, the latest persisted state with the reverts to the requested state root.
+#[derive(Debug)]
+struct ServedState {
+    /// The latest persisted block.
+    tip: u64,
+    /// The state root.
+    root: B256,
+    /// The reverts from the latest persisted state.
+    hashed_state: HashedPostStateSorted,
+    /// The prefix sets of the reverts.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl ServedState {
+    /// Returns the factory for cursors over the hashed state.
+    fn hashed_cursor_factory<'a, TX>(
+        &'a self,
+        tx: &'a TX,
+    ) -> HashedPostStateCursorFactory<'a, DatabaseHashedCursorFactory<'a, TX>> {
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &self.hashed_state)
+    }
+
+    /// Returns the storage root of the account.
+    fn storage_root<TX: DbTx>(&self, tx: &TX, hashed_address: B256) -> ProviderResult<B256> {
+        Ok(StorageRoot::from_tx_hashed(tx, hashed_address)
+            .with_hashed_cursor_factory(self.hashed_cursor_factory(tx))
+            .with_prefix_set(
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .get(&hashed_address)
+                    .cloned()
+                    .unwrap_or_default()
+                    .freeze(),
+            )
+            .root()
+            .map_err(DatabaseError::from)?)
+    }
+
+    /// Returns the nodes of the state trie proving the given keys, ordered by path.
+    fn account_proof<TX: DbTx>(&self, tx: &TX, keys: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        let multiproof = Proof::from_tx(tx)
+            .with_hashed_cursor_factory(self.hashed_cursor_factory(tx))
+            .with_prefix_sets_mut(self.prefix_sets.clone())
+            .multiproof(keys.iter().map(|key| (*key, HashSet::default())).collect())?;
+        Ok(proof_nodes(&multiproof.account_subtree, keys))
+    }
+
+    /// Returns the nodes of the storage trie proving the given keys, ordered by path.
+    fn storage_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        keys: &[B256],
+    ) -> ProviderResult<Vec<Bytes>> {
+        let proof = StorageProof::new_hashed(
+            DatabaseTrieCursorFactory::new(tx),
+            self.hashed_cursor_factory(tx),
+            hashed_address,
+        )
+        .with_prefix_set_mut(
+            self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default(),
+        )
+        .storage_proof(keys.iter().copied().collect())?;
+        Ok(proof_nodes(&proof.subtree, keys))
+    }
+
+    /// Returns the nodes of the state trie at the given paths.
+    fn account_trie_nodes<TX: DbTx>(
+        &self,
+        tx: &TX,
+        paths: &[Nibbles],
+    ) -> ProviderResult<HashMap<Nibbles, Bytes>> {
+        if paths.is_empty() {
+            return Ok(HashMap::default())
+        }
+        let multiproof = Proof::from_tx(tx)
+            .with_hashed_cursor_factory(self.hashed_cursor_factory(tx))
+            .with_prefix_sets_mut(self.prefix_sets.clone())
+            .multiproof(paths.iter().map(|path| (path_key(path), HashSet::default())).collect())?;
+        Ok(nodes_at(&multiproof.account_subtree, paths))
+    }
+
+    /// Returns the nodes of the storage trie at the given paths.
+    fn storage_trie_nodes<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<HashMap<Nibbles, Bytes>> {
+        let proof = StorageProof::new_hashed(
+            DatabaseTrieCursorFactory::new(tx),
+            self.hashed_cursor_factory(tx),
+            hashed_address,
+        )
+        .with_prefix_set_mut(
+            self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default(),
+        )
+        .storage_proof(paths.iter().map(path_key).collect())?;
+        Ok(nodes_at(&proof.subtree, paths))
+    }
+}
+
+/// Paths of requested trie nodes.
+#[derive(Debug)]
+enum TrieNodePaths {
+    /// A path into the state trie.
+    Account(Nibbles),
+    /// Paths into the storage trie of the account.
+    Storage(B256, Vec<Nibbles>),
+}
+
+impl TrieNodePaths {
+    /// Returns the number of requested nodes.
+    fn len(&self) -> usize {
+        match self {
+            Self::Account(_) => 1,
+            Self::Storage(_, paths) => paths.len(),
+        }
+    }
+}
+
+/// Returns the proof nodes of the given keys, ordered by path.
+fn proof_nodes(nodes: &ProofNodes, keys: &[B256]) -> Vec<Bytes> {
+    let mut proof = BTreeMap::new();
+    for key in keys {
+        let key = Nibbles::unpack(key);
+        proof.extend(
+            nodes.matching_nodes_iter(&key).map(|(path, node)| (path.clone(), node.clone())),
+        );
+    }
+    proof.into_values().collect()
+}
+
+/// Returns the lowest block whose changesets of the given history segment weren't pruned, if
+/// any were.
+fn lowest_available_block<TX: DbTx>(
+    tx: &TX,
+    segment: PruneSegment,
+) -> ProviderResult<Option<BlockNumber>> {
+    Ok(tx
+        .get::<tables::PruneCheckpoints>(segment)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map(|block_number| block_number + 1))
+}
+
+/// Decodes an optional hash, which defaults to `default` if empty.
+fn hash_or(bytes: &[u8], default: B256) -> Option<B256> {
+    if bytes.is_empty() {
+        Some(default)
+    } else {
+        B256::try_from(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{address, hex, keccak256, Address, U256};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_api::models::AccountBeforeTx;
+    use reth_db_common::init::init_genesis;
+    use reth_primitives::{Account, Header, StaticFileSegment};
+    use reth_provider::{
+        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+        ProviderFactory, PruneCheckpointWriter, StageCheckpointWriter, StateChangeWriter,
+        StaticFileWriter, TrieWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_stages_types::StageCheckpoint;
+    use reth_trie::{HashBuilder, StateRoot};
+    use reth_trie_common::TrieAccount;
+    use reth_trie_db::DatabaseStateRoot;
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    const CODE: &[u8] = &hex!("6001600055");
+    const CONTRACT: Address = address!("0000000000000000000000000000000000000002");
+
+    /// Returns a handler serving a genesis state with two accounts and a contract with storage,
+    /// and the genesis state root.
+    fn handler() -> (SnapRequestHandler<ProviderFactory<MockNodeTypesWithDB>>, B256) {
+        let contract = GenesisAccount {
+            code: Some(Bytes::from_static(CODE)),
+            storage: Some(BTreeMap::from([
+                (B256::with_last_byte(1), B256::with_last_byte(1)),
+                (B256::with_last_byte(2), B256::with_last_byte(2)),
+            ])),
+            ..Default::default()
+        };
+        let genesis = Genesis::default().extend_accounts([
+            (
+                Address::with_last_byte(1),
+                GenesisAccount { balance: U256::from(1), ..Default::default() },
+            ),
+            (CONTRACT, contract),
+            (Address::with_last_byte(3), GenesisAccount { nonce: Some(3), ..Default::default() }),
+        ]);
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().genesis(genesis).build());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+
+        let (_, rx) = mpsc::channel(1);
+        (SnapRequestHandler::new(factory, rx), chain_spec.genesis_header().state_root)
+    }
+
+    #[test]
+    fn serve_account_range() {
+        let (mut handler, root) = handler();
+
+        let range = handler
+            .get_account_range(GetAccountRange {
+                request_id: 1,
+                root_hash: root,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(range.request_id, 1);
+        assert_eq!(range.accounts.len(), 3);
+        assert_eq!(keccak256(&range.proof[0]), root);
+
+        // the complete range hashes to the state root
+        let mut hash_builder = HashBuilder::default();
+        for AccountData { hash, account } in &range.accounts {
+            let account = TrieAccount {
+                nonce: account.nonce,
+                balance: account.balance,
+                storage_root: account.storage_root(),
+                code_hash: account.code_hash(),
+            };
+            hash_builder.add_leaf(Nibbles::unpack(hash), &alloy_rlp::encode(account));
+        }
+        assert_eq!(hash_builder.root(), root);
+
+        let contract =
+            range.accounts.iter().find(|account| account.hash == keccak256(CONTRACT)).unwrap();
+        assert_eq!(contract.account.code_hash(), keccak256(CODE));
+
+        // at least one account is returned
+        let range = handler
+            .get_account_range(GetAccountRange {
+                request_id: 2,
+                root_hash: root,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 1,
+            })
+            .unwrap();
+        assert_eq!(range.accounts.len(), 1);
+        assert!(!range.proof.is_empty());
+
+        // unknown state roots are not served
+        let range = handler
+            .get_account_range(GetAccountRange {
+                request_id: 3,
+                root_hash: B256::ZERO,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(range, AccountRange { request_id: 3, ..Default::default() });
+    }
+
+    #[test]
+    fn serve_storage_ranges() {
+        let (mut handler, root) = handler();
+        let hashed_address = keccak256(CONTRACT);
+
+        let ranges = handler
+            .get_storage_ranges(GetStorageRanges {
+                request_id: 1,
+                root_hash: root,
+                account_hashes: vec![hashed_address, keccak256(Address::with_last_byte(1))],
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(ranges.slots.len(), 2);
+        assert_eq!(ranges.slots[0].len(), 2);
+        assert!(ranges.slots[1].is_empty());
+        // complete ranges are not proven
+        assert!(ranges.proof.is_empty());
+
+        let mut hash_builder = HashBuilder::default();
+        for StorageData { hash, data } in &ranges.slots[0] {
+            hash_builder.add_leaf(Nibbles::unpack(hash), data);
+        }
+        let storage_root = hash_builder.root();
+
+        // ranges starting within the storage are proven
+        let second = ranges.slots[0][1].hash;
+        let ranges = handler
+            .get_storage_ranges(GetStorageRanges {
+                request_id: 2,
+                root_hash: root,
+                account_hashes: vec![hashed_address],
+                starting_hash: Bytes::copy_from_slice(second.as_slice()),
+                limit_hash: Bytes::new(),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(ranges.slots.len(), 1);
+        assert_eq!(ranges.slots[0].len(), 1);
+        assert_eq!(ranges.slots[0][0].hash, second);
+        assert_eq!(keccak256(&ranges.proof[0]), storage_root);
+    }
+
+    #[test]
+    fn serve_byte_codes() {
+        let (handler, _) = handler();
+
+        let codes = handler
+            .get_byte_codes(GetByteCodes {
+                request_id: 1,
+                hashes: vec![B256::ZERO, keccak256(CODE)],
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(codes.codes, vec![Bytes::from_static(CODE)]);
+    }
+
+    #[test]
+    fn serve_trie_nodes() {
+        let (mut handler, root) = handler();
+        let hashed_address = keccak256(CONTRACT);
+        let storage_root = handler
+            .get_account_range(GetAccountRange {
+                request_id: 0,
+                root_hash: root,
+                starting_hash: hashed_address,
+                limit_hash: hashed_address,
+                response_bytes: 1,
+            })
+            .unwrap()
+            .accounts[0]
+            .account
+            .storage_root();
+
+        // the root nodes of the state trie and the storage trie, and a missing node
+        let root_path = Bytes::from_static(&[0x00]);
+        let nodes = handler
+            .get_trie_nodes(GetTrieNodes {
+                request_id: 1,
+                root_hash: root,
+                paths: vec![
+                    vec![root_path.clone()],
+                    vec![Bytes::copy_from_slice(hashed_address.as_slice()), root_path],
+                    vec![Bytes::from_static(&[0x00, 0xab, 0xcd])],
+                ],
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        assert_eq!(nodes.nodes.len(), 3);
+        assert_eq!(keccak256(&nodes.nodes[0]), root);
+        assert_eq!(keccak256(&nodes.nodes[1]), storage_root);
+        assert!(nodes.nodes[2].is_empty());
+    }
+
+    #[test]
+    fn serve_reverted_state_from_static_files() {
+        let (handler, genesis_root) = handler();
+        let factory = handler.provider_factory;
+        let address = Address::with_last_byte(1);
+
+        // block 1 changes the balance of an account, its changeset was moved to static files
+        let static_file_provider = factory.static_file_provider();
+        let mut writer =
+            static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+        writer.append_account_changeset(0, &[]).unwrap();
+        let info = Account { balance: U256::from(1), ..Default::default() };
+        writer
+            .append_account_changeset(1, &[AccountBeforeTx { address, info: Some(info) }])
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(
+            keccak256(address),
+            Some(Account { balance: U256::from(2), ..Default::default() }),
+        );
+        let (root, updates) =
+            StateRoot::overlay_root_with_updates(provider_rw.tx_ref(), hashed_state.clone())
+                .unwrap();
+        provider_rw.write_hashed_state(&hashed_state.into_sorted()).unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let header = Header { number: 1, state_root: root, ..Default::default() };
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let request = GetAccountRange {
+            request_id: 1,
+            root_hash: genesis_root,
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: SOFT_RESPONSE_LIMIT,
+        };
+        let (_, rx) = mpsc::channel(1);
+        let mut handler = SnapRequestHandler::new(factory.clone(), rx);
+        let range = handler.get_account_range(request.clone()).unwrap();
+        let account = range.accounts.iter().find(|account| account.hash == keccak256(address));
+        assert_eq!(account.unwrap().account.balance, U256::from(1));
+        assert_eq!(keccak256(&range.proof[0]), genesis_root);
+
+        // the genesis state isn't served once the changesets of block 1 are pruned
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let (_, rx) = mpsc::channel(1);
+        let mut handler = SnapRequestHandler::new(factory, rx);
+        let range = handler.get_account_range(request).unwrap();
+        assert_eq!(range, AccountRange { request_id: 1, ..Default::default() });
+    }
+}
@@ -0,0 +1,131 @@
+//! Trie paths and nodes as used by the `snap/1` protocol.
+
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Bytes, B256,
+};
+use reth_db_api::transaction::DbTx;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    proof::{Proof, StorageProof},
+    Nibbles,
+};
+use reth_trie_common::proof::ProofNodes;
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseProof, DatabaseTrieCursorFactory};
+
+/// Returns the nodes of the local state trie at the given paths, if they exist.
+///
+/// The trie tables must be up to date with the hashed state.
+pub fn account_trie_nodes<TX: DbTx>(
+    tx: &TX,
+    paths: &[Nibbles],
+) -> ProviderResult<HashMap<Nibbles, Bytes>> {
+    if paths.is_empty() {
+        return Ok(HashMap::default())
+    }
+    let multiproof = Proof::from_tx(tx)
+        .multiproof(paths.iter().map(|path| (path_key(path), HashSet::default())).collect())?;
+    Ok(nodes_at(&multiproof.account_subtree, paths))
+}
+
+/// Returns the nodes of the local storage trie of the account at the given paths, if they exist.
+///
+/// The trie tables must be up to date with the hashed state.
+pub fn storage_trie_nodes<TX: DbTx>(
+    tx: &TX,
+    hashed_address: B256,
+    paths: &[Nibbles],
+) -> ProviderResult<HashMap<Nibbles, Bytes>> {
+    if paths.is_empty() {
+        return Ok(HashMap::default())
+    }
+    let proof = StorageProof::new_hashed(
+        DatabaseTrieCursorFactory::new(tx),
+        DatabaseHashedCursorFactory::new(tx),
+        hashed_address,
+    )
+    .storage_proof(paths.iter().map(path_key).collect())?;
+    Ok(nodes_at(&proof.subtree, paths))
+}
+
+/// Returns the nodes at the given paths, if they exist.
+pub(crate) fn nodes_at(nodes: &ProofNodes, paths: &[Nibbles]) -> HashMap<Nibbles, Bytes> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let (_, node) =
+                nodes.matching_nodes_iter(path).find(|(node_path, _)| *node_path == path)?;
+            Some((path.clone(), node.clone()))
+        })
+        .collect()
+}
+
+/// Returns the smallest key under the path, which makes the proof of the key include the node at
+/// the path.
+pub fn path_key(path: &Nibbles) -> B256 {
+    let mut nibbles = path.to_vec();
+    nibbles.resize(64, 0);
+    B256::from_slice(&Nibbles::from_nibbles_unchecked(nibbles).pack())
+}
+
+/// Encodes a trie path in the compact (hex-prefix) encoding of non-leaf paths, as requested in
+/// [`GetTrieNodes`](crate::message::GetTrieNodes).
+pub fn encode_compact_path(path: &Nibbles) -> Bytes {
+    let mut encoded = Vec::with_capacity(path.len() / 2 + 1);
+    let nibbles = if path.len() % 2 == 1 {
+        encoded.push(0x10 | path[0]);
+        &path[1..]
+    } else {
+        encoded.push(0x00);
+        &path[..]
+    };
+    encoded.extend(nibbles.chunks_exact(2).map(|pair| pair[0] << 4 | pair[1]));
+    encoded.into()
+}
+
+/// Decodes a compact (hex-prefix) encoded trie path, ignoring the leaf flag.
+pub fn decode_compact_path(encoded: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = encoded.split_first()?;
+    let flag = first >> 4;
+    let odd = flag & 1 == 1;
+    if flag > 3 || (!odd && first & 0x0f != 0) {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if odd {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_paths() {
+        assert_eq!(decode_compact_path(&[0x00]), Some(Nibbles::default()));
+        assert_eq!(
+            decode_compact_path(&[0x1a, 0xbc]),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc]))
+        );
+        assert_eq!(
+            decode_compact_path(&[0x20, 0xab]),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 0xb]))
+        );
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(decode_compact_path(&[0x0a]), None);
+        assert_eq!(decode_compact_path(&[0x40]), None);
+
+        for path in [vec![], vec![0xa], vec![0xa, 0xb], vec![0x1, 0x2, 0x3]] {
+            let path = Nibbles::from_nibbles_unchecked(path);
+            assert_eq!(decode_compact_path(&encode_compact_path(&path)), Some(path));
+        }
+        assert_eq!(
+            encode_compact_path(&Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc])),
+            Bytes::from_static(&[0x1a, 0xbc])
+        );
+    }
+}
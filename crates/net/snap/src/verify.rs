@@ -0,0 +1,401 @@
+//! Verification of the range proofs of [`AccountRange`](crate::message::AccountRange) and
+//! [`StorageRanges`](crate::message::StorageRanges) responses.
+
+use alloy_primitives::{keccak256, map::HashMap, Bytes, B256};
+use alloy_rlp::Decodable;
+use reth_primitives::constants::EMPTY_ROOT_HASH;
+use reth_trie::{HashBuilder, Nibbles};
+use reth_trie_common::{TrieNode, CHILD_INDEX_RANGE};
+use std::cmp::Ordering;
+
+/// Error of an invalid range proof.
+#[derive(Debug, thiserror::Error)]
+pub enum RangeProofError {
+    /// The keys of the range are not in increasing order.
+    #[error("range keys are not in increasing order")]
+    UnorderedKeys,
+    /// The first key of the range is before the requested origin.
+    #[error("range key {0} is before the origin")]
+    KeyBeforeOrigin(B256),
+    /// A node on the path of the first or last key is missing from the proof.
+    #[error("proof node {0} is missing")]
+    MissingNode(B256),
+    /// The proof shows keys after the origin, but the range is empty.
+    #[error("range is empty but keys after the origin exist")]
+    MissingKeys,
+    /// A proof node is invalid.
+    #[error("invalid proof node: {0}")]
+    InvalidNode(#[from] alloy_rlp::Error),
+    /// The range and the proof don't add up to the root.
+    #[error("range doesn't match the root {expected}, got {got}")]
+    RootMismatch {
+        /// The expected root.
+        expected: B256,
+        /// The root of the range and the proof.
+        got: B256,
+    },
+}
+
+/// Verifies that the entries are all leaves of the trie with the given root, starting at `origin`
+/// and up to the last entry. Values are the RLP encoded leaf values.
+///
+/// The proof contains the nodes of the trie on the paths to `origin` and to the last entry, and
+/// can be empty if the entries are the whole trie. The entries and the subtrees to the left and
+/// right of the range, as referenced by the proof, have to add up to the root.
+///
+/// Returns whether the trie has more keys after the last entry.
+pub fn verify_range_proof<V: AsRef<[u8]>>(
+    root: B256,
+    origin: B256,
+    entries: &[(B256, V)],
+    proof: &[Bytes],
+) -> Result<bool, RangeProofError> {
+    if entries.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(RangeProofError::UnorderedKeys)
+    }
+    if let Some((first, _)) = entries.first().filter(|(first, _)| *first < origin) {
+        return Err(RangeProofError::KeyBeforeOrigin(*first))
+    }
+
+    let mut items = entries
+        .iter()
+        .map(|(key, value)| (Nibbles::unpack(key), RangeItem::Leaf(value.as_ref().to_vec())))
+        .collect::<Vec<_>>();
+    let mut more = false;
+    if !proof.is_empty() && root != EMPTY_ROOT_HASH {
+        let left = Nibbles::unpack(origin);
+        let right = entries.last().map_or_else(|| left.clone(), |(last, _)| Nibbles::unpack(last));
+        let mut walker = BoundaryWalker {
+            nodes: proof.iter().map(|node| (keccak256(node), &node[..])).collect(),
+            left,
+            right,
+            items: Vec::new(),
+            more: false,
+        };
+        walker.visit(Nibbles::default(), ChildRef::Hash(root))?;
+        if entries.is_empty() && walker.more {
+            return Err(RangeProofError::MissingKeys)
+        }
+        more = walker.more;
+        items.extend(walker.items);
+        items.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    // the whole trie is outside of the range
+    if let [(path, RangeItem::Hash(hash))] = &items[..] {
+        if path.is_empty() && *hash == root {
+            return Ok(more)
+        }
+    }
+
+    let mut hash_builder = HashBuilder::default();
+    for (path, item) in items {
+        match item {
+            RangeItem::Leaf(value) => hash_builder.add_leaf(path, &value),
+            RangeItem::Hash(hash) => hash_builder.add_branch(path, hash, false),
+        }
+    }
+    let got = hash_builder.root();
+    if got != root {
+        return Err(RangeProofError::RootMismatch { expected: root, got })
+    }
+    Ok(more)
+}
+
+/// An item of the trie, added to the [`HashBuilder`] to compute the root.
+#[derive(Debug)]
+enum RangeItem {
+    /// A leaf with its value.
+    Leaf(Vec<u8>),
+    /// A subtree with its root hash.
+    Hash(B256),
+}
+
+/// A reference to a child node, either by hash or the inlined node if it's shorter than a hash.
+#[derive(Debug, Clone, Copy)]
+enum ChildRef<'a> {
+    Hash(B256),
+    Inline(&'a [u8]),
+}
+
+impl<'a> ChildRef<'a> {
+    fn new(rlp: &'a [u8]) -> Self {
+        if rlp.len() == B256::len_bytes() + 1 {
+            Self::Hash(B256::from_slice(&rlp[1..]))
+        } else {
+            Self::Inline(rlp)
+        }
+    }
+}
+
+/// Position of a subtree relative to the range.
+#[derive(Debug)]
+enum Position {
+    /// All keys of the subtree are before the origin.
+    Left,
+    /// All keys of the subtree are after the last key.
+    Right,
+    /// The subtree contains the origin or the last key.
+    Boundary,
+    /// All keys of the subtree are in the range.
+    Inside,
+}
+
+/// Walks the paths to the origin and the last key and collects the subtrees outside of the range.
+#[derive(Debug)]
+struct BoundaryWalker<'a> {
+    /// Proof nodes by hash.
+    nodes: HashMap<B256, &'a [u8]>,
+    /// Path of the origin.
+    left: Nibbles,
+    /// Path of the last key.
+    right: Nibbles,
+    /// Subtrees and leaves outside of the range.
+    items: Vec<(Nibbles, RangeItem)>,
+    /// Whether there are keys after the range.
+    more: bool,
+}
+
+impl BoundaryWalker<'_> {
+    fn position(&self, path: &Nibbles) -> Position {
+        let len = path.len();
+        match (path[..].cmp(&self.left[..len]), path[..].cmp(&self.right[..len])) {
+            (Ordering::Less, _) => Position::Left,
+            (_, Ordering::Greater) => Position::Right,
+            (Ordering::Equal, _) | (_, Ordering::Equal) => Position::Boundary,
+            _ => Position::Inside,
+        }
+    }
+
+    /// Visits the node at the path, descending into it if it's on the path to the origin or the
+    /// last key.
+    fn visit(&mut self, path: Nibbles, child: ChildRef<'_>) -> Result<(), RangeProofError> {
+        match self.position(&path) {
+            Position::Left => return self.add_outside(path, child),
+            Position::Right => {
+                self.more = true;
+                return self.add_outside(path, child)
+            }
+            Position::Inside => return Ok(()),
+            Position::Boundary => {}
+        }
+
+        let encoded: &[u8] = match child {
+            ChildRef::Hash(hash) => {
+                self.nodes.get(&hash).copied().ok_or(RangeProofError::MissingNode(hash))?
+            }
+            ChildRef::Inline(encoded) => encoded,
+        };
+        match TrieNode::decode(&mut &encoded[..])? {
+            TrieNode::Branch(branch) => {
+                let mut stack_ptr = branch.as_ref().first_child_index();
+                for index in CHILD_INDEX_RANGE {
+                    if branch.state_mask.is_bit_set(index) {
+                        let mut child_path = path.clone();
+                        child_path.push(index);
+                        self.visit(child_path, ChildRef::new(&branch.stack[stack_ptr]))?;
+                        stack_ptr += 1;
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let mut child_path = path.clone();
+                child_path.extend_from_slice(&extension.key);
+                match self.position(&child_path) {
+                    Position::Left => self.add_outside(path, child)?,
+                    Position::Right => {
+                        self.more = true;
+                        self.add_outside(path, child)?
+                    }
+                    Position::Inside => {}
+                    Position::Boundary => {
+                        self.visit(child_path, ChildRef::new(&extension.child))?
+                    }
+                }
+            }
+            TrieNode::Leaf(leaf) => {
+                let mut key = path;
+                key.extend_from_slice(&leaf.key);
+                match self.position(&key) {
+                    Position::Left => self.items.push((key, RangeItem::Leaf(leaf.value))),
+                    Position::Right => {
+                        self.more = true;
+                        self.items.push((key, RangeItem::Leaf(leaf.value)));
+                    }
+                    // leaves in the range are in the entries
+                    Position::Boundary | Position::Inside => {}
+                }
+            }
+            TrieNode::EmptyRoot => {
+                return Err(RangeProofError::InvalidNode(alloy_rlp::Error::Custom(
+                    "unexpected empty root",
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the subtree at the path, which is outside of the range.
+    fn add_outside(&mut self, path: Nibbles, child: ChildRef<'_>) -> Result<(), RangeProofError> {
+        match child {
+            ChildRef::Hash(hash) => self.items.push((path, RangeItem::Hash(hash))),
+            // inlined nodes are too short to reference hashes, collect their leaves
+            ChildRef::Inline(encoded) => match TrieNode::decode(&mut &encoded[..])? {
+                TrieNode::Branch(branch) => {
+                    let mut stack_ptr = branch.as_ref().first_child_index();
+                    for index in CHILD_INDEX_RANGE {
+                        if branch.state_mask.is_bit_set(index) {
+                            let mut child_path = path.clone();
+                            child_path.push(index);
+                            self.add_outside(child_path, ChildRef::new(&branch.stack[stack_ptr]))?;
+                            stack_ptr += 1;
+                        }
+                    }
+                }
+                TrieNode::Extension(extension) => {
+                    let mut child_path = path;
+                    child_path.extend_from_slice(&extension.key);
+                    self.add_outside(child_path, ChildRef::new(&extension.child))?;
+                }
+                TrieNode::Leaf(leaf) => {
+                    let mut key = path;
+                    key.extend_from_slice(&leaf.key);
+                    self.items.push((key, RangeItem::Leaf(leaf.value)));
+                }
+                TrieNode::EmptyRoot => {}
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_trie_common::proof::ProofRetainer;
+    use std::collections::BTreeMap;
+
+    /// Returns the sorted leaves of a trie and its root.
+    fn trie() -> (Vec<(B256, Vec<u8>)>, B256) {
+        let mut leaves = (0..100u64)
+            .map(|i| (keccak256(B256::from(U256::from(i))), alloy_rlp::encode(U256::from(i + 1))))
+            .collect::<Vec<_>>();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut hash_builder = HashBuilder::default();
+        for (key, value) in &leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        (leaves, hash_builder.root())
+    }
+
+    /// Returns the proof of the given keys, ordered by path.
+    fn proof(leaves: &[(B256, Vec<u8>)], keys: &[B256]) -> Vec<Bytes> {
+        let targets = keys.iter().map(Nibbles::unpack).collect::<Vec<_>>();
+        let mut hash_builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter(targets.clone()));
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        hash_builder.root();
+        let nodes = hash_builder.take_proof_nodes();
+
+        let mut proof = BTreeMap::new();
+        for target in &targets {
+            proof.extend(
+                nodes.matching_nodes_iter(target).map(|(path, node)| (path.clone(), node.clone())),
+            );
+        }
+        proof.into_values().collect()
+    }
+
+    #[test]
+    fn whole_trie() {
+        let (leaves, root) = trie();
+        assert!(!verify_range_proof(root, B256::ZERO, &leaves, &[]).unwrap());
+
+        assert!(verify_range_proof(root, B256::ZERO, &leaves[1..], &[]).is_err());
+        assert!(!verify_range_proof::<Vec<u8>>(EMPTY_ROOT_HASH, B256::ZERO, &[], &[]).unwrap());
+    }
+
+    #[test]
+    fn proven_ranges() {
+        let (leaves, root) = trie();
+
+        // range in the middle
+        let range = &leaves[10..=20];
+        let proof = proof(&leaves, &[range[0].0, range[10].0]);
+        assert!(verify_range_proof(root, range[0].0, range, &proof).unwrap());
+
+        // range from the beginning
+        let range = &leaves[..=30];
+        let proof = self::proof(&leaves, &[B256::ZERO, range[30].0]);
+        assert!(verify_range_proof(root, B256::ZERO, range, &proof).unwrap());
+
+        // last range
+        let range = &leaves[90..];
+        let proof = self::proof(&leaves, &[range[0].0, range[9].0]);
+        assert!(!verify_range_proof(root, range[0].0, range, &proof).unwrap());
+
+        // origin between two keys
+        let origin = B256::from(U256::from_be_bytes(leaves[9].0 .0) + U256::from(1));
+        let range = &leaves[10..=20];
+        let proof = self::proof(&leaves, &[origin, range[10].0]);
+        assert!(verify_range_proof(root, origin, range, &proof).unwrap());
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let (leaves, root) = trie();
+        let range = leaves[10..=20].to_vec();
+        let proof = proof(&leaves, &[range[0].0, range[10].0]);
+
+        // missing key
+        let mut missing = range.clone();
+        missing.remove(5);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &missing, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        // altered value
+        let mut altered = range.clone();
+        altered[5].1 = alloy_rlp::encode(U256::from(1000));
+        assert!(verify_range_proof(root, range[0].0, &altered, &proof).is_err());
+
+        // unordered keys
+        let mut unordered = range.clone();
+        unordered.swap(2, 3);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &unordered, &proof),
+            Err(RangeProofError::UnorderedKeys)
+        ));
+
+        // key before the origin
+        assert!(matches!(
+            verify_range_proof(root, range[1].0, &range, &proof),
+            Err(RangeProofError::KeyBeforeOrigin(_))
+        ));
+
+        // incomplete proof
+        assert!(verify_range_proof(root, range[0].0, &range, &proof[1..]).is_err());
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let (leaves, root) = trie();
+
+        // no keys after the origin
+        let origin = B256::from(U256::from_be_bytes(leaves[99].0 .0) + U256::from(1));
+        let proof = proof(&leaves, &[origin]);
+        assert!(!verify_range_proof::<Vec<u8>>(root, origin, &[], &proof).unwrap());
+
+        // keys after the origin are withheld
+        let proof = self::proof(&leaves, &[leaves[50].0]);
+        assert!(matches!(
+            verify_range_proof::<Vec<u8>>(root, leaves[50].0, &[], &proof),
+            Err(RangeProofError::MissingKeys)
+        ));
+    }
+}
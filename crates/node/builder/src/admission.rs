@@ -0,0 +1,27 @@
+//! The admission policy of the transaction pool, served over the `admin` RPC namespace.
+
+use jsonrpsee::core::RpcResult;
+use reth_node_core::rpc::{api::AdminTxPoolApiServer, result::internal_rpc_err};
+use reth_transaction_pool::{AdmissionPolicy, AdmissionRules};
+
+/// Implements the `admin_txpoolPolicy` and `admin_reloadTxpoolPolicy` RPC methods.
+pub(crate) struct AdmissionPolicyRpc {
+    policy: AdmissionPolicy,
+}
+
+impl AdmissionPolicyRpc {
+    /// Creates a new instance serving the given policy of the pool.
+    pub(crate) const fn new(policy: AdmissionPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl AdminTxPoolApiServer<AdmissionRules> for AdmissionPolicyRpc {
+    fn txpool_policy(&self) -> RpcResult<AdmissionRules> {
+        Ok(self.policy.rules())
+    }
+
+    fn reload_txpool_policy(&self) -> RpcResult<AdmissionRules> {
+        self.policy.reload().map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
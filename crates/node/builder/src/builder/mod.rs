@@ -7,7 +7,7 @@ mod states;
 
 pub use states::*;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
@@ -17,8 +17,12 @@ use reth_db_api::{
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
 use reth_exex::ExExContext;
+use reth_net_snap::{
+    SnapPeers, SnapProtocolHandler, SnapRequestHandler, SNAP_REQUEST_CHANNEL_CAPACITY,
+};
 use reth_network::{
-    NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
+    protocol::IntoRlpxSubProtocol, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
+    NetworkHandle, NetworkManager, NetworkProtocols,
 };
 use reth_node_api::{
     FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes, NodeTypesWithDBAdapter,
@@ -31,10 +35,21 @@ use reth_node_core::{
     primitives::Head,
     rpc::eth::{helpers::AddDevSigners, FullEthApiServer},
 };
-use reth_primitives::revm_primitives::EnvKzgSettings;
-use reth_provider::{providers::BlockchainProvider, ChainSpecProvider, FullProvider};
+use reth_primitives::{revm_primitives::EnvKzgSettings, PooledTransactionsElementEcRecovered};
+use reth_provider::{
+    providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, FullProvider,
+};
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+#[cfg(feature = "telos")]
+use reth_telos_net::{
+    TelosPeers, TelosProtocolHandler, TelosRequestHandler, TELOS_REQUEST_CHANNEL_CAPACITY,
+};
+use reth_transaction_pool::{
+    journal::{journal_transactions_task, TransactionJournalConfig},
+    maintain::{backup_local_transactions_task, LocalTransactionBackupConfig},
+    workload::record_workload_task,
+    PoolConfig, PoolTransaction, TransactionOrderingKind, TransactionPool,
+};
 use secp256k1::SecretKey;
 use tracing::{info, trace, warn};
 
@@ -515,6 +530,11 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// The client requesting state from peers over `snap/1`, if the state is snap synced.
+    pub(crate) snap_peers: Option<SnapPeers>,
+    /// The client requesting the extra fields of blocks from peers over `telos/1`.
+    #[cfg(feature = "telos")]
+    pub(crate) telos_peers: TelosPeers,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
@@ -525,7 +545,30 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            snap_peers: None,
+            #[cfg(feature = "telos")]
+            telos_peers: TelosPeers::default(),
+        }
+    }
+
+    /// Registers the connections of peers supporting `snap/1` with the given client in
+    /// [`Self::start_network`].
+    pub fn with_snap_peers(mut self, snap_peers: SnapPeers) -> Self {
+        self.snap_peers = Some(snap_peers);
+        self
+    }
+
+    /// Registers the connections of peers supporting `telos/1` with the given client in
+    /// [`Self::start_network`].
+    #[cfg(feature = "telos")]
+    pub fn with_telos_peers(mut self, telos_peers: TelosPeers) -> Self {
+        self.telos_peers = telos_peers;
+        self
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -570,6 +613,58 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the configured ordering of pending transactions.
+    pub fn transaction_ordering(&self) -> TransactionOrderingKind {
+        self.config().txpool.transaction_ordering()
+    }
+
+    /// Returns the config of the transaction pool journal, if it's enabled.
+    pub fn transaction_journal_config(&self) -> Option<TransactionJournalConfig> {
+        let txpool = &self.config().txpool;
+        txpool.journal.then(|| {
+            TransactionJournalConfig::new(self.config().datadir().txpool_journal())
+                .with_max_age(Duration::from_secs(txpool.journal_max_age))
+        })
+    }
+
+    /// Spawns the tasks persisting the transactions of the pool: the transaction journal if it's
+    /// enabled, or else the backup of local transactions, and the workload recorder if
+    /// `--txpool.record-workload` is set.
+    pub fn spawn_transaction_pool_persistence<Pool>(&self, pool: Pool)
+    where
+        Pool: TransactionPool + Clone + 'static,
+        Pool::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+    {
+        if let Some(journal_config) = self.transaction_journal_config() {
+            // the journal also covers local transactions
+            let pool = pool.clone();
+            self.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "transaction journal task",
+                |shutdown| journal_transactions_task(shutdown, pool, journal_config),
+            );
+        } else {
+            let transactions_backup_config = LocalTransactionBackupConfig::with_local_txs_backup(
+                self.config().datadir().txpool_transactions(),
+            );
+            let pool = pool.clone();
+            self.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "local transactions backup task",
+                |shutdown| {
+                    backup_local_transactions_task(shutdown, pool, transactions_backup_config)
+                },
+            );
+        }
+
+        if let Some(path) = self.config().txpool.record_workload.clone() {
+            let client = self.provider().clone();
+            let chain_events = self.provider().canonical_state_stream();
+            self.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "transaction pool workload recorder task",
+                |shutdown| record_workload_task(shutdown, client, pool, chain_events, path),
+            );
+        }
+    }
+
     /// Loads `EnvKzgSettings::Default`.
     pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
         Ok(EnvKzgSettings::Default)
@@ -596,6 +691,34 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
 
+        if self.config().network.snap_serve || self.snap_peers.is_some() {
+            let mut snap = SnapProtocolHandler::default();
+            if self.config().network.snap_serve {
+                let (tx, rx) = tokio::sync::mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+                snap = SnapProtocolHandler::new(tx);
+                self.executor.spawn_critical_blocking(
+                    "p2p snap request handler",
+                    SnapRequestHandler::new(self.provider().clone(), rx),
+                );
+                info!(target: "reth::cli", "Serving snap/1 protocol");
+            }
+            if let Some(snap_peers) = self.snap_peers.clone() {
+                snap = snap.with_peers(snap_peers);
+            }
+            handle.add_rlpx_sub_protocol(snap.into_rlpx_sub_protocol());
+        }
+
+        #[cfg(feature = "telos")]
+        {
+            let (tx, rx) = tokio::sync::mpsc::channel(TELOS_REQUEST_CHANNEL_CAPACITY);
+            self.executor.spawn_critical_blocking(
+                "p2p telos request handler",
+                TelosRequestHandler::new(self.provider().clone(), rx),
+            );
+            let telos = TelosProtocolHandler::new(tx).with_peers(self.telos_peers.clone());
+            handle.add_rlpx_sub_protocol(telos.into_rlpx_sub_protocol());
+        }
+
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
@@ -14,12 +14,16 @@ use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::Consensus;
 use reth_db_api::database::Database;
-use reth_db_common::init::{init_genesis, InitDatabaseError};
+use reth_db_common::{
+    init::{init_genesis, InitDatabaseError},
+    verify::{DbVerifier, VerifyConfig},
+};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_net_snap::SnapPeers;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB};
 use reth_node_core::{
@@ -51,6 +55,8 @@ use reth_rpc_layer::JwtSecret;
 use reth_stages::{sets::DefaultStages, MetricEvent, PipelineBuilder, PipelineTarget, StageId};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
+#[cfg(feature = "telos")]
+use reth_telos_net::TelosPeers;
 use reth_tracing::tracing::{debug, error, info, warn};
 use tokio::sync::{
     mpsc::{unbounded_channel, Receiver, UnboundedSender},
@@ -408,10 +414,14 @@ where
     pub async fn create_provider_factory<N: NodeTypesWithDB<DB = DB, ChainSpec = ChainSpec>>(
         &self,
     ) -> eyre::Result<ProviderFactory<N>> {
+        let compression = self.toml_config().static_files.compression.clone();
+        compression.validate().map_err(|err| eyre::eyre!(err))?;
+
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
+            StaticFileProvider::read_write(self.data_dir().static_files())?
+                .with_compression(compression),
         )
         .with_prune_modes(self.prune_modes())
         .with_static_files_metrics();
@@ -680,12 +690,22 @@ where
         // fetch the head block from the database
         let head = self.lookup_head()?;
 
-        let builder_ctx = BuilderContext::new(
+        let mut builder_ctx = BuilderContext::new(
             head,
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
         );
+        let snap_peers = self.node_config().sync.is_snap().then(SnapPeers::default);
+        if let Some(snap_peers) = snap_peers.clone() {
+            builder_ctx = builder_ctx.with_snap_peers(snap_peers);
+        }
+        #[cfg(feature = "telos")]
+        let telos_peers = TelosPeers::default();
+        #[cfg(feature = "telos")]
+        {
+            builder_ctx = builder_ctx.with_telos_peers(telos_peers.clone());
+        }
 
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
@@ -730,6 +750,9 @@ where
             node_adapter,
             head,
             consensus,
+            snap_peers,
+            #[cfg(feature = "telos")]
+            telos_peers,
         };
 
         let ctx = LaunchContextWith {
@@ -776,6 +799,58 @@ where
         StaticFileProducer::new(self.provider_factory().clone(), self.prune_modes())
     }
 
+    /// Spawns a task that periodically verifies the database and static files, if enabled with
+    /// `--db.verify`.
+    ///
+    /// Each run writes its report to `<DATADIR>/db-verify/node-report.json`.
+    pub fn spawn_database_verifier(&self) {
+        let args = self.node_config().db_verify;
+        if !args.enabled {
+            return
+        }
+
+        let dir = self.data_dir().db_verify();
+        let report_path = dir.join("node-report.json");
+        let verifier = DbVerifier::new(
+            self.provider_factory().clone(),
+            VerifyConfig {
+                rate_limit: Some(args.rate_limit),
+                progress_path: Some(dir.join("node-progress.json")),
+                ..Default::default()
+            },
+        );
+
+        info!(
+            target: "reth::cli",
+            rate_limit = args.rate_limit,
+            interval = ?args.interval,
+            "Starting database verifier"
+        );
+        self.task_executor().spawn_blocking(async move {
+            loop {
+                let result = verifier.run().and_then(|report| {
+                    reth_fs_util::create_dir_all(&dir)?;
+                    reth_fs_util::write_json_file(&report_path, &report)?;
+                    Ok(report)
+                });
+                match result {
+                    Ok(report) if report.is_ok() => {
+                        info!(target: "reth::cli", "Database verification found no inconsistencies")
+                    }
+                    Ok(report) => warn!(
+                        target: "reth::cli",
+                        findings = report.total_findings,
+                        report = %report_path.display(),
+                        "Database verification found inconsistencies"
+                    ),
+                    Err(err) => error!(target: "reth::cli", %err, "Database verification failed"),
+                }
+
+                tokio::time::sleep(args.interval).await;
+            }
+        });
+    }
+
     /// Returns the current head block.
     pub const fn head(&self) -> Head {
         self.right().head
@@ -871,6 +946,18 @@ where
     pub const fn components(&self) -> &CB::Components {
         &self.node_adapter().components
     }
+
+    /// Returns the client requesting state from peers over `snap/1`, if the node is configured
+    /// with `--sync.mode snap`.
+    pub const fn snap_peers(&self) -> Option<&SnapPeers> {
+        self.right().snap_peers.as_ref()
+    }
+
+    /// Returns the client requesting the extra fields of blocks from peers over `telos/1`.
+    #[cfg(feature = "telos")]
+    pub const fn telos_peers(&self) -> &TelosPeers {
+        &self.right().telos_peers
+    }
 }
 
 impl<T, CB>
@@ -1039,6 +1126,9 @@ where
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
     consensus: Arc<dyn Consensus>,
+    snap_peers: Option<SnapPeers>,
+    #[cfg(feature = "telos")]
+    telos_peers: TelosPeers,
 }
 
 #[cfg(test)]
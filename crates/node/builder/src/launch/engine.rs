@@ -178,6 +178,8 @@ where
         ));
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
+        ctx.spawn_database_verifier();
+
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
@@ -193,6 +195,9 @@ where
             static_file_producer,
             ctx.components().block_executor().clone(),
             pipeline_exex_handle,
+            ctx.snap_peers().cloned(),
+            #[cfg(feature = "telos")]
+            ctx.telos_peers().clone(),
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
@@ -288,6 +293,7 @@ where
             ctx.node_config(),
             jwt_secret,
             rpc,
+            ctx.provider_factory().clone(),
         )
         .await?;
 
@@ -40,7 +40,16 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     /// Spawns all extensions and returns the handle to the exex manager if any extensions are
     /// installed.
     pub async fn launch(self) -> eyre::Result<Option<ExExManagerHandle>> {
-        let Self { head, extensions, components, config_container } = self;
+        #[cfg_attr(not(unix), allow(unused_mut))]
+        let Self { head, mut extensions, components, config_container } = self;
+
+        #[cfg(unix)]
+        if let Some(socket_path) = config_container.config.exex.remote_socket.clone() {
+            let remote = move |ctx: ExExContext<Node>| async move {
+                Ok::<_, eyre::Report>(reth_exex::RemoteExEx::from_context(ctx, socket_path).run())
+            };
+            extensions.push(("remote".to_string(), Box::new(remote)));
+        }
 
         if extensions.is_empty() {
             // nothing to launch
@@ -217,6 +217,8 @@ where
         ));
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
+        ctx.spawn_database_verifier();
+
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
@@ -254,6 +256,9 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
+                None,
+                #[cfg(feature = "telos")]
+                ctx.telos_peers().clone(),
             )?;
 
             let pipeline_events = pipeline.events();
@@ -275,6 +280,9 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
+                ctx.snap_peers().cloned(),
+                #[cfg(feature = "telos")]
+                ctx.telos_peers().clone(),
             )?;
 
             (pipeline, Either::Right(network_client.clone()))
@@ -365,6 +373,7 @@ where
             ctx.node_config(),
             jwt_secret,
             rpc,
+            ctx.provider_factory().clone(),
         )
         .await?;
 
@@ -36,6 +36,9 @@ pub use handle::NodeHandle;
 
 pub mod rpc;
 
+mod admission;
+mod snapshot;
+
 pub mod setup;
 
 /// Support for installing the ExExs (execution extensions) in a node.
@@ -10,22 +10,23 @@ use reth_node_api::{BuilderProvider, FullNodeComponents, NodeTypes, NodeTypesWit
 use reth_node_core::{
     node_config::NodeConfig,
     rpc::{
-        api::EngineApiServer,
+        api::{AdminSnapshotApiServer, AdminTxPoolApiServer, EngineApiServer},
         eth::{EthApiTypes, FullEthApiServer},
     },
 };
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_provider::providers::ProviderNodeTypes;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
+use reth_transaction_pool::TransactionPool;
 
-use crate::{EthApiBuilderCtx, RpcAddOns};
+use crate::{admission::AdmissionPolicyRpc, snapshot::SnapshotRpc, EthApiBuilderCtx, RpcAddOns};
 
 /// Contains the handles to the spawned RPC servers.
 ///
@@ -299,6 +300,7 @@ pub async fn launch_rpc_servers<Node, Engine, EthApi>(
     config: &NodeConfig<<Node::Types as NodeTypes>::ChainSpec>,
     jwt_secret: JwtSecret,
     add_ons: RpcAddOns<Node, EthApi>,
+    provider_factory: ProviderFactory<Node::Types>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node, EthApi>)>
 where
     Node: FullNodeComponents<Types: ProviderNodeTypes> + Clone,
@@ -306,7 +308,9 @@ where
     EthApi: EthApiBuilderProvider<Node> + FullEthApiServer,
 {
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    let mut module_config = config.rpc.transport_rpc_module_config();
+    module_config.config_mut().get_or_insert_with(Default::default).eth_mut().filters_file =
+        Some(config.datadir().rpc_filters());
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -319,6 +323,18 @@ where
         .with_block_executor(node.block_executor().clone())
         .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
 
+    // snapshots need direct access to the database, so they're not part of the registry
+    modules.merge_if_module_configured(
+        RethRpcModule::Admin,
+        SnapshotRpc::new(provider_factory).into_rpc(),
+    )?;
+    if let Some(policy) = node.pool().admission_policy() {
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            AdmissionPolicyRpc::new(policy).into_rpc(),
+        )?;
+    }
+
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
         node: node.clone(),
@@ -11,14 +11,21 @@ use reth_downloaders::{
 };
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
+use reth_net_snap::SnapPeers;
 use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
-use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory, StageCheckpointReader};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, SnapSyncStage},
+    Pipeline, StageId, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::debug;
+#[cfg(feature = "telos")]
+use reth_telos_net::TelosPeers;
+use reth_tracing::tracing::{debug, warn};
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
@@ -35,6 +42,8 @@ pub fn build_networked_pipeline<N, Client, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<SnapPeers>,
+    #[cfg(feature = "telos")] telos_client: TelosPeers,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -62,12 +71,19 @@ where
         static_file_producer,
         executor,
         exex_manager_handle,
+        snap_client,
+        #[cfg(feature = "telos")]
+        telos_client,
     )?;
 
     Ok(pipeline)
 }
 
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
+///
+/// With a snap client, the [`SnapSyncStage`] downloads the state after the bodies, unless blocks
+/// were already executed without it. Telos nodes download the extra fields of the synced blocks
+/// before executing them.
 #[allow(clippy::too_many_arguments)]
 pub fn build_pipeline<N, H, B, Executor>(
     provider_factory: ProviderFactory<N>,
@@ -81,6 +97,8 @@ pub fn build_pipeline<N, H, B, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<SnapPeers>,
+    #[cfg(feature = "telos")] telos_client: TelosPeers,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -99,28 +117,51 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(ExecutionStage::new(
+        executor,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        prune_modes,
+        exex_manager_handle,
+    ));
+
+    if let Some(snap_client) = snap_client {
+        let provider = provider_factory.provider()?;
+        let executed =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        if executed == 0 || provider.is_snap_synced()? {
+            stages = stages.add_after(
+                SnapSyncStage::new(snap_client, stage_config.snap_sync),
+                StageId::Bodies,
+            );
+        } else {
+            warn!(target: "reth::cli", executed, "Blocks were already executed, syncing without snap sync");
+        }
+    }
+
+    // blocks synced from peers can only be executed with their extra fields
+    #[cfg(feature = "telos")]
+    {
+        stages = stages.add_before(
+            reth_stages::stages::TelosExtraFieldsStage::new(telos_client),
+            StageId::Execution,
+        );
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(ExecutionStage::new(
-                executor,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                prune_modes,
-                exex_manager_handle,
-            )),
-        )
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
@@ -0,0 +1,43 @@
+//! Snapshots of the database of a running node, served over the `admin` RPC namespace.
+
+use jsonrpsee::core::{async_trait, RpcResult};
+use reth_db_common::snapshot::{create_snapshot, SnapshotConfig, SnapshotManifest};
+use reth_node_core::rpc::{api::AdminSnapshotApiServer, result::internal_rpc_err};
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Implements the `admin_snapshot` RPC method.
+pub(crate) struct SnapshotRpc<N: ProviderNodeTypes> {
+    provider_factory: ProviderFactory<N>,
+    /// Held while a snapshot is taken, so that only one is taken at a time.
+    in_progress: Arc<Mutex<()>>,
+}
+
+impl<N: ProviderNodeTypes> SnapshotRpc<N> {
+    /// Creates a new instance that snapshots the database of the given factory.
+    pub(crate) fn new(provider_factory: ProviderFactory<N>) -> Self {
+        Self { provider_factory, in_progress: Default::default() }
+    }
+}
+
+#[async_trait]
+impl<N: ProviderNodeTypes> AdminSnapshotApiServer<SnapshotManifest> for SnapshotRpc<N> {
+    async fn snapshot(&self, dir: PathBuf, compact: Option<bool>) -> RpcResult<SnapshotManifest> {
+        let Ok(_in_progress) = self.in_progress.try_lock() else {
+            return Err(internal_rpc_err("a snapshot is already being taken"))
+        };
+
+        let provider_factory = self.provider_factory.clone();
+        let config = SnapshotConfig {
+            compact: compact.unwrap_or_default(),
+            // static files that can still be unwound are always copied
+            copy_static_files: false,
+            pause_persistence: true,
+        };
+        tokio::task::spawn_blocking(move || create_snapshot(&provider_factory, &dir, config))
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
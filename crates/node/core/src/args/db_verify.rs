@@ -0,0 +1,101 @@
+//! clap [Args](clap::Args) for background database verification
+
+use std::time::Duration;
+
+use clap::{value_parser, Args};
+use humantime::parse_duration;
+
+/// Default number of entries read per second by the background database verification.
+pub const DEFAULT_DB_VERIFY_RATE_LIMIT: u64 = 10_000;
+
+/// Default time between two background database verification runs.
+pub const DEFAULT_DB_VERIFY_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Parameters for verifying the database in the background
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Database verification")]
+pub struct DatabaseVerifyArgs {
+    /// Periodically verify the database and static files in the background, like `reth db
+    /// verify` does.
+    ///
+    /// An interrupted run resumes on restart. Reports are written to
+    /// `<DATADIR>/db-verify/node-report.json`.
+    #[arg(long = "db.verify")]
+    pub enabled: bool,
+
+    /// Maximum number of entries, static file rows and blocks read per second by the background
+    /// verification.
+    #[arg(
+        long = "db.verify.rate-limit",
+        value_name = "ENTRIES_PER_SEC",
+        default_value_t = DEFAULT_DB_VERIFY_RATE_LIMIT,
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub rate_limit: u64,
+
+    /// Time to wait after a background verification run before starting the next one.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --db.verify.interval 12h
+    #[arg(
+        long = "db.verify.interval",
+        value_parser = parse_duration,
+        default_value = "1d",
+        verbatim_doc_comment
+    )]
+    pub interval: Duration,
+}
+
+impl Default for DatabaseVerifyArgs {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate_limit: DEFAULT_DB_VERIFY_RATE_LIMIT,
+            interval: DEFAULT_DB_VERIFY_INTERVAL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_db_verify_args() {
+        let args = CommandParser::<DatabaseVerifyArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, DatabaseVerifyArgs::default());
+
+        let args = CommandParser::<DatabaseVerifyArgs>::parse_from([
+            "reth",
+            "--db.verify",
+            "--db.verify.rate-limit",
+            "500",
+            "--db.verify.interval",
+            "12h",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            DatabaseVerifyArgs {
+                enabled: true,
+                rate_limit: 500,
+                interval: Duration::from_secs(12 * 60 * 60)
+            }
+        );
+
+        assert!(CommandParser::<DatabaseVerifyArgs>::try_parse_from([
+            "reth",
+            "--db.verify.rate-limit",
+            "0"
+        ])
+        .is_err());
+    }
+}
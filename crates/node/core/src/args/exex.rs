@@ -0,0 +1,45 @@
+//! clap [Args](clap::Args) for execution extension configuration
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Parameters for execution extensions
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Serve execution extension notifications to an out-of-process consumer over a Unix socket
+    /// at the given path.
+    ///
+    /// Notifications are backed by the ExEx WAL, so a consumer can restart and resume from its
+    /// last processed block. Blocks are not pruned until the consumer acknowledges them.
+    #[arg(long = "exex.remote-socket", value_name = "PATH")]
+    pub remote_socket: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+
+        let args = CommandParser::<ExExArgs>::parse_from([
+            "reth",
+            "--exex.remote-socket",
+            "/tmp/exex.sock",
+        ])
+        .args;
+        assert_eq!(args.remote_socket, Some(PathBuf::from("/tmp/exex.sock")));
+    }
+}
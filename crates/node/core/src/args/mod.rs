@@ -12,6 +12,10 @@ pub use rpc_server::RpcServerArgs;
 mod rpc_state_cache;
 pub use rpc_state_cache::RpcStateCacheArgs;
 
+/// `RpcRateLimitArgs` struct for configuring RPC rate limiting
+mod rpc_rate_limit;
+pub use rpc_rate_limit::RpcRateLimitArgs;
+
 /// DebugArgs struct for debugging purposes
 mod debug;
 pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
@@ -20,6 +24,10 @@ pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
 mod database;
 pub use database::DatabaseArgs;
 
+/// DatabaseVerifyArgs struct for verifying the database in the background
+mod db_verify;
+pub use db_verify::{DatabaseVerifyArgs, DEFAULT_DB_VERIFY_INTERVAL, DEFAULT_DB_VERIFY_RATE_LIMIT};
+
 /// LogArgs struct for configuring the logger
 mod log;
 pub use log::{ColorMode, LogArgs, Verbosity};
@@ -48,6 +56,14 @@ pub use dev::DevArgs;
 mod pruning;
 pub use pruning::PruningArgs;
 
+/// SyncArgs for configuring the initial sync
+mod sync;
+pub use sync::{SyncArgs, SyncMode};
+
+/// ExExArgs for configuring execution extensions
+mod exex;
+pub use exex::ExExArgs;
+
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
 pub use datadir_args::DatadirArgs;
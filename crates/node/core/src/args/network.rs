@@ -154,6 +154,13 @@ pub struct NetworkArgs {
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
     #[arg(long = "net-if.experimental", conflicts_with = "addr", value_name = "IF_NAME")]
     pub net_if: Option<String>,
+
+    /// Serve the snap/1 protocol to peers, so they can snap sync from this node.
+    ///
+    /// State is served from the database for the latest persisted block and the 127 blocks
+    /// before it, which requires their changesets.
+    #[arg(long = "snap.serve", verbatim_doc_comment)]
+    pub snap_serve: bool,
 }
 
 impl NetworkArgs {
@@ -331,6 +338,7 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
+            snap_serve: false,
         }
     }
 }
@@ -0,0 +1,123 @@
+use clap::Args;
+use std::{net::IpAddr, path::PathBuf};
+
+/// Parameters to configure compute unit based rate limiting of the HTTP and WS RPC servers.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "RPC Rate Limit")]
+pub struct RpcRateLimitArgs {
+    /// Compute units per second every client IP may spend. Rate limiting is disabled if not set.
+    ///
+    /// The client IP is the address of the connection's peer, or the address in the
+    /// `X-Forwarded-For` or `X-Real-IP` headers if the peer is a trusted proxy. Only the HTTP and
+    /// WS servers are rate limited, IPC is not.
+    #[arg(long = "rpc.ratelimit.ip-cups", value_name = "CU_PER_SEC")]
+    pub ip_cups: Option<u64>,
+
+    /// Maximum compute units a client IP may spend at once, defaults to the per second budget.
+    #[arg(long = "rpc.ratelimit.ip-burst", value_name = "CU", requires = "ip_cups")]
+    pub ip_burst: Option<u64>,
+
+    /// Comma separated addresses of reverse proxies whose `X-Forwarded-For` and `X-Real-IP`
+    /// headers are trusted.
+    #[arg(
+        long = "rpc.ratelimit.trusted-proxies",
+        value_name = "IP",
+        value_delimiter = ',',
+        requires = "ip_cups"
+    )]
+    pub trusted_proxies: Vec<IpAddr>,
+
+    /// Compute units per second every API key may spend.
+    #[arg(long = "rpc.ratelimit.key-cups", value_name = "CU_PER_SEC", requires = "api_keys")]
+    pub api_key_cups: Option<u64>,
+
+    /// Maximum compute units an API key may spend at once, defaults to the per second budget.
+    #[arg(long = "rpc.ratelimit.key-burst", value_name = "CU", requires = "api_key_cups")]
+    pub api_key_burst: Option<u64>,
+
+    /// Path to a file with one accepted API key per line.
+    ///
+    /// Requests presenting a listed key in the `X-Api-Key` header are accounted against the
+    /// key's budget instead of the client IP's.
+    #[arg(long = "rpc.ratelimit.api-keys", value_name = "PATH", requires = "api_key_cups")]
+    pub api_keys: Option<PathBuf>,
+
+    /// Overrides the compute unit cost of a method, e.g. `debug_traceTransaction=500`.
+    ///
+    /// A trailing `*` sets the cost of all methods with that prefix. Can be specified multiple
+    /// times.
+    #[arg(long = "rpc.ratelimit.method-cost", value_name = "METHOD=CU", value_parser = parse_method_cost)]
+    pub method_costs: Vec<(String, u64)>,
+}
+
+impl RpcRateLimitArgs {
+    /// Returns true if any rate limit budget is configured.
+    pub const fn is_enabled(&self) -> bool {
+        self.ip_cups.is_some() || self.api_key_cups.is_some()
+    }
+}
+
+/// Parses a `METHOD=CU` method cost.
+fn parse_method_cost(s: &str) -> Result<(String, u64), String> {
+    let (method, cost) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid method cost `{s}`, expected METHOD=CU"))?;
+    let cost = cost.trim().parse().map_err(|err| format!("invalid cost for `{method}`: {err}"))?;
+    Ok((method.trim().to_string(), cost))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_rate_limit_args() {
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, RpcRateLimitArgs::default());
+        assert!(!args.is_enabled());
+
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from([
+            "reth",
+            "--rpc.ratelimit.ip-cups",
+            "100",
+            "--rpc.ratelimit.ip-burst",
+            "1000",
+            "--rpc.ratelimit.trusted-proxies",
+            "127.0.0.1,::1",
+            "--rpc.ratelimit.method-cost",
+            "debug_traceTransaction=500",
+            "--rpc.ratelimit.method-cost",
+            "trace_*=300",
+        ])
+        .args;
+        assert!(args.is_enabled());
+        assert_eq!(args.ip_cups, Some(100));
+        assert_eq!(args.ip_burst, Some(1000));
+        assert_eq!(
+            args.trusted_proxies,
+            vec!["127.0.0.1".parse::<IpAddr>().unwrap(), "::1".parse().unwrap()]
+        );
+        assert_eq!(
+            args.method_costs,
+            vec![("debug_traceTransaction".to_string(), 500), ("trace_*".to_string(), 300)]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_method_cost() {
+        let res = CommandParser::<RpcRateLimitArgs>::try_parse_from([
+            "reth",
+            "--rpc.ratelimit.method-cost",
+            "eth_call",
+        ]);
+        assert!(res.is_err());
+    }
+}
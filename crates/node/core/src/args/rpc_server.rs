@@ -15,8 +15,8 @@ use rand::Rng;
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 
 use crate::args::{
-    types::{MaxU32, ZeroAsNoneU64},
-    GasPriceOracleArgs, RpcStateCacheArgs,
+    types::{MaxU32, ZeroAsNoneU32, ZeroAsNoneU64},
+    GasPriceOracleArgs, RpcRateLimitArgs, RpcStateCacheArgs,
 };
 
 /// Default max number of subscriptions per connection.
@@ -134,6 +134,20 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
 
+    /// Maximum number of calls in a JSON-RPC batch request for both HTTP and WS. (0 = no limit)
+    #[arg(long = "rpc.max-batch-size", value_name = "COUNT", default_value_t = ZeroAsNoneU32(None))]
+    pub rpc_max_batch_size: ZeroAsNoneU32,
+
+    /// Maximum total response size of a JSON-RPC batch request over HTTP in megabytes.
+    /// (0 = only limited by `--rpc.max-response-size`)
+    #[arg(long = "rpc.max-batch-response-size", value_name = "MB", default_value_t = ZeroAsNoneU32(None))]
+    pub rpc_max_batch_response_size: ZeroAsNoneU32,
+
+    /// Maximum number of calls of a JSON-RPC batch request over HTTP that are executed
+    /// concurrently. (0 = execute calls on the connection task)
+    #[arg(long = "rpc.batch-parallelism", value_name = "COUNT", default_value_t = ZeroAsNoneU32(None))]
+    pub rpc_batch_parallelism: ZeroAsNoneU32,
+
     /// Maximum number of concurrent tracing requests.
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
@@ -185,6 +199,10 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[command(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Rate limit configuration.
+    #[command(flatten)]
+    pub rpc_rate_limit: RpcRateLimitArgs,
 }
 
 impl RpcServerArgs {
@@ -304,6 +322,9 @@ impl Default for RpcServerArgs {
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
+            rpc_max_batch_size: ZeroAsNoneU32(None),
+            rpc_max_batch_response_size: ZeroAsNoneU32(None),
+            rpc_batch_parallelism: ZeroAsNoneU32(None),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
@@ -312,6 +333,7 @@ impl Default for RpcServerArgs {
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
+            rpc_rate_limit: RpcRateLimitArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
         }
     }
@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The log index stage within the pipeline.
+    ///
+    /// Indexes receipt logs by emitting address and topic.
+    LogIndex,
 }
@@ -0,0 +1,68 @@
+//! clap [Args](clap::Args) for sync configuration
+
+use clap::{Args, ValueEnum};
+use std::fmt;
+
+/// Parameters for the initial sync
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[command(next_help_heading = "Sync")]
+pub struct SyncArgs {
+    /// How the state is obtained on the initial sync.
+    ///
+    /// - full: execute all blocks from genesis
+    /// - snap: download the state at a recent pivot block from peers over `snap/1`, then execute
+    ///   the blocks after it. Only applies to an empty database, and the history before the pivot
+    ///   is not available.
+    #[arg(long = "sync.mode", value_enum, default_value_t, verbatim_doc_comment)]
+    pub mode: SyncMode,
+}
+
+impl SyncArgs {
+    /// Returns whether the state is downloaded with snap sync.
+    pub const fn is_snap(&self) -> bool {
+        matches!(self.mode, SyncMode::Snap)
+    }
+}
+
+/// How the state is obtained on the initial sync.
+#[derive(Debug, Default, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum SyncMode {
+    /// Execute all blocks from genesis
+    #[default]
+    Full,
+    /// Download the state at a pivot block from peers
+    Snap,
+}
+
+impl fmt::Display for SyncMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "full"),
+            Self::Snap => write!(f, "snap"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_sync_args() {
+        let args = CommandParser::<SyncArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, SyncArgs::default());
+        assert!(!args.is_snap());
+
+        let args = CommandParser::<SyncArgs>::parse_from(["reth", "--sync.mode", "snap"]).args;
+        assert_eq!(args, SyncArgs { mode: SyncMode::Snap });
+        assert!(args.is_snap());
+    }
+}
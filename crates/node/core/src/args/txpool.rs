@@ -2,17 +2,22 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_primitives::Address;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use reth_primitives::constants::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use reth_transaction_pool::{
+    admission::AdmissionPolicyError,
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    journal::DEFAULT_JOURNAL_MAX_AGE,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    AdmissionPolicy, LocalTransactionConfig, PoolConfig, PriceBumpConfig, PriorityScript,
+    SubPoolLimit, TransactionOrderingKind, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
     TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
     TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, sync::Arc};
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -86,6 +91,77 @@ pub struct TxPoolArgs {
     /// Maximum number of new transactions to buffer
     #[arg(long = "txpool.max-new-txns", alias = "txpool.max_new_txns", default_value_t = NEW_TX_LISTENER_BUFFER_SIZE)]
     pub new_tx_listener_buffer_size: usize,
+
+    /// How pending transactions are ordered for inclusion in a block.
+    #[arg(long = "txpool.ordering", value_enum, default_value_t = TxPoolOrdering::CoinbaseTip)]
+    pub ordering: TxPoolOrdering,
+
+    /// Priority function of the `script` ordering.
+    ///
+    /// An integer arithmetic expression with `+ - * / %`, parentheses, `min(a, b)` and
+    /// `max(a, b)` over the transaction's `tip`, `max_fee`, `priority_fee`, `gas_limit`, `size`,
+    /// `nonce`, `lane` (position in the sender's nonce lane), `arrival` (milliseconds since the
+    /// node started) and `local` (1 or 0). Higher is better.
+    #[arg(
+        long = "txpool.priority-script",
+        value_name = "EXPR",
+        required_if_eq("ordering", "script")
+    )]
+    pub priority_script: Option<PriorityScript>,
+
+    /// Journal all pooled transactions to disk and reinsert them on startup.
+    ///
+    /// Replaces the backup of local transactions on shutdown.
+    #[arg(long = "txpool.journal")]
+    pub journal: bool,
+
+    /// Maximum age in seconds of journaled transactions reinserted on startup.
+    #[arg(
+        long = "txpool.journal-max-age",
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_JOURNAL_MAX_AGE.as_secs(),
+        requires = "journal"
+    )]
+    pub journal_max_age: u64,
+
+    /// Path to a TOML file with the admission policy of the pool.
+    ///
+    /// The policy can deny transactions by sender, recipient and function selector, and limit
+    /// the rate of transactions per sender. It can be reloaded with `admin_reloadTxpoolPolicy`.
+    #[arg(
+        long = "txpool.admission-policy",
+        value_name = "FILE",
+        value_parser = parse_admission_policy
+    )]
+    pub admission_policy: Option<PathBuf>,
+
+    /// Record the workload of the pool into the given file, to replay it offline with the
+    /// `txpool-replay` example.
+    ///
+    /// Records all transactions entering and leaving the pool and all canonical updates.
+    #[arg(long = "txpool.record-workload", value_name = "FILE")]
+    pub record_workload: Option<PathBuf>,
+}
+
+/// Checks that the admission policy file can be loaded.
+fn parse_admission_policy(path: &str) -> Result<PathBuf, AdmissionPolicyError> {
+    let path = PathBuf::from(path);
+    AdmissionPolicy::load(&path)?;
+    Ok(path)
+}
+
+/// The built-in orderings of pending transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TxPoolOrdering {
+    /// By the tip paid to the block producer, highest first
+    #[default]
+    CoinbaseTip,
+    /// By arrival time in the pool, oldest first, regardless of fees
+    Fifo,
+    /// Round-robin over senders, regardless of fees
+    SenderFair,
+    /// By the priority function of `--txpool.priority-script`
+    Script,
 }
 
 impl Default for TxPoolArgs {
@@ -110,6 +186,12 @@ impl Default for TxPoolArgs {
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            ordering: TxPoolOrdering::default(),
+            priority_script: None,
+            journal: false,
+            journal_max_age: DEFAULT_JOURNAL_MAX_AGE.as_secs(),
+            admission_policy: None,
+            record_workload: None,
         }
     }
 }
@@ -148,6 +230,27 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             gas_limit: self.gas_limit,
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
+            admission_policy: self
+                .admission_policy
+                .as_ref()
+                .map(|path| {
+                    AdmissionPolicy::load(path).expect("the policy is checked when parsing args")
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the ordering of pending transactions.
+    fn transaction_ordering(&self) -> TransactionOrderingKind {
+        match self.ordering {
+            TxPoolOrdering::CoinbaseTip => TransactionOrderingKind::CoinbaseTip,
+            TxPoolOrdering::Fifo => TransactionOrderingKind::Fifo,
+            TxPoolOrdering::SenderFair => TransactionOrderingKind::SenderFair,
+            TxPoolOrdering::Script => TransactionOrderingKind::Script(Arc::new(
+                self.priority_script
+                    .clone()
+                    .expect("the script ordering requires --txpool.priority-script"),
+            )),
         }
     }
 }
@@ -170,4 +273,86 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_ordering() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.ordering", "sender-fair"])
+                .args;
+        assert_eq!(args.transaction_ordering(), TransactionOrderingKind::SenderFair);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.ordering",
+            "script",
+            "--txpool.priority-script",
+            "tip * 1000 - arrival",
+        ])
+        .args;
+        assert_eq!(
+            args.transaction_ordering(),
+            TransactionOrderingKind::Script(Arc::new("tip * 1000 - arrival".parse().unwrap()))
+        );
+
+        // the script ordering requires a priority function, which must be valid
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.ordering",
+            "script"
+        ])
+        .is_err());
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.ordering",
+            "script",
+            "--txpool.priority-script",
+            "tip +",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_journal() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.journal",
+            "--txpool.journal-max-age",
+            "600",
+        ])
+        .args;
+        assert!(args.journal);
+        assert_eq!(args.journal_max_age, 600);
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.journal-max-age",
+            "600"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_admission_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, "denied_selectors = [\"0xa9059cbb\"]").unwrap();
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.admission-policy",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        assert_eq!(args.admission_policy.as_ref(), Some(&path));
+        assert_eq!(args.pool_config().admission_policy.rules().denied_selectors.len(), 1);
+
+        // the policy must be valid
+        std::fs::write(&path, "denied_selectors = 1").unwrap();
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.admission-policy",
+            path.to_str().unwrap(),
+        ])
+        .is_err());
+    }
 }
@@ -2,7 +2,7 @@
 
 use alloy_primitives::Bytes;
 use reth_network::protocol::IntoRlpxSubProtocol;
-use reth_transaction_pool::PoolConfig;
+use reth_transaction_pool::{PoolConfig, TransactionOrderingKind};
 use std::{borrow::Cow, time::Duration};
 
 /// A trait that provides payload builder settings.
@@ -64,4 +64,7 @@ impl RethNetworkConfig for reth_network::NetworkManager {
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Returns the ordering of pending transactions.
+    fn transaction_ordering(&self) -> TransactionOrderingKind;
 }
@@ -315,6 +315,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("known-peers.json")
     }
 
+    /// Returns the path to the file the installed `eth` filters are persisted to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-filters.json`
+    pub fn rpc_filters(&self) -> PathBuf {
+        self.data_dir().join("rpc-filters.json")
+    }
+
     /// Returns the path to the blobstore directory for this chain where blobs of unfinalized
     /// transactions are stored.
     ///
@@ -330,6 +337,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool journal
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal.bin`
+    pub fn txpool_journal(&self) -> PathBuf {
+        self.data_dir().join("txpool-journal.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -355,6 +369,14 @@ impl<D> ChainPath<D> {
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the directory of database verification reports and progress for this
+    /// chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/db-verify`
+    pub fn db_verify(&self) -> PathBuf {
+        self.data_dir().join("db-verify")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatabaseVerifyArgs, DatadirArgs, DebugArgs, DevArgs, ExExArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, SyncArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -122,11 +122,20 @@ pub struct NodeConfig<ChainSpec> {
     /// All database related arguments
     pub db: DatabaseArgs,
 
+    /// All background database verification related arguments with --db.verify prefix
+    pub db_verify: DatabaseVerifyArgs,
+
     /// All dev related arguments with --dev prefix
     pub dev: DevArgs,
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All sync related arguments with --sync prefix
+    pub sync: SyncArgs,
+
+    /// All execution extension related arguments with --exex prefix
+    pub exex: ExExArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -152,8 +161,11 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             builder: PayloadBuilderArgs::default(),
             debug: DebugArgs::default(),
             db: DatabaseArgs::default(),
+            db_verify: DatabaseVerifyArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            sync: SyncArgs::default(),
+            exex: ExExArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
@@ -249,12 +261,30 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the background database verification args for the node
+    pub const fn with_db_verify(mut self, db_verify: DatabaseVerifyArgs) -> Self {
+        self.db_verify = db_verify;
+        self
+    }
+
     /// Set the pruning args for the node
     pub fn with_pruning(mut self, pruning: PruningArgs) -> Self {
         self.pruning = pruning;
         self
     }
 
+    /// Set the sync args for the node
+    pub const fn with_sync(mut self, sync: SyncArgs) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Set the execution extension args for the node
+    pub fn with_exex(mut self, exex: ExExArgs) -> Self {
+        self.exex = exex;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -443,8 +473,11 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             builder: self.builder.clone(),
             debug: self.debug.clone(),
             db: self.db,
+            db_verify: self.db_verify,
             dev: self.dev,
             pruning: self.pruning.clone(),
+            sync: self.sync,
+            exex: self.exex.clone(),
             datadir: self.datadir.clone(),
         }
     }
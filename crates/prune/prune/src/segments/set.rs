@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, Bodies, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    TransactionLookup, UserReceipts,
 };
 use reth_db::transaction::DbTxMut;
 use reth_provider::{
@@ -9,7 +9,10 @@ use reth_provider::{
 };
 use reth_prune_types::PruneModes;
 
-use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
+use super::{
+    StaticFileAccountChangeSets, StaticFileHeaders, StaticFileReceipts,
+    StaticFileStorageChangeSets, StaticFileTransactions,
+};
 
 /// Collection of [Segment]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -59,6 +62,7 @@ where
             receipts,
             account_history,
             storage_history,
+            bodies,
             receipts_log_filter,
         } = prune_modes;
 
@@ -68,11 +72,21 @@ where
             // Static file transactions
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
-            .segment(StaticFileReceipts::new(static_file_provider))
+            .segment(StaticFileReceipts::new(static_file_provider.clone()))
+            // Static file account changesets
+            .segment(StaticFileAccountChangeSets::new(static_file_provider.clone()))
+            // Static file storage changesets
+            .segment(StaticFileStorageChangeSets::new(static_file_provider.clone()))
+            // Bodies
+            .segment_opt(bodies.map(|mode| Bodies::new(mode, static_file_provider.clone())))
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(
+                account_history.map(|mode| AccountHistory::new(mode, static_file_provider.clone())),
+            )
             // Storage history
-            .segment_opt(storage_history.map(StorageHistory::new))
+            .segment_opt(
+                storage_history.map(|mode| StorageHistory::new(mode, static_file_provider)),
+            )
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
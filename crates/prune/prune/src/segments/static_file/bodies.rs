@@ -0,0 +1,219 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    tables,
+    transaction::DbTxMut,
+};
+use reth_provider::{providers::StaticFileProvider, BlockReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::{instrument, trace};
+
+/// Deletes the [`StaticFileSegment::Transactions`] static files of blocks older than the
+/// configured distance from the tip.
+///
+/// Only whole static files are deleted, so up to one static file worth of blocks more than
+/// configured is kept. The static file of the highest block is never deleted. Headers, including
+/// the Telos block extension, and block body indices are kept, so the chain can still be verified
+/// and extended.
+///
+/// The Telos engine API extra fields of the blocks whose bodies were deleted are only needed to
+/// execute them, so they're deleted as well. The checkpoint is the highest block whose body and
+/// extra fields are both gone.
+#[derive(Debug)]
+pub struct Bodies {
+    mode: PruneMode,
+    static_file_provider: StaticFileProvider,
+}
+
+impl Bodies {
+    pub const fn new(mode: PruneMode, static_file_provider: StaticFileProvider) -> Self {
+        Self { mode, static_file_provider }
+    }
+}
+
+impl<Provider: DBProvider<Tx: DbTxMut> + BlockReader> Segment<Provider> for Bodies {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bodies
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let deleted = self
+            .static_file_provider
+            .delete_segment_below_block(StaticFileSegment::Transactions, input.to_block + 1)?;
+        let mut pruned = deleted.iter().filter_map(|header| header.tx_len()).sum::<u64>() as usize;
+        if !deleted.is_empty() {
+            trace!(target: "pruner", %pruned, files = deleted.len(), "Pruned bodies");
+        }
+
+        // The bodies of all blocks below the lowest transactions static file are gone, including
+        // the ones deleted by previous runs whose extra fields weren't all deleted yet.
+        let Some(lowest_block) =
+            self.static_file_provider.get_lowest_static_file_block(StaticFileSegment::Transactions)
+        else {
+            trace!(target: "pruner", "No bodies to prune");
+            return Ok(SegmentOutput::done())
+        };
+        let start = input.get_start_next_block_range();
+        let Some(end) = lowest_block.checked_sub(1).filter(|end| *end >= start) else {
+            trace!(target: "pruner", "No bodies to prune");
+            return Ok(SegmentOutput::done())
+        };
+
+        let mut limiter = input.limiter;
+        let mut cursor = provider.tx_ref().cursor_write::<tables::TelosExtraFields>()?;
+        let mut last_pruned_block = None;
+        for (block, hash) in (start..).zip(provider.canonical_hashes_range(start, end + 1)?) {
+            if limiter.is_limit_reached() {
+                break
+            }
+            if cursor.seek_exact(hash)?.is_some() {
+                cursor.delete_current()?;
+                limiter.increment_deleted_entries_count();
+                pruned += 1;
+            }
+            last_pruned_block = Some(block);
+        }
+        trace!(target: "pruner", ?last_pruned_block, "Pruned Telos extra fields");
+
+        let done = last_pruned_block == Some(end);
+        let tx_number = match last_pruned_block {
+            Some(block) => provider.block_body_indices(block)?.map(|indices| indices.last_tx_num()),
+            None => None,
+        };
+
+        Ok(SegmentOutput {
+            progress: PruneProgress::new(done, &limiter),
+            pruned,
+            checkpoint: last_pruned_block.map(|block_number| SegmentOutputCheckpoint {
+                block_number: Some(block_number),
+                tx_number,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, SegmentOutput};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_chainspec::MAINNET;
+    use reth_db::{
+        tables,
+        test_utils::{create_test_rw_db, create_test_static_files_dir},
+        transaction::DbTxMut,
+    };
+    use reth_provider::{
+        providers::StaticFileProvider, DatabaseProviderFactory, ProviderFactory,
+        PruneCheckpointReader, PruneCheckpointWriter, StaticFileProviderFactory,
+        TransactionsProvider,
+    };
+    use reth_prune_types::{
+        PruneInterruptReason, PruneLimiter, PruneMode, PruneProgress, PruneSegment,
+        SegmentOutputCheckpoint,
+    };
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn prune() {
+        let (temp_static_files_dir, static_files_path) = create_test_static_files_dir();
+        let db = TestStageDB {
+            factory: ProviderFactory::new(
+                create_test_rw_db(),
+                MAINNET.clone(),
+                StaticFileProvider::read_write(static_files_path)
+                    .unwrap()
+                    .with_custom_blocks_per_file(10),
+            ),
+            temp_static_files_dir,
+        };
+        let mut rng = generators::rng();
+
+        // One transaction per block, so transaction numbers are block numbers
+        let blocks = random_block_range(
+            &mut rng,
+            0..=29,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+        db.insert_tx_hash_numbers(
+            blocks.iter().map(|block| (block.body.transactions[0].hash, block.number)),
+        )
+        .expect("insert tx hash numbers");
+        db.commit(|tx| {
+            for block in &blocks {
+                tx.put::<tables::TelosExtraFields>(block.hash(), Default::default())?;
+            }
+            Ok(())
+        })
+        .expect("insert extra fields");
+
+        let prune_mode = PruneMode::Before(25);
+        let segment = super::Bodies::new(prune_mode, db.factory.static_file_provider());
+        let test_prune = |limiter, expected_progress: PruneProgress, expected_block: u64| {
+            let provider = db.factory.database_provider_rw().unwrap();
+            let input = PruneInput {
+                previous_checkpoint: provider.get_prune_checkpoint(PruneSegment::Bodies).unwrap(),
+                to_block: 24,
+                limiter,
+            };
+            let result = segment.prune(&provider, input).unwrap();
+            assert_matches!(
+                result,
+                SegmentOutput {
+                    progress,
+                    checkpoint: Some(SegmentOutputCheckpoint {
+                        block_number: Some(block_number),
+                        tx_number: Some(tx_number),
+                    }),
+                    ..
+                } if progress == expected_progress &&
+                    block_number == expected_block &&
+                    tx_number == expected_block
+            );
+            provider
+                .save_prune_checkpoint(
+                    PruneSegment::Bodies,
+                    result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+        };
+
+        // The static files of blocks 0..=19 are deleted, but the limit is reached after deleting
+        // the extra fields of blocks 0..=4
+        test_prune(
+            PruneLimiter::default().set_deleted_entries_limit(5),
+            PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached),
+            4,
+        );
+        test_prune(PruneLimiter::default(), PruneProgress::Finished, 19);
+
+        // The static file of the highest block is kept
+        assert_eq!(db.table::<tables::TelosExtraFields>().unwrap().len(), 10);
+        let provider = db.factory.provider().unwrap();
+        for block in &blocks {
+            let hash = block.body.transactions[0].hash;
+            let pruned = block.number < 20;
+            assert_eq!(provider.transaction_id(hash).unwrap(), Some(block.number));
+            assert_eq!(provider.transaction_by_id(block.number).unwrap().is_none(), pruned);
+            assert_eq!(provider.transaction_by_hash(hash).unwrap().is_none(), pruned);
+        }
+    }
+}
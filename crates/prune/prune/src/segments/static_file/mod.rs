@@ -1,7 +1,13 @@
+mod account_changesets;
+mod bodies;
 mod headers;
 mod receipts;
+mod storage_changesets;
 mod transactions;
 
+pub use account_changesets::AccountChangeSets;
+pub use bodies::Bodies;
 pub use headers::Headers;
 pub use receipts::Receipts;
+pub use storage_changesets::StorageChangeSets;
 pub use transactions::Transactions;
@@ -7,7 +7,9 @@ use reth_db_api::{
     transaction::DbTxMut,
     DatabaseError,
 };
-use reth_provider::DBProvider;
+use reth_provider::{providers::StaticFileProvider, DBProvider, ProviderResult};
+use reth_static_file_types::StaticFileSegment;
+use tracing::trace;
 
 enum PruneShardOutcome {
     Deleted,
@@ -22,6 +24,25 @@ pub(crate) struct PrunedIndices {
     pub(crate) unchanged: usize,
 }
 
+/// Deletes the static files of the changeset segment that only contain blocks up to `to_block`.
+///
+/// Changesets are only moved to static files while the history isn't pruned, so they're left
+/// behind if history pruning is configured later. Only whole static files are deleted, and the
+/// history indices of their blocks are left untouched.
+///
+/// Returns the number of deleted static files.
+pub(crate) fn delete_static_file_changesets(
+    static_file_provider: &StaticFileProvider,
+    segment: StaticFileSegment,
+    to_block: BlockNumber,
+) -> ProviderResult<usize> {
+    let deleted = static_file_provider.delete_segment_below_block(segment, to_block + 1)?;
+    if !deleted.is_empty() {
+        trace!(target: "pruner", ?segment, files = deleted.len(), "Deleted changeset static files");
+    }
+    Ok(deleted.len())
+}
+
 /// Prune history indices according to the provided list of highest sharded keys.
 ///
 /// Returns total number of deleted, updated and unchanged entities.
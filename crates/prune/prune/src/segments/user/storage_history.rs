@@ -1,16 +1,20 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment, SegmentOutput},
+    segments::{
+        user::history::{delete_static_file_changesets, prune_history_indices},
+        PruneInput, Segment, SegmentOutput,
+    },
     PrunerError,
 };
 use itertools::Itertools;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress};
-use reth_provider::DBProvider;
+use reth_provider::{providers::StaticFileProvider, DBProvider};
 use reth_prune_types::{
     PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment,
     SegmentOutputCheckpoint,
 };
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -20,14 +24,20 @@ use tracing::{instrument, trace};
 /// [`tables::StoragesHistory`]. We want to prune them to the same block number.
 const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 
+/// Prunes the storage changesets and history indices of blocks older than the configured distance
+/// from the tip.
+///
+/// Changesets that were already moved to [`StaticFileSegment::StorageChangeSets`] are deleted in
+/// whole static files.
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    static_file_provider: StaticFileProvider,
 }
 
 impl StorageHistory {
-    pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub const fn new(mode: PruneMode, static_file_provider: StaticFileProvider) -> Self {
+        Self { mode, static_file_provider }
     }
 }
 
@@ -49,6 +59,12 @@ where
 
     #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        delete_static_file_changesets(
+            &self.static_file_provider,
+            StaticFileSegment::StorageChangeSets,
+            input.to_block,
+        )?;
+
         let range = match input.get_next_block_range() {
             Some(range) => range,
             None => {
@@ -140,7 +156,9 @@ mod tests {
     use alloy_primitives::{BlockNumber, B256};
     use assert_matches::assert_matches;
     use reth_db::{tables, BlockNumberList};
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_provider::{
+        DatabaseProviderFactory, PruneCheckpointReader, StaticFileProviderFactory,
+    };
     use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneMode, PruneProgress, PruneSegment};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
@@ -205,7 +223,7 @@ mod tests {
                 to_block,
                 limiter: limiter.clone(),
             };
-            let segment = StorageHistory::new(prune_mode);
+            let segment = StorageHistory::new(prune_mode, db.factory.static_file_provider());
 
             let provider = db.factory.database_provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
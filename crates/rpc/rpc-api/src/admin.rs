@@ -1,6 +1,8 @@
+use alloy_json_rpc::RpcObject;
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
+use std::path::PathBuf;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -46,3 +48,31 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for taking snapshots of the database of a running node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminSnapshotApi<M: RpcObject> {
+    /// Writes a consistent snapshot of the database and static files to `dir` on the host of the
+    /// node, which must not exist or be empty. Persistence is only paused briefly, to pin the
+    /// static files at a block boundary.
+    ///
+    /// Returns the manifest of the snapshot, which can be restored with `reth db restore`.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self, dir: PathBuf, compact: Option<bool>) -> RpcResult<M>;
+}
+
+/// Admin namespace rpc interface for the admission policy of the transaction pool.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminTxPoolApi<R: RpcObject> {
+    /// Returns the rules of the transaction pool admission policy.
+    #[method(name = "txpoolPolicy")]
+    fn txpool_policy(&self) -> RpcResult<R>;
+
+    /// Reloads the transaction pool admission policy from its file and returns the new rules.
+    ///
+    /// The current rules are kept if the file can't be loaded.
+    #[method(name = "reloadTxpoolPolicy")]
+    fn reload_txpool_policy(&self) -> RpcResult<R>;
+}
@@ -2,6 +2,7 @@ use alloy_json_rpc::RpcObject;
 use alloy_primitives::Address;
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::txpool::{TxpoolFilter, TxpoolPage};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,18 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the pool transactions matching the given filter, ordered by sender and nonce.
+    ///
+    /// The filter's offset and limit select the returned page of matching transactions.
+    #[method(name = "contentFiltered")]
+    async fn txpool_content_filtered(&self, filter: TxpoolFilter) -> RpcResult<TxpoolPage<T>>;
+
+    /// Subscribes to the lifecycle events of all pool transactions.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = reth_rpc_eth_types::txpool::TxpoolEvent
+    )]
+    async fn txpool_subscribe(&self) -> jsonrpsee::core::SubscriptionResult;
 }
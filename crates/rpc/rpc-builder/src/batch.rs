@@ -0,0 +1,398 @@
+//! JSON-RPC batch request handling for the http server.
+//!
+//! jsonrpsee executes all calls of a batch on the connection task and only limits the total
+//! response size by the per-response limit. The [`RpcBatchLayer`] intercepts batch requests before
+//! they reach jsonrpsee, enforces the configured limits, records per batch metrics and optionally
+//! executes the calls of a batch concurrently on separate blocking tasks.
+
+use std::{
+    future::{poll_fn, Future},
+    num::NonZeroUsize,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use futures::{channel::oneshot, stream, StreamExt};
+use http::{header::CONTENT_LENGTH, Method};
+use http_body::Body;
+use jsonrpsee::{
+    core::{
+        http_helpers::{read_body, HttpError},
+        BoxError, JsonRawValue,
+    },
+    server::{
+        http::{content_type_is_json, response},
+        HttpBody, HttpRequest, HttpResponse,
+    },
+    types::{
+        error::{reject_too_big_batch_request, reject_too_big_batch_response, ErrorCode},
+        Id,
+    },
+    MethodResponse,
+};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use tokio::task::JoinHandle;
+use tower::{Layer, Service, ServiceExt};
+use tracing::{trace, warn};
+
+use crate::metrics::{RpcServerBatchMetrics, RpcTransport};
+
+/// Default maximum size of a request body, same as the jsonrpsee server default.
+const DEFAULT_MAX_REQUEST_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Configuration of JSON-RPC batch requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcBatchConfig {
+    /// Maximum size of a request body in bytes.
+    max_request_size: u32,
+    /// Maximum number of calls in a batch.
+    max_batch_size: Option<u32>,
+    /// Maximum total size of a batch response in bytes.
+    max_response_size: Option<u32>,
+    /// Maximum number of calls of a batch that are executed concurrently.
+    parallelism: Option<NonZeroUsize>,
+}
+
+impl RpcBatchConfig {
+    /// Sets the maximum size of a request body in bytes.
+    ///
+    /// This should match the limit of the server, batch requests are read before they are passed
+    /// on to the server.
+    pub const fn with_max_request_size(mut self, max_request_size: u32) -> Self {
+        self.max_request_size = max_request_size;
+        self
+    }
+
+    /// Sets the maximum number of calls in a batch, `None` for no limit.
+    pub const fn with_max_batch_size(mut self, max_batch_size: Option<u32>) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Sets the maximum total size of a batch response in bytes, `None` for no limit other than
+    /// the server's response size limit.
+    pub const fn with_max_response_size(mut self, max_response_size: Option<u32>) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Sets the maximum number of calls of a batch that are executed concurrently, each call is
+    /// spawned as a separate blocking task, so that large batches don't occupy the async runtime.
+    ///
+    /// If `None`, the calls of a batch are executed on the connection task.
+    pub const fn with_parallelism(mut self, parallelism: Option<NonZeroUsize>) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Returns the maximum number of calls in a batch.
+    pub const fn max_batch_size(&self) -> Option<u32> {
+        self.max_batch_size
+    }
+
+    /// Returns the maximum total size of a batch response in bytes.
+    pub const fn max_response_size(&self) -> Option<u32> {
+        self.max_response_size
+    }
+
+    /// Returns the maximum number of calls of a batch that are executed concurrently.
+    pub const fn parallelism(&self) -> Option<NonZeroUsize> {
+        self.parallelism
+    }
+}
+
+impl Default for RpcBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_batch_size: None,
+            max_response_size: None,
+            parallelism: None,
+        }
+    }
+}
+
+/// Http middleware layer that handles JSON-RPC batch requests according to the [`RpcBatchConfig`].
+#[derive(Clone, Debug)]
+pub struct RpcBatchLayer {
+    config: RpcBatchConfig,
+    metrics: RpcServerBatchMetrics,
+    executor: Box<dyn TaskSpawner>,
+}
+
+impl RpcBatchLayer {
+    /// Creates a new [`RpcBatchLayer`] that spawns concurrent calls via
+    /// [`tokio::task::spawn_blocking`].
+    pub fn new(config: RpcBatchConfig) -> Self {
+        Self::with_executor(config, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new [`RpcBatchLayer`] that spawns concurrent calls as blocking tasks on the given
+    /// executor.
+    pub fn with_executor(config: RpcBatchConfig, executor: Box<dyn TaskSpawner>) -> Self {
+        Self { config, metrics: RpcTransport::Http.batch_metrics(), executor }
+    }
+}
+
+impl<S> Layer<S> for RpcBatchLayer {
+    type Service = RpcBatchService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcBatchService {
+            config: self.config,
+            metrics: self.metrics.clone(),
+            executor: self.executor.clone(),
+            inner,
+        }
+    }
+}
+
+/// The [`Service`] created by the [`RpcBatchLayer`].
+#[derive(Clone, Debug)]
+pub struct RpcBatchService<S> {
+    config: RpcBatchConfig,
+    metrics: RpcServerBatchMetrics,
+    executor: Box<dyn TaskSpawner>,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcBatchService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        // everything but JSON-RPC calls, e.g. websocket upgrades, is passed through
+        if req.method() != Method::POST || !content_type_is_json(&req) {
+            let fut = self.inner.call(req);
+            return Box::pin(async move { fut.await.map_err(Into::into) })
+        }
+
+        // take the service that was driven to readiness
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        let handler = BatchHandler {
+            config: self.config,
+            metrics: self.metrics.clone(),
+            executor: self.executor.clone(),
+            inner,
+        };
+        Box::pin(handler.handle(req))
+    }
+}
+
+/// Handles a single JSON-RPC http request.
+struct BatchHandler<S> {
+    config: RpcBatchConfig,
+    metrics: RpcServerBatchMetrics,
+    executor: Box<dyn TaskSpawner>,
+    inner: S,
+}
+
+impl<S> BatchHandler<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+{
+    async fn handle(mut self, req: HttpRequest) -> Result<HttpResponse, BoxError> {
+        let started_at = Instant::now();
+        let (parts, body) = req.into_parts();
+
+        let (body, is_single) =
+            match read_body(&parts.headers, body, self.config.max_request_size).await {
+                Ok(res) => res,
+                Err(HttpError::TooLarge) => {
+                    return Ok(response::too_large(self.config.max_request_size))
+                }
+                Err(HttpError::Malformed) => return Ok(response::malformed()),
+                Err(HttpError::Stream(err)) => {
+                    warn!(target: "rpc::batch", %err, "Failed to read request body");
+                    return Ok(response::internal_error())
+                }
+            };
+
+        let calls = if is_single {
+            None
+        } else {
+            serde_json::from_slice::<Vec<&JsonRawValue>>(&body).ok().map(|calls| {
+                calls.into_iter().map(|call| call.get().to_string()).collect::<Vec<_>>()
+            })
+        };
+
+        // single calls, empty and malformed batches are handled by the server
+        let Some(calls) = calls.filter(|calls| !calls.is_empty()) else {
+            return self
+                .inner
+                .call(HttpRequest::from_parts(parts, body.into()))
+                .await
+                .map_err(Into::into)
+        };
+
+        if let Some(max_batch_size) = self.config.max_batch_size {
+            if calls.len() > max_batch_size as usize {
+                trace!(target: "rpc::batch", calls = calls.len(), "Rejecting too large batch");
+                self.metrics.record_too_many_calls();
+                return Ok(batch_error_response(reject_too_big_batch_request(
+                    max_batch_size as usize,
+                )))
+            }
+        }
+
+        let num_calls = calls.len();
+        let max_response_size = self.config.max_response_size.map(|size| size as usize);
+        let exceeds_max_response_size = |size: usize| {
+            max_response_size.is_some_and(|max_response_size| size > max_response_size)
+        };
+        let body = match self.config.parallelism {
+            Some(parallelism) => {
+                let inner = self.inner.clone();
+                let executor = self.executor.clone();
+                let mut responses = stream::iter(calls)
+                    .map(move |call| {
+                        let mut req = HttpRequest::new(HttpBody::from(call));
+                        *req.method_mut() = parts.method.clone();
+                        *req.uri_mut() = parts.uri.clone();
+                        *req.version_mut() = parts.version;
+                        *req.headers_mut() = parts.headers.clone();
+                        req.headers_mut().remove(CONTENT_LENGTH);
+                        *req.extensions_mut() = parts.extensions.clone();
+                        spawn_call(inner.clone(), executor.as_ref(), req, max_response_size)
+                    })
+                    .buffered(parallelism.get());
+
+                // notifications are acknowledged with an empty response
+                let mut body = Vec::new();
+                while let Some(response) = responses.next().await {
+                    // dropping the stream discards the calls that are still in flight
+                    let Some(response) = response? else {
+                        return Ok(self.reject_too_large_response())
+                    };
+                    if response.is_empty() {
+                        continue
+                    }
+                    body.push(if body.is_empty() { b'[' } else { b',' });
+                    body.extend_from_slice(&response);
+                    if exceeds_max_response_size(body.len()) {
+                        return Ok(self.reject_too_large_response())
+                    }
+                }
+                if !body.is_empty() {
+                    body.push(b']');
+                }
+                body
+            }
+            None => {
+                let response =
+                    match self.inner.call(HttpRequest::from_parts(parts, body.into())).await {
+                        Ok(response) => response,
+                        Err(err) => return Err(err.into()),
+                    };
+                if !response.status().is_success() {
+                    return Ok(response)
+                }
+                match collect_body(response.into_body(), max_response_size).await? {
+                    Some(body) => body,
+                    None => return Ok(self.reject_too_large_response()),
+                }
+            }
+        };
+
+        if exceeds_max_response_size(body.len()) {
+            return Ok(self.reject_too_large_response())
+        }
+
+        self.metrics.record_batch(num_calls, body.len(), started_at.elapsed());
+        Ok(response::ok_response(body))
+    }
+
+    /// Returns the response for a batch whose response exceeds the configured maximum size.
+    fn reject_too_large_response(&self) -> HttpResponse {
+        let max_response_size = self.config.max_response_size.unwrap_or(u32::MAX) as usize;
+        trace!(target: "rpc::batch", max_response_size, "Rejecting too large response");
+        self.metrics.record_response_too_large();
+        batch_error_response(reject_too_big_batch_response(max_response_size))
+    }
+}
+
+/// Executes a single call of a batch on a separate blocking task and returns its response body, or
+/// `None` if the body exceeds `max_size` bytes.
+///
+/// The task is aborted if the returned future is dropped before the task started, a running call
+/// completes but its response is discarded.
+fn spawn_call<S>(
+    inner: S,
+    executor: &dyn TaskSpawner,
+    req: HttpRequest,
+    max_size: Option<usize>,
+) -> impl Future<Output = Result<Option<Vec<u8>>, BoxError>> + Send + 'static
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+{
+    let call = inner.oneshot(req);
+    let (tx, rx) = oneshot::channel();
+    let task = AbortOnDrop(executor.spawn_blocking(Box::pin(async move {
+        let res = match call.await {
+            Ok(response) => collect_body(response.into_body(), max_size).await,
+            Err(err) => Err(err.into()),
+        };
+        let _ = tx.send(res);
+    })));
+
+    async move {
+        let _task = task;
+        match rx.await {
+            Ok(res) => res,
+            // the call panicked
+            Err(_) => Ok(Some(
+                MethodResponse::error(Id::Null, ErrorCode::InternalError)
+                    .into_result()
+                    .into_bytes(),
+            )),
+        }
+    }
+}
+
+/// Aborts the task when dropped.
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Reads the entire response body, or returns `None` as soon as it exceeds `max_size` bytes.
+async fn collect_body(
+    body: HttpBody,
+    max_size: Option<usize>,
+) -> Result<Option<Vec<u8>>, BoxError> {
+    let mut body = std::pin::pin!(body);
+    let mut data = Vec::new();
+    while let Some(frame) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+        if let Some(chunk) = frame?.data_ref() {
+            data.extend_from_slice(chunk);
+            if max_size.is_some_and(|max_size| data.len() > max_size) {
+                return Ok(None)
+            }
+        }
+    }
+    Ok(Some(data))
+}
+
+/// Returns the response for a rejected batch request.
+fn batch_error_response(err: jsonrpsee::types::ErrorObjectOwned) -> HttpResponse {
+    response::ok_response(MethodResponse::error(Id::Null, err).into_result())
+}
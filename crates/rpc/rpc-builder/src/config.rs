@@ -1,16 +1,16 @@
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, num::NonZeroUsize, path::PathBuf};
 
-use jsonrpsee::server::ServerBuilder;
+use jsonrpsee::server::{BatchRequestConfig, ServerBuilder};
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
-use reth_rpc_layer::{JwtError, JwtSecret};
+use reth_rpc_layer::{JwtError, JwtSecret, MethodCosts, RateLimitBudget, RateLimitConfig};
 use reth_rpc_server_types::RpcModuleSelection;
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcBatchConfig, RpcModuleConfig,
+    RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -36,6 +36,9 @@ pub trait RethRpcServerConfig {
     /// Returns the max response size in bytes.
     fn rpc_max_response_size_bytes(&self) -> u32;
 
+    /// Returns the max batch response size in bytes.
+    fn rpc_max_batch_response_size_bytes(&self) -> Option<u32>;
+
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
@@ -51,6 +54,12 @@ pub trait RethRpcServerConfig {
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity>;
 
+    /// Creates the [`RateLimitConfig`] from cli args, if rate limiting is enabled.
+    fn rate_limit_config(&self) -> Option<RateLimitConfig>;
+
+    /// Creates the [`RpcBatchConfig`] from cli args.
+    fn batch_config(&self) -> RpcBatchConfig;
+
     /// Creates the [`RpcServerConfig`] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
@@ -118,6 +127,10 @@ impl RethRpcServerConfig for RpcServerArgs {
         self.rpc_max_response_size.get().saturating_mul(1024 * 1024)
     }
 
+    fn rpc_max_batch_response_size_bytes(&self) -> Option<u32> {
+        self.rpc_max_batch_response_size.0.map(|size| size.saturating_mul(1024 * 1024))
+    }
+
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
         self.gas_price_oracle.gas_price_oracle_config()
     }
@@ -155,6 +168,11 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .set_batch_request_config(
+                self.rpc_max_batch_size
+                    .0
+                    .map_or(BatchRequestConfig::Unlimited, BatchRequestConfig::Limit),
+            )
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity> {
@@ -165,8 +183,51 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_connections(self.rpc_max_connections.get())
     }
 
+    fn rate_limit_config(&self) -> Option<RateLimitConfig> {
+        let args = &self.rpc_rate_limit;
+        if !args.is_enabled() {
+            return None
+        }
+
+        let mut config = RateLimitConfig::default()
+            .with_method_costs(
+                args.method_costs.iter().fold(MethodCosts::default(), |costs, (method, cost)| {
+                    costs.with_cost(method.clone(), *cost)
+                }),
+            )
+            .with_trusted_proxies(args.trusted_proxies.iter().copied());
+        if let Some(cups) = args.ip_cups {
+            config = config.with_ip_budget(
+                RateLimitBudget::new(cups).with_burst(args.ip_burst.unwrap_or(cups)),
+            );
+        }
+        if let Some(cups) = args.api_key_cups {
+            config = config.with_api_key_budget(
+                RateLimitBudget::new(cups).with_burst(args.api_key_burst.unwrap_or(cups)),
+            );
+        }
+        if let Some(path) = &args.api_keys {
+            config = config.with_api_keys_file(path.clone());
+        }
+
+        Some(config)
+    }
+
+    fn batch_config(&self) -> RpcBatchConfig {
+        RpcBatchConfig::default()
+            .with_max_request_size(self.rpc_max_request_size_bytes())
+            .with_max_batch_size(self.rpc_max_batch_size.0)
+            .with_max_response_size(self.rpc_max_batch_response_size_bytes())
+            .with_parallelism(
+                self.rpc_batch_parallelism.0.and_then(|n| NonZeroUsize::new(n as usize)),
+            )
+    }
+
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_rate_limit(self.rate_limit_config())
+            .with_batch_config(self.batch_config());
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -177,8 +177,8 @@ use reth_provider::{
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, EthSimBundle, NetApi, OtterscanApi, RPCApi,
+    RethApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -186,14 +186,19 @@ use reth_rpc_eth_api::{
     EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
-use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
+use reth_rpc_layer::{
+    AuthLayer, Claims, JwtAuthValidator, JwtSecret, RateLimitConfig, RateLimitHttpLayer,
+    RpcRateLimiter,
+};
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
 use tower::Layer;
 use tower_http::cors::CorsLayer;
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics, serve::BoundServer,
+};
 
 pub use cors::CorsDomainError;
 
@@ -225,6 +230,13 @@ pub use eth::EthHandlers;
 mod metrics;
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 
+// Rpc batch request handling
+mod batch;
+pub use batch::{RpcBatchConfig, RpcBatchLayer, RpcBatchService};
+
+/// Accept loop of the http and ws servers.
+mod serve;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, BlockExecutor>(
@@ -1027,6 +1039,19 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthSimBundle`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn sim_bundle_api(&self) -> EthSimBundle<EthApi>
+    where
+        EthApi: EthTransactions + LoadPendingBlock + Call,
+    {
+        let eth_api = self.eth_api().clone();
+        EthSimBundle::new(eth_api, self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `DebugApi`
     ///
     /// # Panics
@@ -1184,6 +1209,15 @@ where
                                     .into_rpc(),
                                 )
                                 .expect("No conflicts");
+                            module
+                                .merge(
+                                    EthSimBundle::new(
+                                        eth_api.clone(),
+                                        self.blocking_pool_guard.clone(),
+                                    )
+                                    .into_rpc(),
+                                )
+                                .expect("No conflicts");
 
                             module.into()
                         }
@@ -1198,9 +1232,12 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => {
-                            TxPoolApi::<_, EthApi>::new(self.pool.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Txpool => TxPoolApi::<_, EthApi>::with_spawner(
+                            self.pool.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
                                 .iter()
@@ -1253,6 +1290,10 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Rate limiting of the http and ws servers
+    rate_limit: Option<RateLimitConfig>,
+    /// Batch request handling of the http server
+    batch: RpcBatchConfig,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
 }
@@ -1272,6 +1313,8 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            rate_limit: None,
+            batch: RpcBatchConfig::default(),
             rpc_middleware: RpcServiceBuilder::new(),
         }
     }
@@ -1335,6 +1378,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            rate_limit: self.rate_limit,
+            batch: self.batch,
             rpc_middleware,
         }
     }
@@ -1408,6 +1453,21 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures compute unit based rate limiting for the http and ws servers.
+    ///
+    /// Rate limiting is only enabled if the config contains a budget, see
+    /// [`RateLimitConfig::is_enabled`].
+    pub fn with_rate_limit(mut self, rate_limit: Option<RateLimitConfig>) -> Self {
+        self.rate_limit = rate_limit.filter(RateLimitConfig::is_enabled);
+        self
+    }
+
+    /// Configures how JSON-RPC batch requests are handled by the http server.
+    pub const fn with_batch_config(mut self, batch: RpcBatchConfig) -> Self {
+        self.batch = batch;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1442,6 +1502,24 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`RpcRateLimiter`] if any, with method metrics for the methods of the rate
+    /// limited http and ws modules.
+    fn maybe_rate_limiter(
+        rate_limit: Option<RateLimitConfig>,
+        modules: &TransportRpcModules,
+    ) -> Result<Option<RpcRateLimiter>, RpcError> {
+        rate_limit
+            .map(|config| {
+                let config = config.load_api_keys().map_err(|err| {
+                    RpcError::Custom(format!("failed to load rpc rate limit api keys: {err}"))
+                })?;
+                let methods =
+                    modules.http.iter().chain(&modules.ws).flat_map(|module| module.method_names());
+                Ok(RpcRateLimiter::new(config, methods))
+            })
+            .transpose()
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1467,6 +1545,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        let rate_limiter = Self::maybe_rate_limiter(self.rate_limit, modules)?;
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
@@ -1506,7 +1586,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
-                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(rate_limiter.clone().map(RateLimitHttpLayer::new))
+                            .layer(RpcBatchLayer::new(self.batch)),
                     )
                     .set_rpc_middleware(
                         self.rpc_middleware.clone().layer(
@@ -1515,14 +1597,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                 .as_ref()
                                 .or(modules.ws.as_ref())
                                 .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
+                                .unwrap_or_default()
+                                .with_rate_limiter(rate_limiter.clone()),
                         ),
                     )
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
+                    .to_service_builder();
+                let server = BoundServer::bind(http_socket_addr, server).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
@@ -1554,14 +1636,21 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(rate_limiter.clone().map(RateLimitHttpLayer::new)),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware
-                        .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                    self.rpc_middleware.clone().layer(
+                        modules
+                            .ws
+                            .as_ref()
+                            .map(RpcRequestMetrics::ws)
+                            .unwrap_or_default()
+                            .with_rate_limiter(rate_limiter.clone()),
+                    ),
                 )
-                .build(ws_socket_addr)
+                .to_service_builder();
+            let server = BoundServer::bind(ws_socket_addr, server)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
@@ -1579,14 +1668,22 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(rate_limiter.clone().map(RateLimitHttpLayer::new))
+                        .layer(RpcBatchLayer::new(self.batch)),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        modules
+                            .http
+                            .as_ref()
+                            .map(RpcRequestMetrics::http)
+                            .unwrap_or_default()
+                            .with_rate_limiter(rate_limiter),
                     ),
                 )
-                .build(http_socket_addr)
+                .to_service_builder();
+            let server = BoundServer::bind(http_socket_addr, server)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let local_addr = server
@@ -1817,6 +1914,28 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Merge the given [Methods] in the methods of all transports that have the given
+    /// [`RethRpcModule`] configured.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.config.http().is_some_and(|selection| selection.contains(&module)) {
+            self.merge_http(other.clone())?;
+        }
+        if self.config.ws().is_some_and(|selection| selection.contains(&module)) {
+            self.merge_ws(other.clone())?;
+        }
+        if self.config.ipc().is_some_and(|selection| selection.contains(&module)) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Removes the method with the given name from the configured http methods.
     ///
     /// Returns `true` if the method was found and removed, `false` otherwise.
@@ -1,15 +1,17 @@
+use futures::future::Either;
 use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, RpcModule};
 use reth_metrics::{
     metrics::{Counter, Histogram},
     Metrics,
 };
+use reth_rpc_layer::RpcRateLimiter;
 use std::{
     collections::HashMap,
-    future::Future,
+    future::{ready, Future, Ready},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tower::Layer;
 
@@ -20,9 +22,13 @@ use tower::Layer;
 ///   for WS and IPC)
 /// - Request metrics: metrics for each RPC method (e.g. number of calls started, time taken to
 ///   process a call)
+///
+/// If a rate limiter is set, calls that exceed the client's budget are rejected before they are
+/// metered.
 #[derive(Default, Debug, Clone)]
 pub(crate) struct RpcRequestMetrics {
     inner: Arc<RpcServerMetricsInner>,
+    rate_limiter: Option<RpcRateLimiter>,
 }
 
 impl RpcRequestMetrics {
@@ -34,9 +40,16 @@ impl RpcRequestMetrics {
                     (method, RpcServerCallMetrics::new_with_labels(&[("method", method)]))
                 })),
             }),
+            rate_limiter: None,
         }
     }
 
+    /// Sets the rate limiter that is applied to every call.
+    pub(crate) fn with_rate_limiter(mut self, rate_limiter: Option<RpcRateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
     /// Creates a new instance of the metrics layer for HTTP.
     pub(crate) fn http(module: &RpcModule<()>) -> Self {
         Self::new(module, RpcTransport::Http)
@@ -101,20 +114,26 @@ impl<'a, S> RpcServiceT<'a> for RpcRequestMetricsService<S>
 where
     S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
 {
-    type Future = MeteredRequestFuture<S::Future>;
+    type Future = Either<MeteredRequestFuture<S::Future>, Ready<MethodResponse>>;
 
     fn call(&self, req: Request<'a>) -> Self::Future {
+        if let Some(rejected) =
+            self.metrics.rate_limiter.as_ref().and_then(|limiter| limiter.limit_call(&req))
+        {
+            return Either::Right(ready(rejected))
+        }
+
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
         let call_metrics = self.metrics.inner.call_metrics.get_key_value(req.method.as_ref());
         if let Some((_, call_metrics)) = &call_metrics {
             call_metrics.started_total.increment(1);
         }
-        MeteredRequestFuture {
+        Either::Left(MeteredRequestFuture {
             fut: self.inner.call(req),
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
             method: call_metrics.map(|(method, _)| *method),
-        }
+        })
     }
 }
 
@@ -197,6 +216,11 @@ impl RpcTransport {
     fn connection_metrics(&self) -> RpcServerConnectionMetrics {
         RpcServerConnectionMetrics::new_with_labels(&[("transport", self.as_str())])
     }
+
+    /// Returns the batch request metrics for the transport protocol.
+    pub(crate) fn batch_metrics(&self) -> RpcServerBatchMetrics {
+        RpcServerBatchMetrics::new_with_labels(&[("transport", self.as_str())])
+    }
 }
 
 /// Metrics for the RPC connections
@@ -228,3 +252,41 @@ struct RpcServerCallMetrics {
     /// Response for a single call
     time_seconds: Histogram,
 }
+
+/// Metrics for JSON-RPC batch requests
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.batches")]
+pub(crate) struct RpcServerBatchMetrics {
+    /// The number of batch requests finished
+    batches_total: Counter,
+    /// The number of batch requests rejected because they contained too many calls
+    too_many_calls_total: Counter,
+    /// The number of batch requests rejected because the response exceeded the size limit
+    response_too_large_total: Counter,
+    /// Number of calls in a batch request
+    calls: Histogram,
+    /// Size of a batch response in bytes
+    response_size_bytes: Histogram,
+    /// Response time for a batch request
+    time_seconds: Histogram,
+}
+
+impl RpcServerBatchMetrics {
+    /// Records a batch request that was rejected because it contained too many calls.
+    pub(crate) fn record_too_many_calls(&self) {
+        self.too_many_calls_total.increment(1);
+    }
+
+    /// Records a batch request that was rejected because the response exceeded the size limit.
+    pub(crate) fn record_response_too_large(&self) {
+        self.response_too_large_total.increment(1);
+    }
+
+    /// Records a finished batch request.
+    pub(crate) fn record_batch(&self, calls: usize, response_size: usize, elapsed: Duration) {
+        self.batches_total.increment(1);
+        self.calls.record(calls as f64);
+        self.response_size_bytes.record(response_size as f64);
+        self.time_seconds.record(elapsed.as_secs_f64());
+    }
+}
@@ -0,0 +1,91 @@
+//! Accept loop of the http and ws servers.
+//!
+//! [`Server::start`](jsonrpsee::server::Server::start) doesn't expose the address of a
+//! connection's peer to the http middleware. The servers are therefore started with the low level
+//! jsonrpsee API, which inserts the [`PeerAddr`] into the extensions of every http request.
+
+use std::{io, net::SocketAddr};
+
+use http::Request;
+use hyper::body::Incoming;
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, HttpResponse, ServerHandle, TowerService,
+        TowerServiceBuilder,
+    },
+    Methods,
+};
+use reth_rpc_layer::PeerAddr;
+use tokio::net::TcpListener;
+use tower::Service;
+use tracing::{debug, trace};
+
+/// A bound listener of the http or ws server.
+pub(crate) struct BoundServer<RpcMiddleware, HttpMiddleware> {
+    listener: TcpListener,
+    builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+}
+
+impl<RpcMiddleware, HttpMiddleware> BoundServer<RpcMiddleware, HttpMiddleware> {
+    /// Binds the listener of the server.
+    pub(crate) async fn bind(
+        addr: SocketAddr,
+        builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+    ) -> io::Result<Self> {
+        Ok(Self { listener: TcpListener::bind(addr).await?, builder })
+    }
+
+    /// Returns the local address of the listener.
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Spawns the accept loop, every accepted connection is served on its own task.
+    ///
+    /// The server is stopped once the returned [`ServerHandle`] is stopped or dropped.
+    pub(crate) fn start(self, methods: impl Into<Methods>) -> ServerHandle
+    where
+        RpcMiddleware: Clone + Send + 'static,
+        HttpMiddleware: Clone + Send + 'static,
+        TowerService<RpcMiddleware, HttpMiddleware>:
+            Service<Request<Incoming>, Response = HttpResponse> + Clone + Send + 'static,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<Request<Incoming>>>::Error:
+            Into<BoxError>,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<Request<Incoming>>>::Future:
+            Send + 'static,
+    {
+        let Self { listener, builder } = self;
+        let methods = methods.into();
+        let (stop_handle, server_handle) = stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "Failed to accept connection");
+                            continue
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                trace!(target: "rpc", %peer_addr, "Accepted connection");
+
+                let service = builder.clone().build(methods.clone(), stop_handle.clone());
+                let service = tower::service_fn(move |mut req: Request<Incoming>| {
+                    req.extensions_mut().insert(PeerAddr(peer_addr));
+                    service.clone().call(req)
+                });
+                tokio::spawn(serve_with_graceful_shutdown(
+                    stream,
+                    service,
+                    stop_handle.clone().shutdown(),
+                ));
+            }
+        });
+
+        server_handle
+    }
+}
@@ -0,0 +1,57 @@
+//! Batch request handling of the http server.
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::{client::ClientT, params::BatchRequestBuilder},
+    rpc_params,
+};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{RpcBatchConfig, RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::RethRpcModule;
+use std::num::NonZeroUsize;
+
+fn web3_batch(calls: usize) -> BatchRequestBuilder<'static> {
+    let mut batch = BatchRequestBuilder::new();
+    for _ in 0..calls {
+        batch.insert("web3_clientVersion", rpc_params![]).unwrap();
+    }
+    batch
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_limits_and_parallelism() {
+    reth_tracing::init_test_tracing();
+
+    let modules = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_http([RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_batch_config(
+            RpcBatchConfig::default()
+                .with_max_batch_size(Some(3))
+                .with_parallelism(NonZeroUsize::new(2)),
+        )
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    let response = client.batch_request::<String>(web3_batch(3)).await.unwrap();
+    assert_eq!(response.num_successful_calls(), 3);
+
+    // too many calls
+    assert!(client.batch_request::<String>(web3_batch(4)).await.is_err());
+
+    // too large response
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_batch_config(RpcBatchConfig::default().with_max_response_size(Some(256)))
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+    assert!(client.batch_request::<String>(web3_batch(1)).await.is_ok());
+    assert!(client.batch_request::<String>(web3_batch(10)).await.is_err());
+}
@@ -336,6 +336,18 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject> {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
 
+    /// Sends signed transaction that is kept in the local pool but never propagated to peers,
+    /// returning its hash.
+    ///
+    /// If `max_block` is set, the transaction is dropped if it isn't included in a block up to and
+    /// including `max_block`.
+    #[method(name = "sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(
+        &self,
+        bytes: Bytes,
+        max_block: Option<U64>,
+    ) -> RpcResult<B256>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "sign")]
@@ -773,6 +785,17 @@ where
         Ok(EthTransactions::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendPrivateRawTransaction`
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block: Option<U64>,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", ?tx, ?max_block, "Serving eth_sendPrivateRawTransaction");
+        Ok(EthTransactions::send_private_raw_transaction(self, tx, max_block.map(|n| n.to()))
+            .await?)
+    }
+
     /// Handler for: `eth_sign`
     async fn sign(&self, address: Address, message: Bytes) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?address, ?message, "Serving eth_sign");
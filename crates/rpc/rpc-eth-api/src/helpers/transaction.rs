@@ -4,7 +4,7 @@
 use alloy_dyn_abi::TypedData;
 use alloy_eips::eip2718::Encodable2718;
 use alloy_network::TransactionBuilder;
-use alloy_primitives::{Address, Bytes, TxHash, B256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256};
 use alloy_rpc_types::{BlockNumberOrTag, TransactionInfo};
 use alloy_rpc_types_eth::transaction::TransactionRequest;
 use futures::Future;
@@ -337,6 +337,21 @@ pub trait EthTransactions: LoadTransaction {
         }
     }
 
+    /// Decodes and recovers the transaction and submits it to the pool as a
+    /// [`TransactionOrigin::Private`] transaction, which is never propagated to peers.
+    ///
+    /// If `max_block` is set, the transaction is dropped if it isn't included in a block up to and
+    /// including `max_block`.
+    ///
+    /// Returns the hash of the transaction.
+    fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block: Option<BlockNumber>,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+        add_private_raw_transaction(self.pool(), tx, max_block)
+    }
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     fn send_transaction(
@@ -570,3 +585,31 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes {
         }
     }
 }
+
+/// Decodes and recovers the transaction and adds it to the pool as a private transaction, see
+/// [`EthTransactions::send_private_raw_transaction`].
+///
+/// Fails if `max_block` isn't after the current block of the pool.
+pub async fn add_private_raw_transaction<Pool, Err>(
+    pool: &Pool,
+    tx: Bytes,
+    max_block: Option<BlockNumber>,
+) -> Result<B256, Err>
+where
+    Pool: TransactionPool,
+    Err: FromEthApiError,
+{
+    if let Some(max_block) = max_block {
+        let tip = pool.block_info().last_seen_block_number;
+        if max_block <= tip {
+            return Err(Err::from_eth_err(EthApiError::InvalidParams(format!(
+                "max block {max_block} is not after the current block {tip}"
+            ))))
+        }
+    }
+
+    let recovered = recover_raw_transaction(tx)?;
+    let pool_transaction = Pool::Transaction::from_pooled(recovered);
+
+    pool.add_private_transaction(pool_transaction, max_block).await.map_err(Err::from_eth_err)
+}
@@ -1,8 +1,8 @@
 //! `eth_` RPC API for pubsub subscription.
 
 use alloy_json_rpc::RpcObject;
-use alloy_rpc_types::pubsub::{Params, SubscriptionKind};
 use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::pubsub::{EthSubscriptionKind, EthSubscriptionParams};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
@@ -11,11 +11,11 @@ pub trait EthPubSubApi<T: RpcObject> {
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
-        item = alloy_rpc_types::pubsub::SubscriptionResult
+        item = reth_rpc_eth_types::pubsub::EthSubscriptionResult<T>
     )]
     async fn subscribe(
         &self,
-        kind: SubscriptionKind,
-        params: Option<Params>,
+        kind: EthSubscriptionKind,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -1,6 +1,6 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
@@ -42,6 +42,10 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// File the installed log and block filters are persisted to, so they survive restarts.
+    ///
+    /// If `None`, filters are kept in memory only.
+    pub filters_file: Option<PathBuf>,
 }
 
 impl EthConfig {
@@ -51,6 +55,7 @@ impl EthConfig {
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .filters_file(self.filters_file.clone())
     }
 }
 
@@ -68,6 +73,7 @@ impl Default for EthConfig {
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            filters_file: None,
         }
     }
 }
@@ -144,6 +150,10 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// File the installed log and block filters are persisted to.
+    ///
+    /// If `None` then filters are not persisted.
+    pub filters_file: Option<PathBuf>,
 }
 
 impl EthFilterConfig {
@@ -165,6 +175,12 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the file the installed filters are persisted to.
+    pub fn filters_file(mut self, path: Option<PathBuf>) -> Self {
+        self.filters_file = path;
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -174,6 +190,7 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            filters_file: None,
         }
     }
 }
@@ -658,6 +658,12 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the admission policy of the pool denies the transaction
+    #[error("transaction denied by pool policy")]
+    Denied,
+    /// Thrown if the sender exceeded its rate limit of the pool admission policy
+    #[error("sender rate limited")]
+    RateLimited,
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -684,6 +690,10 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => Self::AddressAlreadyReserved,
+            PoolErrorKind::SenderDenied(_) |
+            PoolErrorKind::RecipientDenied(_) |
+            PoolErrorKind::SelectorDenied(_) => Self::Denied,
+            PoolErrorKind::SenderRateLimited(_) => Self::RateLimited,
         }
     }
 }
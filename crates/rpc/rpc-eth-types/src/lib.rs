@@ -16,10 +16,12 @@ pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod pubsub;
 pub mod receipt;
 pub mod revm_utils;
 pub mod simulate;
 pub mod transaction;
+pub mod txpool;
 pub mod utils;
 
 pub use builder::{
@@ -38,5 +40,12 @@ pub use gas_oracle::{
 pub use id_provider::EthSubscriptionIdProvider;
 pub use logs_utils::EthFilterError;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use pubsub::{
+    EthSubscriptionKind, EthSubscriptionParams, EthSubscriptionResult, ReorgNotification,
+    TransactionReceiptsFilter,
+};
 pub use receipt::ReceiptBuilder;
 pub use transaction::TransactionSource;
+pub use txpool::{
+    TxpoolDiscardReason, TxpoolEvent, TxpoolFilter, TxpoolPage, TxpoolSubPool, TxpoolTransaction,
+};
@@ -0,0 +1,234 @@
+//! Types for the `eth_subscribe` RPC method, extending the standard subscription kinds.
+
+use std::collections::HashSet;
+
+use alloy_primitives::{Address, TxHash, B256};
+use alloy_rpc_types::{
+    pubsub::{SubscriptionKind, SubscriptionResult},
+    AnyTransactionReceipt, Transaction,
+};
+use reth_execution_types::Chain;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Subscription kind.
+///
+/// Superset of the standard [`SubscriptionKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EthSubscriptionKind {
+    /// New block headers, see [`SubscriptionKind::NewHeads`].
+    NewHeads,
+    /// Logs matching a filter, see [`SubscriptionKind::Logs`].
+    Logs,
+    /// New pending transactions, see [`SubscriptionKind::NewPendingTransactions`].
+    ///
+    /// Full transaction bodies are sent instead of hashes if the subscription params are `true`.
+    NewPendingTransactions,
+    /// Sync status changes, see [`SubscriptionKind::Syncing`].
+    Syncing,
+    /// Receipts of the transactions included in new canonical blocks.
+    ///
+    /// Receipts can be narrowed down by passing a [`TransactionReceiptsFilter`] as subscription
+    /// params.
+    TransactionReceipts,
+    /// Reorgs of the canonical chain, see [`ReorgNotification`].
+    Reorgs,
+}
+
+impl From<SubscriptionKind> for EthSubscriptionKind {
+    fn from(kind: SubscriptionKind) -> Self {
+        match kind {
+            SubscriptionKind::NewHeads => Self::NewHeads,
+            SubscriptionKind::Logs => Self::Logs,
+            SubscriptionKind::NewPendingTransactions => Self::NewPendingTransactions,
+            SubscriptionKind::Syncing => Self::Syncing,
+        }
+    }
+}
+
+/// Subscription params.
+///
+/// How an object is interpreted depends on the [`EthSubscriptionKind`]: it's a log filter for
+/// [`EthSubscriptionKind::Logs`] and a [`TransactionReceiptsFilter`] for
+/// [`EthSubscriptionKind::TransactionReceipts`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthSubscriptionParams {
+    /// No params.
+    #[default]
+    None,
+    /// Boolean params, requests full transactions for
+    /// [`EthSubscriptionKind::NewPendingTransactions`].
+    Bool(bool),
+    /// Object params.
+    Object(serde_json::Map<String, serde_json::Value>),
+}
+
+impl EthSubscriptionParams {
+    /// Deserializes the object params into `T`.
+    ///
+    /// Returns `None` if no params were given, and an error if the params are not an object or
+    /// can't be deserialized into `T`.
+    pub fn into_object<T: DeserializeOwned>(self) -> Result<Option<T>, serde_json::Error> {
+        match self {
+            Self::None => Ok(None),
+            Self::Bool(_) => Err(serde::de::Error::custom("expected an object")),
+            Self::Object(object) => serde_json::from_value(object.into()).map(Some),
+        }
+    }
+}
+
+/// Filter for [`EthSubscriptionKind::TransactionReceipts`].
+///
+/// A receipt is sent if the transaction matches all non-empty criteria.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TransactionReceiptsFilter {
+    /// Only send the receipts of these transactions.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub transaction_hashes: HashSet<TxHash>,
+    /// Only send the receipts of transactions sent by one of these addresses.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub from: HashSet<Address>,
+    /// Only send the receipts of transactions to one of these addresses.
+    ///
+    /// Contract creations never match a non-empty recipient filter.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub to: HashSet<Address>,
+}
+
+impl TransactionReceiptsFilter {
+    /// Returns `true` if the transaction matches the filter.
+    pub fn matches(&self, tx_hash: &TxHash, from: &Address, to: Option<&Address>) -> bool {
+        (self.transaction_hashes.is_empty() || self.transaction_hashes.contains(tx_hash)) &&
+            (self.from.is_empty() || self.from.contains(from)) &&
+            (self.to.is_empty() || to.is_some_and(|to| self.to.contains(to)))
+    }
+}
+
+/// Notification sent to [`EthSubscriptionKind::Reorgs`] subscribers when canonical blocks are
+/// reverted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgNotification {
+    /// Number of the last block shared by the old and the new canonical chain.
+    #[serde(with = "alloy_serde::quantity")]
+    pub common_ancestor_number: u64,
+    /// Hash of the last block shared by the old and the new canonical chain.
+    pub common_ancestor_hash: B256,
+    /// Hashes of the blocks removed from the canonical chain, in ascending block order.
+    pub removed: Vec<B256>,
+    /// Hashes of the blocks added to the canonical chain, in ascending block order.
+    ///
+    /// Empty if blocks were only reverted.
+    pub added: Vec<B256>,
+}
+
+impl ReorgNotification {
+    /// Creates a new notification from the reverted and the new canonical chain segment.
+    pub fn new(old: &Chain, new: &Chain) -> Self {
+        let fork_block = old.fork_block();
+        Self {
+            common_ancestor_number: fork_block.number,
+            common_ancestor_hash: fork_block.hash,
+            removed: old.blocks_iter().map(|block| block.hash()).collect(),
+            added: new.blocks_iter().map(|block| block.hash()).collect(),
+        }
+    }
+}
+
+/// Subscription result.
+///
+/// Superset of the standard [`SubscriptionResult`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthSubscriptionResult<T = Transaction> {
+    /// Reorg notification.
+    Reorg(Box<ReorgNotification>),
+    /// Receipt of a transaction included in a new canonical block.
+    TransactionReceipt(Box<AnyTransactionReceipt>),
+    /// Standard subscription result.
+    Standard(SubscriptionResult<T>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_serde() {
+        for (kind, name) in [
+            (EthSubscriptionKind::NewHeads, "newHeads"),
+            (EthSubscriptionKind::Logs, "logs"),
+            (EthSubscriptionKind::NewPendingTransactions, "newPendingTransactions"),
+            (EthSubscriptionKind::Syncing, "syncing"),
+            (EthSubscriptionKind::TransactionReceipts, "transactionReceipts"),
+            (EthSubscriptionKind::Reorgs, "reorgs"),
+        ] {
+            let json = format!("\"{name}\"");
+            assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+            assert_eq!(serde_json::from_str::<EthSubscriptionKind>(&json).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn params_serde() {
+        let params: EthSubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, EthSubscriptionParams::Bool(true));
+
+        let params: EthSubscriptionParams =
+            serde_json::from_str(r#"{"from":["0x0000000000000000000000000000000000000001"]}"#)
+                .unwrap();
+        let filter = params.into_object::<TransactionReceiptsFilter>().unwrap().unwrap();
+        assert_eq!(filter.from, HashSet::from([Address::with_last_byte(1)]));
+        assert!(filter.transaction_hashes.is_empty());
+
+        let params: EthSubscriptionParams = serde_json::from_str(r#"{"sender":[]}"#).unwrap();
+        assert!(params.into_object::<TransactionReceiptsFilter>().is_err());
+        assert!(EthSubscriptionParams::Bool(false)
+            .into_object::<TransactionReceiptsFilter>()
+            .is_err());
+        assert_eq!(
+            EthSubscriptionParams::None.into_object::<TransactionReceiptsFilter>().unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn receipts_filter_matches() {
+        let sender = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+        let hash = TxHash::with_last_byte(3);
+
+        assert!(TransactionReceiptsFilter::default().matches(&hash, &sender, None));
+
+        let filter = TransactionReceiptsFilter {
+            from: HashSet::from([sender]),
+            to: HashSet::from([recipient]),
+            ..Default::default()
+        };
+        assert!(filter.matches(&hash, &sender, Some(&recipient)));
+        assert!(!filter.matches(&hash, &recipient, Some(&recipient)));
+        assert!(!filter.matches(&hash, &sender, None));
+
+        let filter = TransactionReceiptsFilter {
+            transaction_hashes: HashSet::from([hash]),
+            ..Default::default()
+        };
+        assert!(filter.matches(&hash, &recipient, None));
+        assert!(!filter.matches(&TxHash::ZERO, &recipient, None));
+    }
+
+    #[test]
+    fn reorg_notification_serde() {
+        let notification = ReorgNotification {
+            common_ancestor_number: 16,
+            common_ancestor_hash: B256::with_last_byte(1),
+            removed: vec![B256::with_last_byte(2)],
+            added: vec![],
+        };
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["commonAncestorNumber"], "0x10");
+        assert_eq!(serde_json::from_value::<ReorgNotification>(json).unwrap(), notification);
+    }
+}
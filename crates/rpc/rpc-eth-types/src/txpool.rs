@@ -0,0 +1,183 @@
+//! Types for the filtered `txpool_` RPC methods and the `txpool_subscribe` subscription.
+
+use std::collections::HashSet;
+
+use alloy_primitives::{Address, TxHash, B256};
+use serde::{Deserialize, Serialize};
+
+/// Sub-pool a transaction is reported in by the `txpool_` namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubPool {
+    /// Transactions that are ready for inclusion in the next block.
+    Pending,
+    /// Transactions that are parked, e.g. because of a nonce gap or an insufficient fee cap.
+    Queued,
+}
+
+/// Filter for `txpool_contentFiltered`.
+///
+/// A transaction is returned if it matches all set criteria. Matching transactions are ordered by
+/// sender and nonce, which keeps pages stable while the pool doesn't change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TxpoolFilter {
+    /// Only return transactions sent by one of these addresses.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub from: HashSet<Address>,
+    /// Only return transactions to one of these addresses.
+    ///
+    /// Contract creations never match a non-empty recipient filter.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub to: HashSet<Address>,
+    /// Only return transactions whose max fee per gas, or gas price for legacy transactions, is
+    /// at least this value.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub min_fee: Option<u128>,
+    /// Only return transactions from this sub-pool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_pool: Option<TxpoolSubPool>,
+    /// Number of matching transactions to skip.
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum number of transactions to return, all remaining if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl TxpoolFilter {
+    /// Returns `true` if the transaction matches the filter, ignoring paging.
+    pub fn matches(
+        &self,
+        sub_pool: TxpoolSubPool,
+        from: &Address,
+        to: Option<&Address>,
+        max_fee_per_gas: u128,
+    ) -> bool {
+        self.sub_pool.map_or(true, |filter| filter == sub_pool) &&
+            (self.from.is_empty() || self.from.contains(from)) &&
+            (self.to.is_empty() || to.is_some_and(|to| self.to.contains(to))) &&
+            self.min_fee.map_or(true, |min_fee| max_fee_per_gas >= min_fee)
+    }
+}
+
+/// A page of transactions returned by `txpool_contentFiltered`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolPage<T> {
+    /// The matching transactions of this page.
+    pub transactions: Vec<TxpoolTransaction<T>>,
+    /// Total number of matching transactions in the pool.
+    pub total: usize,
+}
+
+/// A transaction returned by `txpool_contentFiltered`, along with its sub-pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolTransaction<T> {
+    /// The sub-pool the transaction is in.
+    pub sub_pool: TxpoolSubPool,
+    /// The transaction.
+    pub transaction: T,
+}
+
+/// Why a transaction was removed from the pool without being mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDiscardReason {
+    /// The transaction was evicted because of the pool limits, or removed because it no longer
+    /// fits the sender's account state.
+    Dropped,
+    /// The private transaction wasn't included before its inclusion deadline.
+    Expired,
+    /// The transaction became invalid.
+    Invalid,
+}
+
+/// Lifecycle event of a pool transaction sent to `txpool_subscribe` subscribers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TxpoolEvent {
+    /// The transaction was added to the pool.
+    #[serde(rename_all = "camelCase")]
+    Added {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// The sub-pool the transaction was added to.
+        sub_pool: TxpoolSubPool,
+    },
+    /// The queued transaction was moved to the pending sub-pool.
+    Promoted {
+        /// Hash of the transaction.
+        hash: TxHash,
+    },
+    /// The pending transaction was moved to the queued sub-pool.
+    Parked {
+        /// Hash of the transaction.
+        hash: TxHash,
+    },
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Hash of the replacement transaction.
+        replaced_by: TxHash,
+    },
+    /// The transaction was included in a block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Hash of the block that includes the transaction.
+        block_hash: B256,
+    },
+    /// The transaction was removed from the pool without being mined.
+    Discarded {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Why the transaction was removed.
+        reason: TxpoolDiscardReason,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_serde() {
+        let filter: TxpoolFilter = serde_json::from_str(
+            r#"{"from":["0x0000000000000000000000000000000000000001"],"minFee":"0x64","subPool":"queued","limit":10}"#,
+        )
+        .unwrap();
+        assert_eq!(filter.from, HashSet::from([Address::with_last_byte(1)]));
+        assert_eq!(filter.min_fee, Some(100));
+        assert_eq!(filter.sub_pool, Some(TxpoolSubPool::Queued));
+        assert_eq!(filter.offset, 0);
+        assert_eq!(filter.limit, Some(10));
+
+        assert!(filter.matches(TxpoolSubPool::Queued, &Address::with_last_byte(1), None, 100));
+        assert!(!filter.matches(TxpoolSubPool::Pending, &Address::with_last_byte(1), None, 100));
+        assert!(!filter.matches(TxpoolSubPool::Queued, &Address::with_last_byte(2), None, 100));
+        assert!(!filter.matches(TxpoolSubPool::Queued, &Address::with_last_byte(1), None, 99));
+
+        assert!(serde_json::from_str::<TxpoolFilter>(r#"{"sender":[]}"#).is_err());
+    }
+
+    #[test]
+    fn event_serde() {
+        let event =
+            TxpoolEvent::Replaced { hash: B256::ZERO, replaced_by: B256::with_last_byte(1) };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "replaced");
+        assert_eq!(json["replacedBy"], B256::with_last_byte(1).to_string());
+        assert_eq!(serde_json::from_value::<TxpoolEvent>(json).unwrap(), event);
+
+        let event =
+            TxpoolEvent::Discarded { hash: B256::ZERO, reason: TxpoolDiscardReason::Invalid };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "discarded");
+        assert_eq!(json["reason"], "invalid");
+    }
+}
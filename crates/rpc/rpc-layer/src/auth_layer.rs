@@ -97,7 +97,7 @@ where
     fn call(&mut self, req: HttpRequest) -> Self::Future {
         match self.validator.validate(req.headers()) {
             Ok(_) => ResponseFuture::future(self.inner.call(req)),
-            Err(res) => ResponseFuture::invalid_auth(res),
+            Err(res) => ResponseFuture::rejected(res),
         }
     }
 }
@@ -112,11 +112,11 @@ pub struct ResponseFuture<F> {
 }
 
 impl<F> ResponseFuture<F> {
-    const fn future(future: F) -> Self {
+    pub(crate) const fn future(future: F) -> Self {
         Self { kind: Kind::Future { future } }
     }
 
-    const fn invalid_auth(err_res: HttpResponse) -> Self {
+    pub(crate) const fn rejected(err_res: HttpResponse) -> Self {
         Self { kind: Kind::Error { response: Some(err_res) } }
     }
 }
@@ -14,6 +14,7 @@ use jsonrpsee_http_client::HttpResponse;
 mod auth_client_layer;
 mod auth_layer;
 mod jwt_validator;
+mod rate_limit;
 
 pub use auth_layer::{AuthService, ResponseFuture};
 
@@ -23,6 +24,11 @@ pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
 pub use jwt_validator::JwtAuthValidator;
+pub use rate_limit::{
+    MethodCosts, PeerAddr, RateLimitBudget, RateLimitConfig, RateLimitHttpLayer,
+    RateLimitHttpService, RpcClient, RpcRateLimitLayer, RpcRateLimitService, RpcRateLimiter,
+    API_KEY_HEADER, DEFAULT_METHOD_COST, RATE_LIMIT_ERROR_CODE, RATE_LIMIT_ERROR_MESSAGE,
+};
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
 /// a validator trait into an [`AuthLayer`].
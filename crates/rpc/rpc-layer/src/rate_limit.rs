@@ -0,0 +1,661 @@
+//! Compute unit based rate limiting for the RPC servers.
+//!
+//! Every RPC method is assigned a cost in compute units (CU) and every client is given a token
+//! bucket that refills at a configured rate. Clients are identified either by an API key sent in
+//! the [`API_KEY_HEADER`] or by their IP address. The IP address is the [`PeerAddr`] of the
+//! connection, unless the peer is a trusted reverse proxy, in which case the client address is
+//! taken from the `X-Forwarded-For` or `X-Real-IP` headers.
+//!
+//! Only the http and ws servers are rate limited, IPC clients are local and not accounted.
+
+use crate::auth_layer::ResponseFuture;
+use http::{header::RETRY_AFTER, HeaderMap, HeaderValue, Response, StatusCode};
+use jsonrpsee::{
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use jsonrpsee_http_client::{HttpBody, HttpRequest, HttpResponse};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// JSON-RPC error code returned for calls that exceed the client's budget.
+pub const RATE_LIMIT_ERROR_CODE: i32 = -32005;
+
+/// JSON-RPC error message returned for calls that exceed the client's budget.
+pub const RATE_LIMIT_ERROR_MESSAGE: &str = "rate limit exceeded";
+
+/// Header used by clients to present an API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Default cost of methods without an explicit cost.
+pub const DEFAULT_METHOD_COST: u64 = 10;
+
+/// Maximum number of client buckets that are tracked before full buckets are evicted.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// Built-in method costs, a trailing `*` matches all methods with that prefix.
+const DEFAULT_METHOD_COSTS: &[(&str, u64)] = &[
+    ("eth_blockNumber", 1),
+    ("eth_chainId", 1),
+    ("net_version", 1),
+    ("net_listening", 1),
+    ("net_peerCount", 1),
+    ("web3_clientVersion", 1),
+    ("eth_syncing", 1),
+    ("eth_gasPrice", 2),
+    ("eth_maxPriorityFeePerGas", 2),
+    ("eth_getBalance", 5),
+    ("eth_getTransactionCount", 5),
+    ("eth_getCode", 5),
+    ("eth_getStorageAt", 5),
+    ("eth_getBlockByNumber", 10),
+    ("eth_getBlockByHash", 10),
+    ("eth_getTransactionByHash", 10),
+    ("eth_getTransactionReceipt", 10),
+    ("eth_sendRawTransaction", 10),
+    ("eth_call", 20),
+    ("eth_estimateGas", 30),
+    ("eth_createAccessList", 30),
+    ("eth_getBlockReceipts", 50),
+    ("eth_feeHistory", 50),
+    ("eth_getLogs", 75),
+    ("eth_getProof", 100),
+    ("eth_simulateV1", 200),
+    ("eth_callBundle", 200),
+    ("mev_simBundle", 200),
+    ("trace_*", 300),
+    ("ots_*", 100),
+    ("debug_trace*", 500),
+];
+
+/// The compute unit costs of RPC methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCosts {
+    /// Cost of methods that are not configured.
+    default_cost: u64,
+    /// Costs by exact method name.
+    methods: HashMap<String, u64>,
+    /// Costs by method name prefix.
+    prefixes: Vec<(String, u64)>,
+}
+
+impl MethodCosts {
+    /// Creates an empty cost table, all methods cost `default_cost`.
+    pub fn new(default_cost: u64) -> Self {
+        Self { default_cost, methods: HashMap::new(), prefixes: Vec::new() }
+    }
+
+    /// Sets the cost of a method.
+    ///
+    /// A trailing `*` configures the cost of all methods starting with the given prefix. Exact
+    /// method names take precedence over prefixes, longer prefixes take precedence over shorter
+    /// ones.
+    pub fn with_cost(mut self, method: impl Into<String>, cost: u64) -> Self {
+        let method = method.into();
+        if let Some(prefix) = method.strip_suffix('*') {
+            self.prefixes.retain(|(existing, _)| existing != prefix);
+            self.prefixes.push((prefix.to_string(), cost));
+            self.prefixes.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        } else {
+            self.methods.insert(method, cost);
+        }
+        self
+    }
+
+    /// Returns the cost of the given method.
+    pub fn cost(&self, method: &str) -> u64 {
+        if let Some(cost) = self.methods.get(method) {
+            return *cost
+        }
+        self.prefixes
+            .iter()
+            .find_map(|(prefix, cost)| method.starts_with(prefix.as_str()).then_some(*cost))
+            .unwrap_or(self.default_cost)
+    }
+}
+
+impl Default for MethodCosts {
+    fn default() -> Self {
+        DEFAULT_METHOD_COSTS.iter().fold(Self::new(DEFAULT_METHOD_COST), |costs, (method, cost)| {
+            costs.with_cost(*method, *cost)
+        })
+    }
+}
+
+/// A token bucket budget in compute units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitBudget {
+    /// Compute units that are refilled per second.
+    pub compute_units_per_second: u64,
+    /// Maximum number of compute units that can be spent at once.
+    pub burst: u64,
+}
+
+impl RateLimitBudget {
+    /// Creates a new budget, the burst defaults to one second worth of compute units.
+    pub const fn new(compute_units_per_second: u64) -> Self {
+        Self { compute_units_per_second, burst: compute_units_per_second }
+    }
+
+    /// Sets the burst size.
+    pub const fn with_burst(mut self, burst: u64) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Configuration of the [`RpcRateLimiter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Budget of every client IP, unlimited if not set.
+    pub per_ip: Option<RateLimitBudget>,
+    /// Budget of every API key, unlimited if not set.
+    pub per_api_key: Option<RateLimitBudget>,
+    /// API keys that are accepted in the [`API_KEY_HEADER`].
+    pub api_keys: HashSet<String>,
+    /// File with additional API keys, see [`RateLimitConfig::load_api_keys`].
+    pub api_keys_file: Option<PathBuf>,
+    /// Addresses of reverse proxies whose `X-Forwarded-For` and `X-Real-IP` headers are trusted.
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// Compute unit cost of the RPC methods.
+    pub method_costs: MethodCosts,
+}
+
+impl RateLimitConfig {
+    /// Sets the budget of every client IP.
+    pub const fn with_ip_budget(mut self, budget: RateLimitBudget) -> Self {
+        self.per_ip = Some(budget);
+        self
+    }
+
+    /// Sets the budget of every API key.
+    pub const fn with_api_key_budget(mut self, budget: RateLimitBudget) -> Self {
+        self.per_api_key = Some(budget);
+        self
+    }
+
+    /// Sets the accepted API keys.
+    pub fn with_api_keys(mut self, api_keys: impl IntoIterator<Item = String>) -> Self {
+        self.api_keys = api_keys.into_iter().collect();
+        self
+    }
+
+    /// Sets a file with one accepted API key per line, see [`Self::load_api_keys`].
+    pub fn with_api_keys_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.api_keys_file = Some(path.into());
+        self
+    }
+
+    /// Reads the API keys from the configured file, if any, and adds them to the accepted keys.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn load_api_keys(mut self) -> io::Result<Self> {
+        if let Some(path) = &self.api_keys_file {
+            let content = std::fs::read_to_string(path)?;
+            self.api_keys.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(ToString::to_string),
+            );
+        }
+        Ok(self)
+    }
+
+    /// Sets the reverse proxies whose forwarding headers are trusted.
+    pub fn with_trusted_proxies(mut self, proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.trusted_proxies = proxies.into_iter().collect();
+        self
+    }
+
+    /// Sets the method costs.
+    pub fn with_method_costs(mut self, method_costs: MethodCosts) -> Self {
+        self.method_costs = method_costs;
+        self
+    }
+
+    /// Returns true if any budget is configured.
+    pub const fn is_enabled(&self) -> bool {
+        self.per_ip.is_some() || self.per_api_key.is_some()
+    }
+}
+
+/// The address of the remote peer of a connection.
+///
+/// Servers insert this into the extensions of every http request, it's used by the
+/// [`RateLimitHttpLayer`] to identify clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerAddr(pub SocketAddr);
+
+/// The identity a request is accounted to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RpcClient {
+    /// A client that presented a known API key.
+    ApiKey(String),
+    /// A client identified by its IP address.
+    Ip(IpAddr),
+    /// A client whose address is unknown, all such clients share a budget.
+    Unknown,
+}
+
+/// A token bucket in compute units.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// Available compute units.
+    tokens: f64,
+    /// Last time the bucket was refilled.
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn full(budget: RateLimitBudget, now: Instant) -> Self {
+        Self { tokens: budget.burst as f64, updated_at: now }
+    }
+
+    /// Refills the bucket for the time elapsed since the last update.
+    fn refill(&mut self, budget: RateLimitBudget, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * budget.compute_units_per_second as f64)
+            .min(budget.burst as f64);
+        self.updated_at = now;
+    }
+
+    /// Tries to take `cost` compute units out of the bucket.
+    ///
+    /// Costs above the burst size are capped, so that expensive methods can still be called by a
+    /// client with a full bucket.
+    fn try_consume(&mut self, cost: u64, budget: RateLimitBudget, now: Instant) -> bool {
+        self.refill(budget, now);
+        let cost = cost.min(budget.burst) as f64;
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if the bucket has no compute units left.
+    fn is_exhausted(&mut self, budget: RateLimitBudget, now: Instant) -> bool {
+        self.refill(budget, now);
+        self.tokens < 1.0
+    }
+
+    /// Returns true if the bucket is full, meaning it can be dropped without loss of information.
+    fn is_full(&mut self, budget: RateLimitBudget, now: Instant) -> bool {
+        self.refill(budget, now);
+        self.tokens >= budget.burst as f64
+    }
+}
+
+/// Tracks the budgets of all RPC clients.
+///
+/// This is shared between the [`RateLimitHttpLayer`], which identifies clients and rejects
+/// exhausted ones with `429 Too Many Requests`, and the RPC middleware, which charges every call
+/// its cost and rejects calls that exceed the budget, see [`RpcRateLimiter::limit_call`].
+#[derive(Debug, Clone)]
+pub struct RpcRateLimiter {
+    inner: Arc<RpcRateLimiterInner>,
+}
+
+#[derive(Debug)]
+struct RpcRateLimiterInner {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<RpcClient, TokenBucket>>,
+    metrics: RateLimitMetrics,
+    /// Metrics of the registered methods.
+    method_metrics: HashMap<&'static str, RateLimitMethodMetrics>,
+    /// Metrics of all calls to methods that aren't registered.
+    other_method_metrics: RateLimitMethodMetrics,
+}
+
+impl RpcRateLimiter {
+    /// Creates a new rate limiter with the given config.
+    ///
+    /// Per method metrics are recorded for the registered `methods`, calls of other methods are
+    /// recorded under the `other` label.
+    pub fn new(config: RateLimitConfig, methods: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            inner: Arc::new(RpcRateLimiterInner {
+                config,
+                buckets: Default::default(),
+                metrics: Default::default(),
+                method_metrics: methods
+                    .into_iter()
+                    .map(|method| {
+                        (method, RateLimitMethodMetrics::new_with_labels(&[("method", method)]))
+                    })
+                    .collect(),
+                other_method_metrics: RateLimitMethodMetrics::new_with_labels(&[(
+                    "method", "other",
+                )]),
+            }),
+        }
+    }
+
+    /// Returns the config of the rate limiter.
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.inner.config
+    }
+
+    /// Identifies the client from the request headers and the address of the connection's peer.
+    ///
+    /// A known API key takes precedence over the IP address. The IP address is the peer address,
+    /// unless the peer is a trusted proxy. Then it's the right-most entry of `X-Forwarded-For`
+    /// that is not a trusted proxy itself, or `X-Real-IP`.
+    pub fn client_from_request(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> RpcClient {
+        if self.inner.config.per_api_key.is_some() {
+            if let Some(key) = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+                if self.inner.config.api_keys.contains(key.trim()) {
+                    return RpcClient::ApiKey(key.trim().to_string())
+                }
+            }
+        }
+
+        let Some(peer) = peer else { return RpcClient::Unknown };
+        let trusted = &self.inner.config.trusted_proxies;
+        if !trusted.contains(&peer) {
+            return RpcClient::Ip(peer)
+        }
+
+        // every proxy appends the address it received the request from, entries to the left of the
+        // first untrusted one are set by the client and can't be trusted
+        headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| {
+                v.rsplit(',')
+                    .map(|ip| ip.trim().parse::<IpAddr>().ok())
+                    .find(|ip| ip.map_or(true, |ip| !trusted.contains(&ip)))
+                    .flatten()
+            })
+            .or_else(|| {
+                headers
+                    .get("x-real-ip")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|ip| ip.trim().parse().ok())
+            })
+            .map(RpcClient::Ip)
+            .unwrap_or(RpcClient::Ip(peer))
+    }
+
+    /// Returns the budget of the given client, `None` if unlimited.
+    fn budget(&self, client: &RpcClient) -> Option<RateLimitBudget> {
+        match client {
+            RpcClient::ApiKey(_) => self.inner.config.per_api_key,
+            RpcClient::Ip(_) | RpcClient::Unknown => self.inner.config.per_ip,
+        }
+    }
+
+    /// Returns true if the client has no compute units left.
+    pub fn is_exhausted(&self, client: &RpcClient) -> bool {
+        let Some(budget) = self.budget(client) else { return false };
+        let now = Instant::now();
+        self.inner
+            .buckets
+            .lock()
+            .get_mut(client)
+            .map(|bucket| bucket.is_exhausted(budget, now))
+            .unwrap_or_default()
+    }
+
+    /// Charges the client for a call of `method` and returns false if the budget is exceeded.
+    pub fn try_acquire(&self, client: &RpcClient, method: &str) -> bool {
+        let cost = self.inner.config.method_costs.cost(method);
+        let allowed = self.try_consume(client, cost, Instant::now());
+
+        let metrics =
+            self.inner.method_metrics.get(method).unwrap_or(&self.inner.other_method_metrics);
+        if allowed {
+            metrics.compute_units_total.increment(cost);
+        } else {
+            metrics.limited_total.increment(1);
+        }
+
+        allowed
+    }
+
+    /// Charges the client of the call, see [`RpcClient`], and returns the error response if the
+    /// budget is exceeded.
+    pub fn limit_call(&self, req: &Request<'_>) -> Option<MethodResponse> {
+        let client = req.extensions().get::<RpcClient>().cloned().unwrap_or(RpcClient::Unknown);
+        if self.try_acquire(&client, req.method_name()) {
+            return None
+        }
+        trace!(target: "rpc::rate_limit", ?client, method = %req.method_name(), "Rate limited call");
+        Some(MethodResponse::error(
+            req.id.clone(),
+            ErrorObject::owned::<()>(RATE_LIMIT_ERROR_CODE, RATE_LIMIT_ERROR_MESSAGE, None),
+        ))
+    }
+
+    fn try_consume(&self, client: &RpcClient, cost: u64, now: Instant) -> bool {
+        let Some(budget) = self.budget(client) else { return true };
+
+        let mut buckets = self.inner.buckets.lock();
+        if !buckets.contains_key(client) && buckets.len() >= MAX_TRACKED_CLIENTS {
+            // full buckets carry no state, so they can be dropped
+            buckets.retain(|client, bucket| {
+                self.budget(client).map_or(false, |budget| !bucket.is_full(budget, now))
+            });
+        }
+        let allowed = buckets
+            .entry(client.clone())
+            .or_insert_with(|| TokenBucket::full(budget, now))
+            .try_consume(cost, budget, now);
+        self.inner.metrics.tracked_clients.set(buckets.len() as f64);
+
+        allowed
+    }
+}
+
+/// Http middleware layer that identifies the [`RpcClient`] of a request and rejects clients that
+/// have exhausted their budget with `429 Too Many Requests`.
+///
+/// The identified [`RpcClient`] is inserted into the request extensions, where it is picked up by
+/// [`RpcRateLimiter::limit_call`].
+#[derive(Debug, Clone)]
+pub struct RateLimitHttpLayer {
+    limiter: RpcRateLimiter,
+}
+
+impl RateLimitHttpLayer {
+    /// Creates a new [`RateLimitHttpLayer`].
+    pub const fn new(limiter: RpcRateLimiter) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitHttpLayer {
+    type Service = RateLimitHttpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitHttpService { limiter: self.limiter.clone(), inner }
+    }
+}
+
+/// The [`Service`] created by the [`RateLimitHttpLayer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitHttpService<S> {
+    limiter: RpcRateLimiter,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RateLimitHttpService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        let peer = req.extensions().get::<PeerAddr>().map(|PeerAddr(addr)| addr.ip());
+        let client = self.limiter.client_from_request(req.headers(), peer);
+        if self.limiter.is_exhausted(&client) {
+            trace!(target: "rpc::rate_limit", ?client, "Rejecting request of exhausted client");
+            self.limiter.inner.metrics.http_rejected_total.increment(1);
+            return ResponseFuture::rejected(too_many_requests())
+        }
+        req.extensions_mut().insert(client);
+        ResponseFuture::future(self.inner.call(req))
+    }
+}
+
+/// Builds the `429 Too Many Requests` response.
+fn too_many_requests() -> HttpResponse {
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(RETRY_AFTER, HeaderValue::from_static("1"))
+        .body(HttpBody::new(RATE_LIMIT_ERROR_MESSAGE.to_string()))
+        .expect("This should never happen")
+}
+
+/// Metrics of the rate limiter.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.rate_limit")]
+struct RateLimitMetrics {
+    /// The number of tracked client buckets
+    tracked_clients: Gauge,
+    /// The number of http requests rejected with 429
+    http_rejected_total: Counter,
+}
+
+/// Rate limiter metrics per RPC method.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.rate_limit.calls")]
+struct RateLimitMethodMetrics {
+    /// The number of compute units charged
+    compute_units_total: Counter,
+    /// The number of calls rejected because the budget was exceeded
+    limited_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn method_costs() {
+        let costs = MethodCosts::default();
+        assert_eq!(costs.cost("eth_blockNumber"), 1);
+        assert_eq!(costs.cost("debug_traceTransaction"), 500);
+        assert_eq!(costs.cost("trace_block"), 300);
+        assert_eq!(costs.cost("eth_unknownMethod"), DEFAULT_METHOD_COST);
+
+        let costs = costs.with_cost("debug_*", 100).with_cost("debug_traceCall", 1000);
+        assert_eq!(costs.cost("debug_traceCall"), 1000);
+        // longer prefix wins
+        assert_eq!(costs.cost("debug_traceBlock"), 500);
+        assert_eq!(costs.cost("debug_getRawBlock"), 100);
+    }
+
+    #[test]
+    fn token_bucket_refills() {
+        let budget = RateLimitBudget::new(10).with_burst(20);
+        let now = Instant::now();
+        let mut bucket = TokenBucket::full(budget, now);
+
+        assert!(bucket.try_consume(15, budget, now));
+        assert!(!bucket.try_consume(10, budget, now));
+        assert!(bucket.try_consume(5, budget, now));
+        assert!(bucket.is_exhausted(budget, now));
+
+        // half a second refills 5 compute units
+        let later = now + Duration::from_millis(500);
+        assert!(bucket.try_consume(5, budget, later));
+        assert!(!bucket.try_consume(1, budget, later));
+
+        // never refills above the burst
+        let much_later = later + Duration::from_secs(60);
+        assert!(bucket.is_full(budget, much_later));
+        // costs above the burst are capped
+        assert!(bucket.try_consume(1000, budget, much_later));
+    }
+
+    #[test]
+    fn client_identification() {
+        let proxy: IpAddr = "192.168.0.1".parse().unwrap();
+        let limiter = RpcRateLimiter::new(
+            RateLimitConfig::default()
+                .with_ip_budget(RateLimitBudget::new(10))
+                .with_api_key_budget(RateLimitBudget::new(100))
+                .with_api_keys(["secret".to_string()])
+                .with_trusted_proxies([proxy]),
+            [],
+        );
+        let peer: IpAddr = "10.0.0.9".parse().unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(limiter.client_from_request(&headers, None), RpcClient::Unknown);
+        assert_eq!(limiter.client_from_request(&headers, Some(peer)), RpcClient::Ip(peer));
+        assert_eq!(limiter.client_from_request(&headers, Some(proxy)), RpcClient::Ip(proxy));
+
+        // forwarding headers of untrusted peers are ignored
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+        assert_eq!(limiter.client_from_request(&headers, Some(peer)), RpcClient::Ip(peer));
+        assert_eq!(
+            limiter.client_from_request(&headers, Some(proxy)),
+            RpcClient::Ip("10.0.0.2".parse().unwrap())
+        );
+
+        // the right-most untrusted entry is used, the others are set by the client
+        headers
+            .insert("x-forwarded-for", HeaderValue::from_static("1.1.1.1, 10.0.0.1, 192.168.0.1"));
+        assert_eq!(
+            limiter.client_from_request(&headers, Some(proxy)),
+            RpcClient::Ip("10.0.0.1".parse().unwrap())
+        );
+        assert_eq!(limiter.client_from_request(&headers, Some(peer)), RpcClient::Ip(peer));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("unknown"));
+        assert_eq!(limiter.client_from_request(&headers, Some(peer)), RpcClient::Ip(peer));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("secret"));
+        assert_eq!(
+            limiter.client_from_request(&headers, Some(peer)),
+            RpcClient::ApiKey("secret".to_string())
+        );
+    }
+
+    #[test]
+    fn budgets_are_per_client() {
+        let limiter = RpcRateLimiter::new(
+            RateLimitConfig::default().with_ip_budget(RateLimitBudget::new(10)),
+            [],
+        );
+        let now = Instant::now();
+        let a = RpcClient::Ip("10.0.0.1".parse().unwrap());
+        let b = RpcClient::Ip("10.0.0.2".parse().unwrap());
+
+        assert!(limiter.try_consume(&a, 10, now));
+        assert!(!limiter.try_consume(&a, 1, now));
+        assert!(limiter.try_consume(&b, 10, now));
+
+        // api keys are unlimited without a key budget
+        let key = RpcClient::ApiKey("key".to_string());
+        assert!(limiter.try_consume(&key, 1000, now));
+        assert!(limiter.try_consume(&key, 1000, now));
+    }
+}
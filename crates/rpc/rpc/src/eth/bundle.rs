@@ -2,21 +2,21 @@
 
 use std::sync::Arc;
 
-use alloy_primitives::{Keccak256, U256};
+use alloy_primitives::{Address, Keccak256, U256};
 use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthChainSpec;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    PooledTransactionsElement,
+    Header, PooledTransactionsElement, TransactionSigned,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::database::{EvmStateProvider, StateProviderDatabase};
 use reth_rpc_eth_api::{FromEthApiError, FromEvmError};
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
-    primitives::{ResultAndState, TxEnv},
+    primitives::{ExecutionResult, ResultAndState, TxEnv},
 };
 use revm_primitives::{EnvKzgSettings, EnvWithHandlerCfg, SpecId, MAX_BLOB_GAS_PER_BLOCK};
 
@@ -26,6 +26,9 @@ use reth_rpc_eth_api::{
     EthCallBundleApiServer,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+#[cfg(feature = "telos")]
+use reth_telos_primitives_traits::TelosBlockExtension;
+
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
     /// All nested fields bundled together.
@@ -159,30 +162,11 @@ where
         self.inner
             .eth_api
             .spawn_with_state_at_block(at, move |state| {
-                let coinbase = block_env.coinbase;
                 let basefee = Some(block_env.basefee.to::<u64>());
                 let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
-                let db = CacheDB::new(StateProviderDatabase::new(state));
-
-                let initial_coinbase = DatabaseRef::basic_ref(&db, coinbase)
-                    .map_err(Eth::Error::from_eth_err)?
-                    .map(|acc| acc.balance)
-                    .unwrap_or_default();
-                let mut coinbase_balance_before_tx = initial_coinbase;
-                let mut coinbase_balance_after_tx = initial_coinbase;
-                let mut total_gas_used = 0u64;
-                let mut total_gas_fess = U256::ZERO;
-                let mut hasher = Keccak256::new();
-
-                let mut evm = Call::evm_config(&eth_api).evm_with_env(db, env);
-
-                let mut results = Vec::with_capacity(transactions.len());
-                let mut transactions = transactions.into_iter().peekable();
 
-                #[cfg(feature = "telos")]
-                let mut tx_index = 0;
-
-                while let Some((tx, signer)) = transactions.next() {
+                let mut bundle_transactions = Vec::with_capacity(transactions.len());
+                for (tx, signer) in transactions {
                     // Verify that the given blob data, commitments, and proofs are all valid for
                     // this transaction.
                     if let PooledTransactionsElement::BlobTransaction(ref tx) = tx {
@@ -190,73 +174,76 @@ where
                             Eth::Error::from_eth_err(EthApiError::InvalidParams(e.to_string()))
                         })?;
                     }
+                    bundle_transactions.push((tx.into_transaction(), signer));
+                }
 
-                    let tx = tx.into_transaction();
-
-                    hasher.update(tx.hash());
-                    let gas_price = tx
-                        .effective_tip_per_gas(basefee)
-                        .ok_or_else(|| RpcInvalidTransactionError::FeeCapTooLow)
-                        .map_err(Eth::Error::from_eth_err)?;
-                    Call::evm_config(&eth_api).fill_tx_env(evm.tx_mut(), &tx, signer, #[cfg(feature = "telos")] telos_block_extension.tx_env_at(tx_index));
-                    let ResultAndState { result, state } =
-                        evm.transact().map_err(Eth::Error::from_evm_err)?;
-
-                    let gas_used = result.gas_used();
-                    total_gas_used += gas_used;
-
-                    let gas_fees = U256::from(gas_used) * U256::from(gas_price);
-                    total_gas_fess += gas_fees;
-
-                    // coinbase is always present in the result state
-                    coinbase_balance_after_tx =
-                        state.get(&coinbase).map(|acc| acc.info.balance).unwrap_or_default();
-                    let coinbase_diff =
-                        coinbase_balance_after_tx.saturating_sub(coinbase_balance_before_tx);
-                    let eth_sent_to_coinbase = coinbase_diff.saturating_sub(gas_fees);
-
-                    // update the coinbase balance
-                    coinbase_balance_before_tx = coinbase_balance_after_tx;
-
-                    // set the return data for the response
-                    let (value, revert) = if result.is_success() {
-                        let value = result.into_output().unwrap_or_default();
-                        (Some(value), None)
-                    } else {
-                        let revert = result.into_output().unwrap_or_default();
-                        (None, Some(revert))
-                    };
-
-                    let tx_res = EthCallBundleTransactionResult {
-                        coinbase_diff,
-                        eth_sent_to_coinbase,
-                        from_address: signer,
-                        gas_fees,
-                        gas_price: U256::from(gas_price),
-                        gas_used,
-                        to_address: tx.to(),
-                        tx_hash: tx.hash(),
-                        value,
-                        revert,
-                    };
-                    results.push(tx_res);
+                let mut coinbase_balance_after_tx = None;
+                let mut total_gas_used = 0u64;
+                let mut total_gas_fess = U256::ZERO;
+                let mut hasher = Keccak256::new();
+                let mut results = Vec::with_capacity(bundle_transactions.len());
 
+                let initial_coinbase = execute_bundle_transactions(
+                    Call::evm_config(&eth_api),
+                    state,
+                    env,
+                    bundle_transactions,
                     #[cfg(feature = "telos")]
-                    {
-                        tx_index += 1;
-                    }
-
-                    // need to apply the state changes of this call before executing the
-                    // next call
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this call before executing
-                        // the next call
-                        evm.context.evm.db.commit(state)
-                    }
-                }
+                    &telos_block_extension,
+                    |executed| -> Result<(), Eth::Error> {
+                        let ExecutedBundleTransaction {
+                            tx,
+                            signer,
+                            result,
+                            coinbase_balance,
+                            coinbase_diff,
+                            ..
+                        } = executed;
+
+                        hasher.update(tx.hash());
+                        let gas_price = tx
+                            .effective_tip_per_gas(basefee)
+                            .ok_or_else(|| RpcInvalidTransactionError::FeeCapTooLow)
+                            .map_err(Eth::Error::from_eth_err)?;
+
+                        let gas_used = result.gas_used();
+                        total_gas_used += gas_used;
+
+                        let gas_fees = U256::from(gas_used) * U256::from(gas_price);
+                        total_gas_fess += gas_fees;
+
+                        let eth_sent_to_coinbase = coinbase_diff.saturating_sub(gas_fees);
+                        coinbase_balance_after_tx = Some(coinbase_balance);
+
+                        // set the return data for the response
+                        let (value, revert) = if result.is_success() {
+                            let value = result.into_output().unwrap_or_default();
+                            (Some(value), None)
+                        } else {
+                            let revert = result.into_output().unwrap_or_default();
+                            (None, Some(revert))
+                        };
+
+                        results.push(EthCallBundleTransactionResult {
+                            coinbase_diff,
+                            eth_sent_to_coinbase,
+                            from_address: signer,
+                            gas_fees,
+                            gas_price: U256::from(gas_price),
+                            gas_used,
+                            to_address: tx.to(),
+                            tx_hash: tx.hash(),
+                            value,
+                            revert,
+                        });
+                        Ok(())
+                    },
+                )?;
 
                 // populate the response
 
+                let coinbase_balance_after_tx =
+                    coinbase_balance_after_tx.unwrap_or(initial_coinbase);
                 let coinbase_diff = coinbase_balance_after_tx.saturating_sub(initial_coinbase);
                 let eth_sent_to_coinbase = coinbase_diff.saturating_sub(total_gas_fess);
                 let bundle_gas_price =
@@ -278,6 +265,83 @@ where
     }
 }
 
+/// A bundle transaction executed by [`execute_bundle_transactions`].
+#[derive(Debug)]
+pub(crate) struct ExecutedBundleTransaction {
+    /// The index of the transaction in the bundle.
+    pub(crate) index: usize,
+    /// The executed transaction.
+    pub(crate) tx: TransactionSigned,
+    /// The signer of the transaction.
+    pub(crate) signer: Address,
+    /// The result of the execution.
+    pub(crate) result: ExecutionResult,
+    /// The coinbase balance after the transaction.
+    pub(crate) coinbase_balance: U256,
+    /// The increase of the coinbase balance caused by the transaction.
+    pub(crate) coinbase_diff: U256,
+}
+
+/// Executes the bundle transactions one after another on top of `state`, each on the state
+/// changes of the previous ones, and passes them to `on_transaction`, which aborts the execution
+/// by returning an error.
+///
+/// Returns the coinbase balance before the bundle.
+pub(crate) fn execute_bundle_transactions<EvmConfig, S, E>(
+    evm_config: &EvmConfig,
+    state: S,
+    env: EnvWithHandlerCfg,
+    transactions: impl IntoIterator<Item = (TransactionSigned, Address)>,
+    #[cfg(feature = "telos")] telos_block_extension: &TelosBlockExtension,
+    mut on_transaction: impl FnMut(ExecutedBundleTransaction) -> Result<(), E>,
+) -> Result<U256, E>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    S: EvmStateProvider,
+    E: From<EthApiError>,
+{
+    let coinbase = env.block.coinbase;
+    let db = CacheDB::new(StateProviderDatabase::new(state));
+
+    let initial_coinbase = DatabaseRef::basic_ref(&db, coinbase)
+        .map_err(E::from_eth_err)?
+        .map(|acc| acc.balance)
+        .unwrap_or_default();
+    let mut coinbase_balance_before_tx = initial_coinbase;
+
+    let mut evm = evm_config.evm_with_env(db, env);
+
+    for (index, (tx, signer)) in transactions.into_iter().enumerate() {
+        evm_config.fill_tx_env(
+            evm.tx_mut(),
+            &tx,
+            signer,
+            #[cfg(feature = "telos")]
+            telos_block_extension.tx_env_at(index as u64),
+        );
+        let ResultAndState { result, state } = evm.transact().map_err(E::from_evm_err)?;
+
+        // coinbase is always present in the result state
+        let coinbase_balance = state.get(&coinbase).map(|acc| acc.info.balance).unwrap_or_default();
+        let coinbase_diff = coinbase_balance.saturating_sub(coinbase_balance_before_tx);
+        coinbase_balance_before_tx = coinbase_balance;
+
+        // need to apply the state changes of this call before executing the next call
+        evm.context.evm.db.commit(state);
+
+        on_transaction(ExecutedBundleTransaction {
+            index,
+            tx,
+            signer,
+            result,
+            coinbase_balance,
+            coinbase_diff,
+        })?;
+    }
+
+    Ok(initial_coinbase)
+}
+
 #[async_trait::async_trait]
 impl<Eth> EthCallBundleApiServer for EthBundle<Eth>
 where
@@ -6,7 +6,11 @@ use std::{
     iter::StepBy,
     marker::PhantomData,
     ops::RangeInclusive,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
@@ -20,7 +24,7 @@ use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_node_api::EthApiTypes;
 use reth_primitives::TransactionSignedEcRecovered;
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogIndexReader, ProviderError};
 use reth_rpc_eth_api::{EthFilterApiServer, FullEthApiTypes, RpcTransaction, TransactionCompat};
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
@@ -30,15 +34,19 @@ use reth_rpc_server_types::ToRpcResult;
 use reth_rpc_types_compat::transaction::from_recovered;
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use tokio::{
     sync::{mpsc::Receiver, Mutex},
     time::MissedTickBehavior,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// How often the installed filters are written to the filters file, if they changed.
+const PERSIST_FILTERS_INTERVAL: Duration = Duration::from_secs(10);
+
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool, Eth: EthApiTypes> {
     /// All nested fields bundled together
@@ -69,7 +77,9 @@ where
     ///
     /// See also [`EthFilterConfig`].
     ///
-    /// This also spawns a task that periodically clears stale filters.
+    /// This also spawns a task that periodically clears stale filters and, if a filters file is
+    /// configured, restores the filters persisted to it and spawns a task that periodically
+    /// persists them.
     pub fn new(
         provider: Provider,
         pool: Pool,
@@ -77,11 +87,19 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            filters_file,
+        } = config;
+        let active_filters = match filters_file.as_deref() {
+            Some(path) => ActiveFilters::restore(path),
+            None => ActiveFilters::new(),
+        };
         let inner = EthFilterInner {
             provider,
-            active_filters: ActiveFilters::new(),
+            active_filters,
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             eth_cache,
@@ -103,6 +121,16 @@ where
             }),
         );
 
+        if let Some(path) = filters_file {
+            let this = eth_filter.clone();
+            eth_filter.inner.task_spawner.spawn_critical(
+                "eth-filters_persist",
+                Box::pin(async move {
+                    this.persist_filters(path).await;
+                }),
+            );
+        }
+
         eth_filter
     }
 
@@ -134,16 +162,37 @@ where
 
             if !is_valid {
                 trace!(target: "rpc::eth", "evict filter with id: {:?}", id);
+                self.active_filters().mark_changed();
             }
 
             is_valid
         })
     }
+
+    /// Endless future that writes the log and block filters to the given file every
+    /// [`PERSIST_FILTERS_INTERVAL`] if they changed since the last write.
+    async fn persist_filters(&self, path: PathBuf) {
+        let mut interval = tokio::time::interval(PERSIST_FILTERS_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if !self.active_filters().changed.swap(false, Ordering::Relaxed) {
+                continue
+            }
+
+            let filters = self.active_filters().persisted().await;
+            if let Err(err) =
+                reth_fs_util::atomic_write_file(&path, |file| serde_json::to_writer(file, &filters))
+            {
+                warn!(target: "rpc::eth::filter", %err, "Failed to persist filters");
+            }
+        }
+    }
 }
 
 impl<Provider, Pool, Eth> EthFilter<Provider, Pool, Eth>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
     Eth: FullEthApiTypes,
@@ -173,6 +222,7 @@ where
             let mut block = best_number + 1;
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
+            self.inner.active_filters.mark_changed();
 
             (block, filter.kind.clone())
         };
@@ -244,7 +294,7 @@ where
 impl<Provider, Pool, Eth> EthFilterApiServer<RpcTransaction<Eth::NetworkTypes>>
     for EthFilter<Provider, Pool, Eth>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     Eth: FullEthApiTypes + 'static,
 {
@@ -316,6 +366,7 @@ where
         let mut filters = self.inner.active_filters.inner.lock().await;
         if filters.remove(&id).is_some() {
             trace!(target: "rpc::eth::filter", ?id, "uninstalled filter");
+            self.inner.active_filters.mark_changed();
             Ok(true)
         } else {
             Ok(false)
@@ -367,7 +418,7 @@ struct EthFilterInner<Provider, Pool, Tx> {
 
 impl<Provider, Pool, Tx> EthFilterInner<Provider, Pool, Tx>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -438,6 +489,7 @@ where
                 kind,
             },
         );
+        self.active_filters.mark_changed();
         Ok(id)
     }
 
@@ -488,6 +540,47 @@ where
             return Ok(all_logs)
         }
 
+        // if the log index covers the start of the range, only visit the candidate blocks it
+        // yields and fall back to the bloom filter scan for the blocks above the index tip
+        let mut bloom_from_block = from_block;
+        let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+        let topics = filter
+            .topics
+            .iter()
+            .map(|topics| topics.iter().copied().collect())
+            .collect::<Vec<Vec<_>>>();
+        if let Some((indexed_to_block, candidates)) =
+            self.provider.log_index_candidates(&addresses, &topics, from_block..=to_block)?
+        {
+            trace!(target: "rpc::eth::filter", candidates=candidates.len(), indexed_to_block, "using log index");
+            for block_number in candidates {
+                let Some(header) = self.provider.sealed_header(block_number)? else { continue };
+
+                if let Some(receipts) = self.eth_cache.get_receipts(header.hash()).await? {
+                    append_matching_block_logs(
+                        &mut all_logs,
+                        &self.provider,
+                        &filter_params,
+                        header.num_hash(),
+                        &receipts,
+                        false,
+                        header.timestamp,
+                    )?;
+
+                    if from_block != to_block && all_logs.len() > self.max_logs_per_response {
+                        return Err(EthFilterError::QueryExceedsMaxResults(
+                            self.max_logs_per_response,
+                        ))
+                    }
+                }
+            }
+
+            if indexed_to_block == to_block {
+                return Ok(all_logs)
+            }
+            bloom_from_block = indexed_to_block + 1;
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -495,7 +588,7 @@ where
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(bloom_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
@@ -546,13 +639,87 @@ where
 #[derive(Debug, Clone, Default)]
 pub struct ActiveFilters<T> {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter<T>>>>,
+    /// Whether the filters changed since they were last persisted.
+    changed: Arc<AtomicBool>,
 }
 
 impl<T> ActiveFilters<T> {
     /// Returns an empty instance.
     pub fn new() -> Self {
-        Self { inner: Arc::new(Mutex::new(HashMap::default())) }
+        Self { inner: Arc::new(Mutex::new(HashMap::default())), changed: Arc::default() }
+    }
+
+    /// Returns an instance with the filters persisted to the given file.
+    ///
+    /// The restored filters are considered polled just now. A missing or unreadable file yields
+    /// an empty instance.
+    fn restore(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::new()
+        }
+
+        let filters = match reth_fs_util::read_json_file::<Vec<PersistedFilter>>(path) {
+            Ok(filters) => filters,
+            Err(err) => {
+                warn!(target: "rpc::eth::filter", %err, "Failed to restore persisted filters");
+                return Self::new()
+            }
+        };
+        trace!(target: "rpc::eth::filter", filters=filters.len(), "restored persisted filters");
+
+        let now = Instant::now();
+        let filters = filters
+            .into_iter()
+            .map(|PersistedFilter { id, block, kind }| {
+                let kind = match kind {
+                    PersistedFilterKind::Log(filter) => FilterKind::Log(filter),
+                    PersistedFilterKind::Block => FilterKind::Block,
+                };
+                (id, ActiveFilter { block, last_poll_timestamp: now, kind })
+            })
+            .collect();
+        Self { inner: Arc::new(Mutex::new(filters)), changed: Arc::default() }
+    }
+
+    /// Returns the log and block filters in their persisted form.
+    ///
+    /// Pending transaction filters are backed by pool listeners and can't be persisted.
+    async fn persisted(&self) -> Vec<PersistedFilter> {
+        self.inner
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(id, filter)| {
+                let kind = match &filter.kind {
+                    FilterKind::Log(filter) => PersistedFilterKind::Log(filter.clone()),
+                    FilterKind::Block => PersistedFilterKind::Block,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                Some(PersistedFilter { id: id.clone(), block: filter.block, kind })
+            })
+            .collect()
     }
+
+    /// Marks the filters as changed since they were last persisted.
+    fn mark_changed(&self) {
+        self.changed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A log or block filter as it's written to the filters file.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedFilter {
+    id: FilterId,
+    block: u64,
+    kind: PersistedFilterKind,
+}
+
+/// The kind of a [`PersistedFilter`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum PersistedFilterKind {
+    Log(Box<Filter>),
+    Block,
 }
 
 /// An installed filter
@@ -722,4 +889,60 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[tokio::test]
+    async fn test_persisted_filters_roundtrip() {
+        let filters = ActiveFilters::<()>::new();
+        {
+            let mut inner = filters.inner.lock().await;
+            let now = Instant::now();
+            let log_filter =
+                Filter::new().from_block(1).address(alloy_primitives::Address::random());
+            inner.insert(
+                FilterId::Num(1),
+                ActiveFilter {
+                    block: 10,
+                    last_poll_timestamp: now,
+                    kind: FilterKind::Log(Box::new(log_filter)),
+                },
+            );
+            inner.insert(
+                FilterId::Str("0x2".to_string()),
+                ActiveFilter { block: 20, last_poll_timestamp: now, kind: FilterKind::Block },
+            );
+            let (_tx, rx) = tokio::sync::mpsc::channel(1);
+            inner.insert(
+                FilterId::Num(3),
+                ActiveFilter {
+                    block: 30,
+                    last_poll_timestamp: now,
+                    kind: FilterKind::PendingTransaction(PendingTransactionKind::Hashes(
+                        PendingTransactionsReceiver::new(rx),
+                    )),
+                },
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rpc-filters.json");
+        let persisted = filters.persisted().await;
+        assert_eq!(persisted.len(), 2);
+        reth_fs_util::write_json_file(&path, &persisted).unwrap();
+
+        let restored = ActiveFilters::<()>::restore(&path);
+        let restored = restored.inner.lock().await;
+        let original = filters.inner.lock().await;
+        assert_eq!(restored.len(), 2);
+        for id in [FilterId::Num(1), FilterId::Str("0x2".to_string())] {
+            let (restored, original) = (&restored[&id], &original[&id]);
+            assert_eq!(restored.block, original.block);
+            match (&restored.kind, &original.kind) {
+                (FilterKind::Log(restored), FilterKind::Log(original)) => {
+                    assert_eq!(restored, original)
+                }
+                (FilterKind::Block, FilterKind::Block) => {}
+                _ => panic!("filter kind changed"),
+            }
+        }
+    }
 }
@@ -4,11 +4,8 @@ use std::{marker::PhantomData, sync::Arc};
 
 use alloy_primitives::TxHash;
 use alloy_rpc_types::{
-    pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
-        SyncStatusMetadata,
-    },
-    FilteredParams, Header, Log, Transaction,
+    pubsub::{PubSubSyncStatus, SubscriptionResult, SyncStatusMetadata},
+    AnyTransactionReceipt, Filter, FilteredParams, Header, Log, Transaction,
 };
 use alloy_serde::WithOtherFields;
 use futures::StreamExt;
@@ -16,9 +13,19 @@ use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_network_api::NetworkInfo;
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{Receipt, TransactionMeta};
+use reth_provider::{
+    BlockReader, CanonStateNotification, CanonStateSubscriptions, Chain, EvmEnvProvider,
+};
 use reth_rpc_eth_api::{pubsub::EthPubSubApiServer, FullEthApiTypes, RpcTransaction};
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{
+    logs_utils,
+    pubsub::{
+        EthSubscriptionKind, EthSubscriptionParams, EthSubscriptionResult, ReorgNotification,
+        TransactionReceiptsFilter,
+    },
+    ReceiptBuilder,
+};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_rpc_types_compat::transaction::from_recovered;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -28,6 +35,7 @@ use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::debug;
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -84,8 +92,8 @@ where
     async fn subscribe(
         &self,
         pending: PendingSubscriptionSink,
-        kind: SubscriptionKind,
-        params: Option<Params>,
+        kind: EthSubscriptionKind,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
@@ -101,8 +109,8 @@ where
 async fn handle_accepted<Provider, Pool, Events, Network, Eth>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
-    kind: SubscriptionKind,
-    params: Option<Params>,
+    kind: EthSubscriptionKind,
+    params: Option<EthSubscriptionParams>,
 ) -> Result<(), ErrorObject<'static>>
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
@@ -112,35 +120,30 @@ where
     Eth: FullEthApiTypes,
 {
     match kind {
-        SubscriptionKind::NewHeads => {
+        EthSubscriptionKind::NewHeads => {
             let stream = pubsub.new_headers_stream().map(|header| {
-                EthSubscriptionResult::<WithOtherFields<Transaction>>::Header(Box::new(
-                    header.into(),
-                ))
+                SubscriptionResult::<WithOtherFields<Transaction>>::Header(Box::new(header.into()))
             });
             pipe_from_stream(accepted_sink, stream).await
         }
-        SubscriptionKind::Logs => {
+        EthSubscriptionKind::Logs => {
             // if no params are provided, used default filter params
-            let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
-                    return Err(invalid_params_rpc_err("Invalid params for logs"))
-                }
-                _ => FilteredParams::default(),
+            let filter = match params.unwrap_or_default().into_object::<Filter>() {
+                Ok(filter) => FilteredParams::new(filter),
+                Err(_) => return Err(invalid_params_rpc_err("Invalid params for logs")),
             };
-            let stream = pubsub.log_stream(filter).map(|log| {
-                EthSubscriptionResult::<WithOtherFields<Transaction>>::Log(Box::new(log))
-            });
+            let stream = pubsub
+                .log_stream(filter)
+                .map(|log| SubscriptionResult::<WithOtherFields<Transaction>>::Log(Box::new(log)));
             pipe_from_stream(accepted_sink, stream).await
         }
-        SubscriptionKind::NewPendingTransactions => {
+        EthSubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
                 match params {
-                    Params::Bool(true) => {
+                    EthSubscriptionParams::Bool(true) => {
                         // full transaction objects requested
                         let stream = pubsub.full_pending_transaction_stream().map(|tx| {
-                            EthSubscriptionResult::FullTransaction(Box::new(from_recovered::<
+                            SubscriptionResult::FullTransaction(Box::new(from_recovered::<
                                 Eth::TransactionCompat,
                             >(
                                 tx.transaction.to_recovered_transaction(),
@@ -148,10 +151,10 @@ where
                         });
                         return pipe_from_stream(accepted_sink, stream).await
                     }
-                    Params::Bool(false) | Params::None => {
+                    EthSubscriptionParams::Bool(false) | EthSubscriptionParams::None => {
                         // only hashes requested
                     }
-                    Params::Logs(_) => {
+                    EthSubscriptionParams::Object(_) => {
                         return Err(invalid_params_rpc_err(
                             "Invalid params for newPendingTransactions",
                         ))
@@ -161,10 +164,39 @@ where
 
             let stream = pubsub
                 .pending_transaction_hashes_stream()
-                .map(EthSubscriptionResult::<WithOtherFields<Transaction>>::TransactionHash);
+                .map(SubscriptionResult::<WithOtherFields<Transaction>>::TransactionHash);
+            pipe_from_stream(accepted_sink, stream).await
+        }
+        EthSubscriptionKind::TransactionReceipts => {
+            // if no params are provided, all receipts are sent
+            let filter = match params.unwrap_or_default().into_object() {
+                Ok(filter) => filter.unwrap_or_default(),
+                Err(err) => {
+                    return Err(invalid_params_rpc_err(format!(
+                        "Invalid params for transactionReceipts: {err}"
+                    )))
+                }
+            };
+            let stream = pubsub.transaction_receipts_stream(filter).map(|receipt| {
+                EthSubscriptionResult::<WithOtherFields<Transaction>>::TransactionReceipt(Box::new(
+                    receipt,
+                ))
+            });
+            pipe_from_stream(accepted_sink, stream).await
+        }
+        EthSubscriptionKind::Reorgs => {
+            if matches!(
+                params,
+                Some(EthSubscriptionParams::Bool(_) | EthSubscriptionParams::Object(_))
+            ) {
+                return Err(invalid_params_rpc_err("Invalid params for reorgs"))
+            }
+            let stream = pubsub.reorgs_stream().map(|reorg| {
+                EthSubscriptionResult::<WithOtherFields<Transaction>>::Reorg(Box::new(reorg))
+            });
             pipe_from_stream(accepted_sink, stream).await
         }
-        SubscriptionKind::Syncing => {
+        EthSubscriptionKind::Syncing => {
             // get new block subscription
             let mut canon_state =
                 BroadcastStream::new(pubsub.chain_events.subscribe_to_canonical_state());
@@ -219,7 +251,7 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<T, St>(
+pub(crate) async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
@@ -278,18 +310,18 @@ where
     Provider: BlockReader + 'static,
 {
     /// Returns the current sync status for the `syncing` subscription
-    fn sync_status(&self, is_syncing: bool) -> EthSubscriptionResult {
+    fn sync_status(&self, is_syncing: bool) -> SubscriptionResult {
         if is_syncing {
             let current_block =
                 self.provider.chain_info().map(|info| info.best_number).unwrap_or_default();
-            EthSubscriptionResult::SyncState(PubSubSyncStatus::Detailed(SyncStatusMetadata {
+            SubscriptionResult::SyncState(PubSubSyncStatus::Detailed(SyncStatusMetadata {
                 syncing: true,
                 starting_block: 0,
                 current_block,
                 highest_block: Some(current_block),
             }))
         } else {
-            EthSubscriptionResult::SyncState(PubSubSyncStatus::Simple(false))
+            SubscriptionResult::SyncState(PubSubSyncStatus::Simple(false))
         }
     }
 }
@@ -345,4 +377,73 @@ where
                 futures::stream::iter(all_logs)
             })
     }
+
+    /// Returns a stream that yields the receipts of all transactions included in new canonical
+    /// blocks that match the given filter.
+    fn transaction_receipts_stream(
+        &self,
+        filter: TransactionReceiptsFilter,
+    ) -> impl Stream<Item = AnyTransactionReceipt> {
+        self.chain_events.canonical_state_stream().flat_map(move |canon_state| {
+            futures::stream::iter(committed_transaction_receipts(&canon_state.committed(), &filter))
+        })
+    }
+
+    /// Returns a stream that yields a notification for every reorg of the canonical chain.
+    fn reorgs_stream(&self) -> impl Stream<Item = ReorgNotification> {
+        self.chain_events.canonical_state_stream().filter_map(|canon_state| {
+            futures::future::ready(match canon_state {
+                CanonStateNotification::Reorg { old, new } => {
+                    Some(ReorgNotification::new(&old, &new))
+                }
+                CanonStateNotification::Commit { .. } => None,
+            })
+        })
+    }
+}
+
+/// Builds the RPC receipts of all transactions in the given chain that match the filter.
+///
+/// Blocks with pruned receipts are skipped.
+fn committed_transaction_receipts(
+    chain: &Chain,
+    filter: &TransactionReceiptsFilter,
+) -> Vec<AnyTransactionReceipt> {
+    let mut receipts = Vec::new();
+    for (block, block_receipts) in chain.blocks_and_receipts() {
+        let Some(block_receipts) = block_receipts.iter().cloned().collect::<Option<Vec<Receipt>>>()
+        else {
+            continue
+        };
+
+        for (index, ((sender, tx), receipt)) in
+            block.transactions_with_sender().zip(block_receipts.iter()).enumerate()
+        {
+            if !filter.matches(&tx.hash, sender, tx.to().as_ref()) {
+                continue
+            }
+
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: index as u64,
+                block_hash: block.hash(),
+                block_number: block.number,
+                base_fee: block.base_fee_per_gas,
+                excess_blob_gas: block.excess_blob_gas,
+                timestamp: block.timestamp,
+            };
+            match ReceiptBuilder::new(tx, meta, receipt, &block_receipts) {
+                Ok(builder) => receipts.push(builder.build()),
+                Err(err) => {
+                    debug!(
+                        target: "rpc::eth::pubsub",
+                        %err,
+                        tx_hash=%tx.hash,
+                        "Failed to build receipt"
+                    );
+                }
+            }
+        }
+    }
+    receipts
 }
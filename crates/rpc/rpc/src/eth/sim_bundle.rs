@@ -1,14 +1,76 @@
 //! `Eth` Sim bundle implementation and helpers.
 
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use alloy_rpc_types_mev::{SendBundleRequest, SimBundleOverrides, SimBundleResponse};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, U256};
+use alloy_rpc_types::{BlockId, Log};
+use alloy_rpc_types_mev::{
+    BundleItem, Inclusion, Privacy, RefundConfig, SendBundleRequest, SimBundleLogs,
+    SimBundleOverrides, SimBundleResponse, Validity,
+};
 use jsonrpsee::core::RpcResult;
+use reth_primitives::TransactionSigned;
+use reth_provider::BlockIdReader;
+#[cfg(feature = "telos")]
+use reth_provider::HeaderProvider;
 use reth_rpc_api::MevSimApiServer;
-use reth_rpc_eth_api::helpers::{Call, EthTransactions, LoadPendingBlock};
-use reth_rpc_eth_types::EthApiError;
+use reth_rpc_eth_api::{
+    helpers::{Call, EthTransactions, LoadPendingBlock},
+    FromEthApiError,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_tasks::pool::BlockingTaskGuard;
-use tracing::info;
+use revm::primitives::TxEnv;
+use revm_primitives::EnvWithHandlerCfg;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::trace;
+
+use crate::eth::bundle::{execute_bundle_transactions, ExecutedBundleTransaction};
+
+/// Maximum number of items in the body of a bundle.
+const MAX_BUNDLE_BODY_SIZE: usize = 50;
+
+/// Default simulation timeout.
+const DEFAULT_SIM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum simulation timeout that can be requested through the overrides.
+const MAX_SIM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Gas charged for each refund payout transaction appended by the builder.
+const SBUNDLE_PAYOUT_MAX_COST: u64 = 30_000;
+
+/// A flattened representation of a bundle item containing the transaction and the constraints
+/// inherited from the bundle it belongs to.
+#[derive(Clone, Debug)]
+pub struct FlattenedBundleItem {
+    /// The signed transaction
+    pub tx: TransactionSigned,
+    /// The recovered signer of the transaction
+    pub signer: Address,
+    /// Whether the transaction is allowed to revert
+    pub can_revert: bool,
+    /// Inclusion constraints of the enclosing bundle
+    pub inclusion: Inclusion,
+    /// Validity constraints of the enclosing bundle
+    pub validity: Option<Validity>,
+    /// Privacy settings of the enclosing bundle
+    pub privacy: Option<Privacy>,
+    /// Refund percent if this item's body index is listed in the enclosing bundle's refunds
+    pub refund_percent: Option<u64>,
+    /// Refund configs of the enclosing bundle
+    pub refund_configs: Option<Vec<RefundConfig>>,
+}
+
+impl FlattenedBundleItem {
+    /// Returns `true` if the logs of this item may be shared according to its privacy hints.
+    fn shares_logs(&self) -> bool {
+        self.privacy.as_ref().and_then(|privacy| privacy.hints.as_ref()).map_or(true, |h| h.logs)
+    }
+}
 
 /// `Eth` sim bundle implementation.
 pub struct EthSimBundle<Eth> {
@@ -27,14 +89,300 @@ impl<Eth> EthSimBundle<Eth>
 where
     Eth: EthTransactions + LoadPendingBlock + Call + 'static,
 {
-    /// Simulates a bundle of transactions.
+    /// Validates the bundle and flattens it into a list of transactions in execution order.
+    fn parse_and_flatten_bundle(
+        &self,
+        request: &SendBundleRequest,
+    ) -> Result<Vec<FlattenedBundleItem>, EthApiError> {
+        let SendBundleRequest { inclusion, bundle_body, validity, privacy, .. } = request;
+
+        let max_block = inclusion.max_block.unwrap_or(inclusion.block);
+        if max_block < inclusion.block || inclusion.block == 0 {
+            return Err(EthApiError::InvalidParams(EthSimBundleError::InvalidInclusion.to_string()))
+        }
+
+        if bundle_body.len() > MAX_BUNDLE_BODY_SIZE {
+            return Err(EthApiError::InvalidParams(EthSimBundleError::BundleTooLarge.to_string()))
+        }
+
+        if let Some(validity) = validity {
+            validate_validity(validity, bundle_body.len())?;
+        }
+
+        let mut items = Vec::with_capacity(bundle_body.len());
+        for (idx, item) in bundle_body.iter().enumerate() {
+            match item {
+                BundleItem::Tx { tx, can_revert } => {
+                    let (tx, signer) = recover_raw_transaction(tx.clone())?.into_components();
+                    let tx = tx.into_transaction();
+
+                    let refund_percent = validity
+                        .as_ref()
+                        .and_then(|validity| validity.refund.as_ref())
+                        .and_then(|refunds| {
+                            refunds.iter().find_map(|refund| {
+                                (refund.body_idx as usize == idx).then_some(refund.percent)
+                            })
+                        });
+                    let refund_configs =
+                        validity.as_ref().and_then(|validity| validity.refund_config.clone());
+
+                    items.push(FlattenedBundleItem {
+                        tx,
+                        signer,
+                        can_revert: *can_revert,
+                        inclusion: inclusion.clone(),
+                        validity: validity.clone(),
+                        privacy: privacy.clone(),
+                        refund_percent,
+                        refund_configs,
+                    });
+                }
+                BundleItem::Hash { .. } => {
+                    // only fully matched bundles can be simulated
+                    return Err(EthApiError::InvalidParams(
+                        EthSimBundleError::UnmatchedBundle.to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Acquires a permit to run a simulation.
+    async fn acquire_sim_permit(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
+        self.inner.blocking_task_guard.clone().acquire_owned().await
+    }
+
+    /// Simulates a MEV-share bundle on top of the state of the `parent_block` override, defaults to
+    /// the pending block.
+    ///
+    /// If `logs` is true, the logs emitted by every transaction of the bundle body are returned.
+    ///
+    /// The simulation waits for a permit of the blocking task guard and fails with
+    /// [`EthSimBundleError::BundleTimeout`] once the `timeout` override, capped at
+    /// [`MAX_SIM_TIMEOUT`], has passed.
     pub async fn sim_bundle(
         &self,
         request: SendBundleRequest,
         overrides: SimBundleOverrides,
-    ) -> RpcResult<SimBundleResponse> {
-        info!("mev_simBundle called, request: {:?}, overrides: {:?}", request, overrides);
-        Err(EthApiError::Unsupported("mev_simBundle is not supported").into())
+        logs: bool,
+    ) -> Result<SimBundleResponse, Eth::Error> {
+        let SimBundleOverrides {
+            parent_block,
+            block_number,
+            coinbase,
+            timestamp,
+            gas_limit,
+            base_fee,
+            timeout,
+            ..
+        } = overrides;
+        let timeout = timeout
+            .map(|timeout| Duration::from_secs(timeout).min(MAX_SIM_TIMEOUT))
+            .unwrap_or(DEFAULT_SIM_TIMEOUT);
+        let deadline = Instant::now() + timeout;
+
+        // validate the bundle and flatten it, so it's easier to process
+        let flattened_bundle = self.parse_and_flatten_bundle(&request)?;
+
+        // restrict the number of concurrent simulations
+        let permit =
+            tokio::time::timeout(timeout, self.acquire_sim_permit()).await.map_err(|_| {
+                EthApiError::InvalidParams(EthSimBundleError::BundleTimeout.to_string())
+            })?;
+
+        let block_id = parent_block.unwrap_or(BlockId::Number(BlockNumberOrTag::Pending));
+        let (cfg, mut block_env, at) = self.inner.eth_api.evm_env_at(block_id).await?;
+
+        let provider = LoadPendingBlock::provider(&self.inner.eth_api);
+        let state_block_number = provider
+            .block_number_for_id(at)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(at))?;
+
+        #[cfg(feature = "telos")]
+        let telos_block_extension = provider
+            .header_by_number(state_block_number)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(state_block_number.into()))?
+            .telos_block_extension
+            .to_child();
+
+        // apply overrides
+        if let Some(block_number) = block_number {
+            block_env.number = U256::from(block_number);
+        }
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(timestamp) = timestamp {
+            block_env.timestamp = U256::from(timestamp);
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = U256::from(base_fee);
+        }
+
+        let eth_api = self.inner.eth_api.clone();
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(at, move |state| {
+                // hold the permit until the simulation finished
+                let _permit = permit;
+                let current_block_number = block_env.number.saturating_to::<u64>();
+                let basefee = block_env.basefee;
+                let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+
+                // check inclusion constraints
+                for item in &flattened_bundle {
+                    let max_block = item.inclusion.max_block.unwrap_or(item.inclusion.block);
+                    if current_block_number < item.inclusion.block ||
+                        current_block_number > max_block
+                    {
+                        return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                            EthSimBundleError::InvalidInclusion.to_string(),
+                        )))
+                    }
+                }
+
+                let mut total_gas_used = 0u64;
+                let mut total_profit = U256::ZERO;
+                let mut refundable_value = U256::ZERO;
+                let mut tx_logs = Vec::with_capacity(flattened_bundle.len());
+
+                execute_bundle_transactions(
+                    Call::evm_config(&eth_api),
+                    state,
+                    env,
+                    flattened_bundle.iter().map(|item| (item.tx.clone(), item.signer)),
+                    #[cfg(feature = "telos")]
+                    &telos_block_extension,
+                    |executed| -> Result<(), Eth::Error> {
+                        let ExecutedBundleTransaction {
+                            index: tx_index,
+                            tx,
+                            result,
+                            coinbase_diff,
+                            ..
+                        } = executed;
+                        let item = &flattened_bundle[tx_index];
+
+                        if !result.is_success() && !item.can_revert {
+                            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                                EthSimBundleError::BundleTransactionFailed.to_string(),
+                            )))
+                        }
+
+                        let gas_used = result.gas_used();
+                        total_gas_used += gas_used;
+                        total_profit += coinbase_diff;
+
+                        // only transactions without a refund contribute to the refundable value
+                        if item.refund_percent.is_none() {
+                            refundable_value += coinbase_diff;
+                        }
+
+                        trace!(
+                            target: "rpc::eth::sim_bundle",
+                            tx_hash = ?tx.hash(),
+                            tx_index,
+                            gas_used,
+                            success = result.is_success(),
+                            %coinbase_diff,
+                            "Simulated bundle transaction"
+                        );
+
+                        if logs {
+                            let logs = item.shares_logs().then(|| {
+                                result
+                                    .logs()
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(log_index, log)| Log {
+                                        inner: log.clone(),
+                                        block_hash: None,
+                                        block_number: None,
+                                        block_timestamp: None,
+                                        transaction_hash: Some(tx.hash()),
+                                        transaction_index: Some(tx_index as u64),
+                                        log_index: Some(log_index as u64),
+                                        removed: false,
+                                    })
+                                    .collect()
+                            });
+                            tx_logs.push(logs);
+                        }
+
+                        // the caller stops waiting after the timeout, so stop simulating as well
+                        if Instant::now() > deadline {
+                            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                                EthSimBundleError::BundleTimeout.to_string(),
+                            )))
+                        }
+
+                        Ok(())
+                    },
+                )?;
+
+                // after all transactions have been executed, process the refunds
+                for item in &flattened_bundle {
+                    let Some(refund_percent) = item.refund_percent else { continue };
+
+                    let refund_configs = item.refund_configs.clone().unwrap_or_else(|| {
+                        vec![RefundConfig { address: item.signer, percent: 100 }]
+                    });
+
+                    // every refund config results in a payout transaction paid by the bundle
+                    let payout_gas = SBUNDLE_PAYOUT_MAX_COST * refund_configs.len() as u64;
+                    let payout_tx_fee = basefee * U256::from(payout_gas);
+                    total_gas_used += payout_gas;
+
+                    let payout_value =
+                        refundable_value * U256::from(refund_percent) / U256::from(100);
+                    if payout_tx_fee > payout_value {
+                        return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                            EthSimBundleError::NegativeProfit.to_string(),
+                        )))
+                    }
+
+                    total_profit = total_profit.checked_sub(payout_value).ok_or_else(|| {
+                        EthApiError::InvalidParams(EthSimBundleError::NegativeProfit.to_string())
+                    })?;
+                    refundable_value =
+                        refundable_value.checked_sub(payout_value).ok_or_else(|| {
+                            EthApiError::InvalidParams(
+                                EthSimBundleError::NegativeProfit.to_string(),
+                            )
+                        })?;
+                }
+
+                let mev_gas_price =
+                    total_profit.checked_div(U256::from(total_gas_used)).unwrap_or_default();
+
+                let logs = logs.then(|| {
+                    tx_logs
+                        .into_iter()
+                        .map(|tx_logs| SimBundleLogs { tx_logs, bundle_logs: None })
+                        .collect()
+                });
+
+                Ok(SimBundleResponse {
+                    success: true,
+                    error: None,
+                    state_block: state_block_number,
+                    mev_gas_price: mev_gas_price.saturating_to(),
+                    profit: total_profit.saturating_to(),
+                    refundable_value: refundable_value.saturating_to(),
+                    gas_used: total_gas_used,
+                    logs,
+                })
+            })
+            .await
     }
 }
 
@@ -48,18 +396,49 @@ where
         request: SendBundleRequest,
         overrides: SimBundleOverrides,
     ) -> RpcResult<SimBundleResponse> {
-        Self::sim_bundle(self, request, overrides).await
+        trace!(target: "rpc::eth", ?request, ?overrides, "Serving mev_simBundle");
+
+        Self::sim_bundle(self, request, overrides, true).await.map_err(Into::into)
     }
 }
 
+/// Validates the refund constraints of a bundle with `body_len` items.
+fn validate_validity(validity: &Validity, body_len: usize) -> Result<(), EthApiError> {
+    if let Some(refunds) = &validity.refund {
+        let mut total_percent = 0u64;
+        for refund in refunds {
+            if refund.body_idx as usize >= body_len ||
+                refund.percent > 100u64.saturating_sub(total_percent)
+            {
+                return Err(EthApiError::InvalidParams(
+                    EthSimBundleError::InvalidValidity.to_string(),
+                ))
+            }
+            total_percent += refund.percent;
+        }
+    }
+
+    if let Some(refund_configs) = &validity.refund_config {
+        let mut total_percent = 0u64;
+        for refund_config in refund_configs {
+            if refund_config.percent > 100u64.saturating_sub(total_percent) {
+                return Err(EthApiError::InvalidParams(
+                    EthSimBundleError::InvalidValidity.to_string(),
+                ))
+            }
+            total_percent += refund_config.percent;
+        }
+    }
+
+    Ok(())
+}
+
 /// Container type for `EthSimBundle` internals
 #[derive(Debug)]
 struct EthSimBundleInner<Eth> {
     /// Access to commonly used code of the `eth` namespace
-    #[allow(dead_code)]
     eth_api: Eth,
-    // restrict the number of concurrent tracing calls.
-    #[allow(dead_code)]
+    /// Restricts the number of concurrent simulations.
     blocking_task_guard: BlockingTaskGuard,
 }
 
@@ -74,3 +453,173 @@ impl<Eth> Clone for EthSimBundle<Eth> {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
+
+/// [`EthSimBundle`] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum EthSimBundleError {
+    /// Thrown when a bundle is unmatched, i.e. contains transaction hashes only.
+    #[error("unmatched bundle")]
+    UnmatchedBundle,
+    /// Thrown when a bundle body has more than [`MAX_BUNDLE_BODY_SIZE`] items.
+    #[error("bundle too large")]
+    BundleTooLarge,
+    /// Thrown when the refund constraints of a bundle are invalid.
+    #[error("invalid validity")]
+    InvalidValidity,
+    /// Thrown when the inclusion constraints of a bundle are invalid or not satisfied.
+    #[error("invalid inclusion")]
+    InvalidInclusion,
+    /// Thrown when the simulation exceeded its timeout.
+    #[error("bundle simulation timed out")]
+    BundleTimeout,
+    /// Thrown when a transaction that is not allowed to revert reverted.
+    #[error("bundle transaction failed")]
+    BundleTransactionFailed,
+    /// Thrown when the refunds exceed the bundle's profit.
+    #[error("bundle simulation returned negative profit")]
+    NegativeProfit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_consensus::TxLegacy;
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{TxKind, B256};
+    use alloy_rpc_types_mev::{ProtocolVersion, Refund};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, public_key_to_address, Block, Transaction,
+    };
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        ChainSpecProvider,
+    };
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn sim_bundle_pays_coinbase() {
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        let coinbase = Address::random();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000_000)));
+        let parent_hash = B256::random();
+        provider.add_block(parent_hash, Block::default());
+
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+        let sim_bundle = EthSimBundle::new(eth_api, BlockingTaskGuard::new(1));
+
+        // a transfer to the coinbase, which is the profit of the bundle
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 21_000,
+                to: TxKind::Call(coinbase),
+                value: U256::from(1_000),
+                ..Default::default()
+            }),
+        );
+        let request = SendBundleRequest::new(
+            1,
+            None,
+            ProtocolVersion::V0_1,
+            vec![BundleItem::Tx { tx: tx.encoded_2718().into(), can_revert: false }],
+        );
+        let overrides = SimBundleOverrides {
+            parent_block: Some(parent_hash.into()),
+            block_number: Some(1),
+            coinbase: Some(coinbase),
+            ..Default::default()
+        };
+
+        let response =
+            sim_bundle.sim_bundle(request.clone(), overrides.clone(), true).await.unwrap();
+        assert!(response.success);
+        assert_eq!(response.state_block, 0);
+        assert_eq!(response.gas_used, 21_000);
+        assert_eq!(response.profit, 1_000);
+        assert_eq!(response.refundable_value, 1_000);
+        assert_eq!(
+            response.logs,
+            Some(vec![SimBundleLogs { tx_logs: Some(Vec::new()), bundle_logs: None }])
+        );
+
+        // the bundle can't be included in a block outside of its inclusion range
+        let overrides = SimBundleOverrides { block_number: Some(2), ..overrides };
+        assert!(sim_bundle.sim_bundle(request, overrides, true).await.is_err());
+    }
+
+    #[test]
+    fn validity_refund_percentages() {
+        let validity = Validity {
+            refund: Some(vec![
+                Refund { body_idx: 0, percent: 60 },
+                Refund { body_idx: 1, percent: 40 },
+            ]),
+            refund_config: None,
+        };
+        assert!(validate_validity(&validity, 2).is_ok());
+
+        // refunds must not exceed 100 percent in total
+        let validity = Validity {
+            refund: Some(vec![
+                Refund { body_idx: 0, percent: 60 },
+                Refund { body_idx: 1, percent: 41 },
+            ]),
+            refund_config: None,
+        };
+        assert!(validate_validity(&validity, 2).is_err());
+
+        // refunds must point into the bundle body
+        let validity = Validity {
+            refund: Some(vec![Refund { body_idx: 2, percent: 10 }]),
+            refund_config: None,
+        };
+        assert!(validate_validity(&validity, 2).is_err());
+    }
+
+    #[test]
+    fn validity_refund_configs() {
+        let validity = Validity {
+            refund: None,
+            refund_config: Some(vec![
+                RefundConfig { address: Address::random(), percent: 50 },
+                RefundConfig { address: Address::random(), percent: 50 },
+            ]),
+        };
+        assert!(validate_validity(&validity, 1).is_ok());
+
+        let validity = Validity {
+            refund: None,
+            refund_config: Some(vec![RefundConfig { address: Address::random(), percent: 101 }]),
+        };
+        assert!(validate_validity(&validity, 1).is_err());
+    }
+}
@@ -46,7 +46,7 @@ mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub, EthSimBundle};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
@@ -1,18 +1,30 @@
-use std::{collections::BTreeMap, marker::PhantomData};
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+};
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult as Result;
+use futures::{future, StreamExt};
+use jsonrpsee::{core::RpcResult as Result, PendingSubscriptionSink};
 use reth_primitives::TransactionSignedEcRecovered;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_eth_api::{FullEthApiTypes, RpcTransaction};
+use reth_rpc_eth_types::txpool::{
+    TxpoolDiscardReason, TxpoolEvent, TxpoolFilter, TxpoolPage, TxpoolSubPool, TxpoolTransaction,
+};
 use reth_rpc_types_compat::{transaction::from_recovered, TransactionCompat};
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_transaction_pool::{
+    AllPoolTransactions, FullTransactionEvent, PoolTransaction, TransactionPool,
+};
 use tracing::trace;
 
+use crate::eth::pubsub::pipe_from_stream;
+
 /// `txpool` API implementation.
 ///
 /// This type provides the functionality for handling `txpool` related requests.
@@ -20,13 +32,27 @@ use tracing::trace;
 pub struct TxPoolApi<Pool, Eth> {
     /// An interface to interact with the pool
     pool: Pool,
+    /// The type that's used to spawn subscription tasks, [`TokioTaskExecutor`] if not set.
+    subscription_task_spawner: Option<Box<dyn TaskSpawner>>,
     _tx_resp_builder: PhantomData<Eth>,
 }
 
 impl<Pool, Eth> TxPoolApi<Pool, Eth> {
     /// Creates a new instance of `TxpoolApi`.
+    ///
+    /// Subscription tasks are spawned via [`tokio::task::spawn`]
     pub const fn new(pool: Pool) -> Self {
-        Self { pool, _tx_resp_builder: PhantomData }
+        Self { pool, subscription_task_spawner: None, _tx_resp_builder: PhantomData }
+    }
+
+    /// Creates a new instance of `TxpoolApi` that spawns subscription tasks with the given
+    /// spawner.
+    pub fn with_spawner(pool: Pool, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self {
+            pool,
+            subscription_task_spawner: Some(subscription_task_spawner),
+            _tx_resp_builder: PhantomData,
+        }
     }
 }
 
@@ -35,7 +61,8 @@ where
     Pool: TransactionPool + 'static,
     Eth: FullEthApiTypes,
 {
-    fn content(&self) -> TxpoolContent<RpcTransaction<Eth::NetworkTypes>> {
+    /// Returns the pending and queued transactions of the pool, by sender and nonce.
+    pub fn content(&self) -> TxpoolContent<RpcTransaction<Eth::NetworkTypes>> {
         #[inline]
         fn insert<Tx, RpcTxB>(
             tx: &Tx,
@@ -62,6 +89,38 @@ where
 
         content
     }
+
+    fn content_filtered(
+        &self,
+        filter: &TxpoolFilter,
+    ) -> TxpoolPage<RpcTransaction<Eth::NetworkTypes>> {
+        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+
+        let mut matching = pending
+            .iter()
+            .map(|tx| (TxpoolSubPool::Pending, tx))
+            .chain(queued.iter().map(|tx| (TxpoolSubPool::Queued, tx)))
+            .filter(|(sub_pool, tx)| {
+                filter.matches(*sub_pool, &tx.sender(), tx.to().as_ref(), tx.max_fee_per_gas())
+            })
+            .collect::<Vec<_>>();
+        matching.sort_unstable_by_key(|(_, tx)| (tx.sender(), tx.nonce()));
+
+        let total = matching.len();
+        let transactions = matching
+            .into_iter()
+            .skip(filter.offset)
+            .take(filter.limit.unwrap_or(usize::MAX))
+            .map(|(sub_pool, tx)| TxpoolTransaction {
+                sub_pool,
+                transaction: from_recovered::<Eth::TransactionCompat>(
+                    tx.transaction.clone().into_consensus(),
+                ),
+            })
+            .collect();
+
+        TxpoolPage { transactions, total }
+    }
 }
 
 #[async_trait]
@@ -144,6 +203,108 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content())
     }
+
+    /// Returns the pool transactions matching the given filter, ordered by sender and nonce.
+    ///
+    /// Handler for `txpool_contentFiltered`
+    async fn txpool_content_filtered(
+        &self,
+        filter: TxpoolFilter,
+    ) -> Result<TxpoolPage<RpcTransaction<Eth::NetworkTypes>>> {
+        trace!(target: "rpc::eth", ?filter, "Serving txpool_contentFiltered");
+        Ok(self.content_filtered(&filter))
+    }
+
+    /// Subscribes to the lifecycle events of all pool transactions.
+    ///
+    /// Handler for `txpool_subscribe`
+    async fn txpool_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+
+        // listen before taking the snapshot, so no event of a transaction in it is missed
+        let events = self.pool.all_transactions_event_listener();
+        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let mut tracker = TxpoolEventTracker::default();
+        tracker.sub_pools.extend(pending.iter().map(|tx| (*tx.hash(), TxpoolSubPool::Pending)));
+        tracker.sub_pools.extend(queued.iter().map(|tx| (*tx.hash(), TxpoolSubPool::Queued)));
+
+        let stream = events.filter_map(move |event| future::ready(tracker.on_event(event)));
+        let fut = Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        });
+        match &self.subscription_task_spawner {
+            Some(spawner) => spawner.spawn(fut),
+            None => TokioTaskExecutor::default().spawn(fut),
+        };
+
+        Ok(())
+    }
+}
+
+/// Turns the pool's transaction events into [`TxpoolEvent`]s.
+///
+/// The pool reports new transactions and transactions that moved between sub-pools alike, so the
+/// sub-pool of every transaction is tracked to tell them apart.
+///
+/// Note: moves caused by base fee or blob fee changes are not reported by the pool.
+#[derive(Debug, Default)]
+struct TxpoolEventTracker {
+    /// The sub-pool of every transaction in the pool.
+    sub_pools: HashMap<TxHash, TxpoolSubPool>,
+}
+
+impl TxpoolEventTracker {
+    /// Returns the [`TxpoolEvent`] for the pool event, if any.
+    fn on_event<T: PoolTransaction>(
+        &mut self,
+        event: FullTransactionEvent<T>,
+    ) -> Option<TxpoolEvent> {
+        let event = match event {
+            FullTransactionEvent::Pending(hash) => {
+                self.on_sub_pool(hash, TxpoolSubPool::Pending)?
+            }
+            FullTransactionEvent::Queued(hash) => self.on_sub_pool(hash, TxpoolSubPool::Queued)?,
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                // the pool reports all transactions of the block, including unknown ones
+                self.sub_pools.remove(&tx_hash)?;
+                TxpoolEvent::Mined { hash: tx_hash, block_hash }
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                let hash = *transaction.hash();
+                self.sub_pools.remove(&hash);
+                TxpoolEvent::Replaced { hash, replaced_by }
+            }
+            FullTransactionEvent::Discarded(hash) => {
+                self.sub_pools.remove(&hash);
+                TxpoolEvent::Discarded { hash, reason: TxpoolDiscardReason::Dropped }
+            }
+            FullTransactionEvent::Expired(hash) => {
+                self.sub_pools.remove(&hash);
+                TxpoolEvent::Discarded { hash, reason: TxpoolDiscardReason::Expired }
+            }
+            FullTransactionEvent::Invalid(hash) => {
+                self.sub_pools.remove(&hash);
+                TxpoolEvent::Discarded { hash, reason: TxpoolDiscardReason::Invalid }
+            }
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+        Some(event)
+    }
+
+    /// Records that the transaction is now in the given sub-pool.
+    fn on_sub_pool(&mut self, hash: TxHash, sub_pool: TxpoolSubPool) -> Option<TxpoolEvent> {
+        match self.sub_pools.insert(hash, sub_pool) {
+            None => Some(TxpoolEvent::Added { hash, sub_pool }),
+            Some(previous) if previous == sub_pool => None,
+            Some(_) => Some(match sub_pool {
+                TxpoolSubPool::Pending => TxpoolEvent::Promoted { hash },
+                TxpoolSubPool::Queued => TxpoolEvent::Parked { hash },
+            }),
+        }
+    }
 }
 
 impl<Pool, Eth> std::fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -151,3 +312,50 @@ impl<Pool, Eth> std::fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_transaction_pool::test_utils::MockTransaction;
+
+    #[test]
+    fn tracks_transaction_lifecycle() {
+        let mut tracker = TxpoolEventTracker::default();
+        let hash = B256::with_last_byte(1);
+        let mut on_event = |event: FullTransactionEvent<MockTransaction>| tracker.on_event(event);
+
+        assert_eq!(
+            on_event(FullTransactionEvent::Queued(hash)),
+            Some(TxpoolEvent::Added { hash, sub_pool: TxpoolSubPool::Queued })
+        );
+        assert_eq!(on_event(FullTransactionEvent::Queued(hash)), None);
+        assert_eq!(
+            on_event(FullTransactionEvent::Pending(hash)),
+            Some(TxpoolEvent::Promoted { hash })
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Queued(hash)),
+            Some(TxpoolEvent::Parked { hash })
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Discarded(hash)),
+            Some(TxpoolEvent::Discarded { hash, reason: TxpoolDiscardReason::Dropped })
+        );
+        on_event(FullTransactionEvent::Pending(hash));
+        assert_eq!(
+            on_event(FullTransactionEvent::Expired(hash)),
+            Some(TxpoolEvent::Discarded { hash, reason: TxpoolDiscardReason::Expired })
+        );
+
+        // unknown transactions of a mined block are skipped
+        let block_hash = B256::with_last_byte(2);
+        assert_eq!(on_event(FullTransactionEvent::Mined { tx_hash: hash, block_hash }), None);
+        on_event(FullTransactionEvent::Pending(hash));
+        assert_eq!(
+            on_event(FullTransactionEvent::Mined { tx_hash: hash, block_hash }),
+            Some(TxpoolEvent::Mined { hash, block_hash })
+        );
+        assert!(tracker.sub_pools.is_empty());
+    }
+}
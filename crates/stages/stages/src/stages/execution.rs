@@ -12,12 +12,14 @@ use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_exex::{ExExManagerHandle, ExExNotification};
 use reth_primitives::{Header, SealedHeader, StaticFileSegment};
 use reth_primitives_traits::format_gas_throughput;
+#[cfg(feature = "telos")]
+use reth_provider::TelosExtraFieldsReader;
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
     writer::UnifiedStorageWriter,
     BlockReader, DBProvider, HeaderProvider, LatestStateProviderRef, OriginalValuesKnown,
-    ProviderError, StateChangeWriter, StateWriter, StaticFileProviderFactory, StatsReader,
-    TransactionVariant,
+    ProviderError, StageCheckpointReader, StateChangeWriter, StateWriter,
+    StaticFileProviderFactory, StatsReader, TransactionVariant,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
@@ -174,8 +176,12 @@ impl<E> ExecutionStage<E> {
 impl<E, Provider> Stage<Provider> for ExecutionStage<E>
 where
     E: BlockExecutorProvider,
-    Provider:
-        DBProvider + BlockReader + StaticFileProviderFactory + StatsReader + StateChangeWriter,
+    Provider: DBProvider
+        + BlockReader
+        + StaticFileProviderFactory
+        + StatsReader
+        + StateChangeWriter
+        + StageCheckpointReader,
     for<'a> UnifiedStorageWriter<'a, Provider, StaticFileProviderRWRefMut<'a>>: StateWriter,
 {
     /// Return the id of the stage
@@ -204,9 +210,11 @@ where
         let prune_modes = self.adjust_prune_modes(provider, start_block, max_block)?;
         let static_file_provider = provider.static_file_provider();
 
-        // We only use static files for Receipts, if there is no receipt pruning of any kind.
+        // We only use static files for Receipts, if there is no receipt pruning of any kind and
+        // the receipts before the pivot block are not missing because of snap sync.
         let static_file_producer = if self.prune_modes.receipts.is_none() &&
-            self.prune_modes.receipts_log_filter.is_empty()
+            self.prune_modes.receipts_log_filter.is_empty() &&
+            !provider.is_snap_synced()?
         {
             debug!(target: "sync::stages::execution", start = start_block, "Preparing static file producer");
             let mut producer =
@@ -265,6 +273,15 @@ where
                 .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
                 .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
 
+            // the fields are not part of the block, they're stored by hash next to it
+            #[cfg(feature = "telos")]
+            let telos_extra_fields = {
+                let hash = provider
+                    .block_hash(block_number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                provider.telos_extra_fields(hash)?
+            };
+
             fetch_block_duration += fetch_block_start.elapsed();
 
             cumulative_gas += block.gas_used;
@@ -279,10 +296,16 @@ where
                 let sealed = block.header.clone().seal_slow();
                 let (header, seal) = sealed.into_parts();
 
-                executor.execute_and_verify_one(input).map_err(|error| StageError::Block {
-                    block: Box::new(SealedHeader::new(header, seal)),
-                    error: BlockErrorKind::Execution(error),
-                })
+                executor
+                    .execute_and_verify_one(
+                        input,
+                        #[cfg(feature = "telos")]
+                        telos_extra_fields,
+                    )
+                    .map_err(|error| StageError::Block {
+                        block: Box::new(SealedHeader::new(header, seal)),
+                        error: BlockErrorKind::Execution(error),
+                    })
             })?;
 
             execution_duration += execute_start.elapsed();
@@ -437,8 +460,12 @@ where
         let static_file_provider = provider.static_file_provider();
 
         // Unwind all receipts for transactions in the block range
-        if self.prune_modes.receipts.is_none() && self.prune_modes.receipts_log_filter.is_empty() {
-            // We only use static files for Receipts, if there is no receipt pruning of any kind.
+        if self.prune_modes.receipts.is_none() &&
+            self.prune_modes.receipts_log_filter.is_empty() &&
+            !provider.is_snap_synced()?
+        {
+            // We only use static files for Receipts, if there is no receipt pruning of any kind,
+            // and the database wasn't snap synced.
 
             // prepare_static_file_producer does a consistency check that will unwind static files
             // if the expected highest receipt in the files is higher than the database.
@@ -447,7 +474,8 @@ where
                 prepare_static_file_producer(provider, &static_file_provider, *range.start())?;
         } else {
             // If there is any kind of receipt pruning/filtering we use the database, since static
-            // files do not support filters.
+            // files do not support filters, nor the missing receipts before the pivot block of snap
+            // sync.
             //
             // If we hit this case, the receipts have already been unwound by the call to
             // `take_state`.
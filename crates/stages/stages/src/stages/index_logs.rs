@@ -0,0 +1,335 @@
+use super::{load_history_indices, DEFAULT_CACHE_THRESHOLD};
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::ShardedKey,
+    table::{Decode, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_etl::Collector;
+use reth_provider::{DBProvider, LogIndexWriter, ReceiptProvider};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+use tracing::info;
+
+/// Stage indexing the logs of the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage] by emitting address and by topic.
+///
+/// The resulting [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] are sharded the same
+/// way as [`tables::AccountsHistory`] and let range log queries skip every block that can't
+/// contain a match.
+///
+/// This stage is optional and only added to the pipeline if enabled in the [`IndexLogsConfig`].
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + ReceiptProvider + LogIndexWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the tables since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, ?range, "Collecting indices");
+        let (address_collector, topic_collector) =
+            collect_log_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading address indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading topic indices into database");
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_indices(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Walks the receipts of the blocks in `range` and collects the blocks every log address and
+/// topic appears in, see [`collect_history_indices`](super::collect_history_indices) for the
+/// layout of the collected entries.
+fn collect_log_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+>
+where
+    Provider: DBProvider + ReceiptProvider,
+{
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut address_cache: HashMap<Address, Vec<u64>> = HashMap::default();
+    let mut topic_cache: HashMap<B256, Vec<u64>> = HashMap::default();
+
+    // observability
+    let total_blocks = range.end() - range.start() + 1;
+    let interval = (total_blocks / 100).max(1);
+
+    let mut flush_counter = 0;
+    let mut body_cursor = provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?;
+    for (idx, entry) in body_cursor.walk_range(range)?.enumerate() {
+        let (block_number, body) = entry?;
+
+        if idx > 0 && idx as u64 % interval == 0 && total_blocks > 100 {
+            info!(target: "sync::stages::index_logs", progress = %format!("{:.2}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+
+        if !body.is_empty() {
+            for receipt in provider.receipts_by_tx_range(body.tx_num_range())? {
+                for log in &receipt.logs {
+                    push_block(address_cache.entry(log.address).or_default(), block_number);
+                    for topic in log.topics() {
+                        push_block(topic_cache.entry(*topic).or_default(), block_number);
+                    }
+                }
+            }
+        }
+
+        flush_counter += 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush_cache::<tables::LogAddressIndex, _>(&mut address_cache, &mut address_collector)?;
+            flush_cache::<tables::LogTopicIndex, _>(&mut topic_cache, &mut topic_collector)?;
+            flush_counter = 0;
+        }
+    }
+    flush_cache::<tables::LogAddressIndex, _>(&mut address_cache, &mut address_collector)?;
+    flush_cache::<tables::LogTopicIndex, _>(&mut topic_cache, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+/// Appends the block number to the list unless it was already recorded for the same block.
+fn push_block(blocks: &mut Vec<u64>, block_number: BlockNumber) {
+    if blocks.last() != Some(&block_number) {
+        blocks.push(block_number);
+    }
+}
+
+/// Moves the cached block lists into the collector, keyed by their highest block number.
+fn flush_cache<H, P>(
+    cache: &mut HashMap<P, Vec<u64>>,
+    collector: &mut Collector<H::Key, H::Value>,
+) -> Result<(), StageError>
+where
+    H: Table<Key = ShardedKey<P>, Value = BlockNumberList>,
+    P: Copy + Eq + Hash,
+{
+    for (key, indices) in cache.drain() {
+        let last = *indices.last().expect("qed");
+        collector.insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(indices))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{address, b256, Log};
+    use reth_primitives::Receipt;
+    use reth_provider::{DatabaseProviderFactory, LogIndexReader};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::collections::BTreeMap;
+
+    const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+    const TOPIC: B256 = b256!("0000000000000000000000000000000000000000000000000000000000000002");
+    const MAX_BLOCK: BlockNumber = 30;
+
+    /// Inserts blocks `1..=MAX_BLOCK` with one log per transaction. Logs of even blocks are
+    /// emitted by [`ADDRESS`], logs of blocks divisible by three carry [`TOPIC`], all other logs
+    /// use keys unique to their block.
+    fn setup(db: &TestStageDB) {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            1..=MAX_BLOCK,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).unwrap();
+
+        let mut tx_num = 0;
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for _ in &block.body.transactions {
+                let filler = 0x80 + block.number as u8;
+                let address =
+                    if block.number % 2 == 0 { ADDRESS } else { Address::with_last_byte(filler) };
+                let topic =
+                    if block.number % 3 == 0 { TOPIC } else { B256::with_last_byte(filler) };
+                #[allow(clippy::needless_update)] // side-effect of optimism fields
+                let receipt = Receipt {
+                    success: true,
+                    logs: vec![Log::new_unchecked(address, vec![topic], Default::default())],
+                    ..Default::default()
+                };
+                receipts.push((tx_num, receipt));
+                tx_num += 1;
+            }
+        }
+        db.insert_receipts(receipts).unwrap();
+    }
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    fn blocks_of<T, K>(db: &TestStageDB, key: K) -> Vec<u64>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Default + Ord,
+    {
+        db.table::<T>()
+            .unwrap()
+            .into_iter()
+            .filter(|(sharded_key, _)| sharded_key.key == key)
+            .flat_map(|(_, list)| list.iter().collect::<Vec<_>>())
+            .collect()
+    }
+
+    fn expected(to: BlockNumber, divisor: u64) -> Vec<u64> {
+        (1..=to).filter(|block| block % divisor == 0).collect()
+    }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        run(&db, MAX_BLOCK, None);
+        assert_eq!(blocks_of::<tables::LogAddressIndex, _>(&db, ADDRESS), expected(MAX_BLOCK, 2));
+        assert_eq!(blocks_of::<tables::LogTopicIndex, _>(&db, TOPIC), expected(MAX_BLOCK, 3));
+
+        unwind(&db, MAX_BLOCK, 10);
+        assert_eq!(blocks_of::<tables::LogAddressIndex, _>(&db, ADDRESS), expected(10, 2));
+        assert_eq!(blocks_of::<tables::LogTopicIndex, _>(&db, TOPIC), expected(10, 3));
+
+        // every other key only has a single block, so nothing above the unwind block is left
+        let tables = (
+            db.table::<tables::LogAddressIndex>().unwrap(),
+            db.table::<tables::LogTopicIndex>().unwrap(),
+        );
+        assert!(tables.0.iter().all(|(_, list)| list.iter().all(|block| block <= 10)));
+        assert!(tables.1.iter().all(|(_, list)| list.iter().all(|block| block <= 10)));
+    }
+
+    #[tokio::test]
+    async fn execute_incrementally() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        run(&db, 10, None);
+        run(&db, MAX_BLOCK, Some(10));
+
+        // incremental runs merge into the last shard of every key
+        let shards = db
+            .table::<tables::LogAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .filter(|(sharded_key, _)| sharded_key.key == ADDRESS)
+            .map(|(sharded_key, list)| (sharded_key.highest_block_number, list.iter().collect()))
+            .collect::<BTreeMap<_, Vec<_>>>();
+        assert_eq!(shards, BTreeMap::from([(u64::MAX, expected(MAX_BLOCK, 2))]));
+    }
+
+    #[tokio::test]
+    async fn provider_reads_index_range() {
+        let db = TestStageDB::default();
+        setup(&db);
+        run(&db, MAX_BLOCK, None);
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.blocks_with_log_address(ADDRESS, 5..=11).unwrap(), vec![6, 8, 10]);
+        assert_eq!(provider.blocks_with_log_topic(TOPIC, 5..=11).unwrap(), vec![6, 9]);
+    }
+}
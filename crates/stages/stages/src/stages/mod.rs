@@ -12,6 +12,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index of receipt logs by address and topic
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -19,6 +21,10 @@ mod merkle;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage downloading the state at a pivot block.
+mod snap_sync;
+#[cfg(feature = "telos")]
+mod telos_extra_fields;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -29,10 +35,14 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use sender_recovery::*;
+pub use snap_sync::*;
+#[cfg(feature = "telos")]
+pub use telos_extra_fields::*;
 pub use tx_lookup::*;
 
 mod utils;
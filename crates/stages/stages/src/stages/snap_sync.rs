@@ -0,0 +1,1350 @@
+use alloy_primitives::{keccak256, map::HashSet, BlockNumber, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use futures_util::{stream, FutureExt, StreamExt};
+use reth_codecs::Compact;
+use reth_config::config::SnapSyncConfig;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_net_snap::{
+    message::{
+        AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+        StorageRanges, TrieNodes,
+    },
+    trie::{account_trie_nodes, encode_compact_path, path_key, storage_trie_nodes},
+    verify_range_proof, RangeProofError, SnapClient, SnapResponseFut,
+};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, Account, Bytecode, StorageEntry};
+use reth_primitives_traits::constants::KECCAK_EMPTY;
+use reth_provider::{
+    BlockReader, DBProvider, HeaderProvider, ProviderError, PruneCheckpointWriter,
+    StageCheckpointReader, StageCheckpointWriter, TrieWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, MerkleCheckpoint, SnapSyncCheckpoint, Stage, StageCheckpoint,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut, HashBuilder, IntermediateStateRootState, Nibbles, StateRoot,
+    StateRootProgress, StorageRoot, StoredSubNode, TrieAccount, TrieNode, CHILD_INDEX_RANGE,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
+use std::{
+    ops::RangeInclusive,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// The number of ranges the account hashes are split into, which are downloaded concurrently.
+const ACCOUNT_RANGES: u8 = 16;
+
+/// The maximum number of accounts whose storage is requested at once.
+const MAX_STORAGE_ACCOUNTS_PER_REQUEST: usize = 128;
+
+/// The maximum number of bytecodes requested at once.
+const MAX_CODES_PER_REQUEST: usize = 64;
+
+/// The maximum number of trie nodes requested at once.
+const MAX_TRIE_NODES_PER_REQUEST: usize = 512;
+
+/// The maximum number of accounts checked for missing bytecode in one execution of the stage.
+const MAX_CODE_SCAN: usize = 100_000;
+
+/// The number of empty responses after which the state of the pivot block is assumed to be no
+/// longer served by peers.
+const MAX_UNAVAILABLE_RESPONSES: usize = 8;
+
+/// The delay before retrying a request that failed or wasn't served.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The snap sync stage downloads the state at a recent pivot block from peers over `snap/1`,
+/// instead of executing all blocks up to it.
+///
+/// The account and storage ranges are verified against the state root of the pivot block with
+/// range proofs and written to the hashed state. Then the trie of the downloaded state is built,
+/// and since the pivot may move while the ranges are downloaded, the trie nodes that don't match
+/// the state root of the pivot are healed. Finally the missing bytecodes are downloaded, and the
+/// stages from [`StageId::SenderRecovery`] to [`StageId::IndexLogs`] continue from the pivot.
+///
+/// The plain state of the accounts and storage slots not changed since the pivot is not
+/// written; state providers read it from the hashed state instead.
+///
+/// The stage runs once on an empty database: when its checkpoint is set, it only moves the
+/// checkpoint to the target. If the chain is too short, or the state of the pivot is no longer
+/// served by peers, it finishes without moving its checkpoint, so the following stages wait for
+/// the next pipeline run.
+///
+/// # Tables
+///
+/// This stage writes to:
+/// - [`tables::HashedAccounts`]
+/// - [`tables::HashedStorages`]
+/// - [`tables::AccountsTrie`]
+/// - [`tables::StoragesTrie`]
+/// - [`tables::Bytecodes`]
+///
+/// It clears the plain state tables when the download starts.
+#[derive(Debug)]
+pub struct SnapSyncStage<C> {
+    /// The client requesting the state from peers.
+    client: C,
+    /// The snap sync configuration.
+    config: SnapSyncConfig,
+    /// The requests to send in the next download.
+    requests: Vec<SnapRequest>,
+    /// The state root the requests are sent for.
+    root: B256,
+    /// The running download.
+    download: Option<JoinHandle<SnapDownload>>,
+    /// The finished download, which is written in the next execution.
+    downloaded: Option<SnapDownload>,
+    /// The trie nodes to check against the local trie and request if they don't match.
+    heal_queue: Vec<HealNode>,
+}
+
+impl<C> SnapSyncStage<C> {
+    /// Creates a new snap sync stage requesting the state with the given client.
+    pub const fn new(client: C, config: SnapSyncConfig) -> Self {
+        Self {
+            client,
+            config,
+            requests: Vec::new(),
+            root: B256::ZERO,
+            download: None,
+            downloaded: None,
+            heal_queue: Vec::new(),
+        }
+    }
+
+    fn max_concurrent_requests(&self) -> usize {
+        self.config.max_concurrent_requests.max(1)
+    }
+}
+
+impl<C> Drop for SnapSyncStage<C> {
+    fn drop(&mut self) {
+        if let Some(download) = self.download.take() {
+            download.abort();
+        }
+    }
+}
+
+impl<Provider, C> Stage<Provider> for SnapSyncStage<C>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + BlockReader
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + PruneCheckpointWriter
+        + TrieWriter,
+    C: SnapClient + Clone + 'static,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() ||
+            self.downloaded.is_some() ||
+            (self.requests.is_empty() && self.download.is_none())
+        {
+            return Poll::Ready(Ok(()))
+        }
+
+        if self.download.is_none() {
+            self.download = Some(tokio::spawn(download(
+                self.client.clone(),
+                self.config,
+                self.root,
+                std::mem::take(&mut self.requests),
+            )));
+        }
+        let result = ready!(self.download.as_mut().expect("download is spawned").poll_unpin(cx));
+        self.download = None;
+        Poll::Ready(match result {
+            Ok(downloaded) => {
+                self.downloaded = Some(downloaded);
+                Ok(())
+            }
+            Err(err) => Err(StageError::Fatal(Box::new(err))),
+        })
+    }
+
+    /// Write the downloaded state and plan the next requests.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+        // The state is downloaded only once, the following blocks are executed.
+        if input.checkpoint().block_number > 0 {
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        self.requests.clear();
+        let target = input.target();
+        let mut checkpoint = match load_checkpoint(provider)? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let executed = provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number;
+                if executed > 0 {
+                    return Err(StageError::Fatal(Box::new(SnapSyncError::AlreadyExecuted(
+                        executed,
+                    ))))
+                }
+
+                let pivot = target.saturating_sub(self.config.pivot_distance);
+                let Some(state_root) = pivot_state_root(provider, pivot)?.filter(|_| pivot > 0)
+                else {
+                    info!(target: "sync::stages::snap_sync", target, "Waiting for more headers to pick the snap sync pivot");
+                    return Ok(ExecOutput::done(input.checkpoint()))
+                };
+                start(provider, pivot, state_root)?
+            }
+        };
+
+        let mut stale =
+            pivot_state_root(provider, checkpoint.pivot)? != Some(checkpoint.state_root);
+        if let Some(downloaded) = self.downloaded.take() {
+            if !stale && downloaded.root == checkpoint.state_root {
+                self.write(provider, &mut checkpoint, downloaded.responses)?;
+            }
+            stale |= downloaded.stale;
+        }
+
+        if stale {
+            let pivot = target.saturating_sub(self.config.pivot_distance);
+            match pivot_state_root(provider, pivot)?
+                .filter(|state_root| pivot > 0 && *state_root != checkpoint.state_root)
+            {
+                Some(state_root) => {
+                    info!(target: "sync::stages::snap_sync", old_pivot = checkpoint.pivot, pivot, "Moving the snap sync pivot");
+                    checkpoint.pivot = pivot;
+                    checkpoint.state_root = state_root;
+                    checkpoint.code_scan = None;
+                    self.heal_queue.clear();
+                }
+                None => {
+                    save_checkpoint(provider, &checkpoint)?;
+                    warn!(target: "sync::stages::snap_sync", pivot = checkpoint.pivot, "State of the snap sync pivot is no longer served, waiting for new headers");
+                    return Ok(ExecOutput::done(input.checkpoint()))
+                }
+            }
+        }
+
+        if !checkpoint.account_ranges.is_empty() {
+            info!(target: "sync::stages::snap_sync", pivot = checkpoint.pivot, remaining_ranges = checkpoint.account_ranges.len(), "Downloading state");
+            self.requests.extend(
+                checkpoint.account_ranges.iter().take(self.max_concurrent_requests()).map(
+                    |(origin, limit)| SnapRequest::AccountRange { origin: *origin, limit: *limit },
+                ),
+            );
+        } else if !checkpoint.healing {
+            build_trie(provider, &mut checkpoint)?;
+        } else if checkpoint.code_scan.is_none() {
+            if self.heal(provider, &checkpoint)? {
+                checkpoint.code_scan = Some(B256::ZERO);
+            }
+        } else if self.scan_code(provider, &mut checkpoint)? {
+            finish(provider, checkpoint.pivot)?;
+            save_checkpoint(provider, &checkpoint)?;
+            info!(target: "sync::stages::snap_sync", pivot = checkpoint.pivot, "Finished snap sync");
+            return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+        }
+
+        save_checkpoint(provider, &checkpoint)?;
+        self.root = checkpoint.state_root;
+        Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+    }
+
+    /// The downloaded state cannot be unwound, only the blocks after the pivot.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if input.checkpoint.block_number > 0 {
+            if let Some(checkpoint) = load_checkpoint(provider)? {
+                if input.unwind_to < checkpoint.pivot {
+                    return Err(StageError::Fatal(Box::new(SnapSyncError::UnwindBeforePivot {
+                        pivot: checkpoint.pivot,
+                        unwind_to: input.unwind_to,
+                    })))
+                }
+            }
+        }
+        Ok(UnwindOutput {
+            checkpoint: StageCheckpoint::new(input.unwind_to.min(input.checkpoint.block_number)),
+        })
+    }
+}
+
+impl<C> SnapSyncStage<C> {
+    /// Writes the downloaded responses.
+    fn write<Provider>(
+        &mut self,
+        provider: &Provider,
+        checkpoint: &mut SnapSyncCheckpoint,
+        responses: Vec<SnapResponse>,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    {
+        let tx = provider.tx_ref();
+        let mut healer = TrieHealer::new(tx);
+        for response in responses {
+            match response {
+                SnapResponse::AccountRange { origin, next, accounts, storages } => {
+                    for (hashed_address, account) in accounts {
+                        tx.put::<tables::HashedAccounts>(hashed_address, into_account(&account))?;
+                    }
+                    let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                    for (hashed_address, slots) in storages {
+                        for (key, value) in slots.into_iter().filter(|(_, value)| !value.is_zero())
+                        {
+                            cursor.upsert(hashed_address, StorageEntry { key, value })?;
+                        }
+                    }
+
+                    let Some(index) =
+                        checkpoint.account_ranges.iter().position(|(next, _)| *next == origin)
+                    else {
+                        continue
+                    };
+                    match next {
+                        Some(next) => checkpoint.account_ranges[index].0 = next,
+                        None => {
+                            checkpoint.account_ranges.remove(index);
+                        }
+                    }
+                }
+                SnapResponse::ByteCodes(codes) => {
+                    for (hash, code) in codes {
+                        tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+                    }
+                }
+                SnapResponse::TrieNodes(nodes) => {
+                    for (node, trie_node) in nodes {
+                        healer.apply(node.account, node.path, trie_node)?;
+                    }
+                }
+            }
+        }
+
+        if healer.is_empty() {
+            return Ok(())
+        }
+        let TrieHealer { prefix_sets, queue, .. } = healer;
+        let (root, updates) = StateRoot::from_tx(tx)
+            .with_prefix_sets(prefix_sets.freeze())
+            .root_with_updates()
+            .map_err(|e| StageError::Fatal(Box::new(e)))?;
+        provider.write_trie_updates(&updates)?;
+        debug!(target: "sync::stages::snap_sync", %root, expected = %checkpoint.state_root, queued = queue.len(), "Healed trie nodes");
+        self.heal_queue.extend(queue);
+        Ok(())
+    }
+
+    /// Plans the requests of the trie nodes that don't match the local trie.
+    ///
+    /// Returns `true` if the trie matches the state root of the pivot.
+    fn heal<Provider>(
+        &mut self,
+        provider: &Provider,
+        checkpoint: &SnapSyncCheckpoint,
+    ) -> Result<bool, StageError>
+    where
+        Provider: DBProvider,
+    {
+        let tx = provider.tx_ref();
+        if self.heal_queue.is_empty() {
+            let root = StateRoot::from_tx(tx).root().map_err(|e| StageError::Fatal(Box::new(e)))?;
+            if root == checkpoint.state_root {
+                return Ok(true)
+            }
+            info!(target: "sync::stages::snap_sync", pivot = checkpoint.pivot, %root, expected = %checkpoint.state_root, "Healing the trie of the downloaded state");
+            self.heal_queue.push(HealNode {
+                account: None,
+                path: Nibbles::default(),
+                hash: checkpoint.state_root,
+            });
+        }
+
+        let max_nodes = MAX_TRIE_NODES_PER_REQUEST * self.max_concurrent_requests();
+        let mut nodes = self.heal_queue.split_off(self.heal_queue.len().saturating_sub(max_nodes));
+        nodes.sort_unstable_by(|a, b| (a.account, &a.path).cmp(&(b.account, &b.path)));
+
+        let mut missing = Vec::with_capacity(nodes.len());
+        for group in nodes.chunk_by(|a, b| a.account == b.account) {
+            let paths = group.iter().map(|node| node.path.clone()).collect::<Vec<_>>();
+            let local = match group[0].account {
+                None => account_trie_nodes(tx, &paths)?,
+                Some(hashed_address) => storage_trie_nodes(tx, hashed_address, &paths)?,
+            };
+            missing.extend(
+                group
+                    .iter()
+                    .filter(|node| {
+                        local.get(&node.path).map_or(true, |local| keccak256(local) != node.hash)
+                    })
+                    .cloned(),
+            );
+        }
+        self.requests.extend(
+            missing
+                .chunks(MAX_TRIE_NODES_PER_REQUEST)
+                .map(|nodes| SnapRequest::TrieNodes(nodes.to_vec())),
+        );
+        Ok(false)
+    }
+
+    /// Plans the requests of the missing bytecodes.
+    ///
+    /// Returns `true` if no bytecode is missing.
+    fn scan_code<Provider>(
+        &mut self,
+        provider: &Provider,
+        checkpoint: &mut SnapSyncCheckpoint,
+    ) -> Result<bool, StageError>
+    where
+        Provider: DBProvider,
+    {
+        let tx = provider.tx_ref();
+        let max_missing = MAX_CODES_PER_REQUEST * self.max_concurrent_requests();
+        let mut missing = HashSet::<B256>::default();
+        let mut first_missing = None;
+        let mut next = None;
+
+        let mut cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+        for (scanned, entry) in cursor.walk(checkpoint.code_scan)?.enumerate() {
+            let (hashed_address, account) = entry?;
+            if scanned >= MAX_CODE_SCAN || missing.len() >= max_missing {
+                next = Some(hashed_address);
+                break
+            }
+            let Some(code_hash) = account.bytecode_hash else { continue };
+            if !missing.contains(&code_hash) && tx.get::<tables::Bytecodes>(code_hash)?.is_none() {
+                first_missing.get_or_insert(hashed_address);
+                missing.insert(code_hash);
+            }
+        }
+
+        // Scan again from the first account with missing bytecode once it's downloaded.
+        let Some(code_scan) = first_missing.or(next) else { return Ok(true) };
+        checkpoint.code_scan = Some(code_scan);
+        debug!(target: "sync::stages::snap_sync", missing = missing.len(), %code_scan, "Downloading bytecodes");
+        let missing = missing.into_iter().collect::<Vec<_>>();
+        self.requests.extend(
+            missing
+                .chunks(MAX_CODES_PER_REQUEST)
+                .map(|hashes| SnapRequest::ByteCodes(hashes.to_vec())),
+        );
+        Ok(false)
+    }
+}
+
+/// Snap sync errors.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapSyncError {
+    /// Blocks were already executed, so the state cannot be downloaded.
+    #[error("blocks up to {0} were already executed, snap sync requires an empty database")]
+    AlreadyExecuted(BlockNumber),
+    /// The unwind target is before the pivot, whose state was downloaded.
+    #[error("cannot unwind to block {unwind_to} before the snap sync pivot block {pivot}")]
+    UnwindBeforePivot {
+        /// The pivot block.
+        pivot: BlockNumber,
+        /// The unwind target.
+        unwind_to: BlockNumber,
+    },
+}
+
+/// A response that doesn't match its request, for which the peer is reported.
+#[derive(Debug, thiserror::Error)]
+enum InvalidResponse {
+    #[error(transparent)]
+    RangeProof(#[from] RangeProofError),
+    #[error("node hash {0} was not requested")]
+    HashMismatch(B256),
+    #[error("response contains unrequested items")]
+    Unrequested,
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    #[error("storage does not match the storage root")]
+    StorageRootMismatch,
+}
+
+/// A request sent in a download.
+#[derive(Debug)]
+enum SnapRequest {
+    /// The accounts from the origin to the limit, and their storage.
+    AccountRange { origin: B256, limit: B256 },
+    /// Bytecodes by hash.
+    ByteCodes(Vec<B256>),
+    /// Trie nodes to heal.
+    TrieNodes(Vec<HealNode>),
+}
+
+/// The verified response to a [`SnapRequest`].
+#[derive(Debug)]
+enum SnapResponse {
+    AccountRange {
+        /// The origin of the request.
+        origin: B256,
+        /// The next hash of the range, if the range isn't complete.
+        next: Option<B256>,
+        accounts: Vec<(B256, TrieAccount)>,
+        storages: Vec<(B256, Vec<(B256, U256)>)>,
+    },
+    ByteCodes(Vec<(B256, Bytes)>),
+    TrieNodes(Vec<(HealNode, TrieNode)>),
+}
+
+/// The result of a download.
+#[derive(Debug)]
+struct SnapDownload {
+    /// The state root the requests were sent for.
+    root: B256,
+    responses: Vec<SnapResponse>,
+    /// Whether the state of the root was no longer served by peers.
+    stale: bool,
+}
+
+/// A trie node of the pivot state.
+#[derive(Debug, Clone)]
+struct HealNode {
+    /// The hashed address of the account whose storage trie the node is in, or `None` for the
+    /// account trie.
+    account: Option<B256>,
+    path: Nibbles,
+    hash: B256,
+}
+
+/// Applies the downloaded trie nodes to the hashed state, collecting the changed keys and the
+/// children to heal.
+///
+/// The keys under a node which the node doesn't reference are deleted, and the leaves are
+/// written.
+#[derive(Debug)]
+struct TrieHealer<'a, TX> {
+    tx: &'a TX,
+    prefix_sets: TriePrefixSetsMut,
+    queue: Vec<HealNode>,
+    applied: bool,
+}
+
+impl<'a, TX: DbTxMut + DbTx> TrieHealer<'a, TX> {
+    fn new(tx: &'a TX) -> Self {
+        Self { tx, prefix_sets: TriePrefixSetsMut::default(), queue: Vec::new(), applied: false }
+    }
+
+    const fn is_empty(&self) -> bool {
+        !self.applied
+    }
+
+    fn apply(
+        &mut self,
+        account: Option<B256>,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError> {
+        self.applied = true;
+        match node {
+            TrieNode::Branch(branch) => {
+                let mut children = branch.stack.iter();
+                for nibble in CHILD_INDEX_RANGE {
+                    let mut child_path = path.clone();
+                    child_path.push(nibble);
+                    let child =
+                        if branch.state_mask.is_bit_set(nibble) { children.next() } else { None };
+                    match child {
+                        Some(child) => self.apply_child(account, child_path, child)?,
+                        None => self.delete(account, &child_path, None)?,
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let mut child_path = path.clone();
+                child_path.extend_from_slice(&extension.key);
+                self.delete(account, &path, Some(&child_path))?;
+                self.apply_child(account, child_path, &extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let mut key = path.clone();
+                key.extend_from_slice(&leaf.key);
+                self.delete(account, &path, Some(&key))?;
+                self.write_leaf(account, key, &leaf.value)?;
+            }
+            TrieNode::EmptyRoot => self.delete(account, &path, None)?,
+        }
+        Ok(())
+    }
+
+    /// Queues the child referenced by hash, or applies the inlined child.
+    fn apply_child(
+        &mut self,
+        account: Option<B256>,
+        path: Nibbles,
+        child: &[u8],
+    ) -> Result<(), StageError> {
+        if child.len() == B256::len_bytes() + 1 {
+            let hash = B256::from_slice(&child[1..]);
+            self.queue.push(HealNode { account, path, hash });
+            return Ok(())
+        }
+        let node = TrieNode::decode(&mut &child[..]).map_err(|e| StageError::Fatal(Box::new(e)))?;
+        self.apply(account, path, node)
+    }
+
+    fn write_leaf(
+        &mut self,
+        account: Option<B256>,
+        key: Nibbles,
+        value: &[u8],
+    ) -> Result<(), StageError> {
+        if key.len() != B256::len_bytes() * 2 {
+            return Err(StageError::Fatal(Box::new(alloy_rlp::Error::Custom(
+                "invalid trie leaf key length",
+            ))))
+        }
+        let hashed_key = B256::from_slice(&key.pack());
+        match account {
+            None => {
+                let account = TrieAccount::decode(&mut &value[..])
+                    .map_err(|e| StageError::Fatal(Box::new(e)))?;
+                self.tx.put::<tables::HashedAccounts>(hashed_key, into_account(&account))?;
+                self.prefix_sets.account_prefix_set.insert(key);
+
+                if account.storage_root == EMPTY_ROOT_HASH {
+                    self.wipe_storage(hashed_key)?;
+                } else if StorageRoot::from_tx_hashed(self.tx, hashed_key)
+                    .root()
+                    .map_err(|e| StageError::Fatal(Box::new(e)))? !=
+                    account.storage_root
+                {
+                    self.queue.push(HealNode {
+                        account: Some(hashed_key),
+                        path: Nibbles::default(),
+                        hash: account.storage_root,
+                    });
+                }
+            }
+            Some(hashed_address) => {
+                let value =
+                    U256::decode(&mut &value[..]).map_err(|e| StageError::Fatal(Box::new(e)))?;
+                let mut cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+                if cursor
+                    .seek_by_key_subkey(hashed_address, hashed_key)?
+                    .is_some_and(|entry| entry.key == hashed_key)
+                {
+                    cursor.delete_current()?;
+                }
+                if !value.is_zero() {
+                    cursor.upsert(hashed_address, StorageEntry { key: hashed_key, value })?;
+                }
+                self.prefix_sets.storage_prefix_sets.entry(hashed_address).or_default().insert(key);
+                self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the keys under the prefix, except the keys under `keep`.
+    fn delete(
+        &mut self,
+        account: Option<B256>,
+        prefix: &Nibbles,
+        keep: Option<&Nibbles>,
+    ) -> Result<(), StageError> {
+        for range in key_ranges(prefix, keep) {
+            match account {
+                None => {
+                    let mut cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
+                    let mut walker = cursor.walk_range(range)?;
+                    while let Some((hashed_address, _)) = walker.next().transpose()? {
+                        walker.delete_current()?;
+                        self.wipe_storage(hashed_address)?;
+                        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                        self.prefix_sets.destroyed_accounts.insert(hashed_address);
+                    }
+                }
+                Some(hashed_address) => {
+                    let mut cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+                    let mut walker = cursor.walk_dup(Some(hashed_address), Some(*range.start()))?;
+                    while let Some((_, entry)) = walker.next().transpose()? {
+                        if entry.key > *range.end() {
+                            break
+                        }
+                        walker.delete_current()?;
+                        self.prefix_sets
+                            .storage_prefix_sets
+                            .entry(hashed_address)
+                            .or_default()
+                            .insert(Nibbles::unpack(entry.key));
+                        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the storage and the storage trie of the account.
+    fn wipe_storage(&self, hashed_address: B256) -> Result<(), StageError> {
+        let mut storages = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        if storages.seek_exact(hashed_address)?.is_some() {
+            storages.delete_current_duplicates()?;
+        }
+        let mut tries = self.tx.cursor_dup_write::<tables::StoragesTrie>()?;
+        if tries.seek_exact(hashed_address)?.is_some() {
+            tries.delete_current_duplicates()?;
+        }
+        Ok(())
+    }
+}
+
+/// Clears the state and starts the download of the state at the pivot.
+fn start<Provider>(
+    provider: &Provider,
+    pivot: BlockNumber,
+    state_root: B256,
+) -> Result<SnapSyncCheckpoint, StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    info!(target: "sync::stages::snap_sync", pivot, %state_root, "Starting snap sync");
+    let tx = provider.tx_ref();
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+
+    let account_ranges = (0..ACCOUNT_RANGES)
+        .map(|nibble| {
+            let mut first = B256::ZERO;
+            let mut last = B256::repeat_byte(0xff);
+            first[0] = nibble << 4;
+            last[0] = nibble << 4 | 0x0f;
+            (first, last)
+        })
+        .collect();
+    Ok(SnapSyncCheckpoint { pivot, state_root, account_ranges, ..Default::default() })
+}
+
+/// Builds the trie of the downloaded state, in steps saved to the checkpoint.
+fn build_trie<Provider>(
+    provider: &Provider,
+    checkpoint: &mut SnapSyncCheckpoint,
+) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+{
+    let tx = provider.tx_ref();
+    if checkpoint.trie.is_none() {
+        info!(target: "sync::stages::snap_sync", pivot = checkpoint.pivot, "Building the trie of the downloaded state");
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+    }
+
+    let progress = StateRoot::from_tx(tx)
+        .with_intermediate_state(checkpoint.trie.take().map(IntermediateStateRootState::from))
+        .root_with_progress()
+        .map_err(|e| StageError::Fatal(Box::new(e)))?;
+    match progress {
+        StateRootProgress::Progress(state, _, updates) => {
+            provider.write_trie_updates(&updates)?;
+            checkpoint.trie = Some(MerkleCheckpoint::new(
+                checkpoint.pivot,
+                state.last_account_key,
+                state.walker_stack.into_iter().map(StoredSubNode::from).collect(),
+                state.hash_builder.into(),
+            ));
+        }
+        StateRootProgress::Complete(root, _, updates) => {
+            provider.write_trie_updates(&updates)?;
+            debug!(target: "sync::stages::snap_sync", %root, expected = %checkpoint.state_root, "Built the trie of the downloaded state");
+            checkpoint.healing = true;
+        }
+    }
+    Ok(())
+}
+
+/// Moves the checkpoints of the stages that process the state to the pivot, and marks the
+/// history and receipts before the pivot as pruned.
+fn finish<Provider>(provider: &Provider, pivot: BlockNumber) -> Result<(), StageError>
+where
+    Provider: BlockReader + StageCheckpointWriter + PruneCheckpointWriter,
+{
+    for stage_id in [
+        StageId::SenderRecovery,
+        StageId::Execution,
+        StageId::AccountHashing,
+        StageId::StorageHashing,
+        StageId::MerkleExecute,
+        StageId::IndexAccountHistory,
+        StageId::IndexStorageHistory,
+        StageId::IndexLogs,
+    ] {
+        provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(pivot))?;
+    }
+
+    let last_tx = provider
+        .block_body_indices(pivot)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(pivot))?
+        .last_tx_num();
+    for (segment, tx_number) in [
+        (PruneSegment::SenderRecovery, Some(last_tx)),
+        (PruneSegment::Receipts, Some(last_tx)),
+        (PruneSegment::AccountHistory, None),
+        (PruneSegment::StorageHistory, None),
+    ] {
+        provider.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(pivot),
+                tx_number,
+                prune_mode: PruneMode::Before(pivot + 1),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn load_checkpoint<Provider: StageCheckpointReader>(
+    provider: &Provider,
+) -> Result<Option<SnapSyncCheckpoint>, StageError> {
+    let buf = provider.get_stage_checkpoint_progress(StageId::SnapSync)?.unwrap_or_default();
+    if buf.is_empty() {
+        return Ok(None)
+    }
+    let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&buf, buf.len());
+    Ok(Some(checkpoint))
+}
+
+fn save_checkpoint<Provider: StageCheckpointWriter>(
+    provider: &Provider,
+    checkpoint: &SnapSyncCheckpoint,
+) -> Result<(), StageError> {
+    let mut buf = Vec::new();
+    checkpoint.to_compact(&mut buf);
+    Ok(provider.save_stage_checkpoint_progress(StageId::SnapSync, buf)?)
+}
+
+fn pivot_state_root<Provider: HeaderProvider>(
+    provider: &Provider,
+    pivot: BlockNumber,
+) -> Result<Option<B256>, StageError> {
+    Ok(provider.header_by_number(pivot)?.map(|header| header.state_root))
+}
+
+fn into_account(account: &TrieAccount) -> Account {
+    Account {
+        nonce: account.nonce,
+        balance: account.balance,
+        bytecode_hash: (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash),
+    }
+}
+
+/// Returns the ranges of the keys under the prefix, without the keys under `keep`.
+fn key_ranges(prefix: &Nibbles, keep: Option<&Nibbles>) -> Vec<RangeInclusive<B256>> {
+    let (first, last) = (path_key(prefix), last_key(prefix));
+    let Some(keep) = keep else { return vec![first..=last] };
+    [
+        prev_key(path_key(keep)).map(|end| first..=end),
+        next_key(last_key(keep)).map(|start| start..=last),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|range| range.start() <= range.end())
+    .collect()
+}
+
+/// Returns the largest key under the path.
+fn last_key(path: &Nibbles) -> B256 {
+    let mut nibbles = path.to_vec();
+    nibbles.resize(B256::len_bytes() * 2, 0x0f);
+    B256::from_slice(&Nibbles::from_nibbles_unchecked(nibbles).pack())
+}
+
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+fn prev_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_sub(U256::from(1)).map(B256::from)
+}
+
+/// Returns the root of the storage trie with the given slots.
+fn storage_trie_root(slots: &[(B256, U256)]) -> B256 {
+    let mut hash_builder = HashBuilder::default();
+    for (key, value) in slots {
+        hash_builder.add_leaf(Nibbles::unpack(key), &alloy_rlp::encode(value));
+    }
+    hash_builder.root()
+}
+
+/// Returns the paths of the trie nodes as requested in [`GetTrieNodes`], with consecutive storage
+/// trie nodes of an account grouped.
+fn trie_node_paths(nodes: &[HealNode]) -> Vec<Vec<Bytes>> {
+    let mut paths: Vec<Vec<Bytes>> = Vec::new();
+    for (index, node) in nodes.iter().enumerate() {
+        let path = encode_compact_path(&node.path);
+        match node.account {
+            None => paths.push(vec![path]),
+            Some(hashed_address)
+                if index > 0 && nodes[index - 1].account == Some(hashed_address) =>
+            {
+                paths.last_mut().expect("previous node").push(path)
+            }
+            Some(hashed_address) => paths.push(vec![hashed_address.into(), path]),
+        }
+    }
+    paths
+}
+
+/// Downloads the responses to the requests, with the configured number of requests in flight.
+async fn download<C: SnapClient>(
+    client: C,
+    config: SnapSyncConfig,
+    root: B256,
+    requests: Vec<SnapRequest>,
+) -> SnapDownload {
+    let client = &client;
+    let mut downloads = stream::iter(requests)
+        .map(|request| async move {
+            match request {
+                SnapRequest::AccountRange { origin, limit } => {
+                    download_account_range(client, config, root, origin, limit).await
+                }
+                SnapRequest::ByteCodes(hashes) => {
+                    Some(SnapResponse::ByteCodes(download_byte_codes(client, config, hashes).await))
+                }
+                SnapRequest::TrieNodes(nodes) => download_trie_nodes(client, config, root, nodes)
+                    .await
+                    .map(SnapResponse::TrieNodes),
+            }
+        })
+        .buffer_unordered(config.max_concurrent_requests.max(1));
+
+    let mut responses = Vec::new();
+    let mut stale = false;
+    while let Some(response) = downloads.next().await {
+        match response {
+            Some(response) => responses.push(response),
+            None => stale = true,
+        }
+    }
+    SnapDownload { root, responses, stale }
+}
+
+/// Sends the request until a peer serves a valid response, reporting the peers that send invalid
+/// ones.
+///
+/// Returns `None` if the request isn't served after [`MAX_UNAVAILABLE_RESPONSES`] empty responses.
+async fn request<C: SnapClient, T, R>(
+    client: &C,
+    send: impl Fn(&C) -> SnapResponseFut<T>,
+    verify: impl Fn(T) -> Result<Option<R>, InvalidResponse>,
+) -> Option<R> {
+    let mut unavailable = 0;
+    loop {
+        match send(client).await {
+            Ok(response) => {
+                let (peer_id, response) = response.split();
+                match verify(response) {
+                    Ok(Some(verified)) => return Some(verified),
+                    Ok(None) => {
+                        unavailable += 1;
+                        if unavailable >= MAX_UNAVAILABLE_RESPONSES {
+                            return None
+                        }
+                    }
+                    Err(err) => {
+                        debug!(target: "sync::stages::snap_sync", %peer_id, %err, "Invalid snap response");
+                        client.report_bad_message(peer_id);
+                        continue
+                    }
+                }
+            }
+            Err(err) => trace!(target: "sync::stages::snap_sync", %err, "Snap request failed"),
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+/// Downloads the accounts of the range from the origin, up to the response limit, and their
+/// storage.
+async fn download_account_range<C: SnapClient>(
+    client: &C,
+    config: SnapSyncConfig,
+    root: B256,
+    origin: B256,
+    limit: B256,
+) -> Option<SnapResponse> {
+    let (mut accounts, more) = request(
+        client,
+        |client| {
+            client.get_account_range(GetAccountRange {
+                request_id: 0,
+                root_hash: root,
+                starting_hash: origin,
+                limit_hash: limit,
+                response_bytes: config.response_bytes,
+            })
+        },
+        |response| verify_account_range(root, origin, response),
+    )
+    .await?;
+
+    let last = accounts.last().map(|(hashed_address, _)| *hashed_address);
+    let next = last.filter(|last| more && *last < limit).and_then(next_key);
+    accounts.retain(|(hashed_address, _)| *hashed_address <= limit);
+
+    let with_storage = accounts
+        .iter()
+        .filter(|(_, account)| account.storage_root != EMPTY_ROOT_HASH)
+        .map(|(hashed_address, account)| (*hashed_address, account.storage_root))
+        .collect::<Vec<_>>();
+    let storages = download_storages(client, config, root, &with_storage).await?;
+
+    Some(SnapResponse::AccountRange { origin, next, accounts, storages })
+}
+
+fn verify_account_range(
+    root: B256,
+    origin: B256,
+    response: AccountRange,
+) -> Result<Option<(Vec<(B256, TrieAccount)>, bool)>, InvalidResponse> {
+    if response.accounts.is_empty() && response.proof.is_empty() {
+        return Ok(None)
+    }
+    let accounts = response
+        .accounts
+        .into_iter()
+        .map(|data| {
+            let account = TrieAccount {
+                nonce: data.account.nonce,
+                balance: data.account.balance,
+                storage_root: data.account.storage_root(),
+                code_hash: data.account.code_hash(),
+            };
+            (data.hash, account)
+        })
+        .collect::<Vec<_>>();
+    let encoded = accounts
+        .iter()
+        .map(|(hashed_address, account)| (*hashed_address, alloy_rlp::encode(account)))
+        .collect::<Vec<_>>();
+    let more = verify_range_proof(root, origin, &encoded, &response.proof)?;
+    Ok(Some((accounts, more)))
+}
+
+/// Downloads the storage of the accounts with the given storage roots.
+async fn download_storages<C: SnapClient>(
+    client: &C,
+    config: SnapSyncConfig,
+    root: B256,
+    mut accounts: &[(B256, B256)],
+) -> Option<Vec<(B256, Vec<(B256, U256)>)>> {
+    let mut storages: Vec<(B256, Vec<(B256, U256)>)> = Vec::with_capacity(accounts.len());
+    let mut origin = B256::ZERO;
+    while !accounts.is_empty() {
+        // the rest of a large storage is requested on its own
+        let batch = if origin.is_zero() {
+            &accounts[..accounts.len().min(MAX_STORAGE_ACCOUNTS_PER_REQUEST)]
+        } else {
+            &accounts[..1]
+        };
+        let (slots, next) = request(
+            client,
+            |client| {
+                client.get_storage_ranges(GetStorageRanges {
+                    request_id: 0,
+                    root_hash: root,
+                    account_hashes: batch
+                        .iter()
+                        .map(|(hashed_address, _)| *hashed_address)
+                        .collect(),
+                    starting_hash: if origin.is_zero() { Bytes::new() } else { origin.into() },
+                    limit_hash: Bytes::new(),
+                    response_bytes: config.response_bytes,
+                })
+            },
+            |response| verify_storage_ranges(batch, origin, response),
+        )
+        .await?;
+
+        let served = slots.len();
+        for ((hashed_address, _), slots) in batch.iter().zip(slots) {
+            match storages.last_mut() {
+                Some((last, storage)) if last == hashed_address => storage.extend(slots),
+                _ => storages.push((*hashed_address, slots)),
+            }
+        }
+        match next {
+            Some(next) => {
+                origin = next;
+                accounts = &accounts[served - 1..];
+            }
+            None => {
+                origin = B256::ZERO;
+                accounts = &accounts[served..];
+            }
+        }
+    }
+    Some(storages)
+}
+
+/// Verifies the storage ranges of the accounts with the given storage roots.
+///
+/// Returns the slots of the served accounts, and the next slot hash of the last account if its
+/// storage isn't complete.
+fn verify_storage_ranges(
+    accounts: &[(B256, B256)],
+    origin: B256,
+    response: StorageRanges,
+) -> Result<Option<(Vec<Vec<(B256, U256)>>, Option<B256>)>, InvalidResponse> {
+    if response.slots.is_empty() {
+        return Ok(None)
+    }
+    if response.slots.len() > accounts.len() {
+        return Err(InvalidResponse::Unrequested)
+    }
+
+    let last = response.slots.len() - 1;
+    let mut verified = Vec::with_capacity(response.slots.len());
+    let mut next = None;
+    for (index, ((_, storage_root), slots)) in accounts.iter().zip(response.slots).enumerate() {
+        let slots = slots
+            .into_iter()
+            .map(|slot| Ok((slot.hash, U256::decode(&mut &slot.data[..])?)))
+            .collect::<Result<Vec<_>, alloy_rlp::Error>>()?;
+        if !slots.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+            return Err(RangeProofError::UnorderedKeys.into())
+        }
+
+        // only the last storage may be partial, which is proven by the proof
+        if index == last && (!origin.is_zero() || !response.proof.is_empty()) {
+            let encoded = slots
+                .iter()
+                .map(|(key, value)| (*key, alloy_rlp::encode(value)))
+                .collect::<Vec<_>>();
+            if verify_range_proof(*storage_root, origin, &encoded, &response.proof)? {
+                next = slots.last().and_then(|(key, _)| next_key(*key));
+            }
+        } else if storage_trie_root(&slots) != *storage_root {
+            return Err(InvalidResponse::StorageRootMismatch)
+        }
+        verified.push(slots);
+    }
+    Ok(Some((verified, next)))
+}
+
+/// Downloads the bytecodes with the given hashes, as far as they are served.
+async fn download_byte_codes<C: SnapClient>(
+    client: &C,
+    config: SnapSyncConfig,
+    mut hashes: Vec<B256>,
+) -> Vec<(B256, Bytes)> {
+    let mut codes = Vec::with_capacity(hashes.len());
+    while !hashes.is_empty() {
+        let Some(served) = request(
+            client,
+            |client| {
+                client.get_byte_codes(GetByteCodes {
+                    request_id: 0,
+                    hashes: hashes.clone(),
+                    response_bytes: config.response_bytes,
+                })
+            },
+            |response| verify_byte_codes(&hashes, response),
+        )
+        .await
+        else {
+            // the missing bytecodes are requested again after the next scan
+            break
+        };
+        hashes.retain(|hash| !served.iter().any(|(served, _)| served == hash));
+        codes.extend(served);
+    }
+    codes
+}
+
+fn verify_byte_codes(
+    hashes: &[B256],
+    response: ByteCodes,
+) -> Result<Option<Vec<(B256, Bytes)>>, InvalidResponse> {
+    if response.codes.is_empty() {
+        return Ok(None)
+    }
+    response
+        .codes
+        .into_iter()
+        .map(|code| {
+            let hash = keccak256(&code);
+            if hashes.contains(&hash) {
+                Ok((hash, code))
+            } else {
+                Err(InvalidResponse::Unrequested)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Downloads the trie nodes, requesting the nodes that weren't served again.
+async fn download_trie_nodes<C: SnapClient>(
+    client: &C,
+    config: SnapSyncConfig,
+    root: B256,
+    mut nodes: Vec<HealNode>,
+) -> Option<Vec<(HealNode, TrieNode)>> {
+    let mut healed = Vec::with_capacity(nodes.len());
+    while !nodes.is_empty() {
+        let paths = trie_node_paths(&nodes);
+        let served = request(
+            client,
+            |client| {
+                client.get_trie_nodes(GetTrieNodes {
+                    request_id: 0,
+                    root_hash: root,
+                    paths: paths.clone(),
+                    response_bytes: config.response_bytes,
+                })
+            },
+            |response| verify_trie_nodes(&nodes, response),
+        )
+        .await?;
+
+        let rest = nodes.split_off(served.len());
+        healed.extend(nodes.into_iter().zip(served));
+        nodes = rest;
+    }
+    Some(healed)
+}
+
+/// Verifies the trie nodes served for the first requested nodes, up to the first missing one.
+fn verify_trie_nodes(
+    nodes: &[HealNode],
+    response: TrieNodes,
+) -> Result<Option<Vec<TrieNode>>, InvalidResponse> {
+    if response.nodes.len() > nodes.len() {
+        return Err(InvalidResponse::Unrequested)
+    }
+    let served = response
+        .nodes
+        .iter()
+        .take_while(|encoded| !encoded.is_empty())
+        .zip(nodes)
+        .map(|(encoded, node)| {
+            let hash = keccak256(encoded);
+            if hash != node.hash {
+                return Err(InvalidResponse::HashMismatch(hash))
+            }
+            Ok(TrieNode::decode(&mut &encoded[..])?)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((!served.is_empty()).then_some(served))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_net_snap::message::StorageData;
+
+    #[test]
+    fn key_ranges_exclude_kept_path() {
+        let prefix = Nibbles::from_nibbles_unchecked([0x1]);
+        let keep = Nibbles::from_nibbles_unchecked([0x1, 0x2]);
+        let ranges = key_ranges(&prefix, Some(&keep));
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(*ranges[0].start(), path_key(&prefix));
+        assert_eq!(next_key(*ranges[0].end()), Some(path_key(&keep)));
+        assert_eq!(prev_key(*ranges[1].start()), Some(last_key(&keep)));
+        assert_eq!(*ranges[1].end(), last_key(&prefix));
+
+        // nothing before the first and after the last key under the prefix
+        let keep = Nibbles::from_nibbles_unchecked([0x1, 0x0]);
+        assert_eq!(key_ranges(&prefix, Some(&keep)).len(), 1);
+        assert_eq!(
+            key_ranges(&Nibbles::default(), None),
+            vec![B256::ZERO..=B256::repeat_byte(0xff)]
+        );
+    }
+
+    #[test]
+    fn trie_node_paths_group_storage() {
+        let account = B256::repeat_byte(0xaa);
+        let node = |account, path: &[u8]| HealNode {
+            account,
+            path: Nibbles::from_nibbles_unchecked(path),
+            hash: B256::ZERO,
+        };
+        let paths = trie_node_paths(&[
+            node(None, &[0x1]),
+            node(Some(account), &[]),
+            node(Some(account), &[0x2]),
+            node(None, &[0x3]),
+        ]);
+        assert_eq!(
+            paths,
+            vec![
+                vec![encode_compact_path(&Nibbles::from_nibbles_unchecked([0x1]))],
+                vec![
+                    account.into(),
+                    encode_compact_path(&Nibbles::default()),
+                    encode_compact_path(&Nibbles::from_nibbles_unchecked([0x2]))
+                ],
+                vec![encode_compact_path(&Nibbles::from_nibbles_unchecked([0x3]))],
+            ]
+        );
+    }
+
+    #[test]
+    fn storage_ranges_verified_by_root() {
+        let slots = vec![
+            (B256::repeat_byte(0x01), U256::from(1)),
+            (B256::repeat_byte(0x02), U256::from(2)),
+        ];
+        let storage_root = storage_trie_root(&slots);
+        let account = (B256::repeat_byte(0xaa), storage_root);
+        let response = |slots: &[(B256, U256)]| StorageRanges {
+            request_id: 0,
+            slots: vec![slots
+                .iter()
+                .map(|(hash, value)| StorageData {
+                    hash: *hash,
+                    data: alloy_rlp::encode(value).into(),
+                })
+                .collect()],
+            proof: Vec::new(),
+        };
+
+        let (verified, next) =
+            verify_storage_ranges(&[account], B256::ZERO, response(&slots)).unwrap().unwrap();
+        assert_eq!(verified, vec![slots.clone()]);
+        assert_eq!(next, None);
+
+        assert!(matches!(
+            verify_storage_ranges(&[account], B256::ZERO, response(&slots[..1])),
+            Err(InvalidResponse::StorageRootMismatch)
+        ));
+        assert!(matches!(
+            verify_storage_ranges(&[account], B256::ZERO, StorageRanges::default()),
+            Ok(None)
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use alloy_primitives::B256;
+use futures_util::{stream, FutureExt, StreamExt};
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_provider::{
+    BlockHashReader, DBProvider, ProviderError, StageCheckpointReader, TelosExtraFieldsWriter,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_telos_net::TelosClient;
+use reth_telos_rpc_engine_api::structs::TelosEngineAPIExtraFields;
+use std::{
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// The maximum number of blocks checked for missing extra fields in one execution of the stage.
+const MAX_BLOCKS_PER_EXECUTION: u64 = 100_000;
+
+/// The maximum number of blocks whose extra fields are requested at once.
+const MAX_BLOCKS_PER_REQUEST: usize = 128;
+
+/// The maximum number of requests in flight.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// The delay before requesting the extra fields again if no peer served any of them.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Extra fields downloaded from peers, by block hash.
+type DownloadedExtraFields = Vec<(B256, TelosEngineAPIExtraFields)>;
+
+/// The Telos extra fields stage downloads the extra fields of the blocks that aren't stored yet
+/// from peers over `telos/1`, so that the blocks can be executed.
+///
+/// The extra fields of the blocks received from the consensus client are stored when the blocks
+/// are committed, the stage only downloads the fields of blocks that were synced from peers. The
+/// fields can't be verified against the block, they're checked by the execution of the block.
+///
+/// The stage moves its checkpoint to the last block up to which all fields are stored, starting
+/// after the executed blocks. While fields are missing it returns without finishing, and downloads
+/// them before the next execution.
+///
+/// # Tables
+///
+/// This stage writes to [`tables::TelosExtraFields`].
+#[derive(Debug)]
+pub struct TelosExtraFieldsStage<C> {
+    /// The client requesting the extra fields from peers.
+    client: C,
+    /// The hashes of the blocks whose extra fields to download next.
+    missing: Vec<B256>,
+    /// The running download.
+    download: Option<JoinHandle<DownloadedExtraFields>>,
+    /// The finished download, which is written in the next execution.
+    downloaded: Option<DownloadedExtraFields>,
+}
+
+impl<C> TelosExtraFieldsStage<C> {
+    /// Creates a new stage requesting the extra fields with the given client.
+    pub const fn new(client: C) -> Self {
+        Self { client, missing: Vec::new(), download: None, downloaded: None }
+    }
+}
+
+impl<C> Drop for TelosExtraFieldsStage<C> {
+    fn drop(&mut self) {
+        if let Some(download) = self.download.take() {
+            download.abort();
+        }
+    }
+}
+
+impl<Provider, C> Stage<Provider> for TelosExtraFieldsStage<C>
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockHashReader + StageCheckpointReader,
+    C: TelosClient + Clone + 'static,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::TelosExtraFields
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() ||
+            self.downloaded.is_some() ||
+            (self.missing.is_empty() && self.download.is_none())
+        {
+            return Poll::Ready(Ok(()))
+        }
+
+        if self.download.is_none() {
+            self.download = Some(tokio::spawn(download(
+                self.client.clone(),
+                std::mem::take(&mut self.missing),
+            )));
+        }
+        let result = ready!(self.download.as_mut().expect("download is spawned").poll_unpin(cx));
+        self.download = None;
+        Poll::Ready(match result {
+            Ok(downloaded) => {
+                self.downloaded = Some(downloaded);
+                Ok(())
+            }
+            Err(err) => Err(StageError::Fatal(Box::new(err))),
+        })
+    }
+
+    /// Write the downloaded extra fields and look for the next missing ones.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        if let Some(downloaded) = self.downloaded.take() {
+            for (hash, fields) in downloaded {
+                provider.insert_telos_extra_fields(hash, &fields)?;
+            }
+        }
+
+        // blocks that were already executed don't need their fields anymore
+        let executed =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        let start = input.checkpoint().block_number.max(executed.min(input.target()));
+        let input = ExecInput { checkpoint: Some(StageCheckpoint::new(start)), ..input };
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, _) = input.next_block_range_with_threshold(MAX_BLOCKS_PER_EXECUTION);
+        let end = *range.end();
+        let mut checkpoint = input.checkpoint().block_number;
+        self.missing.clear();
+        for number in range {
+            let hash = provider
+                .block_hash(number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+            if provider.tx_ref().get::<tables::TelosExtraFields>(hash)?.is_some() {
+                if self.missing.is_empty() {
+                    checkpoint = number;
+                }
+                continue
+            }
+
+            self.missing.push(hash);
+            if self.missing.len() >= MAX_BLOCKS_PER_REQUEST * MAX_CONCURRENT_REQUESTS {
+                break
+            }
+        }
+
+        if !self.missing.is_empty() {
+            debug!(target: "sync::stages::telos_extra_fields", checkpoint, missing = self.missing.len(), "Downloading missing extra fields");
+        }
+        let done = self.missing.is_empty() && end == input.target();
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(checkpoint), done })
+    }
+
+    /// The extra fields are stored by block hash and kept on unwind.
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        self.missing.clear();
+        self.downloaded = None;
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// Downloads the extra fields of the blocks with the given hashes, as far as they are served.
+///
+/// Peers sending fields that can't be decoded are reported.
+async fn download<C: TelosClient>(client: C, hashes: Vec<B256>) -> DownloadedExtraFields {
+    let client = &client;
+    let mut downloads = stream::iter(hashes.chunks(MAX_BLOCKS_PER_REQUEST).map(<[B256]>::to_vec))
+        .map(|hashes| async move {
+            let response = match client.get_extra_fields(hashes.clone()).await {
+                Ok(response) => response,
+                Err(err) => {
+                    trace!(target: "sync::stages::telos_extra_fields", %err, "Extra fields request failed");
+                    return Vec::new()
+                }
+            };
+            let (peer_id, response) = response.split();
+            match response.decode_fields() {
+                Ok(fields) if fields.len() <= hashes.len() => hashes
+                    .into_iter()
+                    .zip(fields)
+                    .filter_map(|(hash, fields)| Some((hash, fields?)))
+                    .collect(),
+                Ok(_) => {
+                    debug!(target: "sync::stages::telos_extra_fields", %peer_id, "Unrequested extra fields");
+                    client.report_bad_message(peer_id);
+                    Vec::new()
+                }
+                Err(err) => {
+                    debug!(target: "sync::stages::telos_extra_fields", %peer_id, %err, "Invalid extra fields");
+                    client.report_bad_message(peer_id);
+                    Vec::new()
+                }
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS);
+
+    let mut downloaded = Vec::new();
+    while let Some(fields) = downloads.next().await {
+        downloaded.extend(fields);
+    }
+    if downloaded.is_empty() {
+        // don't busy loop the pipeline while no peer serves the fields
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+    downloaded
+}
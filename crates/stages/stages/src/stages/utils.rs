@@ -16,7 +16,7 @@ use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
-const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
+pub(crate) const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
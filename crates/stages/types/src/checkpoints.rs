@@ -74,6 +74,90 @@ impl Compact for MerkleCheckpoint {
     }
 }
 
+/// Saves the progress of the `SnapSync` stage.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SnapSyncCheckpoint {
+    /// The pivot block whose state is downloaded.
+    pub pivot: BlockNumber,
+    /// The state root of the pivot block.
+    pub state_root: B256,
+    /// The account hash ranges that are yet to be downloaded, as the next hash and the last hash
+    /// of each range.
+    pub account_ranges: Vec<(B256, B256)>,
+    /// The progress of building the trie of the downloaded state.
+    pub trie: Option<MerkleCheckpoint>,
+    /// Whether the trie of the downloaded state is built, so the missing and stale trie nodes
+    /// are being healed.
+    pub healing: bool,
+    /// The hash of the next account to check for missing bytecode, once the trie is healed.
+    pub code_scan: Option<B256>,
+}
+
+impl Compact for SnapSyncCheckpoint {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u64(self.pivot);
+        buf.put_slice(self.state_root.as_slice());
+        buf.put_u32(self.account_ranges.len() as u32);
+        for (next, last) in &self.account_ranges {
+            buf.put_slice(next.as_slice());
+            buf.put_slice(last.as_slice());
+        }
+        let mut len = 8 + 32 + 4 + self.account_ranges.len() * 64;
+
+        buf.put_u8(self.trie.is_some() as u8);
+        len += 1;
+        if let Some(trie) = &self.trie {
+            len += trie.to_compact(buf);
+        }
+
+        buf.put_u8(self.healing as u8);
+        len += 1;
+
+        buf.put_u8(self.code_scan.is_some() as u8);
+        len += 1;
+        if let Some(code_scan) = self.code_scan {
+            buf.put_slice(code_scan.as_slice());
+            len += 32;
+        }
+        len
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let pivot = buf.get_u64();
+
+        let state_root = B256::from_slice(&buf[..32]);
+        buf.advance(32);
+
+        let ranges_len = buf.get_u32() as usize;
+        let mut account_ranges = Vec::with_capacity(ranges_len);
+        for _ in 0..ranges_len {
+            let next = B256::from_slice(&buf[..32]);
+            let last = B256::from_slice(&buf[32..64]);
+            buf.advance(64);
+            account_ranges.push((next, last));
+        }
+
+        let trie = if buf.get_u8() != 0 {
+            let (trie, rest) = MerkleCheckpoint::from_compact(buf, 0);
+            buf = rest;
+            Some(trie)
+        } else {
+            None
+        };
+
+        let healing = buf.get_u8() != 0;
+        let code_scan = (buf.get_u8() != 0).then(|| {
+            let code_scan = B256::from_slice(&buf[..32]);
+            buf.advance(32);
+            code_scan
+        });
+        (Self { pivot, state_root, account_ranges, trie, healing, code_scan }, buf)
+    }
+}
+
 /// Saves the progress of AccountHashing stage.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Compact)]
 #[cfg_attr(test, derive(arbitrary::Arbitrary))]
@@ -406,4 +490,28 @@ mod tests {
         let (decoded, _) = MerkleCheckpoint::from_compact(&buf, encoded);
         assert_eq!(decoded, checkpoint);
     }
+
+    #[test]
+    fn snap_sync_checkpoint_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let checkpoint = SnapSyncCheckpoint {
+            pivot: rng.gen(),
+            state_root: rng.gen(),
+            account_ranges: vec![(rng.gen(), rng.gen()), (rng.gen(), rng.gen())],
+            trie: Some(MerkleCheckpoint::new(
+                rng.gen(),
+                rng.gen(),
+                Vec::new(),
+                HashBuilderState::default(),
+            )),
+            healing: true,
+            code_scan: Some(rng.gen()),
+        };
+
+        let mut buf = Vec::new();
+        let encoded = checkpoint.to_compact(&mut buf);
+        let (decoded, rest) = SnapSyncCheckpoint::from_compact(&buf, encoded);
+        assert_eq!(decoded, checkpoint);
+        assert!(rest.is_empty());
+    }
 }
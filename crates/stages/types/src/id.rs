@@ -20,6 +20,14 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing receipt logs by address and topic. Not part of [`StageId::ALL`].
+    IndexLogs,
+    /// Optional stage downloading the state at a pivot block from peers instead of executing the
+    /// blocks up to it. Not part of [`StageId::ALL`].
+    SnapSync,
+    /// Optional stage downloading the Telos extra fields of the blocks from peers before they are
+    /// executed. Not part of [`StageId::ALL`].
+    TelosExtraFields,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -75,6 +83,9 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexLogs => "IndexLogs",
+            Self::SnapSync => "SnapSync",
+            Self::TelosExtraFields => "TelosExtraFields",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -120,6 +131,9 @@ mod tests {
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
+        assert_eq!(StageId::TelosExtraFields.to_string(), "TelosExtraFields");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
@@ -72,12 +72,18 @@ pub struct StaticFileTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<RangeInclusive<BlockNumber>>,
     transactions: Option<RangeInclusive<BlockNumber>>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
     /// Returns `true` if any of the targets are [Some].
     pub const fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some()
     }
 
     // Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -87,6 +93,8 @@ impl StaticFileTargets {
             (self.headers.as_ref(), static_files.headers),
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
+            (self.account_changesets.as_ref(), static_files.account_changesets),
+            (self.storage_changesets.as_ref(), static_files.storage_changesets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -152,6 +160,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -190,15 +204,25 @@ where
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        // Changesets are copied only once the history indices pointing to them are built, since the
+        // history stages read them straight from the database.
+        let stages_checkpoints = [
+            StageId::Headers,
+            StageId::Execution,
+            StageId::Bodies,
+            StageId::IndexAccountHistory,
+            StageId::IndexStorageHistory,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            account_changesets: stages_checkpoints[3],
+            storage_changesets: stages_checkpoints[4],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -219,9 +243,11 @@ where
             headers: finalized_block_numbers.headers.and_then(|finalized_block_number| {
                 self.get_static_file_target(highest_static_files.headers, finalized_block_number)
             }),
-            // StaticFile receipts only if they're not pruned according to the user configuration
+            // StaticFile receipts only if they're not pruned according to the user configuration,
+            // and not missing before the pivot block of snap sync
             receipts: if self.prune_modes.receipts.is_none() &&
-                self.prune_modes.receipts_log_filter.is_empty()
+                self.prune_modes.receipts_log_filter.is_empty() &&
+                !self.provider.database_provider_ro()?.is_snap_synced()?
             {
                 finalized_block_numbers.receipts.and_then(|finalized_block_number| {
                     self.get_static_file_target(
@@ -238,6 +264,29 @@ where
                     finalized_block_number,
                 )
             }),
+            // StaticFile changesets only if they're not pruned according to the user configuration.
+            // Changesets moved before pruning was configured are deleted by the history segments
+            // of the pruner.
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -269,16 +318,18 @@ mod tests {
     };
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::tables;
+    use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
     use reth_provider::{
         providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
         ProviderFactory, StaticFileProviderFactory,
     };
-    use reth_prune_types::PruneModes;
+    use reth_prune_types::{PruneMode, PruneModes};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::generators::{
-        self, random_block_range, random_receipt, BlockRangeParams,
+        self, random_block_range, random_changeset_range, random_eoa_accounts, random_receipt,
+        BlockRangeParams,
     };
     use std::{sync::mpsc::channel, time::Duration};
     use tempfile::TempDir;
@@ -334,6 +385,7 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -341,13 +393,20 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -355,6 +414,7 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -362,13 +422,20 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -376,6 +443,7 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -383,7 +451,9 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(
@@ -392,7 +462,94 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn run_changesets() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+        );
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            random_eoa_accounts(&mut rng, 3).into_iter().map(|(addr, acc)| (addr, (acc, vec![]))),
+            0..3,
+            0..256,
+        );
+        db.insert_changesets(changesets, None).expect("insert changesets");
+
+        let finalized_block_numbers = HighestStaticFiles {
+            account_changesets: Some(3),
+            storage_changesets: Some(3),
+            ..Default::default()
+        };
+
+        // Changesets are pruned by the user, so they're not moved to static files
+        let static_file_producer = StaticFileProducerInner::new(
+            db.factory.clone(),
+            PruneModes {
+                account_history: Some(PruneMode::Full),
+                storage_history: Some(PruneMode::Full),
+                ..PruneModes::none()
+            },
+        );
+        let targets = static_file_producer
+            .get_static_file_targets(finalized_block_numbers)
+            .expect("get static file targets");
+        assert!(!targets.any());
+
+        let static_file_producer =
+            StaticFileProducerInner::new(db.factory.clone(), PruneModes::default());
+        let targets = static_file_producer
+            .get_static_file_targets(finalized_block_numbers)
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: Some(0..=3),
+                storage_changesets: Some(0..=3),
+            }
+        );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = db.factory.static_file_provider();
+        let highest_static_files = static_file_provider.get_highest_static_files();
+        assert_eq!(highest_static_files.account_changesets, Some(3));
+        assert_eq!(highest_static_files.storage_changesets, Some(3));
+
+        let tx = db.factory.db_ref().tx().expect("init tx");
+        assert_eq!(
+            static_file_provider.account_changesets_range(0..4).unwrap(),
+            tx.cursor_read::<tables::AccountChangeSets>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        );
+        assert_eq!(
+            static_file_provider.storage_changesets_range(0..4).unwrap(),
+            tx.cursor_read::<tables::StorageChangeSets>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
         );
     }
 
@@ -420,6 +577,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -1,20 +1,163 @@
-use strum::AsRefStr;
+use crate::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, IntoEnumIterator};
+
+/// Default maximum size of a zstd dictionary trained for a static file column.
+pub const DEFAULT_DICTIONARY_MAX_SIZE: usize = 100_000;
+
+/// Default maximum number of rows sampled to train a zstd dictionary for a static file column.
+pub const DEFAULT_DICTIONARY_SAMPLES: usize = 100_000;
 
 /// Static File compression types.
-#[derive(Debug, Copy, Clone, Default, AsRefStr)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum Compression {
     /// LZ4 compression algorithm.
     #[strum(serialize = "lz4")]
+    #[serde(rename = "lz4")]
     Lz4,
     /// Zstandard (Zstd) compression algorithm.
     #[strum(serialize = "zstd")]
+    #[serde(rename = "zstd")]
     Zstd,
     /// Zstandard (Zstd) compression algorithm with a dictionary.
     #[strum(serialize = "zstd-dict")]
+    #[serde(rename = "zstd-dict")]
+    #[cfg_attr(feature = "clap", value(name = "zstd-dict"))]
     ZstdWithDictionary,
     /// No compression.
     #[strum(serialize = "uncompressed")]
+    #[serde(rename = "uncompressed")]
     #[default]
     Uncompressed,
 }
+
+/// Compression of each column of newly created static files, per segment. Existing static files
+/// keep the compression they were created with.
+///
+/// Columns compressed with [`Compression::ZstdWithDictionary`] get a dictionary trained on samples
+/// of the previous static file of the segment. The first static file of a segment has nothing to
+/// sample from, so it uses [`Compression::Zstd`] instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionConfig {
+    /// Compression of the header, total difficulty and hash columns of
+    /// [`StaticFileSegment::Headers`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<Compression>>,
+    /// Compression of the transaction column of [`StaticFileSegment::Transactions`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<Compression>>,
+    /// Compression of the receipt column of [`StaticFileSegment::Receipts`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<Compression>>,
+    /// Compression of the changeset column of [`StaticFileSegment::AccountChangeSets`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_changesets: Option<Vec<Compression>>,
+    /// Compression of the changeset column of [`StaticFileSegment::StorageChangeSets`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_changesets: Option<Vec<Compression>>,
+    /// Maximum size of a trained zstd dictionary in bytes.
+    pub dictionary_max_size: usize,
+    /// Maximum number of rows of the previous static file sampled to train a zstd dictionary.
+    pub dictionary_samples: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            headers: None,
+            transactions: None,
+            receipts: None,
+            account_changesets: None,
+            storage_changesets: None,
+            dictionary_max_size: DEFAULT_DICTIONARY_MAX_SIZE,
+            dictionary_samples: DEFAULT_DICTIONARY_SAMPLES,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Returns the compression of each column of the segment, falling back to
+    /// [`StaticFileSegment::default_compression`] if it's not configured.
+    pub fn columns(&self, segment: StaticFileSegment) -> Vec<Compression> {
+        self.configured(segment).cloned().unwrap_or_else(|| segment.default_compression())
+    }
+
+    /// Sets the compression of each column of the segment.
+    pub fn set_columns(&mut self, segment: StaticFileSegment, columns: Vec<Compression>) {
+        *self.configured_mut(segment) = Some(columns);
+    }
+
+    /// Returns an error if the number of configured columns of any segment doesn't match the
+    /// number of columns of the segment.
+    pub fn validate(&self) -> Result<(), String> {
+        for segment in StaticFileSegment::iter() {
+            if let Some(columns) = self.configured(segment) {
+                if columns.len() != segment.columns() {
+                    return Err(format!(
+                        "static file segment {segment} has {} columns, but compression of {} \
+                         columns is configured",
+                        segment.columns(),
+                        columns.len()
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    const fn configured(&self, segment: StaticFileSegment) -> Option<&Vec<Compression>> {
+        match segment {
+            StaticFileSegment::Headers => self.headers.as_ref(),
+            StaticFileSegment::Transactions => self.transactions.as_ref(),
+            StaticFileSegment::Receipts => self.receipts.as_ref(),
+            StaticFileSegment::AccountChangeSets => self.account_changesets.as_ref(),
+            StaticFileSegment::StorageChangeSets => self.storage_changesets.as_ref(),
+        }
+    }
+
+    fn configured_mut(&mut self, segment: StaticFileSegment) -> &mut Option<Vec<Compression>> {
+        match segment {
+            StaticFileSegment::Headers => &mut self.headers,
+            StaticFileSegment::Transactions => &mut self.transactions,
+            StaticFileSegment::Receipts => &mut self.receipts,
+            StaticFileSegment::AccountChangeSets => &mut self.account_changesets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_changesets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let mut config = CompressionConfig::default();
+        assert_eq!(
+            config.columns(StaticFileSegment::Headers),
+            StaticFileSegment::Headers.default_compression()
+        );
+        assert!(config.validate().is_ok());
+
+        config.set_columns(
+            StaticFileSegment::Headers,
+            vec![Compression::Lz4, Compression::Lz4, Compression::Uncompressed],
+        );
+        config.set_columns(StaticFileSegment::Transactions, vec![Compression::ZstdWithDictionary]);
+        assert_eq!(
+            config.columns(StaticFileSegment::Headers),
+            vec![Compression::Lz4, Compression::Lz4, Compression::Uncompressed]
+        );
+        assert_eq!(
+            config.columns(StaticFileSegment::Transactions),
+            vec![Compression::ZstdWithDictionary]
+        );
+        assert!(config.validate().is_ok());
+
+        config.set_columns(StaticFileSegment::Receipts, vec![Compression::Lz4, Compression::Lz4]);
+        assert!(config.validate().is_err());
+    }
+}
@@ -34,6 +34,16 @@ pub enum StaticFileSegment {
     #[strum(serialize = "receipts")]
     /// Static File segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "account-changesets"))]
+    /// Static File segment responsible for the `AccountChangeSets` table. Each row holds all
+    /// account changes of a block.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "storage-changesets"))]
+    /// Static File segment responsible for the `StorageChangeSets` table. Each row holds all
+    /// storage changes of a block.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -43,6 +53,8 @@ impl StaticFileSegment {
             Self::Headers => "headers",
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
+            Self::AccountChangeSets => "account-changesets",
+            Self::StorageChangeSets => "storage-changesets",
         }
     }
 
@@ -55,10 +67,23 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
         }
     }
 
+    /// Returns the compression of each column used by default.
+    ///
+    /// Block based segments are compressed with [`Compression::Lz4`], while transactions and
+    /// receipts already use a compact encoding with zstd dictionaries natively.
+    pub fn default_compression(&self) -> Vec<Compression> {
+        let compression =
+            if self.is_block_based() { Compression::Lz4 } else { Compression::Uncompressed };
+        vec![compression; self.columns()]
+    }
+
     /// Returns the default file name for the provided segment and range.
     pub fn filename(&self, block_range: &SegmentRangeInclusive) -> String {
         // ATTENTION: if changing the name format, be sure to reflect those changes in
@@ -118,6 +143,18 @@ impl StaticFileSegment {
         matches!(self, Self::Headers)
     }
 
+    /// Returns `true` if the segment has one row per block: `StaticFileSegment::Headers`,
+    /// `StaticFileSegment::AccountChangeSets` or `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_block_based(&self) -> bool {
+        matches!(self, Self::Headers | Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_changesets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if the segment is `StaticFileSegment::Receipts`.
     pub const fn is_receipts(&self) -> bool {
         matches!(self, Self::Receipts)
@@ -228,38 +265,25 @@ impl SegmentHeader {
 
     /// Increments tx end range depending on segment
     pub fn increment_tx(&mut self) {
-        match self.segment {
-            StaticFileSegment::Headers => (),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                if let Some(tx_range) = &mut self.tx_range {
-                    tx_range.end += 1;
-                } else {
-                    self.tx_range = Some(SegmentRangeInclusive::new(0, 0));
-                }
+        if self.segment.is_tx_based() {
+            if let Some(tx_range) = &mut self.tx_range {
+                tx_range.end += 1;
+            } else {
+                self.tx_range = Some(SegmentRangeInclusive::new(0, 0));
             }
         }
     }
 
     /// Removes `num` elements from end of tx or block range.
     pub fn prune(&mut self, num: u64) {
-        match self.segment {
-            StaticFileSegment::Headers => {
-                if let Some(range) = &mut self.block_range {
-                    if num > range.end - range.start {
-                        self.block_range = None;
-                    } else {
-                        range.end = range.end.saturating_sub(num);
-                    }
-                };
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                if let Some(range) = &mut self.tx_range {
-                    if num > range.end - range.start {
-                        self.tx_range = None;
-                    } else {
-                        range.end = range.end.saturating_sub(num);
-                    }
-                };
+        let range =
+            if self.segment.is_block_based() { &mut self.block_range } else { &mut self.tx_range };
+
+        if let Some(inner) = range {
+            if num > inner.end - inner.start {
+                *range = None;
+            } else {
+                inner.end = inner.end.saturating_sub(num);
             }
         };
     }
@@ -286,9 +310,10 @@ impl SegmentHeader {
 
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> Option<u64> {
-        match self.segment {
-            StaticFileSegment::Headers => self.block_start(),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => self.tx_start(),
+        if self.segment.is_block_based() {
+            self.block_start()
+        } else {
+            self.tx_start()
         }
     }
 }
@@ -359,6 +384,18 @@ mod tests {
         let test_vectors = [
             (StaticFileSegment::Headers, 2..=30, "static_file_headers_2_30", None),
             (StaticFileSegment::Receipts, 30..=300, "static_file_receipts_30_300", None),
+            (
+                StaticFileSegment::AccountChangeSets,
+                0..=499_999,
+                "static_file_account-changesets_0_499999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                500_000..=999_999,
+                "static_file_storage-changesets_500000_999999_none_lz4",
+                Some(Compression::Lz4),
+            ),
             (
                 StaticFileSegment::Transactions,
                 1_123_233..=11_223_233,
@@ -3,7 +3,7 @@ use crate::{
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, path::Path, sync::Arc};
 
 /// Main Database trait that can open read-only and read-write transactions.
 ///
@@ -49,6 +49,14 @@ pub trait Database: Send + Sync + Debug {
 
         Ok(res)
     }
+
+    /// Writes a copy of the database to `path`, which must not exist yet.
+    ///
+    /// The copy is consistent with the last committed read-write transaction and can be taken
+    /// while the database is in use. If `compact` is set, free pages are omitted from the copy.
+    fn copy_to(&self, _path: &Path, _compact: bool) -> Result<(), DatabaseError> {
+        Err(DatabaseError::Other("database does not support copies".to_string()))
+    }
 }
 
 impl<DB: Database> Database for Arc<DB> {
@@ -62,6 +70,10 @@ impl<DB: Database> Database for Arc<DB> {
     fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_to(self, path, compact)
+    }
 }
 
 impl<DB: Database> Database for &DB {
@@ -75,4 +87,8 @@ impl<DB: Database> Database for &DB {
     fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_to(self, path, compact)
+    }
 }
@@ -24,7 +24,10 @@ pub mod storage_sharded_key;
 pub use accounts::*;
 pub use blocks::*;
 pub use reth_db_models::{
-    AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
+    AccountBeforeTx, BlockAccountChangeSet, BlockStorageChangeSet, ClientVersion, StorageBeforeTx,
+    StoredBlockBodyIndices, StoredBlockWithdrawals, StoredTelosAccountRow,
+    StoredTelosAccountStateRow, StoredTelosExtraFields, StoredTelosRevisionChange,
+    StoredTelosTxValue,
 };
 pub use sharded_key::ShardedKey;
 
@@ -225,12 +228,15 @@ impl_compression_for_compact!(
     StoredBlockWithdrawals,
     Bytecode,
     AccountBeforeTx,
+    BlockAccountChangeSet,
+    BlockStorageChangeSet,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
     Requests,
+    StoredTelosExtraFields,
     // Non-DB
     GenesisAccount
 );
@@ -342,6 +348,11 @@ mod tests {
         assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
+        assert_eq!(StoredTelosAccountRow::bitflag_encoded_bytes(), 2);
+        assert_eq!(StoredTelosAccountStateRow::bitflag_encoded_bytes(), 2);
+        assert_eq!(StoredTelosExtraFields::bitflag_encoded_bytes(), 1);
+        assert_eq!(StoredTelosRevisionChange::bitflag_encoded_bytes(), 1);
+        assert_eq!(StoredTelosTxValue::bitflag_encoded_bytes(), 2);
         assert_eq!(Withdrawals::bitflag_encoded_bytes(), 0);
 
         validate_bitflag_backwards_compat!(Account, UnusedBits::NotZero);
@@ -365,6 +376,11 @@ mod tests {
         validate_bitflag_backwards_compat!(StoredBlockOmmers, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockWithdrawals, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StorageHashingCheckpoint, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(StoredTelosAccountRow, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(StoredTelosAccountStateRow, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(StoredTelosExtraFields, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(StoredTelosRevisionChange, UnusedBits::Zero);
+        validate_bitflag_backwards_compat!(StoredTelosTxValue, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(Withdrawals, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(Requests, UnusedBits::Zero);
     }
@@ -1,11 +1,19 @@
 //! Reth genesis initialization utility functions.
 
 use alloy_genesis::GenesisAccount;
+#[cfg(feature = "telos")]
+use alloy_primitives::Bytes;
 use alloy_primitives::{Address, B256, U256};
 use reth_chainspec::EthChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
+#[cfg(feature = "telos")]
+use reth_db_api::{
+    models::AddressStorageKey,
+    table::{Decode, Decompress},
+    transaction::DbTx,
+};
 use reth_db_api::{transaction::DbTxMut, DatabaseError};
 use reth_etl::Collector;
 use reth_primitives::{Account, Bytecode, GotExpected, Receipts, StaticFileSegment, StorageEntry};
@@ -19,9 +27,17 @@ use reth_provider::{
     StateWriter, StaticFileProviderFactory, TrieWriter,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
+#[cfg(feature = "telos")]
+use reth_telos_primitives_traits::TelosBlockExtension;
+#[cfg(feature = "telos")]
+use reth_telos_rpc_engine_api::structs::{
+    TelosAccountCodeTableRow, TelosAccountStateTableRow, TelosAccountTableRow,
+};
 use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
 use reth_trie_db::DatabaseStateRoot;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "telos")]
+use std::collections::BTreeMap;
 use std::{collections::HashMap, io::BufRead};
 use tracing::{debug, error, info, trace};
 
@@ -570,6 +586,246 @@ where
     }
 }
 
+/// Reads an `eosio.evm` table snapshot and initializes it as state of the base block, which is the
+/// highest block that can be found on database.
+///
+/// `accounts`, `account_states` and `account_codes` are JSONL dumps of the `account`,
+/// `accountstate` and `accountcode` tables, one [`TelosAccountTableRow`],
+/// [`TelosAccountStateTableRow`] and [`TelosAccountCodeTableRow`] per line, as produced by the
+/// nodeos snapshot and SHiP tooling. Rows marked as removed are skipped. Older contract versions
+/// inline the code in the account rows and have no `accountcode` table.
+///
+/// Unlike [`init_from_state_dump`], the base block header doesn't commit to the snapshot state, its
+/// hash is fixed by the native chain. The header is rewritten with the computed state root and the
+/// given [`TelosBlockExtension`], so the computed state root must match `expected_state_root`.
+#[cfg(feature = "telos")]
+pub fn init_from_telos_snapshot<Provider>(
+    accounts: impl BufRead,
+    account_states: impl BufRead,
+    account_codes: Option<impl BufRead>,
+    block_extension: TelosBlockExtension,
+    expected_state_root: B256,
+    provider_rw: &Provider,
+    etl_config: EtlConfig,
+) -> eyre::Result<B256>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockNumReader
+        + BlockHashReader
+        + ChainSpecProvider
+        + StaticFileProviderFactory
+        + StageCheckpointWriter
+        + HistoryWriter
+        + HeaderProvider
+        + HashingWriter
+        + StateChangeWriter
+        + TrieWriter
+        + AsRef<Provider>,
+{
+    let block = provider_rw.last_block_number()?;
+    let hash = provider_rw.block_hash(block)?.unwrap();
+    let mut header = provider_rw
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+    let total_difficulty = provider_rw
+        .header_td_by_number(block)?
+        .ok_or_else(|| ProviderError::TotalDifficultyNotFound(block))?;
+
+    if provider_rw.tx_ref().entries::<tables::PlainAccountState>()? > 0 {
+        eyre::bail!("database already contains state, a telos snapshot can only be imported once")
+    }
+
+    debug!(target: "reth::cli",
+        block,
+        chain=%provider_rw.chain_spec().chain(),
+        "Initializing telos snapshot at block"
+    );
+
+    let codes = account_codes.map(parse_telos_account_codes).transpose()?.unwrap_or_default();
+    let storage = parse_telos_account_states(account_states, etl_config.clone())?;
+    let collector = parse_telos_accounts(accounts, &codes, storage, etl_config)?;
+
+    // write state to db
+    dump_state(collector, provider_rw, block)?;
+
+    // compute state root. this advances the stage checkpoints.
+    let computed_state_root = compute_state_root(provider_rw)?;
+    if computed_state_root != expected_state_root {
+        error!(target: "reth::cli",
+            ?computed_state_root,
+            ?expected_state_root,
+            "Computed state root does not match expected state root of telos snapshot"
+        );
+
+        return Err(InitDatabaseError::StateRootMismatch(GotExpected {
+            got: computed_state_root,
+            expected: expected_state_root,
+        })
+        .into())
+    }
+
+    info!(target: "reth::cli",
+        ?computed_state_root,
+        ?block_extension,
+        "Writing base block header"
+    );
+
+    // the base block is the only header in static files, replace it
+    header.state_root = computed_state_root;
+    header.telos_block_extension = block_extension;
+    let static_file_provider = provider_rw.static_file_provider();
+    let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+    writer.prune_headers(1)?;
+    writer.commit()?;
+    writer.append_header(&header, total_difficulty, &hash)?;
+
+    // insert sync stages for stages that require state
+    for stage in StageId::STATE_REQUIRED {
+        provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(block))?;
+    }
+
+    Ok(hash)
+}
+
+/// Parses `accountcode` table rows into a map of the code by row index.
+#[cfg(feature = "telos")]
+fn parse_telos_account_codes(mut reader: impl BufRead) -> eyre::Result<HashMap<u64, Bytes>> {
+    let mut line = String::new();
+    let mut codes = HashMap::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        let row: TelosAccountCodeTableRow = serde_json::from_str(&line)?;
+        line.clear();
+
+        if row.removed {
+            continue
+        }
+        codes.insert(row.index, row.code);
+    }
+
+    trace!(target: "reth::cli",
+        parsed_codes=codes.len(),
+        "Parsed telos account codes"
+    );
+
+    Ok(codes)
+}
+
+/// Parses `accountstate` table rows and pushes the storage slots to a [`Collector`], sorted by
+/// account address.
+#[cfg(feature = "telos")]
+fn parse_telos_account_states(
+    mut reader: impl BufRead,
+    etl_config: EtlConfig,
+) -> eyre::Result<Collector<AddressStorageKey, StorageEntry>> {
+    let mut line = String::new();
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+
+    while reader.read_line(&mut line)? > 0 {
+        let row: TelosAccountStateTableRow = serde_json::from_str(&line)?;
+        line.clear();
+
+        if row.removed || row.value.is_zero() {
+            continue
+        }
+
+        let key = B256::from(row.key);
+        collector.insert(
+            AddressStorageKey((row.address, key)),
+            StorageEntry { key, value: row.value },
+        )?;
+    }
+
+    trace!(target: "reth::cli",
+        parsed_storage_slots=collector.len(),
+        "Parsed telos account states"
+    );
+
+    Ok(collector)
+}
+
+/// Parses `account` table rows, resolves their code in `codes` if it's not inlined, merges them
+/// with the storage slots collected by [`parse_telos_account_states`] and pushes them to a
+/// [`Collector`].
+#[cfg(feature = "telos")]
+fn parse_telos_accounts(
+    mut reader: impl BufRead,
+    codes: &HashMap<u64, Bytes>,
+    mut storage: Collector<AddressStorageKey, StorageEntry>,
+    etl_config: EtlConfig,
+) -> eyre::Result<Collector<Address, GenesisAccount>> {
+    let mut line = String::new();
+    let mut accounts = Collector::new(etl_config.file_size, etl_config.dir.clone());
+
+    while reader.read_line(&mut line)? > 0 {
+        let row: TelosAccountTableRow = serde_json::from_str(&line)?;
+        line.clear();
+
+        if row.removed {
+            continue
+        }
+
+        let code = match row.code_id {
+            Some(code_id) if row.code.is_empty() => Some(
+                codes
+                    .get(&code_id)
+                    .cloned()
+                    .ok_or_else(|| eyre::eyre!("code {code_id} of {} not found", row.address))?,
+            ),
+            _ => Some(row.code),
+        };
+        let account = GenesisAccount {
+            nonce: Some(row.nonce),
+            balance: row.balance,
+            code: code.filter(|code| !code.is_empty()),
+            ..Default::default()
+        };
+        accounts.insert(row.address, account)?;
+    }
+
+    // both collectors are sorted by address, so storage slots can be assigned in one pass
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+    let mut storage = storage.iter()?.peekable();
+
+    for entry in accounts.iter()? {
+        let (address, account) = entry?;
+        let (address, _) = Address::from_compact(address.as_slice(), address.len());
+        let (mut account, _) = GenesisAccount::from_compact(account.as_slice(), account.len());
+
+        let mut slots = BTreeMap::new();
+        while let Some(entry) = storage.next_if(|entry| {
+            entry.as_ref().map_or(true, |(key, _)| key[..20] <= *address.as_slice())
+        }) {
+            let (key, value) = entry?;
+            let AddressStorageKey((slot_address, _)) = AddressStorageKey::decode(&key)?;
+            if slot_address != address {
+                eyre::bail!("account state of {slot_address} has no matching account row")
+            }
+            let StorageEntry { key, value } = StorageEntry::decompress(&value)?;
+            slots.insert(key, B256::from(value));
+        }
+        if !slots.is_empty() {
+            account.storage = Some(slots);
+        }
+
+        collector.insert(address, account)?;
+
+        if collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0 {
+            info!(target: "reth::cli",
+                parsed_new_accounts=collector.len(),
+            );
+        }
+    }
+
+    if let Some(entry) = storage.next() {
+        let (key, _) = entry?;
+        let AddressStorageKey((slot_address, _)) = AddressStorageKey::decode(&key)?;
+        eyre::bail!("account state of {slot_address} has no matching account row")
+    }
+
+    Ok(collector)
+}
+
 /// Type to deserialize state root from state dump file.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct StateRoot {
@@ -771,4 +1027,88 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    #[cfg(feature = "telos")]
+    fn parse_telos_snapshot() {
+        let accounts = r#"{"removed":false,"address":"0x0000000000000000000000000000000000000002","account":"bob","nonce":1,"code":"0x6000","balance":"0x10"}
+{"removed":true,"address":"0x0000000000000000000000000000000000000003","account":"carol","nonce":0,"code":"0x","balance":"0x0"}
+{"removed":false,"address":"0x0000000000000000000000000000000000000001","account":"alice","nonce":0,"code":"0x","balance":"0x1"}
+{"removed":false,"address":"0x0000000000000000000000000000000000000004","account":"dave","nonce":1,"code":"0x","code_id":7,"balance":"0x0"}
+"#;
+        let account_codes = r#"{"removed":false,"index":7,"code":"0x6001"}
+{"removed":true,"index":8,"code":"0x6002"}
+"#;
+        let account_states = r#"{"removed":false,"address":"0x0000000000000000000000000000000000000002","key":"0x2","value":"0x3"}
+{"removed":false,"address":"0x0000000000000000000000000000000000000002","key":"0x1","value":"0x0"}
+"#;
+
+        let codes = parse_telos_account_codes(account_codes.as_bytes()).unwrap();
+        assert_eq!(codes, HashMap::from([(7, Bytes::from_static(&[0x60, 0x01]))]));
+        let storage =
+            parse_telos_account_states(account_states.as_bytes(), EtlConfig::default()).unwrap();
+        let mut collector =
+            parse_telos_accounts(accounts.as_bytes(), &codes, storage, EtlConfig::default())
+                .unwrap();
+        let parsed = collector
+            .iter()
+            .unwrap()
+            .map(|entry| {
+                let (address, account) = entry.unwrap();
+                (
+                    Address::from_compact(address.as_slice(), address.len()).0,
+                    GenesisAccount::from_compact(account.as_slice(), account.len()).0,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    Address::with_last_byte(1),
+                    GenesisAccount { nonce: Some(0), balance: U256::from(1), ..Default::default() }
+                ),
+                (
+                    Address::with_last_byte(2),
+                    GenesisAccount {
+                        nonce: Some(1),
+                        balance: U256::from(16),
+                        code: Some(Bytes::from_static(&[0x60, 0x00])),
+                        storage: Some(BTreeMap::from([(
+                            B256::with_last_byte(2),
+                            B256::with_last_byte(3)
+                        )])),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    Address::with_last_byte(4),
+                    GenesisAccount {
+                        nonce: Some(1),
+                        code: Some(Bytes::from_static(&[0x60, 0x01])),
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+
+        // code of an account that's missing from the account codes
+        let storage = parse_telos_account_states(&b""[..], EtlConfig::default()).unwrap();
+        assert!(parse_telos_accounts(
+            accounts.as_bytes(),
+            &HashMap::new(),
+            storage,
+            EtlConfig::default()
+        )
+        .is_err());
+
+        // account state of the removed account
+        let account_states = r#"{"removed":false,"address":"0x0000000000000000000000000000000000000003","key":"0x1","value":"0x1"}
+"#;
+        let storage =
+            parse_telos_account_states(account_states.as_bytes(), EtlConfig::default()).unwrap();
+        assert!(parse_telos_accounts(accounts.as_bytes(), &codes, storage, EtlConfig::default())
+            .is_err());
+    }
 }
@@ -10,5 +10,9 @@
 
 pub mod init;
 
+pub mod verify;
+
+pub mod snapshot;
+
 mod db_tool;
 pub use db_tool::*;
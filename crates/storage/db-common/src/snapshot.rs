@@ -0,0 +1,529 @@
+//! Consistent point-in-time snapshots of the database and static files.
+//!
+//! A snapshot directory contains a copy of the MDBX database in `db/`, the static files in
+//! `static_files/` and a [`SnapshotManifest`] with the chain tip and the checksums of all files.
+//! The manifest is written last, so a directory without one is an incomplete snapshot.
+//!
+//! Static files of finalized blocks can't be modified anymore and are hard-linked into the
+//! snapshot, which makes taking one cheap even for large static file directories.
+
+use alloy_primitives::{BlockHash, BlockNumber, B256};
+use eyre::{eyre, WrapErr};
+use reth_chainspec::EthChainSpec;
+use reth_db::{
+    mdbx::DatabaseArguments,
+    open_db_read_only, tables,
+    version::{create_db_version_file, DB_VERSION},
+};
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, models::ClientVersion, transaction::DbTx,
+};
+use reth_fs_util::{self as fs, FsPathError};
+use reth_primitives::StaticFileSegment;
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockHashReader, ChainSpecProvider, FinalizedBlockReader,
+    ProviderFactory, StaticFileProviderFactory,
+};
+use reth_stages_types::StageId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    io::Read,
+    path::{Component, Path, PathBuf},
+    time::Instant,
+};
+use tracing::info;
+
+use crate::verify::unix_timestamp;
+
+/// Version of the [`SnapshotManifest`] format.
+pub const SNAPSHOT_VERSION: u64 = 1;
+
+/// Name of the manifest file in a snapshot directory.
+pub const SNAPSHOT_MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Directory of the database in a snapshot directory.
+const DB_DIR: &str = "db";
+
+/// Directory of the static files in a snapshot directory.
+const STATIC_FILES_DIR: &str = "static_files";
+
+/// Name of the MDBX data file.
+const MDBX_DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the MDBX lock file.
+const MDBX_LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Size of the buffer used to compute checksums.
+const CHECKSUM_BUFFER_SIZE: usize = 1 << 20;
+
+/// Configuration of [`create_snapshot`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnapshotConfig {
+    /// Omit free pages from the database copy, which makes it smaller but slower to take.
+    pub compact: bool,
+    /// Copy all static files instead of hard-linking the ones of finalized blocks.
+    ///
+    /// Hard links share their contents with the live static files. Static files that still
+    /// contain non-finalized blocks can be truncated in place by an unwind, so they're always
+    /// copied.
+    pub copy_static_files: bool,
+    /// Briefly block database and static file writes to pin the static files, so that they can
+    /// be copied while the node keeps running. Requires write access and is needed to snapshot a
+    /// running node.
+    pub pause_persistence: bool,
+}
+
+/// Configuration of [`restore_snapshot`].
+#[derive(Debug, Clone, Copy)]
+pub struct RestoreConfig {
+    /// Verify the sizes and checksums of all files before restoring them.
+    pub verify: bool,
+    /// Move the files out of the snapshot instead of copying them.
+    pub move_files: bool,
+}
+
+impl Default for RestoreConfig {
+    fn default() -> Self {
+        Self { verify: true, move_files: false }
+    }
+}
+
+/// Describes the contents of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Version of the manifest format, see [`SNAPSHOT_VERSION`].
+    pub version: u64,
+    /// Version of the database schema, see [`DB_VERSION`].
+    pub db_version: u64,
+    /// Last client version that opened the database with write access.
+    pub client_version: Option<ClientVersion>,
+    /// Chain ID of the snapshot.
+    pub chain_id: u64,
+    /// Number of the highest fully synced block, i.e. the checkpoint of the `Finish` stage.
+    pub tip_number: BlockNumber,
+    /// Hash of the highest fully synced block.
+    pub tip_hash: BlockHash,
+    /// Unix timestamp at which the snapshot was taken.
+    pub created_at: u64,
+    /// All files of the snapshot.
+    pub files: Vec<SnapshotFile>,
+}
+
+impl SnapshotManifest {
+    /// Loads the manifest of the snapshot in `dir`.
+    pub fn load(dir: &Path) -> eyre::Result<Self> {
+        Ok(fs::read_json_file(&dir.join(SNAPSHOT_MANIFEST_FILE_NAME))?)
+    }
+
+    /// Returns the total size of all files of the snapshot in bytes.
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// A file of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path of the file, relative to the snapshot directory.
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// SHA-256 checksum of the file contents.
+    pub sha256: B256,
+}
+
+/// Takes a snapshot of the database and static files of `provider_factory` and writes it to
+/// `dest`, which must not exist or be empty.
+///
+/// The database is copied first, in a read transaction of its own. The static files are written
+/// before the database, so once they're pinned afterwards they contain all blocks of the copy, and
+/// the blocks they're ahead by are healed on startup. The snapshot fails if static files were
+/// truncated while they were copied.
+///
+/// Without [`SnapshotConfig::pause_persistence`], the database and static files must not be
+/// written to concurrently, e.g. because the node is stopped.
+pub fn create_snapshot<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    dest: &Path,
+    config: SnapshotConfig,
+) -> eyre::Result<SnapshotManifest> {
+    ensure_empty_dir(dest)?;
+    let db_dir = dest.join(DB_DIR);
+    let static_files_dir = dest.join(STATIC_FILES_DIR);
+    fs::create_dir_all(&db_dir)?;
+    fs::create_dir_all(&static_files_dir)?;
+
+    info!(target: "reth::db::snapshot", dest = %dest.display(), ?config, "Taking snapshot");
+    let start = Instant::now();
+
+    copy_database(provider_factory.db_ref(), &db_dir, config.compact)?;
+    let (tip_number, client_version) = read_tip(&db_dir)?;
+
+    // Finalized blocks stay finalized, so reading it before the pause is conservative.
+    let finalized_block = provider_factory.provider()?.last_finalized_block_number()?;
+    let static_file_provider = provider_factory.static_file_provider();
+    let pinned = {
+        // Opening the read-write transaction waits for the persistence to finish the block it is
+        // writing, and blocks it until the static files are pinned. The transaction is aborted
+        // on drop.
+        let _provider_rw =
+            config.pause_persistence.then(|| provider_factory.provider_rw()).transpose()?;
+        let _writers = config.pause_persistence.then(|| static_file_provider.lock_writers());
+        pin_static_files(
+            static_file_provider.directory(),
+            &static_files_dir,
+            (!config.copy_static_files).then_some(finalized_block).flatten(),
+            static_file_provider.truncations(),
+        )?
+    };
+    pinned.copy()?;
+    let tip_hash = provider_factory
+        .block_hash(tip_number)?
+        .ok_or_else(|| eyre!("Missing hash of tip block {tip_number}"))?;
+    eyre::ensure!(
+        static_file_provider.truncations() == pinned.truncations,
+        "Static files were unwound while the snapshot was taken"
+    );
+    info!(
+        target: "reth::db::snapshot",
+        tip_number,
+        elapsed = ?start.elapsed(),
+        "Copied database and static files, computing checksums"
+    );
+
+    let manifest = SnapshotManifest {
+        version: SNAPSHOT_VERSION,
+        db_version: DB_VERSION,
+        client_version,
+        chain_id: provider_factory.chain_spec().chain().id(),
+        tip_number,
+        tip_hash,
+        created_at: unix_timestamp(),
+        files: list_files(dest)?,
+    };
+    fs::write_json_file(&dest.join(SNAPSHOT_MANIFEST_FILE_NAME), &manifest)?;
+
+    info!(
+        target: "reth::db::snapshot",
+        dest = %dest.display(),
+        tip_number,
+        %tip_hash,
+        size = manifest.size(),
+        elapsed = ?start.elapsed(),
+        "Snapshot taken"
+    );
+    Ok(manifest)
+}
+
+/// Restores the snapshot in `snapshot` into the database directory `db_path` and the static
+/// files directory `static_files_path`, which must not exist or be empty.
+///
+/// Fails if the snapshot was taken on a chain other than `chain_id` or with a different
+/// database version.
+pub fn restore_snapshot(
+    snapshot: &Path,
+    chain_id: u64,
+    db_path: &Path,
+    static_files_path: &Path,
+    config: RestoreConfig,
+) -> eyre::Result<SnapshotManifest> {
+    let manifest = SnapshotManifest::load(snapshot)?;
+    eyre::ensure!(
+        manifest.version == SNAPSHOT_VERSION,
+        "Unsupported snapshot version {}, expected {SNAPSHOT_VERSION}",
+        manifest.version
+    );
+    eyre::ensure!(
+        manifest.db_version == DB_VERSION,
+        "Snapshot has database version {}, expected {DB_VERSION}",
+        manifest.db_version
+    );
+    eyre::ensure!(
+        manifest.chain_id == chain_id,
+        "Snapshot was taken on chain {}, expected {chain_id}",
+        manifest.chain_id
+    );
+    ensure_empty_dir(db_path)?;
+    ensure_empty_dir(static_files_path)?;
+
+    info!(
+        target: "reth::db::snapshot",
+        snapshot = %snapshot.display(),
+        tip_number = manifest.tip_number,
+        tip_hash = %manifest.tip_hash,
+        ?config,
+        "Restoring snapshot"
+    );
+    let start = Instant::now();
+
+    let mut targets = Vec::with_capacity(manifest.files.len());
+    for file in &manifest.files {
+        let target_dir = match file.path.components().next() {
+            Some(Component::Normal(dir)) if dir == DB_DIR => db_path,
+            Some(Component::Normal(dir)) if dir == STATIC_FILES_DIR => static_files_path,
+            _ => return Err(eyre!("Unexpected file {} in snapshot", file.path.display())),
+        };
+        let (Some(file_name), 2) = (file.path.file_name(), file.path.components().count()) else {
+            return Err(eyre!("Unexpected file {} in snapshot", file.path.display()))
+        };
+
+        let source = snapshot.join(&file.path);
+        if config.verify {
+            let size = fs::metadata(&source)?.len();
+            eyre::ensure!(
+                size == file.size,
+                "Size mismatch of {}: expected {}, got {size}",
+                file.path.display(),
+                file.size
+            );
+            let sha256 = sha256_file(&source)?;
+            eyre::ensure!(
+                sha256 == file.sha256,
+                "Checksum mismatch of {}: expected {}, got {sha256}",
+                file.path.display(),
+                file.sha256
+            );
+        }
+        targets.push((source, target_dir.join(file_name)));
+    }
+
+    fs::create_dir_all(db_path)?;
+    fs::create_dir_all(static_files_path)?;
+    for (source, target) in targets {
+        if config.move_files {
+            fs::rename(&source, &target)?;
+        } else {
+            copy_file(&source, &target)?;
+        }
+    }
+
+    info!(
+        target: "reth::db::snapshot",
+        db = %db_path.display(),
+        static_files = %static_files_path.display(),
+        elapsed = ?start.elapsed(),
+        "Snapshot restored"
+    );
+    Ok(manifest)
+}
+
+/// Returns the number of the highest fully synced block and the last client version that wrote
+/// to the database copy in `db_dir`.
+fn read_tip(db_dir: &Path) -> eyre::Result<(BlockNumber, Option<ClientVersion>)> {
+    let tip = {
+        let db = open_db_read_only(db_dir, DatabaseArguments::default())?;
+        let tx = db.tx()?;
+        let tip_number = tx
+            .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        let client_version =
+            tx.cursor_read::<tables::VersionHistory>()?.last()?.map(|(_, version)| version);
+        (tip_number, client_version)
+    };
+    // opening the copy created a lock file, which is not part of the snapshot
+    let lock_file = db_dir.join(MDBX_LOCK_FILE_NAME);
+    if lock_file.exists() {
+        fs::remove_file(&lock_file)?;
+    }
+    Ok(tip)
+}
+
+/// Copies the database into `dest` and writes its version file.
+fn copy_database<DB: Database>(db: &DB, dest: &Path, compact: bool) -> eyre::Result<()> {
+    db.copy_to(&dest.join(MDBX_DATA_FILE_NAME), compact)?;
+    create_db_version_file(dest)?;
+    Ok(())
+}
+
+/// Static files pinned by [`pin_static_files`], which are copied after the writes are resumed.
+#[derive(Debug)]
+struct PinnedStaticFiles {
+    /// Files to copy, with the number of bytes to copy.
+    files: Vec<(PathBuf, PathBuf, u64)>,
+    /// [`StaticFileProvider::truncations`](reth_provider::providers::StaticFileProvider::truncations)
+    /// when the static files were pinned.
+    truncations: u64,
+}
+
+impl PinnedStaticFiles {
+    /// Copies the pinned length of all files.
+    fn copy(&self) -> eyre::Result<()> {
+        for (source, target, len) in &self.files {
+            let mut reader = std::fs::File::open(source)
+                .map_err(|err| FsPathError::open(err, source))?
+                .take(*len);
+            let mut writer = fs::create_file(target)?;
+            let copied = std::io::copy(&mut reader, &mut writer).wrap_err_with(|| {
+                format!("Failed to copy {} to {}", source.display(), target.display())
+            })?;
+            eyre::ensure!(copied == *len, "{} was truncated while copying", source.display());
+        }
+        Ok(())
+    }
+}
+
+/// Pins the static files in `src` while static file writes are blocked.
+///
+/// The static files of blocks up to `finalized_block` are hard-linked to `dest`. All others can
+/// still be truncated, so their configuration is copied right away, and their data is copied up
+/// to its current length once writes are resumed.
+fn pin_static_files(
+    src: &Path,
+    dest: &Path,
+    finalized_block: Option<BlockNumber>,
+    truncations: u64,
+) -> eyre::Result<PinnedStaticFiles> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(src)? {
+        let path = entry.map_err(|err| FsPathError::read_dir(err, src))?.path();
+        // Data files have no extension, offsets and configuration files share their stem.
+        let Some((_, range)) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(StaticFileSegment::parse_filename)
+            .filter(|_| path.is_file())
+        else {
+            continue
+        };
+
+        let target = dest.join(path.file_name().expect("static file has a name"));
+        let immutable = finalized_block.is_some_and(|finalized| range.end() <= finalized);
+        if immutable && std::fs::hard_link(&path, &target).is_ok() {
+            continue
+        }
+        if path.extension().is_some_and(|extension| extension == "conf") {
+            // rewritten on every commit, so it must match the pinned data
+            copy_file(&path, &target)?;
+        } else {
+            let len = fs::metadata(&path)?.len();
+            files.push((path, target, len));
+        }
+    }
+    Ok(PinnedStaticFiles { files, truncations })
+}
+
+/// Lists the files of the snapshot in `dir` with their sizes and checksums.
+fn list_files(dir: &Path) -> eyre::Result<Vec<SnapshotFile>> {
+    let mut files = Vec::new();
+    for sub_dir in [DB_DIR, STATIC_FILES_DIR] {
+        for entry in fs::read_dir(dir.join(sub_dir))? {
+            let entry = entry.map_err(|err| FsPathError::read_dir(err, dir.join(sub_dir)))?;
+            let path = entry.path();
+            files.push(SnapshotFile {
+                path: Path::new(sub_dir).join(entry.file_name()),
+                size: fs::metadata(&path)?.len(),
+                sha256: sha256_file(&path)?,
+            });
+        }
+    }
+    files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Computes the SHA-256 checksum of the file at `path`.
+fn sha256_file(path: &Path) -> eyre::Result<B256> {
+    let mut file = std::fs::File::open(path).map_err(|err| FsPathError::open(err, path))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; CHECKSUM_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buf).map_err(|err| FsPathError::read(err, path))?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(B256::from_slice(&hasher.finalize()))
+}
+
+/// Copies the file at `source` to `target`.
+fn copy_file(source: &Path, target: &Path) -> eyre::Result<()> {
+    std::fs::copy(source, target)
+        .wrap_err_with(|| format!("Failed to copy {} to {}", source.display(), target.display()))?;
+    Ok(())
+}
+
+/// Fails if `path` exists and is not an empty directory.
+fn ensure_empty_dir(path: &Path) -> eyre::Result<()> {
+    if path.exists() {
+        eyre::ensure!(fs::read_dir(path)?.next().is_none(), "{} is not empty", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn snapshot_and_restore() {
+        let factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot");
+        let config = SnapshotConfig { pause_persistence: true, ..Default::default() };
+        let manifest = create_snapshot(&factory, &snapshot, config).unwrap();
+        assert_eq!(manifest, SnapshotManifest::load(&snapshot).unwrap());
+        assert_eq!((manifest.tip_number, manifest.tip_hash), (0, genesis_hash));
+        assert_eq!(manifest.chain_id, factory.chain_spec().chain().id());
+        assert!(manifest.files.iter().any(|file| file.path == Path::new("db/mdbx.dat")));
+        assert!(manifest.files.iter().any(|file| file.path.starts_with(STATIC_FILES_DIR)));
+
+        // The destination must be empty.
+        create_snapshot(&factory, &snapshot, config).unwrap_err();
+
+        let (db_path, static_files_path) = (dir.path().join("db"), dir.path().join("static"));
+        restore_snapshot(
+            &snapshot,
+            manifest.chain_id + 1,
+            &db_path,
+            &static_files_path,
+            Default::default(),
+        )
+        .unwrap_err();
+        restore_snapshot(
+            &snapshot,
+            manifest.chain_id,
+            &db_path,
+            &static_files_path,
+            Default::default(),
+        )
+        .unwrap();
+
+        let db = open_db_read_only(&db_path, DatabaseArguments::default()).unwrap();
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.get::<tables::HeaderNumbers>(genesis_hash).unwrap(), Some(0));
+        assert_eq!(fs::read_dir(&static_files_path).unwrap().count(), {
+            manifest.files.iter().filter(|file| file.path.starts_with(STATIC_FILES_DIR)).count()
+        });
+    }
+
+    #[test]
+    fn restore_detects_corruption() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot");
+        let manifest = create_snapshot(&factory, &snapshot, SnapshotConfig::default()).unwrap();
+
+        let file = &manifest.files[0];
+        let mut contents = fs::read(snapshot.join(&file.path)).unwrap();
+        contents[0] ^= 1;
+        fs::write(snapshot.join(&file.path), contents).unwrap();
+
+        let err = restore_snapshot(
+            &snapshot,
+            manifest.chain_id,
+            &dir.path().join("db"),
+            &dir.path().join("static"),
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Checksum mismatch"), "{err}");
+    }
+}
@@ -0,0 +1,38 @@
+use super::Check;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use std::collections::HashMap;
+
+#[derive(Metrics)]
+#[metrics(scope = "database.verify")]
+pub(crate) struct VerifyMetrics {
+    /// Number of verified table entries, static file rows and blocks
+    pub(crate) entries: Counter,
+    /// Number of completed verification runs
+    pub(crate) runs: Counter,
+    /// Number of findings of the last completed run
+    pub(crate) last_run_findings: Gauge,
+    /// Duration of the last completed run in seconds
+    pub(crate) last_run_duration_seconds: Gauge,
+    #[metric(skip)]
+    checks: HashMap<Check, CheckMetrics>,
+}
+
+impl VerifyMetrics {
+    /// Returns existing or initializes a new instance of [`CheckMetrics`] for the provided
+    /// [`Check`].
+    pub(crate) fn check(&mut self, check: Check) -> &mut CheckMetrics {
+        self.checks
+            .entry(check)
+            .or_insert_with(|| CheckMetrics::new_with_labels(&[("check", check.as_str())]))
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "database.verify.checks")]
+pub(crate) struct CheckMetrics {
+    /// Number of findings of this check
+    pub(crate) findings: Counter,
+}
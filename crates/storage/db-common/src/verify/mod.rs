@@ -0,0 +1,782 @@
+//! Online verification of the database and static files.
+//!
+//! The [`DbVerifier`] walks all MDBX tables and static files and checks that every entry decodes,
+//! then validates invariants across tables:
+//! - `BlockBodyIndices` are contiguous and their transactions exist.
+//! - `HeaderNumbers` maps every canonical hash back to its block number.
+//! - Static files are not ahead of the stage checkpoints and join up with the database.
+//!
+//! Reads are throttled and happen in short-lived read-only transactions, so a verification can
+//! run alongside a live node. Progress is persisted periodically, so an interrupted run resumes
+//! where it stopped.
+
+use alloy_primitives::{hex, BlockHash, Bytes};
+use reth_db::{
+    static_file::{
+        iter_static_files, AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor,
+        StorageChangeSetMask, TransactionMask,
+    },
+    tables, RawKey, RawTable, TableViewer, Tables,
+};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{BlockAccountChangeSet, BlockStorageChangeSet},
+    table::{Decode, Table},
+    transaction::DbTx,
+};
+use reth_fs_util as fs;
+use reth_primitives::{Header, StaticFileSegment};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockHashReader, BlockNumReader, BlockReader, DBProvider,
+    ProviderFactory, ProviderResult, StageCheckpointReader, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use reth_stages_types::StageId;
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+mod metrics;
+use metrics::VerifyMetrics;
+
+mod report;
+pub(crate) use report::unix_timestamp;
+pub use report::{Check, Finding, TableProgress, VerifyProgress, VerifyReport};
+
+/// Default maximum number of findings kept in a [`VerifyReport`].
+pub const DEFAULT_MAX_FINDINGS: usize = 10_000;
+
+/// Maximum lifetime of a read-only transaction. Longer walks continue in a new transaction, so
+/// the database can reclaim pages freed by a running node in the meantime.
+const MAX_TRANSACTION_DURATION: Duration = Duration::from_secs(60);
+
+/// Interval at which the progress file is written.
+const PROGRESS_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Static file segments and the stages whose checkpoints they may not be ahead of.
+const STATIC_FILE_STAGES: [(StaticFileSegment, StageId); 5] = [
+    (StaticFileSegment::Headers, StageId::Headers),
+    (StaticFileSegment::Transactions, StageId::Bodies),
+    (StaticFileSegment::Receipts, StageId::Execution),
+    (StaticFileSegment::AccountChangeSets, StageId::Execution),
+    (StaticFileSegment::StorageChangeSets, StageId::Execution),
+];
+
+/// Configuration of a [`DbVerifier`].
+#[derive(Debug, Clone)]
+pub struct VerifyConfig {
+    /// Tables to decode. `None` decodes all tables.
+    pub tables: Option<Vec<Tables>>,
+    /// Whether to decode all static files.
+    pub static_files: bool,
+    /// Whether to verify invariants across tables and static files.
+    pub invariants: bool,
+    /// Maximum number of entries, static file rows and blocks read per second. `None` reads as
+    /// fast as possible.
+    pub rate_limit: Option<u64>,
+    /// Path of the file the progress is persisted to. Runs can't be resumed if `None`.
+    pub progress_path: Option<PathBuf>,
+    /// Maximum number of findings kept in the report.
+    pub max_findings: usize,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        Self {
+            tables: None,
+            static_files: true,
+            invariants: true,
+            rate_limit: None,
+            progress_path: None,
+            max_findings: DEFAULT_MAX_FINDINGS,
+        }
+    }
+}
+
+/// Verifies the database and static files of a [`ProviderFactory`].
+#[derive(Debug)]
+pub struct DbVerifier<N: ProviderNodeTypes> {
+    provider_factory: ProviderFactory<N>,
+    config: VerifyConfig,
+}
+
+impl<N: ProviderNodeTypes> DbVerifier<N> {
+    /// Creates a new verifier with the given configuration.
+    pub const fn new(provider_factory: ProviderFactory<N>, config: VerifyConfig) -> Self {
+        Self { provider_factory, config }
+    }
+
+    /// Returns the configuration of the verifier.
+    pub const fn config(&self) -> &VerifyConfig {
+        &self.config
+    }
+
+    /// Removes the persisted progress, so the next run starts from scratch.
+    pub fn clear_progress(&self) -> eyre::Result<()> {
+        if let Some(path) = self.config.progress_path.as_deref().filter(|path| path.exists()) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Runs a full verification, resuming from the persisted progress if there is any.
+    ///
+    /// Inconsistencies are returned as findings of the [`VerifyReport`]. Errors are only returned
+    /// if the verification itself fails, e.g. because the database can't be read. The progress is
+    /// removed once the run completes.
+    pub fn run(&self) -> eyre::Result<VerifyReport> {
+        let mut run = VerifyRun::new(&self.config, self.load_progress());
+        let started_at = Instant::now();
+        info!(
+            target: "reth::db::verify",
+            resumed = run.progress.report.started_at != 0,
+            rate_limit = ?self.config.rate_limit,
+            "Verifying database"
+        );
+        if run.progress.report.started_at == 0 {
+            run.progress.report = VerifyReport::new();
+        }
+
+        let tables = self.config.tables.as_deref().unwrap_or(Tables::ALL);
+        for table in tables {
+            if run.progress.tables.get(table.name()) == Some(&TableProgress::Done) {
+                continue
+            }
+
+            info!(target: "reth::db::verify", table = table.name(), "Verifying table");
+            table.view(&TableVerifier {
+                provider_factory: &self.provider_factory,
+                run: RefCell::new(&mut run),
+            })?;
+            run.progress.tables.insert(table.name().to_string(), TableProgress::Done);
+            run.save()?;
+        }
+
+        if self.config.static_files {
+            self.verify_static_files(&mut run)?;
+        }
+
+        if self.config.invariants {
+            info!(target: "reth::db::verify", "Verifying invariants");
+            if let Err(err) = self.verify_body_indices(&mut run) {
+                let location = tables::BlockBodyIndices::NAME;
+                run.record(Check::BodyIndices, location, None, format!("check aborted: {err}"));
+            }
+            if let Err(err) = self.verify_header_numbers(&mut run) {
+                let location = tables::HeaderNumbers::NAME;
+                run.record(Check::HeaderNumbers, location, None, format!("check aborted: {err}"));
+            }
+            if let Err(err) = self.verify_static_file_tips(&mut run) {
+                run.record(
+                    Check::StaticFileTip,
+                    "static_files",
+                    None,
+                    format!("check aborted: {err}"),
+                );
+            }
+        }
+
+        let mut report = run.progress.report;
+        report.finished_at = Some(unix_timestamp());
+        run.metrics.runs.increment(1);
+        run.metrics.last_run_findings.set(report.total_findings as f64);
+        run.metrics.last_run_duration_seconds.set(started_at.elapsed().as_secs_f64());
+        self.clear_progress()?;
+
+        info!(
+            target: "reth::db::verify",
+            findings = report.total_findings,
+            elapsed = ?started_at.elapsed(),
+            "Database verification finished"
+        );
+
+        Ok(report)
+    }
+
+    /// Loads the persisted progress, falling back to a fresh run if there is none or it can't be
+    /// read.
+    fn load_progress(&self) -> VerifyProgress {
+        let Some(path) = self.config.progress_path.as_deref().filter(|path| path.exists()) else {
+            return VerifyProgress::default()
+        };
+
+        fs::read_json_file(path).unwrap_or_else(|err| {
+            warn!(target: "reth::db::verify", %err, "Failed to read progress, starting over");
+            VerifyProgress::default()
+        })
+    }
+
+    /// Decodes every row of all static files.
+    fn verify_static_files(&self, run: &mut VerifyRun<'_>) -> eyre::Result<()> {
+        let static_file_provider = self.provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+
+        for (segment, _) in STATIC_FILE_STAGES {
+            let Some(ranges) = static_files.get(&segment) else { continue };
+            let location = format!("static_file_{segment}");
+            info!(target: "reth::db::verify", %segment, "Verifying static files");
+
+            for (block_range, tx_range) in ranges {
+                if run
+                    .progress
+                    .static_files
+                    .get(segment.as_str())
+                    .is_some_and(|verified| block_range.end() <= *verified)
+                {
+                    continue
+                }
+
+                let jar = static_file_provider.get_segment_provider_from_block(
+                    segment,
+                    block_range.start(),
+                    None,
+                )?;
+                let mut cursor = jar.cursor()?;
+                let rows = if segment.is_block_based() {
+                    Some(block_range.start()..=block_range.end())
+                } else {
+                    tx_range.as_ref().map(|range| range.start()..=range.end())
+                };
+
+                for number in rows.into_iter().flatten() {
+                    match catch_decode_panic(|| read_static_file_row(&mut cursor, segment, number))
+                    {
+                        Ok(true) => {}
+                        Ok(false) => run.record(
+                            Check::StaticFileDecode,
+                            &location,
+                            Some(number.to_string()),
+                            "missing row",
+                        ),
+                        Err(err) => run.record(
+                            Check::StaticFileDecode,
+                            &location,
+                            Some(number.to_string()),
+                            format!("failed to decode row: {err}"),
+                        ),
+                    }
+                    run.verified(&location, 1);
+                }
+
+                run.progress.static_files.insert(segment.as_str().to_string(), block_range.end());
+                if run.should_save() {
+                    run.save()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that `BlockBodyIndices` are contiguous and that the last transaction of every
+    /// block exists.
+    fn verify_body_indices(&self, run: &mut VerifyRun<'_>) -> eyre::Result<()> {
+        let location = tables::BlockBodyIndices::NAME;
+        let mut next_block = run.progress.body_indices.unwrap_or_default();
+        let mut previous = match next_block.checked_sub(1) {
+            Some(block) => self.provider_factory.provider()?.block_body_indices(block)?,
+            None => None,
+        };
+
+        loop {
+            let provider = self.provider_factory.provider()?;
+            let started_at = Instant::now();
+            let mut cursor = provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?;
+            let mut interrupted = false;
+
+            for entry in cursor.walk(Some(next_block))? {
+                let (block, indices) = entry?;
+
+                if block != next_block {
+                    run.record(
+                        Check::BodyIndices,
+                        location,
+                        Some(next_block.to_string()),
+                        format!("missing body indices for blocks {next_block}..{block}"),
+                    );
+                }
+                if let Some(previous) =
+                    previous.filter(|previous| previous.next_tx_num() != indices.first_tx_num())
+                {
+                    run.record(
+                        Check::BodyIndices,
+                        location,
+                        Some(block.to_string()),
+                        format!(
+                            "first transaction {} does not follow the previous block ending at {}",
+                            indices.first_tx_num(),
+                            previous.next_tx_num()
+                        ),
+                    );
+                }
+                if !indices.is_empty() {
+                    match provider.transaction_by_id_no_hash(indices.last_tx_num()) {
+                        Ok(Some(_)) => {}
+                        Ok(None) => run.record(
+                            Check::BodyIndices,
+                            location,
+                            Some(block.to_string()),
+                            format!("missing last transaction {}", indices.last_tx_num()),
+                        ),
+                        Err(err) => run.record(
+                            Check::BodyIndices,
+                            location,
+                            Some(block.to_string()),
+                            format!("failed to read last transaction: {err}"),
+                        ),
+                    }
+                }
+
+                previous = Some(indices);
+                next_block = block + 1;
+                run.verified(Check::BodyIndices.as_str(), 1);
+
+                if run.should_save() {
+                    run.progress.body_indices = Some(next_block);
+                    run.save()?;
+                }
+                if started_at.elapsed() >= MAX_TRANSACTION_DURATION {
+                    interrupted = true;
+                    break
+                }
+            }
+
+            run.progress.body_indices = Some(next_block);
+            if !interrupted {
+                return Ok(())
+            }
+        }
+    }
+
+    /// Verifies that `HeaderNumbers` maps the hash of every canonical block to its number.
+    fn verify_header_numbers(&self, run: &mut VerifyRun<'_>) -> eyre::Result<()> {
+        let location = tables::HeaderNumbers::NAME;
+        let mut block = run.progress.header_numbers.unwrap_or_default();
+
+        loop {
+            let provider = self.provider_factory.provider()?;
+            let started_at = Instant::now();
+            let tip = provider.last_block_number()?;
+
+            while block <= tip && started_at.elapsed() < MAX_TRANSACTION_DURATION {
+                match provider.block_hash(block)? {
+                    Some(hash) => match provider.tx_ref().get::<tables::HeaderNumbers>(hash)? {
+                        Some(number) if number == block => {}
+                        Some(number) => run.record(
+                            Check::HeaderNumbers,
+                            location,
+                            Some(hash.to_string()),
+                            format!("canonical hash of block {block} maps to block {number}"),
+                        ),
+                        None => run.record(
+                            Check::HeaderNumbers,
+                            location,
+                            Some(hash.to_string()),
+                            format!("missing canonical hash of block {block}"),
+                        ),
+                    },
+                    None => run.record(
+                        Check::HeaderNumbers,
+                        tables::CanonicalHeaders::NAME,
+                        Some(block.to_string()),
+                        "missing canonical hash",
+                    ),
+                }
+
+                block += 1;
+                run.verified(Check::HeaderNumbers.as_str(), 1);
+
+                if run.should_save() {
+                    run.progress.header_numbers = Some(block);
+                    run.save()?;
+                }
+            }
+
+            run.progress.header_numbers = Some(block);
+            if block > tip {
+                return Ok(())
+            }
+        }
+    }
+
+    /// Verifies that static files are not ahead of the stage checkpoints, that transaction based
+    /// static files end at the last transaction of their last block and that headers continue in
+    /// the database where the static files end.
+    fn verify_static_file_tips(&self, run: &mut VerifyRun<'_>) -> eyre::Result<()> {
+        if run.progress.static_file_tips {
+            return Ok(())
+        }
+
+        let provider = self.provider_factory.provider()?;
+        let static_file_provider = provider.static_file_provider();
+
+        for (segment, stage) in STATIC_FILE_STAGES {
+            let location = format!("static_file_{segment}");
+            let Some(highest_block) = static_file_provider.get_highest_static_file_block(segment)
+            else {
+                continue
+            };
+
+            let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+            if highest_block > checkpoint {
+                run.record(
+                    Check::StaticFileTip,
+                    &location,
+                    Some(highest_block.to_string()),
+                    format!(
+                        "static files end at block {highest_block}, ahead of the {stage} stage \
+                         checkpoint at block {checkpoint}"
+                    ),
+                );
+            }
+
+            if segment.is_tx_based() {
+                let next_tx = static_file_provider
+                    .get_highest_static_file_tx(segment)
+                    .map_or(0, |highest_tx| highest_tx + 1);
+                match provider.block_body_indices(highest_block)? {
+                    Some(indices) if indices.next_tx_num() == next_tx => {}
+                    Some(indices) => run.record(
+                        Check::StaticFileTip,
+                        &location,
+                        Some(highest_block.to_string()),
+                        format!(
+                            "static files end before transaction {next_tx}, but their last \
+                             block ends before transaction {}",
+                            indices.next_tx_num()
+                        ),
+                    ),
+                    None => run.record(
+                        Check::StaticFileTip,
+                        &location,
+                        Some(highest_block.to_string()),
+                        "missing body indices of the last block",
+                    ),
+                }
+            }
+
+            run.verified(Check::StaticFileTip.as_str(), 1);
+        }
+
+        let checkpoint =
+            provider.get_stage_checkpoint(StageId::Headers)?.unwrap_or_default().block_number;
+        let next_block = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(0, |highest_block| highest_block + 1);
+        if next_block <= checkpoint &&
+            provider.tx_ref().get::<tables::CanonicalHeaders>(next_block)?.is_none()
+        {
+            run.record(
+                Check::StaticFileTip,
+                tables::CanonicalHeaders::NAME,
+                Some(next_block.to_string()),
+                format!(
+                    "header is neither in static files nor in the database, but the Headers \
+                     stage checkpoint is at block {checkpoint}"
+                ),
+            );
+        }
+
+        run.progress.static_file_tips = true;
+        Ok(())
+    }
+}
+
+/// State of a single verification run.
+#[derive(Debug)]
+struct VerifyRun<'a> {
+    progress: VerifyProgress,
+    progress_path: Option<&'a Path>,
+    max_findings: usize,
+    limiter: RateLimiter,
+    last_saved_at: Instant,
+    metrics: VerifyMetrics,
+}
+
+impl<'a> VerifyRun<'a> {
+    fn new(config: &'a VerifyConfig, progress: VerifyProgress) -> Self {
+        Self {
+            progress,
+            progress_path: config.progress_path.as_deref(),
+            max_findings: config.max_findings,
+            limiter: RateLimiter::new(config.rate_limit),
+            last_saved_at: Instant::now(),
+            metrics: VerifyMetrics::default(),
+        }
+    }
+
+    /// Records an inconsistency.
+    fn record(
+        &mut self,
+        check: Check,
+        location: &str,
+        key: Option<String>,
+        message: impl Into<String>,
+    ) {
+        let message = message.into();
+        warn!(target: "reth::db::verify", %check, location, ?key, message, "Found inconsistency");
+
+        self.metrics.check(check).findings.increment(1);
+        let report = &mut self.progress.report;
+        report.total_findings += 1;
+        if report.findings.len() < self.max_findings {
+            report.findings.push(Finding { check, location: location.to_string(), key, message });
+        }
+    }
+
+    /// Accounts for verified entries and throttles the run according to the rate limit.
+    fn verified(&mut self, location: &str, entries: u64) {
+        match self.progress.report.entries.get_mut(location) {
+            Some(verified) => *verified += entries,
+            None => {
+                self.progress.report.entries.insert(location.to_string(), entries);
+            }
+        }
+        self.metrics.entries.increment(entries);
+        self.limiter.throttle(entries);
+    }
+
+    /// Returns `true` if the progress is due to be persisted.
+    fn should_save(&self) -> bool {
+        self.progress_path.is_some() && self.last_saved_at.elapsed() >= PROGRESS_SAVE_INTERVAL
+    }
+
+    /// Persists the progress.
+    fn save(&mut self) -> eyre::Result<()> {
+        if let Some(path) = self.progress_path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::atomic_write_file(path, |file| serde_json::to_writer(file, &self.progress))?;
+        }
+        self.last_saved_at = Instant::now();
+        Ok(())
+    }
+}
+
+/// Decodes every entry of a table, continuing from the persisted progress.
+struct TableVerifier<'a, 'b, N: ProviderNodeTypes> {
+    provider_factory: &'a ProviderFactory<N>,
+    run: RefCell<&'a mut VerifyRun<'b>>,
+}
+
+impl<N: ProviderNodeTypes> TableViewer<()> for TableVerifier<'_, '_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let mut run = self.run.borrow_mut();
+        let mut next_key = match run.progress.tables.get(T::NAME) {
+            Some(TableProgress::InProgress { next_key }) => {
+                Some(RawKey::<T::Key>::decode(next_key)?)
+            }
+            _ => None,
+        };
+
+        loop {
+            let provider = self.provider_factory.provider()?;
+            let started_at = Instant::now();
+            let mut cursor = provider.tx_ref().cursor_read::<RawTable<T>>()?;
+            let mut current_key = None;
+
+            for entry in cursor.walk(next_key.take())? {
+                let (key, value) = entry?;
+
+                // Transactions are only renewed and the progress is only saved once all values of
+                // the previous key have been verified, so dupsort tables resume correctly.
+                if current_key.as_ref() != Some(key.raw_key()) {
+                    if started_at.elapsed() >= MAX_TRANSACTION_DURATION {
+                        next_key = Some(key);
+                        break
+                    }
+                    if run.should_save() {
+                        run.progress.tables.insert(
+                            T::NAME.to_string(),
+                            TableProgress::InProgress {
+                                next_key: Bytes::copy_from_slice(key.raw_key()),
+                            },
+                        );
+                        run.save()?;
+                    }
+                    current_key = Some(key.raw_key().clone());
+                }
+
+                if let Err(err) = catch_decode_panic(|| key.key()) {
+                    run.record(
+                        Check::Decode,
+                        T::NAME,
+                        Some(hex::encode_prefixed(key.raw_key())),
+                        format!("failed to decode key: {err}"),
+                    );
+                }
+                if let Err(err) = catch_decode_panic(|| value.value()) {
+                    run.record(
+                        Check::Decode,
+                        T::NAME,
+                        Some(hex::encode_prefixed(key.raw_key())),
+                        format!("failed to decode value: {err}"),
+                    );
+                }
+                run.verified(T::NAME, 1);
+            }
+
+            if next_key.is_none() {
+                return Ok(())
+            }
+        }
+    }
+}
+
+/// Reads and decodes all columns of a static file row, returning `false` if the row is missing.
+fn read_static_file_row(
+    cursor: &mut StaticFileCursor<'_>,
+    segment: StaticFileSegment,
+    number: u64,
+) -> ProviderResult<bool> {
+    Ok(match segment {
+        StaticFileSegment::Headers => {
+            cursor.get_two::<HeaderMask<Header, BlockHash>>(number.into())?.is_some() &&
+                cursor
+                    .get_one::<HeaderMask<<tables::HeaderTerminalDifficulties as Table>::Value>>(
+                        number.into(),
+                    )?
+                    .is_some()
+        }
+        StaticFileSegment::Transactions => cursor
+            .get_one::<TransactionMask<<tables::Transactions as Table>::Value>>(number.into())?
+            .is_some(),
+        StaticFileSegment::Receipts => cursor
+            .get_one::<ReceiptMask<<tables::Receipts as Table>::Value>>(number.into())?
+            .is_some(),
+        StaticFileSegment::AccountChangeSets => {
+            cursor.get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(number.into())?.is_some()
+        }
+        StaticFileSegment::StorageChangeSets => {
+            cursor.get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(number.into())?.is_some()
+        }
+    })
+}
+
+/// Runs a decoding function, turning panics on malformed data into errors.
+fn catch_decode_panic<T, E: ToString>(decode: impl FnOnce() -> Result<T, E>) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(decode)) {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(_) => Err("decoding panicked".to_string()),
+    }
+}
+
+/// Limits the number of entries read per second.
+#[derive(Debug)]
+struct RateLimiter {
+    limit: Option<u64>,
+    window_started_at: Instant,
+    window_entries: u64,
+}
+
+impl RateLimiter {
+    /// Sleeps shorter than this are deferred, to not sleep after every entry.
+    const MIN_SLEEP: Duration = Duration::from_millis(10);
+
+    fn new(limit: Option<u64>) -> Self {
+        Self {
+            limit: limit.filter(|limit| *limit > 0),
+            window_started_at: Instant::now(),
+            window_entries: 0,
+        }
+    }
+
+    /// Accounts for read entries, sleeping if they were read faster than the limit allows.
+    fn throttle(&mut self, entries: u64) {
+        let Some(limit) = self.limit else { return };
+
+        self.window_entries += entries;
+        let expected = Duration::from_secs_f64(self.window_entries as f64 / limit as f64);
+        if let Some(ahead) = expected.checked_sub(self.window_started_at.elapsed()) {
+            if ahead >= Self::MIN_SLEEP {
+                thread::sleep(ahead);
+            }
+        }
+
+        if self.window_started_at.elapsed() >= Duration::from_secs(1) {
+            self.window_started_at = Instant::now();
+            self.window_entries = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn verify_genesis() {
+        let factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&factory).unwrap();
+        let config = VerifyConfig { static_files: false, ..Default::default() };
+
+        let report = DbVerifier::new(factory.clone(), config.clone()).run().unwrap();
+        assert!(report.is_ok(), "{:?}", report.findings);
+        assert_eq!(report.entries.get(Check::HeaderNumbers.as_str()), Some(&1));
+        assert!(report.finished_at.is_some());
+
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::HeaderNumbers>(genesis_hash, 1).unwrap();
+        provider.commit().unwrap();
+
+        let report = DbVerifier::new(factory, config).run().unwrap();
+        assert_eq!(report.total_findings, 1);
+        assert_eq!(report.findings[0].check, Check::HeaderNumbers);
+        assert_eq!(report.findings[0].key, Some(genesis_hash.to_string()));
+    }
+
+    #[test]
+    fn resume_from_progress() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let progress_path = dir.path().join("progress.json");
+        let mut progress = VerifyProgress::default();
+        for table in Tables::ALL {
+            progress.tables.insert(table.name().to_string(), TableProgress::Done);
+        }
+        progress.header_numbers = Some(1);
+        fs::write_json_file(&progress_path, &progress).unwrap();
+
+        let config = VerifyConfig {
+            static_files: false,
+            progress_path: Some(progress_path.clone()),
+            ..Default::default()
+        };
+        let report = DbVerifier::new(factory, config).run().unwrap();
+
+        // Tables and header numbers were skipped, only the remaining checks ran.
+        assert!(report
+            .entries
+            .keys()
+            .all(|location| Tables::ALL.iter().all(|table| table.name() != location)));
+        assert_eq!(report.entries.get(Check::HeaderNumbers.as_str()), None);
+        assert_eq!(report.entries.get(Check::BodyIndices.as_str()), Some(&1));
+        assert!(!progress_path.exists());
+    }
+
+    #[test]
+    fn rate_limiter_throttles() {
+        let mut limiter = RateLimiter::new(Some(100));
+        let started_at = Instant::now();
+        limiter.throttle(10);
+        assert!(started_at.elapsed() >= Duration::from_millis(100));
+
+        let mut limiter = RateLimiter::new(Some(0));
+        let started_at = Instant::now();
+        limiter.throttle(1_000);
+        assert!(started_at.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,146 @@
+use alloy_primitives::{BlockNumber, Bytes};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Kind of check that produced a [`Finding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    /// A database table entry could not be decoded.
+    Decode,
+    /// A static file row is missing or could not be decoded.
+    StaticFileDecode,
+    /// `BlockBodyIndices` are not contiguous or point to missing transactions.
+    BodyIndices,
+    /// `HeaderNumbers` does not map a canonical hash back to its block number.
+    HeaderNumbers,
+    /// Static files are inconsistent with the stage checkpoints or the database.
+    StaticFileTip,
+}
+
+impl Check {
+    /// Returns the check as a string.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Decode => "decode",
+            Self::StaticFileDecode => "static_file_decode",
+            Self::BodyIndices => "body_indices",
+            Self::HeaderNumbers => "header_numbers",
+            Self::StaticFileTip => "static_file_tip",
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single inconsistency found by the [`DbVerifier`](super::DbVerifier).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// The check that produced the finding.
+    pub check: Check,
+    /// The table or static file segment the finding is about.
+    pub location: String,
+    /// The key, block or transaction number of the offending entry, if any.
+    pub key: Option<String>,
+    /// Description of the inconsistency.
+    pub message: String,
+}
+
+/// Report of a verification run, written as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyReport {
+    /// Unix timestamp in seconds of when the run started.
+    pub started_at: u64,
+    /// Unix timestamp in seconds of when the run finished, `None` while it is in progress.
+    pub finished_at: Option<u64>,
+    /// Number of verified entries per table, static file segment and cross-table check.
+    pub entries: BTreeMap<String, u64>,
+    /// Total number of findings, including the ones omitted from [`Self::findings`].
+    pub total_findings: u64,
+    /// Findings, up to the configured maximum.
+    pub findings: Vec<Finding>,
+}
+
+impl VerifyReport {
+    /// Creates a new report of a run starting now.
+    pub fn new() -> Self {
+        Self { started_at: unix_timestamp(), ..Default::default() }
+    }
+
+    /// Returns `true` if the run found no inconsistencies.
+    pub const fn is_ok(&self) -> bool {
+        self.total_findings == 0
+    }
+}
+
+/// Position of the verification of a single table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableProgress {
+    /// All entries before the raw key have been verified.
+    InProgress {
+        /// The first raw key that has not been verified yet.
+        next_key: Bytes,
+    },
+    /// The whole table has been verified.
+    Done,
+}
+
+/// Progress of an interrupted verification run, persisted to resume it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyProgress {
+    /// Progress per table. Tables missing from the map have not been started.
+    pub tables: BTreeMap<String, TableProgress>,
+    /// Per static file segment, the last block of the last fully verified static file.
+    pub static_files: BTreeMap<String, BlockNumber>,
+    /// The next block to verify `BlockBodyIndices` for.
+    pub body_indices: Option<BlockNumber>,
+    /// The next block to verify `HeaderNumbers` for.
+    pub header_numbers: Option<BlockNumber>,
+    /// Whether the static file tips have been verified.
+    pub static_file_tips: bool,
+    /// Report of the run so far.
+    pub report: VerifyReport,
+}
+
+/// Returns the current unix timestamp in seconds.
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_roundtrip() {
+        let mut progress = VerifyProgress::default();
+        progress.tables.insert(
+            "Headers".to_string(),
+            TableProgress::InProgress { next_key: Bytes::from_static(&[0, 1]) },
+        );
+        progress.tables.insert("Bodies".to_string(), TableProgress::Done);
+        progress.static_files.insert("headers".to_string(), 499_999);
+        progress.body_indices = Some(10);
+        progress.report.findings.push(Finding {
+            check: Check::Decode,
+            location: "Headers".to_string(),
+            key: Some("0x0001".to_string()),
+            message: "failed to decode value".to_string(),
+        });
+        progress.report.total_findings = 1;
+
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains(r#""next_key":"0x0001""#));
+        assert!(json.contains(r#""check":"decode""#));
+        assert_eq!(serde_json::from_str::<VerifyProgress>(&json).unwrap(), progress);
+    }
+}
@@ -0,0 +1,92 @@
+use alloy_primitives::{Address, B256, U256};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives::StorageEntry;
+use serde::Serialize;
+
+use crate::AccountBeforeTx;
+
+/// Storage slot as it was before a block changed it.
+///
+/// Flattened `(Address, StorageEntry)` entry of the `StorageChangeSets` table, as stored in
+/// static files.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Storage value before the block.
+    pub value: U256,
+}
+
+impl StorageBeforeTx {
+    /// Creates a new [`StorageBeforeTx`] from an address and a storage changeset entry.
+    pub const fn new(address: Address, entry: StorageEntry) -> Self {
+        Self { address, key: entry.key, value: entry.value }
+    }
+
+    /// Returns the storage changeset entry.
+    pub const fn entry(&self) -> StorageEntry {
+        StorageEntry { key: self.key, value: self.value }
+    }
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        buf.put_slice(self.key.as_slice());
+        self.value.to_compact(buf) + 52
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let key = B256::from_slice(&buf[20..52]);
+        let (value, out) = U256::from_compact(&buf[52..], len - 52);
+        (Self { address, key, value }, out)
+    }
+}
+
+/// All account changes of a block. Row of the account changesets static file segment.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct BlockAccountChangeSet(pub Vec<AccountBeforeTx>);
+
+impl Compact for BlockAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.0.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, out) = Vec::from_compact(buf, len);
+        (Self(changes), out)
+    }
+}
+
+/// All storage changes of a block. Row of the storage changesets static file segment.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct BlockStorageChangeSet(pub Vec<StorageBeforeTx>);
+
+impl Compact for BlockStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.0.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, out) = Vec::from_compact(buf, len);
+        (Self(changes), out)
+    }
+}
@@ -4,6 +4,10 @@
 pub mod accounts;
 pub use accounts::AccountBeforeTx;
 
+/// Changesets
+pub mod changesets;
+pub use changesets::{BlockAccountChangeSet, BlockStorageChangeSet, StorageBeforeTx};
+
 /// Blocks
 pub mod blocks;
 pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals};
@@ -11,3 +15,10 @@ pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals};
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Telos engine API extra fields
+pub mod telos;
+pub use telos::{
+    StoredTelosAccountRow, StoredTelosAccountStateRow, StoredTelosExtraFields,
+    StoredTelosRevisionChange, StoredTelosTxValue,
+};
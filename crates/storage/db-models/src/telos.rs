@@ -0,0 +1,88 @@
+//! Telos engine API extra fields models.
+
+use alloy_primitives::{Address, Bytes, U256};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives::Receipt;
+use serde::{Deserialize, Serialize};
+
+/// The Telos engine API extra fields of a block as they are saved in the database.
+///
+/// Mirrors the engine API `TelosEngineAPIExtraFields`, with the tuples turned into structs so
+/// they can be [`Compact`] encoded.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredTelosExtraFields {
+    /// State diffs of the `account` table.
+    pub statediffs_account: Option<Vec<StoredTelosAccountRow>>,
+    /// State diffs of the `accountstate` table.
+    pub statediffs_accountstate: Option<Vec<StoredTelosAccountStateRow>>,
+    /// Revision change in the block.
+    pub revision_change: Option<StoredTelosRevisionChange>,
+    /// Gas price change in the block.
+    pub gas_price_change: Option<StoredTelosTxValue>,
+    /// New addresses created by the `create` action in the block.
+    pub new_addresses_using_create: Option<Vec<StoredTelosTxValue>>,
+    /// New addresses created by the `openwallet` action in the block.
+    pub new_addresses_using_openwallet: Option<Vec<StoredTelosTxValue>>,
+    /// Receipts produced by the `telos.evm` contract.
+    pub receipts: Option<Vec<Receipt>>,
+}
+
+/// Row of the Telos EVM `account` table as it is saved in the database.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredTelosAccountRow {
+    /// Whether the row was removed.
+    pub removed: bool,
+    /// Nonce.
+    pub nonce: u64,
+    /// Index of the row in the `accountcode` table holding the code, if not inlined in `code`.
+    pub code_id: Option<u64>,
+    /// Balance.
+    pub balance: U256,
+    /// Address.
+    pub address: Address,
+    /// UTF-8 encoded Antelope account name.
+    pub account: Vec<u8>,
+    /// Code.
+    pub code: Bytes,
+}
+
+/// Row of the Telos EVM `accountstate` table as it is saved in the database.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredTelosAccountStateRow {
+    /// Whether the row was removed.
+    pub removed: bool,
+    /// Storage key.
+    pub key: U256,
+    /// Storage value.
+    pub value: U256,
+    /// Address.
+    pub address: Address,
+}
+
+/// Revision set by the transaction at `tx_index` of a block.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredTelosRevisionChange {
+    /// Index of the transaction in the block.
+    pub tx_index: u64,
+    /// New revision.
+    pub revision: u64,
+}
+
+/// Value set by the transaction at `tx_index` of a block, e.g. a gas price or a new address.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredTelosTxValue {
+    /// Index of the transaction in the block.
+    pub tx_index: u64,
+    /// Value.
+    pub value: U256,
+}
@@ -111,6 +111,10 @@ pub mod test_utils {
         fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
             self.db().tx_mut()
         }
+
+        fn copy_to(&self, path: &Path, compact: bool) -> Result<(), DatabaseError> {
+            self.db().copy_to(path, compact)
+        }
     }
 
     impl<DB: DatabaseMetrics> DatabaseMetrics for TempDatabase<DB> {
@@ -1,11 +1,14 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{AccountChangeSetMask, ReceiptMask, StorageChangeSetMask, TransactionMask};
 use crate::{
     add_static_file_mask,
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     HeaderTerminalDifficulties, RawValue, Receipts, Transactions,
 };
 use alloy_primitives::BlockHash;
-use reth_db_api::table::Table;
+use reth_db_api::{
+    models::{BlockAccountChangeSet, BlockStorageChangeSet},
+    table::Table,
+};
 use reth_primitives::Header;
 
 // HEADER MASKS
@@ -21,3 +24,9 @@ add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 // TRANSACTION MASKS
 add_static_file_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
 add_static_file_mask!(TransactionMask, RawValue<<Transactions as Table>::Value>, 0b1);
+
+// ACCOUNT CHANGESET MASKS
+add_static_file_mask!(AccountChangeSetMask, BlockAccountChangeSet, 0b1);
+
+// STORAGE CHANGESET MASKS
+add_static_file_mask!(StorageChangeSetMask, BlockStorageChangeSet, 0b1);
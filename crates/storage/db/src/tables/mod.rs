@@ -26,7 +26,7 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, ShardedKey, StoredBlockBodyIndices,
-        StoredBlockWithdrawals,
+        StoredBlockWithdrawals, StoredTelosExtraFields,
     },
     table::{Decode, DupSort, Encode, Table},
 };
@@ -359,6 +359,22 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores pointers to the blocks that contain at least one log emitted by an address.
+    ///
+    /// Shards follow the same layout as [`AccountsHistory`]: the last shard of every address is
+    /// keyed with `u64::MAX`, all others with the highest block number they contain.
+    ///
+    /// Only populated when the optional `IndexLogs` stage is enabled.
+    table LogAddressIndex<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores pointers to the blocks that contain at least one log with the given topic, in any
+    /// topic position.
+    ///
+    /// Shards follow the same layout as [`AccountsHistory`].
+    ///
+    /// Only populated when the optional `IndexLogs` stage is enabled.
+    table LogTopicIndex<Key = ShardedKey<B256>, Value = BlockNumberList>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -407,6 +423,12 @@ tables! {
     /// Stores EIP-7685 EL -> CL requests, indexed by block number.
     table BlockRequests<Key = BlockNumber, Value = Requests>;
 
+    /// Stores the Telos engine API extra fields of a block, indexed by block hash.
+    ///
+    /// Keyed by hash because the fields are not part of the block and must survive reorgs. Only
+    /// written by Telos nodes.
+    table TelosExtraFields<Key = BlockHash, Value = StoredTelosExtraFields>;
+
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
 }
@@ -154,6 +154,19 @@ impl Environment {
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env_ptr(), force, false) })
     }
 
+    /// Copies the environment to a new database file at `dest`, which must not exist yet.
+    ///
+    /// The copy is taken in a read transaction, so it is consistent with the last committed write
+    /// transaction and can be taken while the environment is in use. If `compact` is set, free
+    /// pages are omitted and the data is renumbered sequentially, which makes the copy smaller but
+    /// slower to write.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = path_to_cstring(dest)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+
     /// Retrieves statistics about this environment.
     pub fn stat(&self) -> Result<Stat> {
         unsafe {
@@ -671,24 +684,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
@@ -895,6 +891,25 @@ pub(crate) mod read_transactions {
     }
 }
 
+/// Converts a path to a C string that can be passed to the MDBX API.
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    fn path_to_bytes(path: &Path) -> Vec<u8> {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+
+    #[cfg(windows)]
+    fn path_to_bytes(path: &Path) -> Vec<u8> {
+        // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+        // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+        // really help.
+        path.to_string_lossy().to_string().into_bytes()
+    }
+
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
+}
+
 /// Converts a [`HandleSlowReadersCallback`] to the actual FFI function pointer.
 #[allow(clippy::missing_transmute_annotations)]
 fn convert_hsr_fn(callback: Option<HandleSlowReadersCallback>) -> ffi::MDBX_hsr_func {
@@ -82,6 +82,37 @@ fn test_sync() {
     }
 }
 
+#[test]
+fn test_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let tx = env.begin_rw_txn().unwrap();
+    tx.put(tx.open_db(None).unwrap().dbi(), b"key", b"val", WriteFlags::empty()).unwrap();
+    tx.commit().unwrap();
+
+    // Uncommitted writes are not part of the copy.
+    let tx = env.begin_rw_txn().unwrap();
+    tx.put(tx.open_db(None).unwrap().dbi(), b"key2", b"val2", WriteFlags::empty()).unwrap();
+
+    for compact in [false, true] {
+        let dest = tempdir().unwrap();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                env.copy(&dest.path().join("mdbx.dat"), compact).unwrap();
+                // The destination must not exist yet.
+                env.copy(&dest.path().join("mdbx.dat"), compact).unwrap_err();
+            });
+        });
+
+        let copy = Environment::builder().open(dest.path()).unwrap();
+        let txn = copy.begin_ro_txn().unwrap();
+        let db = txn.open_db(None).unwrap();
+        assert_eq!(txn.get(db.dbi(), b"key").unwrap(), Some(*b"val"));
+        assert_eq!(txn.get::<()>(db.dbi(), b"key2").unwrap(), None);
+    }
+    drop(tx);
+}
+
 #[test]
 fn test_stat() {
     let dir = tempdir().unwrap();
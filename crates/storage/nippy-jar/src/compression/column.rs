@@ -0,0 +1,291 @@
+use crate::{
+    compression::{Compression, Lz4, Zstd},
+    NippyJarError,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::HashMap,
+    io::Read,
+    sync::{Arc, OnceLock, RwLock},
+};
+use zstd::{
+    bulk::{Compressor, Decompressor},
+    dict::{DecoderDictionary, EncoderDictionary},
+};
+
+/// Registered [`Codec`]s, by name.
+static CODECS: OnceLock<RwLock<HashMap<&'static str, Arc<dyn Codec>>>> = OnceLock::new();
+
+/// A compression codec which can be selected for a column with [`ColumnCompression::Custom`].
+///
+/// Only the name of the codec is persisted in the jar configuration, so the codec needs to be
+/// registered with [`register_codec`] before writing to or reading from a jar using it.
+pub trait Codec: std::fmt::Debug + Send + Sync + 'static {
+    /// Name of the codec. It's persisted in the jar configuration, so it must never change.
+    fn name(&self) -> &'static str;
+
+    /// Appends compressed data from `src` to `dest`.
+    ///
+    /// Returns number of bytes written to `dest`.
+    fn compress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<usize, NippyJarError>;
+
+    /// Appends decompressed data from `src` to `dest`.
+    fn decompress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError>;
+}
+
+/// Registers a [`Codec`], replacing any previously registered codec with the same name.
+pub fn register_codec(codec: Arc<dyn Codec>) {
+    CODECS.get_or_init(Default::default).write().expect("not poisoned").insert(codec.name(), codec);
+}
+
+/// Returns the registered [`Codec`] with the given name.
+pub fn codec(name: &str) -> Option<Arc<dyn Codec>> {
+    CODECS.get()?.read().expect("not poisoned").get(name).cloned()
+}
+
+/// Compression of a single column of a [`NippyJar`](crate::NippyJar).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ColumnCompression {
+    /// Values are stored as they are, and read straight from the memory-mapped file.
+    None,
+    /// LZ4 compression.
+    Lz4,
+    /// Zstd compression without a dictionary. A level of `0` uses zstd's default.
+    Zstd {
+        /// Compression level.
+        level: i32,
+    },
+    /// Zstd compression with a dictionary trained on samples of the column. The dictionary needs
+    /// to be trained with [`ColumnCompression::train`] before any value is compressed.
+    ZstdDictionary {
+        /// Compression level.
+        level: i32,
+        /// Maximum size of the dictionary in bytes.
+        max_size: usize,
+        /// The trained dictionary.
+        dictionary: Option<ZstdColumnDictionary>,
+    },
+    /// A [`Codec`] registered with [`register_codec`].
+    Custom(String),
+}
+
+impl ColumnCompression {
+    /// Returns `true` if values can be compressed and decompressed.
+    pub fn is_ready(&self) -> bool {
+        match self {
+            Self::ZstdDictionary { dictionary, .. } => dictionary.is_some(),
+            Self::Custom(name) => codec(name).is_some(),
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if a dictionary needs to be trained before compressing.
+    pub const fn needs_training(&self) -> bool {
+        matches!(self, Self::ZstdDictionary { dictionary: None, .. })
+    }
+
+    /// Trains a dictionary on the provided samples, if the column uses one. Does nothing
+    /// otherwise.
+    pub fn train<T: AsRef<[u8]>>(&mut self, samples: &[T]) -> Result<(), NippyJarError> {
+        if let Self::ZstdDictionary { max_size, dictionary, .. } = self {
+            // ZSTD requires all training data to be continuous in memory, alongside the size of
+            // each entry
+            let sizes = samples.iter().map(|sample| sample.as_ref().len()).collect::<Vec<_>>();
+            let data =
+                samples.iter().flat_map(|sample| sample.as_ref()).copied().collect::<Vec<_>>();
+
+            *dictionary = Some(ZstdColumnDictionary::new(zstd::dict::from_continuous(
+                &data, &sizes, *max_size,
+            )?));
+        }
+
+        Ok(())
+    }
+
+    /// Appends compressed data from `src` to `dest`.
+    ///
+    /// Returns number of bytes written to `dest`.
+    pub fn compress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<usize, NippyJarError> {
+        let compressed = match self {
+            Self::None => {
+                dest.extend_from_slice(src);
+                return Ok(src.len())
+            }
+            Self::Lz4 => Lz4.compress(src)?,
+            Self::Zstd { level } => zstd::bulk::compress(src, *level)?,
+            Self::ZstdDictionary { level, dictionary, .. } => {
+                let dictionary = dictionary.as_ref().ok_or(NippyJarError::CompressorNotReady)?;
+                Compressor::with_prepared_dictionary(dictionary.encoder(*level))?.compress(src)?
+            }
+            Self::Custom(name) => return codec_by_name(name)?.compress_to(src, dest),
+        };
+
+        dest.extend_from_slice(&compressed);
+        Ok(compressed.len())
+    }
+
+    /// Appends decompressed data from `src` to `dest`. Requires `dest` to have sufficient capacity
+    /// for LZ4 and dictionary zstd.
+    pub fn decompress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
+        match self {
+            Self::None => {
+                dest.extend_from_slice(src);
+                Ok(())
+            }
+            Self::Lz4 => Lz4.decompress_to(src, dest),
+            Self::Zstd { .. } => {
+                zstd::Decoder::new(src)?.read_to_end(dest)?;
+                Ok(())
+            }
+            Self::ZstdDictionary { dictionary, .. } => {
+                let dictionary = dictionary.as_ref().ok_or(NippyJarError::DecompressorNotReady)?;
+                let mut decompressor =
+                    Decompressor::with_prepared_dictionary(dictionary.decoder())?;
+                Zstd::decompress_with_dictionary(src, dest, &mut decompressor)
+            }
+            Self::Custom(name) => codec_by_name(name)?.decompress_to(src, dest),
+        }
+    }
+}
+
+fn codec_by_name(name: &str) -> Result<Arc<dyn Codec>, NippyJarError> {
+    codec(name).ok_or_else(|| NippyJarError::UnknownCodec(name.to_string()))
+}
+
+/// A trained zstd dictionary of a column. Only the raw dictionary is serialized, while the
+/// prepared encoder and decoder dictionaries are created when needed.
+#[derive(Clone)]
+pub struct ZstdColumnDictionary(Arc<ZstdColumnDictionaryInner>);
+
+struct ZstdColumnDictionaryInner {
+    raw: Vec<u8>,
+    decoder: DecoderDictionary<'static>,
+    encoder: OnceLock<(i32, EncoderDictionary<'static>)>,
+}
+
+impl ZstdColumnDictionary {
+    /// Creates a [`ZstdColumnDictionary`] from a raw dictionary.
+    pub fn new(raw: Vec<u8>) -> Self {
+        let decoder = DecoderDictionary::copy(&raw);
+        Self(Arc::new(ZstdColumnDictionaryInner { raw, decoder, encoder: OnceLock::new() }))
+    }
+
+    /// Returns the raw dictionary.
+    pub fn raw(&self) -> &[u8] {
+        &self.0.raw
+    }
+
+    /// Returns the prepared decoder dictionary.
+    pub fn decoder(&self) -> &DecoderDictionary<'static> {
+        &self.0.decoder
+    }
+
+    /// Returns the prepared encoder dictionary. A column is always compressed with the same level,
+    /// so it's only prepared once.
+    fn encoder(&self, level: i32) -> &EncoderDictionary<'static> {
+        let (prepared_level, encoder) =
+            self.0.encoder.get_or_init(|| (level, EncoderDictionary::copy(&self.0.raw, level)));
+        debug_assert_eq!(*prepared_level, level);
+        encoder
+    }
+}
+
+impl std::fmt::Debug for ZstdColumnDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdColumnDictionary").field("size", &self.0.raw.len()).finish()
+    }
+}
+
+impl Serialize for ZstdColumnDictionary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ZstdColumnDictionary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::new(Vec::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for ZstdColumnDictionary {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw() == other.raw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Reverse;
+
+    impl Codec for Reverse {
+        fn name(&self) -> &'static str {
+            "reverse"
+        }
+
+        fn compress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<usize, NippyJarError> {
+            dest.extend(src.iter().rev());
+            Ok(src.len())
+        }
+
+        fn decompress_to(&self, src: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
+            dest.extend(src.iter().rev());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn compress_decompress() {
+        let samples = (0..1000u64)
+            .map(|i| format!("{{\"nonce\":{i},\"to\":\"0xdeadbeef\",\"value\":{}}}", i * 7))
+            .collect::<Vec<_>>();
+
+        let mut dictionary =
+            ColumnCompression::ZstdDictionary { level: 0, max_size: 1024, dictionary: None };
+        assert!(!dictionary.is_ready());
+        assert!(matches!(
+            dictionary.compress_to(b"value", &mut Vec::new()),
+            Err(NippyJarError::CompressorNotReady)
+        ));
+        dictionary.train(&samples).unwrap();
+        assert!(dictionary.is_ready());
+
+        let custom = ColumnCompression::Custom("reverse".to_string());
+        assert!(matches!(
+            custom.compress_to(b"value", &mut Vec::new()),
+            Err(NippyJarError::UnknownCodec(name)) if name == "reverse"
+        ));
+        register_codec(Arc::new(Reverse));
+
+        for compression in [
+            ColumnCompression::None,
+            ColumnCompression::Lz4,
+            ColumnCompression::Zstd { level: 0 },
+            dictionary,
+            custom,
+        ] {
+            for sample in &samples {
+                let mut compressed = vec![1];
+                let written = compression.compress_to(sample.as_bytes(), &mut compressed).unwrap();
+                assert_eq!(written, compressed.len() - 1);
+
+                let mut decompressed = Vec::with_capacity(sample.len() + 1);
+                decompressed.push(1);
+                compression.decompress_to(&compressed[1..], &mut decompressed).unwrap();
+                assert_eq!(&decompressed[1..], sample.as_bytes(), "{compression:?}");
+            }
+
+            // Dictionaries are serialized as raw bytes and prepared again on deserialization.
+            let serialized = bincode::serialize(&compression).unwrap();
+            assert_eq!(
+                bincode::deserialize::<ColumnCompression>(&serialized).unwrap(),
+                compression
+            );
+        }
+    }
+}
@@ -5,6 +5,8 @@ mod zstd;
 pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState};
 mod lz4;
 pub use self::lz4::Lz4;
+mod column;
+pub use self::column::{codec, register_codec, Codec, ColumnCompression, ZstdColumnDictionary};
 
 /// Trait that will compress column values
 pub trait Compression: Serialize + for<'a> Deserialize<'a> {
@@ -46,6 +48,18 @@ pub trait Compression: Serialize + for<'a> Deserialize<'a> {
 pub enum Compressors {
     Zstd(Zstd),
     Lz4(Lz4),
+    /// A [`ColumnCompression`] for each column.
+    PerColumn(Vec<ColumnCompression>),
+}
+
+impl Compressors {
+    /// Returns the [`ColumnCompression`] of each column, if compression is selected per column.
+    pub fn columns(&self) -> Option<&[ColumnCompression]> {
+        match self {
+            Self::PerColumn(columns) => Some(columns),
+            _ => None,
+        }
+    }
 }
 
 impl Compression for Compressors {
@@ -53,12 +67,14 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.decompress_to(value, dest),
             Self::Lz4(lz4) => lz4.decompress_to(value, dest),
+            Self::PerColumn(_) => Err(NippyJarError::ColumnCompressionRequired),
         }
     }
     fn decompress(&self, value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
         match self {
             Self::Zstd(zstd) => zstd.decompress(value),
             Self::Lz4(lz4) => lz4.decompress(value),
+            Self::PerColumn(_) => Err(NippyJarError::ColumnCompressionRequired),
         }
     }
 
@@ -68,6 +84,7 @@ impl Compression for Compressors {
             let result = match self {
                 Self::Zstd(zstd) => zstd.compress_to(src, dest),
                 Self::Lz4(lz4) => lz4.compress_to(src, dest),
+                Self::PerColumn(_) => Err(NippyJarError::ColumnCompressionRequired),
             };
 
             match result {
@@ -86,6 +103,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.compress(src),
             Self::Lz4(lz4) => lz4.compress(src),
+            Self::PerColumn(_) => Err(NippyJarError::ColumnCompressionRequired),
         }
     }
 
@@ -93,6 +111,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.is_ready(),
             Self::Lz4(lz4) => lz4.is_ready(),
+            Self::PerColumn(columns) => columns.iter().all(ColumnCompression::is_ready),
         }
     }
 
@@ -104,6 +123,15 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.prepare_compression(columns),
             Self::Lz4(lz4) => lz4.prepare_compression(columns),
+            Self::PerColumn(compressions) => {
+                if columns.len() != compressions.len() {
+                    return Err(NippyJarError::ColumnLenMismatch(compressions.len(), columns.len()))
+                }
+                for (compression, column) in compressions.iter_mut().zip(columns) {
+                    compression.train(&column.into_iter().collect::<Vec<_>>())?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    compression::{Compression, Compressors, Zstd},
+    compression::{ColumnCompression, Compression, Compressors, Zstd},
     DataReader, NippyJar, NippyJarError, NippyJarHeader, RefRow,
 };
 use std::{ops::Range, sync::Arc};
@@ -158,7 +158,22 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             value_offset..next_value_offset
         };
 
-        if let Some(compression) = self.jar.compressor() {
+        if let Some(column_compression) = self
+            .jar
+            .compressor()
+            .and_then(|compression| compression.columns())
+            .map(|columns| &columns[column])
+            .filter(|compression| !matches!(compression, ColumnCompression::None))
+        {
+            let from = self.internal_buffer.len();
+            column_compression
+                .decompress_to(self.reader.data(column_offset_range), &mut self.internal_buffer)?;
+            let to = self.internal_buffer.len();
+
+            row.push(ValueRange::Internal(from..to));
+        } else if let Some(compression) =
+            self.jar.compressor().filter(|compression| compression.columns().is_none())
+        {
             let from = self.internal_buffer.len();
             match compression {
                 Compressors::Zstd(z) if z.use_dict => {
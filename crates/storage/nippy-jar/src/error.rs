@@ -53,4 +53,8 @@ pub enum NippyJarError {
     InconsistentState,
     #[error("Missing file: {0}.")]
     MissingFile(PathBuf),
+    #[error("codec {0} is not registered.")]
+    UnknownCodec(String),
+    #[error("per-column compression requires the column of the value.")]
+    ColumnCompressionRequired,
 }
@@ -169,6 +169,41 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Selects the compression of each column. Columns with a zstd dictionary need to be trained
+    /// with [`NippyJar::train_column_compression`] before any data is written.
+    pub fn with_column_compression(mut self, columns: Vec<compression::ColumnCompression>) -> Self {
+        debug_assert_eq!(columns.len(), self.columns);
+        self.compressor = Some(Compressors::PerColumn(columns));
+        self
+    }
+
+    /// Trains the dictionaries of columns compressed with per-column compression, using `samples`
+    /// of each column. Columns which don't need training are left untouched.
+    ///
+    /// Can only be called before any data is written.
+    pub fn train_column_compression(
+        &mut self,
+        samples: &[Vec<Vec<u8>>],
+    ) -> Result<(), NippyJarError> {
+        if self.rows > 0 {
+            return Err(NippyJarError::FrozenJar)
+        }
+
+        if let Some(Compressors::PerColumn(columns)) = &mut self.compressor {
+            if samples.len() != columns.len() {
+                return Err(NippyJarError::ColumnLenMismatch(columns.len(), samples.len()))
+            }
+
+            for (column, samples) in columns.iter_mut().zip(samples) {
+                if column.needs_training() {
+                    column.train(samples)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -243,6 +278,26 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Returns up to `max_rows` rows spread evenly over the jar, as a list of values per column.
+    ///
+    /// Used to train the dictionaries of [`NippyJar::train_column_compression`].
+    pub fn sample_columns(&self, max_rows: usize) -> Result<Vec<Vec<Vec<u8>>>, NippyJarError> {
+        let mut columns = vec![Vec::new(); self.columns];
+        if self.rows == 0 || max_rows == 0 {
+            return Ok(columns)
+        }
+
+        let mut cursor = NippyJarCursor::new(self)?;
+        for row in (0..self.rows).step_by(self.rows.div_ceil(max_rows)) {
+            let Some(values) = cursor.row_by_number(row)? else { break };
+            for (column, value) in columns.iter_mut().zip(values) {
+                column.push(value.to_vec());
+            }
+        }
+
+        Ok(columns)
+    }
+
     /// Returns a [`DataReader`] of the data and offset file
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
         DataReader::new(self.data_path())
@@ -585,6 +640,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_column_compression() {
+        let (col1, col2) = test_data(None);
+        let col3 = (0..col1.len())
+            .map(|i| format!("{{\"nonce\":{i},\"to\":\"0xdeadbeef\"}}").into_bytes())
+            .collect::<Vec<_>>();
+        let num_rows = col1.len() as u64;
+        let num_columns = 3;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_column_compression(vec![
+                compression::ColumnCompression::None,
+                compression::ColumnCompression::Lz4,
+                compression::ColumnCompression::ZstdDictionary {
+                    level: 0,
+                    max_size: 1024,
+                    dictionary: None,
+                },
+            ]);
+        assert!(!nippy.compressor().unwrap().is_ready());
+        assert!(matches!(
+            nippy.train_column_compression(&[col1.clone(), col2.clone()]),
+            Err(NippyJarError::ColumnLenMismatch(3, 2))
+        ));
+
+        // Only the dictionary column is trained, the samples of other columns are ignored.
+        nippy.train_column_compression(&[vec![], vec![], col3.clone()]).unwrap();
+        assert!(nippy.compressor().unwrap().is_ready());
+
+        let nippy = nippy
+            .freeze(
+                vec![clone_with_result(&col1), clone_with_result(&col2), clone_with_result(&col3)],
+                num_rows,
+            )
+            .unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy, loaded_nippy);
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!(
+                (row[0], row[1], row[2]),
+                (
+                    col1[row_index].as_slice(),
+                    col2[row_index].as_slice(),
+                    col3[row_index].as_slice()
+                )
+            );
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+
+        // Read only some columns
+        let row = cursor.row_by_number_with_cols(7, 0b101).unwrap().unwrap();
+        assert_eq!((row[0], row[1]), (col1[7].as_slice(), col3[7].as_slice()));
+    }
+
     #[test]
     fn test_zstd_no_dictionaries() {
         let (col1, col2) = test_data(None);
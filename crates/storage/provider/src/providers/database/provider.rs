@@ -1,6 +1,9 @@
 use crate::{
     bundle_state::StorageRevertsIter,
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{
+        database::metrics, state::snap::HashedStateFallback, static_file::StaticFileWriter,
+        StaticFileProvider,
+    },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
@@ -9,14 +12,17 @@ use crate::{
     AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, DBProvider, EvmEnvProvider, FinalizedBlockReader,
     FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HistoricalStateProvider, HistoryWriter, LatestStateProvider, OriginalValuesKnown,
-    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, RevertsInit,
-    StageCheckpointReader, StateChangeWriter, StateProviderBox, StateReader, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageReader, StorageTrieWriter, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+    HistoricalStateProvider, HistoryWriter, LatestStateProvider, LogIndexReader, LogIndexWriter,
+    OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
+    RequestsProvider, RevertsInit, StageCheckpointReader, StateChangeWriter, StateProviderBox,
+    StateReader, StateWriter, StaticFileProviderFactory, StatsReader, StorageReader,
+    StorageTrieWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    TrieWriter, WithdrawalsProvider,
 };
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{
+    keccak256, Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
+};
 use itertools::{izip, Itertools};
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, EthereumHardforks};
@@ -311,6 +317,37 @@ where
     Ok(Vec::new())
 }
 
+/// Collects the block numbers in `range` from the log index shards of the given key.
+///
+/// Shards are walked starting from the first one that can contain `range.start()`, and the walk
+/// stops as soon as a shard covers `range.end()`.
+fn log_index_blocks<K, T, C>(
+    cursor: &mut C,
+    key: K,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    K: Copy + PartialEq,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    let (from, to) = range.into_inner();
+    let mut blocks = Vec::new();
+    let mut item = cursor.seek(ShardedKey::new(key, from))?;
+    while let Some((sharded_key, list)) = item {
+        if sharded_key.key != key {
+            break
+        }
+        blocks
+            .extend(list.iter().skip_while(|block| *block < from).take_while(|block| *block <= to));
+        if sharded_key.highest_block_number >= to {
+            break
+        }
+        item = cursor.next()?;
+    }
+    Ok(blocks)
+}
+
 impl<TX: DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -373,6 +410,44 @@ impl<TX: DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
         self.tx.cursor_read::<T>()?.walk_range(range)?.collect::<Result<Vec<_>, _>>()
     }
 
+    /// Returns the account changesets of the given block range, reading them from static files
+    /// and the database.
+    pub fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.account_changesets_range(range),
+            |range, _| Ok(self.get::<tables::AccountChangeSets>(range)?),
+            |_| true,
+        )
+    }
+
+    /// Returns the storage changesets of the given range, reading them from static files and the
+    /// database.
+    pub fn storage_changesets_range(
+        &self,
+        range: Range<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let block_range = range.start.block_number()..range.end.block_number().saturating_add(1);
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            block_range,
+            |static_file, block_range, _| {
+                let mut changesets = static_file.storage_changesets_range(block_range)?;
+                changesets.retain(|(key, _)| range.contains(key));
+                Ok(changesets)
+            },
+            |block_range, _| {
+                let start = range.start.max(BlockNumberAddress((block_range.start, Address::ZERO)));
+                Ok(self.get::<tables::StorageChangeSets>(start..range.end)?)
+            },
+            |_| true,
+        )
+    }
+
     /// Iterates over read only values in the given table and collects them into a vector.
     ///
     /// Early-returns if the range is empty, without opening a cursor transaction.
@@ -857,8 +932,8 @@ impl<TX: DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
 
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let storage_changeset = self.get::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.get::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.storage_changesets_range(storage_range)?;
+        let account_changeset = self.account_changesets_range(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -1020,6 +1095,40 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
         Ok(items)
     }
 
+    /// Removes the account and storage changesets of the given block range from the database and
+    /// static files, returning them.
+    ///
+    /// The static file removal is only applied once the static file writers are committed.
+    fn take_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(
+        Vec<(BlockNumber, AccountBeforeTx)>,
+        Vec<(BlockNumberAddress, StorageEntry)>,
+    )> {
+        let storage_changeset =
+            self.storage_changesets_range(BlockNumberAddress::range(range.clone()))?;
+        let account_changeset = self.account_changesets_range(range.clone())?;
+
+        self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?;
+        self.remove::<tables::AccountChangeSets>(range.clone())?;
+
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            if let Some(highest_block) = self
+                .static_file_provider
+                .get_highest_static_file_block(segment)
+                .filter(|highest_block| *highest_block >= *range.start())
+            {
+                self.static_file_provider
+                    .latest_writer(segment)?
+                    .prune_changesets(highest_block - *range.start() + 1)?;
+            }
+        }
+
+        Ok((account_changeset, storage_changeset))
+    }
+
     /// Remove requested block transactions, without returning them.
     ///
     /// This will remove block data for the given range from the following tables:
@@ -1370,7 +1479,10 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
 
 impl<TX: DbTx, Spec: Send + Sync> AccountReader for DatabaseProvider<TX, Spec> {
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        Ok(self.tx.get::<tables::PlainAccountState>(address)?)
+        if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+        HashedStateFallback::new().account(&self.tx, address)
     }
 }
 
@@ -1379,13 +1491,11 @@ impl<TX: DbTx, Spec: Send + Sync> AccountExtReader for DatabaseProvider<TX, Spec
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -1403,16 +1513,13 @@ impl<TX: DbTx, Spec: Send + Sync> AccountExtReader for DatabaseProvider<TX, Spec
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -1423,15 +1530,25 @@ impl<TX: DbTx, Spec: Send + Sync> ChangeSetReader for DatabaseProvider<TX, Spec>
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |static_file| static_file.account_block_changeset(block_number).map(Some),
+                || {
+                    let range = block_number..=block_number;
+                    self.tx
+                        .cursor_read::<tables::AccountChangeSets>()?
+                        .walk_range(range)?
+                        .map(|result| -> ProviderResult<_> {
+                            let (_, account_before) = result?;
+                            Ok(account_before)
+                        })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
     }
 }
 
@@ -2235,6 +2352,31 @@ impl<TX: DbTx, Spec: Send + Sync> StageCheckpointReader for DatabaseProvider<TX,
     }
 }
 
+impl<TX: DbTx, Spec: Send + Sync> LogIndexReader for DatabaseProvider<TX, Spec> {
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(StageId::IndexLogs)?
+            .map(|checkpoint| checkpoint.block_number)
+            .filter(|block_number| *block_number > 0))
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        log_index_blocks(&mut self.tx.cursor_read::<tables::LogAddressIndex>()?, address, range)
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        log_index_blocks(&mut self.tx.cursor_read::<tables::LogTopicIndex>()?, topic, range)
+    }
+}
+
 impl<TX: DbTxMut, Spec: Send + Sync> StageCheckpointWriter for DatabaseProvider<TX, Spec> {
     /// Save stage checkpoint.
     fn save_stage_checkpoint(
@@ -2304,36 +2446,36 @@ impl<TX: DbTx, Spec: Send + Sync> StorageReader for DatabaseProvider<TX, Spec> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets_range(BlockNumberAddress::range(range))?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            .fold(
+                BTreeMap::new(),
+                |mut accounts: BTreeMap<Address, BTreeSet<B256>>,
+                 (BlockNumberAddress((_, address)), storage_entry)| {
+                    accounts.entry(address).or_default().insert(storage_entry.key);
+                    accounts
+                },
+            ))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
         let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
+            self.storage_changesets_range(BlockNumberAddress::range(range))?.into_iter().fold(
                 BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
+                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
                     storages
                         .entry((index.address(), storage.key))
                         .or_default()
                         .push(index.block_number());
-                    Ok(storages)
+                    storages
                 },
-            )?;
+            );
 
         Ok(storage_changeset_lists)
     }
@@ -2543,10 +2685,7 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> StateChangeWriter for DatabaseProvid
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) = self.take_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2636,10 +2775,7 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> StateChangeWriter for DatabaseProvid
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) = self.take_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2812,12 +2948,9 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HashingWriter for DatabaseProvider<T
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
         let hashed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(_, e)| (keccak256(e.address), e.info)))
-            .collect::<Result<Vec<_>, _>>()?
+            .account_changesets_range(range)?
             .into_iter()
+            .map(|(_, e)| (keccak256(e.address), e.info))
             .rev()
             .collect::<BTreeMap<_, _>>();
 
@@ -2856,15 +2989,13 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HashingWriter for DatabaseProvider<T
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         // Aggregate all block changesets and make list of accounts that have been changed.
-        let mut changesets = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-        let mut hashed_storages = changesets
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                    (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
-                })
+        let mut hashed_storages = self
+            .storage_changesets_range(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), storage_entry)| {
+                (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
 
         // Apply values to HashedState, and remove the account if it's None.
@@ -3022,17 +3153,94 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HashingWriter for DatabaseProvider<T
     }
 }
 
+impl<TX: DbTxMut + DbTx, Spec: Send + Sync> LogIndexWriter for DatabaseProvider<TX, Spec> {
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let first_block = *range.start();
+
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+            let (_, body) = entry?;
+            for receipt in self.receipts_by_tx_range(body.tx_num_range())? {
+                for log in receipt.logs {
+                    addresses.insert(log.address);
+                    topics.extend(log.topics().iter().copied());
+                }
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressIndex>()?;
+        for &address in &addresses {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressIndex, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_block,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicIndex>()?;
+        for &topic in &topics {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicIndex, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                first_block,
+                |sharded_key| sharded_key.key == topic,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len() + topics.len())
+    }
+
+    fn insert_log_indices<'a>(
+        &self,
+        logs: impl IntoIterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        let push_block = |blocks: &mut Vec<BlockNumber>, block_number: BlockNumber| {
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+        };
+        for (block_number, log) in logs {
+            push_block(addresses.entry(log.address).or_default(), block_number);
+            for topic in log.topics() {
+                push_block(topics.entry(*topic).or_default(), block_number);
+            }
+        }
+
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+}
+
 impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HistoryWriter for DatabaseProvider<TX, Spec> {
     fn unwind_account_history_indices(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
         let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(index, account)| (account.address, index))
+            .collect::<Vec<_>>();
         last_indices.sort_by_key(|(a, _)| *a);
 
         // Unwind the account history index.
@@ -3074,13 +3282,10 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HistoryWriter for DatabaseProvider<T
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
         let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+            .storage_changesets_range(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
+            .collect::<Vec<_>>();
         storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
 
         let mut cursor = self.tx.cursor_write::<tables::StoragesHistory>()?;
@@ -3200,6 +3405,17 @@ impl<TX: DbTxMut + DbTx + 'static, Spec: Send + Sync + EthereumHardforks + 'stat
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind log indices if the optional log index stage has indexed this range. This has to
+        // happen before the receipts are removed, and since the stage is not part of
+        // `StageId::ALL` its checkpoint is moved back here.
+        if self.log_index_tip()?.is_some_and(|tip| tip >= *range.start()) {
+            self.unwind_log_indices(range.clone())?;
+            self.save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(range.start().saturating_sub(1)),
+            )?;
+        }
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
@@ -3295,6 +3511,17 @@ impl<TX: DbTxMut + DbTx + 'static, Spec: Send + Sync + EthereumHardforks + 'stat
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind log indices if the optional log index stage has indexed this range. This has to
+        // happen before the receipts are removed, and since the stage is not part of
+        // `StageId::ALL` its checkpoint is moved back here.
+        if self.log_index_tip()?.is_some_and(|tip| tip >= *range.start()) {
+            self.unwind_log_indices(range.clone())?;
+            self.save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(range.start().saturating_sub(1)),
+            )?;
+        }
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
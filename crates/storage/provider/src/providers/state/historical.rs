@@ -1,19 +1,24 @@
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
-    AccountReader, BlockHashReader, ProviderError, StateProvider, StateRootProvider,
+    providers::{
+        state::{macros::delegate_provider_impls, snap::HashedStateFallback},
+        StaticFileProvider,
+    },
+    AccountReader, BlockHashReader, ChangeSetReader, ProviderError, StateProvider,
+    StateRootProvider,
 };
 use alloy_primitives::{
+    keccak256,
     map::{HashMap, HashSet},
-    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
     table::Table,
     transaction::DbTx,
 };
-use reth_primitives::{constants::EPOCH_SLOTS, Account, Bytecode, StaticFileSegment};
+use reth_primitives::{constants::EPOCH_SLOTS, Account, Bytecode, StaticFileSegment, StorageEntry};
 use reth_storage_api::{StateProofProvider, StorageRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -24,7 +29,7 @@ use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageRoot, DatabaseTrieWitness,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Range};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -37,6 +42,10 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets that have been moved to static files are read from the
+/// [`StaticFileSegment::AccountChangeSets`] and [`StaticFileSegment::StorageChangeSets`] segments
+/// instead.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -47,6 +56,8 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Lookups of the state missing from the plain state of a snap synced database.
+    hashed_state_fallback: HashedStateFallback,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -64,7 +75,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            hashed_state_fallback: HashedStateFallback::new(),
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -75,7 +92,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         lowest_available_blocks: LowestAvailableBlocks,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks, static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            static_file_provider,
+            hashed_state_fallback: HashedStateFallback::new(),
+        }
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -127,8 +150,11 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         Ok(tip.saturating_sub(self.block_number) > limit)
     }
 
-    /// Retrieve revert hashed state for this history provider.
-    fn revert_state(&self) -> ProviderResult<HashedPostState> {
+    /// Retrieve revert hashed state for this history provider, from the changesets in the database
+    /// and in static files.
+    ///
+    /// Returns [`ProviderError::StateAtBlockPruned`] if the changesets were pruned.
+    pub fn revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -143,7 +169,71 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedPostState::from_reverts(self.tx, self.block_number)?)
+        let mut revert_state = HashedPostState::from_reverts(self.tx, self.block_number)?;
+        revert_state.extend(self.static_file_revert_state()?);
+        Ok(revert_state)
+    }
+
+    /// Retrieve revert hashed state from the changesets in static files, which precede the ones
+    /// in the database.
+    ///
+    /// The changesets are read one block at a time, only the earliest value of each changed key
+    /// is kept.
+    fn static_file_revert_state(&self) -> ProviderResult<HashedPostState> {
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::AccountChangeSets)
+        {
+            for changeset in self.static_file_provider.account_changesets_iter(range)? {
+                for AccountBeforeTx { address, info } in changeset?.1 {
+                    accounts.entry(address).or_insert(info);
+                }
+            }
+        }
+
+        let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::StorageChangeSets)
+        {
+            for changeset in self.static_file_provider.storage_changesets_iter(range)? {
+                for change in changeset?.1 {
+                    storages
+                        .entry(change.address)
+                        .or_default()
+                        .entry(change.key)
+                        .or_insert(change.value);
+                }
+            }
+        }
+
+        Ok(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (keccak256(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the range of blocks from this provider's block whose changesets are in static
+    /// files, if any.
+    fn static_file_changesets_range(
+        &self,
+        segment: StaticFileSegment,
+    ) -> Option<Range<BlockNumber>> {
+        self.static_file_provider
+            .get_highest_static_file_block(segment)
+            .filter(|highest_block| *highest_block >= self.block_number)
+            .map(|highest_block| self.block_number..highest_block + 1)
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -160,7 +250,73 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx, address, self.block_number)?)
+        let mut revert_storage = HashedStorage::from_reverts(self.tx, address, self.block_number)?;
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::StorageChangeSets)
+        {
+            let mut storage = HashMap::<B256, U256>::default();
+            for changeset in self.static_file_provider.storage_changesets_iter(range)? {
+                for change in changeset?.1.into_iter().filter(|change| change.address == address) {
+                    storage.entry(change.key).or_insert(change.value);
+                }
+            }
+            revert_storage.extend(&HashedStorage::from_iter(
+                false,
+                storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+            ));
+        }
+        Ok(revert_storage)
+    }
+
+    /// Looks up the account as it was before the given block in the changesets.
+    fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            |static_file| {
+                Ok(static_file
+                    .account_block_changeset(block_number)?
+                    .into_iter()
+                    .find(|acc| acc.address == address))
+            },
+            || {
+                Ok(self
+                    .tx
+                    .cursor_dup_read::<tables::AccountChangeSets>()?
+                    .seek_by_key_subkey(block_number, address)?
+                    .filter(|acc| acc.address == address))
+            },
+        )
+    }
+
+    /// Looks up the storage slot as it was before the given block in the changesets.
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            |static_file| {
+                Ok(static_file
+                    .storage_block_changeset(block_number)?
+                    .into_iter()
+                    .find(|entry| entry.address == address && entry.key == storage_key)
+                    .map(|entry| entry.entry()))
+            },
+            || {
+                Ok(self
+                    .tx
+                    .cursor_dup_read::<tables::StorageChangeSets>()?
+                    .seek_by_key_subkey((block_number, address).into(), storage_key)?
+                    .filter(|entry| entry.key == storage_key))
+            },
+        )
     }
 
     fn history_info<T, K>(
@@ -233,17 +389,17 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .account_changeset(changeset_block_number, address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx.get::<tables::PlainAccountState>(address)?)
+                if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+                    return Ok(Some(account))
+                }
+                self.hashed_state_fallback.account(self.tx, address)
             }
         }
     }
@@ -374,10 +530,7 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -385,13 +538,20 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                if let Some(entry) = self
+                    .tx
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                {
+                    return Ok(Some(entry.value))
+                }
+                Ok(self
+                    .hashed_state_fallback
+                    .storage(self.tx, address, storage_key)?
+                    .or(Some(StorageValue::ZERO)))
+            }
         }
     }
 
@@ -1,5 +1,8 @@
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
+    providers::{
+        state::{macros::delegate_provider_impls, snap::HashedStateFallback},
+        StaticFileProvider,
+    },
     AccountReader, BlockHashReader, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{
@@ -27,19 +30,24 @@ pub struct LatestStateProviderRef<'b, TX: DbTx> {
     tx: &'b TX,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Lookups of the state missing from the plain state of a snap synced database.
+    hashed_state_fallback: HashedStateFallback,
 }
 
 impl<'b, TX: DbTx> LatestStateProviderRef<'b, TX> {
     /// Create new state provider
     pub const fn new(tx: &'b TX, static_file_provider: StaticFileProvider) -> Self {
-        Self { tx, static_file_provider }
+        Self { tx, static_file_provider, hashed_state_fallback: HashedStateFallback::new() }
     }
 }
 
 impl<'b, TX: DbTx> AccountReader for LatestStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        self.tx.get::<tables::PlainAccountState>(address).map_err(Into::into)
+        if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+        self.hashed_state_fallback.account(self.tx, address)
     }
 }
 
@@ -159,7 +167,7 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        self.hashed_state_fallback.storage(self.tx, account, storage_key)
     }
 
     /// Get account code by its hash
@@ -0,0 +1,196 @@
+use alloy_primitives::{keccak256, map::HashSet, Address, BlockNumber, StorageKey, StorageValue};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::BlockNumberAddress,
+    transaction::DbTx,
+};
+use reth_primitives::Account;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::ProviderResult;
+use std::sync::OnceLock;
+
+/// Returns whether the database was snap synced, see
+/// [`StageCheckpointReader::is_snap_synced`](reth_storage_api::StageCheckpointReader::is_snap_synced).
+pub(crate) fn is_snap_synced<TX: DbTx>(tx: &TX) -> ProviderResult<bool> {
+    Ok(tx
+        .get::<tables::StageCheckpoints>(StageId::SnapSync.to_string())?
+        .is_some_and(|checkpoint| checkpoint.block_number > 0))
+}
+
+/// Reads the accounts and storage slots missing from the plain state of a snap synced database
+/// from the hashed state, which holds the state that didn't change since the pivot block.
+///
+/// The hashed state lags behind the plain state while the pipeline executes blocks, so accounts
+/// and storage slots that changed in the executed blocks that weren't hashed yet are never read
+/// from it: if they are missing from the plain state, they were deleted.
+///
+/// On databases that weren't snap synced, the plain state is complete and nothing is read.
+#[derive(Debug, Default)]
+pub(crate) struct HashedStateFallback {
+    /// The changes the hashed state is missing, or [`None`] if the database wasn't snap synced.
+    /// Read on the first lookup.
+    unhashed_changes: OnceLock<Option<UnhashedChanges>>,
+}
+
+impl HashedStateFallback {
+    /// Creates a new fallback.
+    pub(crate) const fn new() -> Self {
+        Self { unhashed_changes: OnceLock::new() }
+    }
+
+    fn unhashed_changes<TX: DbTx>(&self, tx: &TX) -> ProviderResult<Option<&UnhashedChanges>> {
+        if let Some(unhashed_changes) = self.unhashed_changes.get() {
+            return Ok(unhashed_changes.as_ref())
+        }
+        let unhashed_changes =
+            if is_snap_synced(tx)? { Some(UnhashedChanges::read(tx)?) } else { None };
+        Ok(self.unhashed_changes.get_or_init(|| unhashed_changes).as_ref())
+    }
+
+    /// Returns the account missing from the plain state.
+    pub(crate) fn account<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+    ) -> ProviderResult<Option<Account>> {
+        let Some(unhashed_changes) = self.unhashed_changes(tx)? else { return Ok(None) };
+        if unhashed_changes.accounts.contains(&address) {
+            return Ok(None)
+        }
+        Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+    }
+
+    /// Returns the storage slot missing from the plain state.
+    pub(crate) fn storage<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let Some(unhashed_changes) = self.unhashed_changes(tx)? else { return Ok(None) };
+        if unhashed_changes.storages.contains(&(address, storage_key)) {
+            return Ok(None)
+        }
+        let hashed_slot = keccak256(storage_key);
+        Ok(tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|entry| entry.key == hashed_slot)
+            .map(|entry| entry.value))
+    }
+}
+
+/// The accounts and storage slots changed in the blocks that were executed, but not hashed yet.
+#[derive(Debug, Default)]
+struct UnhashedChanges {
+    /// Accounts changed after the [`StageId::AccountHashing`] checkpoint.
+    accounts: HashSet<Address>,
+    /// Storage slots changed after the [`StageId::StorageHashing`] checkpoint.
+    storages: HashSet<(Address, StorageKey)>,
+}
+
+impl UnhashedChanges {
+    /// Reads the changesets of the blocks between the hashing and the execution checkpoints.
+    ///
+    /// Once the pipeline is done, or when the engine persists blocks, all checkpoints are equal
+    /// and nothing is read.
+    fn read<TX: DbTx>(tx: &TX) -> ProviderResult<Self> {
+        let checkpoint = |stage_id: StageId| -> ProviderResult<BlockNumber> {
+            Ok(tx
+                .get::<tables::StageCheckpoints>(stage_id.to_string())?
+                .map(|checkpoint| checkpoint.block_number)
+                .unwrap_or_default())
+        };
+        let executed = checkpoint(StageId::Execution)?;
+        let mut changes = Self::default();
+
+        let account_range = checkpoint(StageId::AccountHashing)? + 1..=executed;
+        for entry in tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(account_range)? {
+            changes.accounts.insert(entry?.1.address);
+        }
+
+        let storage_range = checkpoint(StageId::StorageHashing)? + 1..=executed;
+        for entry in tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(storage_range))?
+        {
+            let (BlockNumberAddress((_, address)), entry) = entry?;
+            changes.storages.insert((address, entry.key));
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::create_test_provider_factory, AccountReader, DBProvider,
+        LatestStateProviderRef, StageCheckpointWriter, StateProvider, StaticFileProviderFactory,
+    };
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use reth_db::tables;
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_primitives::{Account, StorageEntry};
+    use reth_stages_types::{StageCheckpoint, StageId};
+
+    #[test]
+    fn deleted_state_is_not_read_from_stale_hashed_state() {
+        let factory = create_test_provider_factory();
+        let (destroyed, contract) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (cleared, kept) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let account = Account { nonce: 1, ..Default::default() };
+
+        // the state at the pivot block 1 is only in the hashed state
+        let provider_rw = factory.provider_rw().unwrap();
+        for address in [destroyed, contract] {
+            provider_rw
+                .tx_ref()
+                .put::<tables::HashedAccounts>(keccak256(address), account)
+                .unwrap();
+        }
+        for slot in [cleared, kept] {
+            let entry = StorageEntry { key: keccak256(slot), value: U256::from(1) };
+            provider_rw.tx_ref().put::<tables::HashedStorages>(keccak256(contract), entry).unwrap();
+        }
+        for stage_id in [
+            StageId::SnapSync,
+            StageId::Execution,
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+        ] {
+            provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(1)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // a first batch executes block 2, which destroys an account and clears a storage slot
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountChangeSets>(
+                2,
+                AccountBeforeTx { address: destroyed, info: Some(account) },
+            )
+            .unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::StorageChangeSets>(
+                (2, contract).into(),
+                StorageEntry { key: cleared, value: U256::from(1) },
+            )
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+
+        // a later batch reads the state before block 2 was hashed
+        let provider = factory.provider().unwrap();
+        let state = LatestStateProviderRef::new(provider.tx_ref(), factory.static_file_provider());
+        assert_eq!(state.basic_account(destroyed).unwrap(), None);
+        assert_eq!(state.basic_account(contract).unwrap(), Some(account));
+        assert_eq!(state.storage(contract, cleared).unwrap(), None);
+        assert_eq!(state.storage(contract, kept).unwrap(), Some(U256::from(1)));
+        assert_eq!(provider.basic_account(destroyed).unwrap(), None);
+        assert_eq!(provider.basic_account(contract).unwrap(), Some(account));
+    }
+}
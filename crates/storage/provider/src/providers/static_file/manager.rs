@@ -1,11 +1,12 @@
 use super::{
     metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    StaticFileWritersGuard,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, RequestsProvider, StageCheckpointReader, StatsReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader, StatsReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
@@ -15,24 +16,30 @@ use parking_lot::RwLock;
 use reth_chainspec::{ChainInfo, ChainSpecProvider};
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor,
+        StorageChangeSetMask, TransactionMask,
+    },
     tables,
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::{CompactU256, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, BlockAccountChangeSet, BlockNumberAddress, BlockStorageChangeSet,
+        CompactU256, StorageBeforeTx, StoredBlockBodyIndices,
+    },
     table::Table,
     transaction::DbTx,
 };
 use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_primitives::{
     static_file::{
-        find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive,
-        DEFAULT_BLOCKS_PER_STATIC_FILE,
+        find_fixed_range, CompressionConfig, HighestStaticFiles, SegmentHeader,
+        SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     },
     Block, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
-    StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash, Withdrawal,
-    Withdrawals,
+    StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    Withdrawal, Withdrawals,
 };
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_storage_api::DBProvider;
@@ -41,7 +48,10 @@ use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
-    sync::{mpsc, Arc},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
 };
 use strum::IntoEnumIterator;
 use tracing::{info, trace, warn};
@@ -203,6 +213,12 @@ pub struct StaticFileProviderInner {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file block for each segment, higher than zero if lower static files were
+    /// pruned.
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file transaction for each transaction based segment, higher than zero if lower
+    /// static files were pruned.
+    static_files_min_tx: RwLock<HashMap<StaticFileSegment, TxNumber>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
@@ -214,6 +230,10 @@ pub struct StaticFileProviderInner {
     access: StaticFileAccess,
     /// Number of blocks per file.
     blocks_per_file: u64,
+    /// Compression of newly created static files.
+    compression: CompressionConfig,
+    /// Number of truncations of static files, which are the only in-place modifications.
+    truncations: AtomicU64,
     /// Write lock for when access is [`StaticFileAccess::RW`].
     _lock_file: Option<StorageLock>,
 }
@@ -231,11 +251,15 @@ impl StaticFileProviderInner {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min_block: Default::default(),
+            static_files_min_tx: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
+            compression: CompressionConfig::default(),
+            truncations: AtomicU64::new(0),
             _lock_file,
         };
 
@@ -251,6 +275,24 @@ impl StaticFileProviderInner {
     pub const fn find_fixed_range(&self, block: BlockNumber) -> SegmentRangeInclusive {
         find_fixed_range(block, self.blocks_per_file)
     }
+
+    /// Returns the number of times rows were truncated from static files since they were opened.
+    ///
+    /// Truncations are the only in-place modifications of static files, so while the count is
+    /// unchanged, static files were at most appended to.
+    pub fn truncations(&self) -> u64 {
+        self.truncations.load(Ordering::Acquire)
+    }
+
+    /// Records the start of a truncation.
+    pub(crate) fn record_truncation(&self) {
+        self.truncations.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Returns the compression of newly created static files.
+    pub const fn compression(&self) -> &CompressionConfig {
+        &self.compression
+    }
 }
 
 impl StaticFileProvider {
@@ -263,6 +305,15 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Sets the compression of newly created static files. Existing static files keep the
+    /// compression they were created with.
+    pub fn with_compression(self, compression: CompressionConfig) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.compression = compression;
+        Self(Arc::new(provider))
+    }
+
     /// Enables metrics on the [`StaticFileProvider`].
     pub fn with_metrics(self) -> Self {
         let mut provider =
@@ -415,6 +466,58 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Deletes all static files of the segment whose block range ends below `block`, except for
+    /// the highest one. Returns the headers of the deleted static files.
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<Vec<SegmentHeader>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let Some(highest_block) = self.get_highest_static_file_block(segment) else {
+            return Ok(Vec::new())
+        };
+        let highest_fixed_block_range = self.find_fixed_range(highest_block);
+
+        let ranges = iter_static_files(&self.path)
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            .remove(&segment)
+            .unwrap_or_default();
+
+        let mut deleted = Vec::new();
+        // Static files are sorted by block range.
+        for (block_range, _) in ranges {
+            let fixed_block_range = self.find_fixed_range(block_range.start());
+            if fixed_block_range.end() >= block ||
+                fixed_block_range.end() >= highest_fixed_block_range.end()
+            {
+                break
+            }
+
+            let jar = if let Some((_, jar)) = self.map.remove(&(fixed_block_range.end(), segment)) {
+                jar.jar
+            } else {
+                NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&fixed_block_range)),
+                )
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            };
+            deleted.push(jar.user_header().clone());
+            jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        }
+
+        if !deleted.is_empty() {
+            self.initialize_index()?;
+        }
+
+        Ok(deleted)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -450,6 +553,10 @@ impl StaticFileProvider {
         segment: StaticFileSegment,
         block: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.get_lowest_static_file_block(segment).is_some_and(|min| min > block) {
+            return None
+        }
+
         self.static_files_max_block
             .read()
             .get(&segment)
@@ -464,6 +571,7 @@ impl StaticFileProvider {
         segment: StaticFileSegment,
         tx: u64,
     ) -> Option<SegmentRangeInclusive> {
+        let min_tx = self.static_files_min_tx.read().get(&segment).copied().unwrap_or_default();
         let static_files = self.static_files_tx_index.read();
         let segment_static_files = static_files.get(&segment)?;
 
@@ -476,7 +584,8 @@ impl StaticFileProvider {
                 // request tx is higher than highest static file tx
                 return None
             }
-            let tx_start = static_files_rev_iter.peek().map(|(tx_end, _)| *tx_end + 1).unwrap_or(0);
+            let tx_start =
+                static_files_rev_iter.peek().map(|(tx_end, _)| *tx_end + 1).unwrap_or(min_tx);
             if tx_start <= tx {
                 return Some(self.find_fixed_range(block_range.end()))
             }
@@ -496,6 +605,8 @@ impl StaticFileProvider {
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
+        let mut min_tx = self.static_files_min_tx.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         match segment_max_block {
@@ -509,6 +620,12 @@ impl StaticFileProvider {
                 )
                 .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
+                // The first static file of the segment was just created.
+                min_block.entry(segment).or_insert(fixed_range.start());
+                if let Some(tx_start) = jar.user_header().tx_start() {
+                    min_tx.entry(segment).or_insert(tx_start);
+                }
+
                 // Updates the tx index by first removing all entries which have a higher
                 // block_start than our current static file.
                 if let Some(tx_range) = jar.user_header().tx_range() {
@@ -558,6 +675,8 @@ impl StaticFileProvider {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min_block.remove(&segment);
+                min_tx.remove(&segment);
             }
         };
 
@@ -567,18 +686,28 @@ impl StaticFileProvider {
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
+        let mut min_tx = self.static_files_min_tx.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         max_block.clear();
+        min_block.clear();
+        min_tx.clear();
         tx_index.clear();
 
         for (segment, ranges) in
             iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
         {
-            // Update last block for each segment
+            // Update first and last block for each segment
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, block_range.start());
+            }
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
             }
+            if let Some(tx_range) = ranges.iter().find_map(|(_, tx_range)| *tx_range) {
+                min_tx.insert(segment, tx_range.start());
+            }
 
             // Update tx -> block_range index
             for (block_range, tx_range) in ranges {
@@ -665,6 +794,10 @@ impl StaticFileProvider {
             }
         };
 
+        // Snap synced nodes don't have the receipts before the pivot block, and store the later
+        // ones in the database.
+        let has_receipt_pruning = has_receipt_pruning || provider.is_snap_synced()?;
+
         for segment in StaticFileSegment::iter() {
             if has_receipt_pruning && segment.is_receipts() {
                 // Pruned nodes (including full node) do not store receipts as static files.
@@ -673,6 +806,12 @@ impl StaticFileProvider {
 
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
+            if segment.is_changesets() && initial_highest_block.is_none() {
+                // Changesets are only moved to static files by the `StaticFileProducer`, so
+                // there is nothing to check until it has run.
+                continue
+            }
+
             //  File consistency is broken if:
             //
             // * appending data was interrupted before a config commit, then data file will be
@@ -760,6 +899,9 @@ impl StaticFileProvider {
                     highest_tx,
                     highest_block,
                 )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.ensure_changeset_invariants(provider, segment, highest_block)?
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -844,7 +986,9 @@ impl StaticFileProvider {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -889,6 +1033,52 @@ impl StaticFileProvider {
         Ok(None)
     }
 
+    /// Check invariants for a changeset static file segment.
+    ///
+    /// Changesets are only copied to static files once the history indices have been built from
+    /// them, so the database always holds the changesets above the highest static file block and
+    /// there is no continuity between the two to check. However, if an unwind was committed to
+    /// the database but not to static files, the static file is ahead of the
+    /// [`StageId::Execution`] checkpoint, and the extra rows are removed.
+    ///
+    /// Always returns [`None`], since no pipeline unwind is ever required.
+    fn ensure_changeset_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: StageCheckpointReader,
+    {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            writer.prune_changesets(highest_static_file_block - checkpoint_block_number)?;
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
+    /// Gets the lowest static file block if it exists for a static file segment. It's only higher
+    /// than zero if lower static files were pruned.
+    ///
+    /// If there is nothing on disk for the given segment, this will return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -912,6 +1102,10 @@ impl StaticFileProvider {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -954,11 +1148,10 @@ impl StaticFileProvider {
         F: FnMut(&mut StaticFileCursor<'_>, u64) -> ProviderResult<Option<T>>,
         P: FnMut(&T) -> bool,
     {
-        let get_provider = |start: u64| match segment {
-            StaticFileSegment::Headers => {
+        let get_provider = |start: u64| {
+            if segment.is_block_based() {
                 self.get_segment_provider_from_block(segment, start, None)
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -993,7 +1186,7 @@ impl StaticFileProvider {
                                 "Could not find block or tx number on a range request"
                             );
 
-                            let err = if segment.is_headers() {
+                            let err = if segment.is_block_based() {
                                 ProviderError::MissingStaticFileBlock(segment, number)
                             } else {
                                 ProviderError::MissingStaticFileTx(segment, number)
@@ -1030,11 +1223,10 @@ impl StaticFileProvider {
         F: Fn(&mut StaticFileCursor<'_>, u64) -> ProviderResult<Option<T>> + 'a,
         T: std::fmt::Debug,
     {
-        let get_provider = move |start: u64| match segment {
-            StaticFileSegment::Headers => {
+        let get_provider = move |start: u64| {
+            if segment.is_block_based() {
                 self.get_segment_provider_from_block(segment, start, None)
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -1061,6 +1253,15 @@ impl StaticFileProvider {
         &self.path
     }
 
+    /// Blocks all static file writes until the returned guard is dropped.
+    ///
+    /// Writes already in progress finish first. Writers are acquired in the same order as by the
+    /// persistence, which holds a database read-write transaction while writing static files, so
+    /// callers that need both must open the read-write transaction first.
+    pub fn lock_writers(&self) -> StaticFileWritersGuard<'_> {
+        self.writers.lock_all()
+    }
+
     /// Retrieves data from the database or static file, wherever it's available.
     ///
     /// # Arguments
@@ -1082,11 +1283,10 @@ impl StaticFileProvider {
         FD: Fn() -> ProviderResult<Option<T>>,
     {
         // If there is, check the maximum block or transaction number of the segment.
-        let static_file_upper_bound = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+        let static_file_upper_bound = if segment.is_block_based() {
+            self.get_highest_static_file_block(segment)
+        } else {
+            self.get_highest_static_file_tx(segment)
         };
 
         if static_file_upper_bound
@@ -1124,11 +1324,10 @@ impl StaticFileProvider {
         let mut data = Vec::new();
 
         // If there is, check the maximum block or transaction number of the segment.
-        if let Some(static_file_upper_bound) = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+        if let Some(static_file_upper_bound) = if segment.is_block_based() {
+            self.get_highest_static_file_block(segment)
+        } else {
+            self.get_highest_static_file_tx(segment)
         } {
             if block_or_tx_range.start <= static_file_upper_bound {
                 let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
@@ -1148,6 +1347,100 @@ impl StaticFileProvider {
         Ok(data)
     }
 
+    /// Returns the account changesets of the given block range, paired with their block number.
+    pub fn account_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        Ok(self
+            .fetch_range_with_predicate(
+                StaticFileSegment::AccountChangeSets,
+                range,
+                |cursor, number| {
+                    Ok(cursor
+                        .get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(number.into())?
+                        .map(|changeset| (number, changeset)))
+                },
+                |_| true,
+            )?
+            .into_iter()
+            .flat_map(|(number, changeset)| changeset.0.into_iter().map(move |c| (number, c)))
+            .collect())
+    }
+
+    /// Returns the storage changesets of the given block range, keyed the same way as the
+    /// [`tables::StorageChangeSets`] table.
+    pub fn storage_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .fetch_range_with_predicate(
+                StaticFileSegment::StorageChangeSets,
+                range,
+                |cursor, number| {
+                    Ok(cursor
+                        .get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(number.into())?
+                        .map(|changeset| (number, changeset)))
+                },
+                |_| true,
+            )?
+            .into_iter()
+            .flat_map(|(number, changeset)| {
+                changeset.0.into_iter().map(move |change| {
+                    (BlockNumberAddress((number, change.address)), change.entry())
+                })
+            })
+            .collect())
+    }
+
+    /// Returns an iterator over the account changesets of the given block range, one block at a
+    /// time, so that long ranges don't have to be held in memory.
+    pub fn account_changesets_iter(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<
+        impl Iterator<Item = ProviderResult<(BlockNumber, Vec<AccountBeforeTx>)>> + '_,
+    > {
+        self.fetch_range_iter(StaticFileSegment::AccountChangeSets, range, |cursor, number| {
+            Ok(cursor
+                .get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(number.into())?
+                .map(|changeset| (number, changeset.0)))
+        })
+    }
+
+    /// Returns an iterator over the storage changesets of the given block range, one block at a
+    /// time, so that long ranges don't have to be held in memory.
+    pub fn storage_changesets_iter(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<
+        impl Iterator<Item = ProviderResult<(BlockNumber, Vec<StorageBeforeTx>)>> + '_,
+    > {
+        self.fetch_range_iter(StaticFileSegment::StorageChangeSets, range, |cursor, number| {
+            Ok(cursor
+                .get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(number.into())?
+                .map(|changeset| (number, changeset.0)))
+        })
+    }
+
+    /// Returns the storage changes of a block.
+    pub fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<StorageBeforeTx>> {
+        Ok(self
+            .get_segment_provider_from_block(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                None,
+            )?
+            .cursor()?
+            .get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(block_number.into())?
+            .map(|changeset| changeset.0)
+            .unwrap_or_default())
+    }
+
     /// Returns `static_files` directory
     #[cfg(any(test, feature = "test-utils"))]
     pub fn path(&self) -> &Path {
@@ -1652,6 +1945,24 @@ impl RequestsProvider for StaticFileProvider {
     }
 }
 
+impl ChangeSetReader for StaticFileProvider {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .get_segment_provider_from_block(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                None,
+            )?
+            .cursor()?
+            .get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(block_number.into())?
+            .map(|changeset| changeset.0)
+            .unwrap_or_default())
+    }
+}
+
 impl StatsReader for StaticFileProvider {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
@@ -5,7 +5,7 @@ mod jar;
 pub use jar::StaticFileJarProvider;
 
 mod writer;
-pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
+pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWritersGuard};
 
 mod metrics;
 
@@ -63,12 +63,16 @@ mod tests {
         HeaderTerminalDifficulties, Headers,
     };
     use reth_db_api::transaction::DbTxMut;
+    use reth_nippy_jar::compression::ColumnCompression;
     use reth_primitives::{
-        static_file::{find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE},
+        static_file::{
+            find_fixed_range, Compression, CompressionConfig, SegmentRangeInclusive,
+            DEFAULT_BLOCKS_PER_STATIC_FILE,
+        },
         Header, Receipt, TransactionSignedNoHash,
     };
     use reth_storage_api::{ReceiptProvider, TransactionsProvider};
-    use reth_testing_utils::generators::{self, random_header_range};
+    use reth_testing_utils::generators::{self, random_header_range, random_signed_tx};
     use std::{fmt::Debug, fs, ops::Range, path::Path};
 
     fn assert_eyre<T: PartialEq + Debug>(got: T, expected: T, msg: &str) -> eyre::Result<()> {
@@ -546,4 +550,91 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_delete_segment_below_block() {
+        let segment = StaticFileSegment::Transactions;
+        let blocks_per_file = 10; // Number of blocks per file
+        let files_per_range = 3; // Number of files per range (data/conf/offset files)
+
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw = StaticFileProvider::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+
+        setup_tx_based_scenario(&sf_rw, segment, blocks_per_file);
+        assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(0));
+
+        // Nothing is deleted if the block is within the first block range.
+        assert!(sf_rw.delete_segment_below_block(segment, blocks_per_file - 1).unwrap().is_empty());
+
+        // The highest block range is never deleted.
+        let deleted = sf_rw.delete_segment_below_block(segment, blocks_per_file * 10).unwrap();
+        assert_eq!(
+            deleted.iter().map(|header| header.block_range().copied()).collect::<Vec<_>>(),
+            vec![Some(SegmentRangeInclusive::new(0, 9)), Some(SegmentRangeInclusive::new(10, 19))]
+        );
+        assert_eq!(fs::read_dir(&static_dir).unwrap().count(), files_per_range + 1);
+
+        // Indexes are updated for both the reused and a freshly opened provider.
+        let sf_ro = StaticFileProvider::read_only(&static_dir, false)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        for sf in [&sf_rw, &sf_ro] {
+            assert_eq!(sf.get_lowest_static_file_block(segment), Some(blocks_per_file * 2));
+            assert_eq!(sf.get_highest_static_file_block(segment), Some(blocks_per_file * 3 - 1));
+            assert_eq!(sf.transaction_by_id(0).unwrap(), None);
+            assert_eq!(sf.transaction_by_id(9).unwrap().map(|tx| tx.nonce()), Some(9));
+        }
+    }
+
+    #[test]
+    fn test_column_compression() {
+        let segment = StaticFileSegment::Transactions;
+        let blocks_per_file = 10;
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let mut compression = CompressionConfig { dictionary_max_size: 4096, ..Default::default() };
+        compression.set_columns(segment, vec![Compression::ZstdWithDictionary]);
+        let sf_rw = StaticFileProvider::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_compression(compression);
+
+        let mut rng = generators::rng();
+        let mut transactions = Vec::new();
+        {
+            let mut writer = sf_rw.latest_writer(segment).unwrap();
+            for block in 0..blocks_per_file * 2 {
+                writer.increment_block(block).unwrap();
+                for _ in 0..20 {
+                    let tx = random_signed_tx(&mut rng);
+                    writer
+                        .append_transaction(transactions.len() as TxNumber, &tx.clone().into())
+                        .unwrap();
+                    transactions.push(tx);
+                }
+            }
+            writer.commit().unwrap();
+        }
+
+        let column_compression = |block| {
+            let path = static_dir
+                .as_ref()
+                .join(segment.filename(&find_fixed_range(block, blocks_per_file)));
+            let jar = NippyJar::<SegmentHeader>::load(&path).unwrap();
+            jar.compressor().and_then(|compression| compression.columns()).map(<[_]>::to_vec)
+        };
+
+        // The first static file has nothing to train a dictionary on, so it falls back to zstd.
+        assert!(matches!(column_compression(0).as_deref(), Some([ColumnCompression::Zstd { .. }])));
+        assert!(matches!(
+            column_compression(blocks_per_file).as_deref(),
+            Some([ColumnCompression::ZstdDictionary { dictionary: Some(_), .. }])
+        ));
+
+        for (tx_number, tx) in transactions.iter().enumerate() {
+            assert_eq!(sf_rw.transaction_by_id(tx_number as TxNumber).unwrap().as_ref(), Some(tx));
+        }
+    }
 }
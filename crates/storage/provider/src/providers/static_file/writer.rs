@@ -5,10 +5,13 @@ use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
-use reth_db_api::models::CompactU256;
-use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
+use reth_db_api::models::{AccountBeforeTx, CompactU256, StorageBeforeTx};
+use reth_nippy_jar::{
+    compression::{ColumnCompression, Compressors},
+    NippyJar, NippyJarError, NippyJarWriter,
+};
 use reth_primitives::{
-    static_file::{SegmentHeader, SegmentRangeInclusive},
+    static_file::{Compression, SegmentHeader, SegmentRangeInclusive},
     Header, Receipt, StaticFileSegment, TransactionSignedNoHash,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -18,7 +21,7 @@ use std::{
     sync::{Arc, Weak},
     time::Instant,
 };
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// Static file writers for every known [`StaticFileSegment`].
 ///
@@ -29,6 +32,8 @@ pub(crate) struct StaticFileWriters {
     headers: RwLock<Option<StaticFileProviderRW>>,
     transactions: RwLock<Option<StaticFileProviderRW>>,
     receipts: RwLock<Option<StaticFileProviderRW>>,
+    account_changesets: RwLock<Option<StaticFileProviderRW>>,
+    storage_changesets: RwLock<Option<StaticFileProviderRW>>,
 }
 
 impl StaticFileWriters {
@@ -41,6 +46,8 @@ impl StaticFileWriters {
             StaticFileSegment::Headers => self.headers.write(),
             StaticFileSegment::Transactions => self.transactions.write(),
             StaticFileSegment::Receipts => self.receipts.write(),
+            StaticFileSegment::AccountChangeSets => self.account_changesets.write(),
+            StaticFileSegment::StorageChangeSets => self.storage_changesets.write(),
         };
 
         if write_guard.is_none() {
@@ -51,7 +58,13 @@ impl StaticFileWriters {
     }
 
     pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
+        for writer_lock in [
+            &self.headers,
+            &self.transactions,
+            &self.receipts,
+            &self.account_changesets,
+            &self.storage_changesets,
+        ] {
             let mut writer = writer_lock.write();
             if let Some(writer) = writer.as_mut() {
                 writer.commit()?;
@@ -59,8 +72,29 @@ impl StaticFileWriters {
         }
         Ok(())
     }
+
+    /// Locks the writers of all segments, without creating them.
+    pub(crate) fn lock_all(&self) -> StaticFileWritersGuard<'_> {
+        StaticFileWritersGuard(
+            [
+                &self.headers,
+                &self.transactions,
+                &self.receipts,
+                &self.account_changesets,
+                &self.storage_changesets,
+            ]
+            .map(|writer_lock| writer_lock.write()),
+        )
+    }
 }
 
+/// Holds the write locks of all [`StaticFileProviderRW`]s, blocking any static file writes until
+/// it is dropped.
+#[derive(Debug)]
+pub struct StaticFileWritersGuard<'a>(
+    #[allow(dead_code)] [RwLockWriteGuard<'a, RawRwLock, Option<StaticFileProviderRW>>; 5],
+);
+
 /// Mutable reference to a [`StaticFileProviderRW`] behind a [`RwLockWriteGuard`].
 #[derive(Debug)]
 pub struct StaticFileProviderRWRefMut<'a>(
@@ -152,7 +186,7 @@ impl StaticFileProviderRW {
             ),
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
+                (create_jar(&static_file_provider, segment, &path, block_range)?, path)
             }
             Err(err) => return Err(err),
         };
@@ -187,7 +221,7 @@ impl StaticFileProviderRW {
     /// [`NippyJarWriter`] for more on healing.
     fn ensure_end_range_consistency(&mut self) -> ProviderResult<()> {
         // If we have lost rows (in this run or previous), we need to update the [SegmentHeader].
-        let expected_rows = if self.user_header().segment().is_headers() {
+        let expected_rows = if self.user_header().segment().is_block_based() {
             self.user_header().block_len().unwrap_or_default()
         } else {
             self.user_header().tx_len().unwrap_or_default()
@@ -217,6 +251,9 @@ impl StaticFileProviderRW {
                 StaticFileSegment::Receipts => {
                     self.prune_receipt_data(to_delete, last_block_number.expect("should exist"))?
                 }
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.prune_changeset_data(to_delete)?
+                }
             }
         }
 
@@ -380,16 +417,18 @@ impl StaticFileProviderRW {
     /// # Note
     /// Commits to the configuration file at the end.
     fn truncate(&mut self, num_rows: u64, last_block: Option<u64>) -> ProviderResult<()> {
+        // recorded before any data is removed, so readers can detect truncations in progress
+        if num_rows > 0 {
+            self.reader().record_truncation();
+        }
+
         let mut remaining_rows = num_rows;
         let segment = self.writer.user_header().segment();
         while remaining_rows > 0 {
-            let len = match segment {
-                StaticFileSegment::Headers => {
-                    self.writer.user_header().block_len().unwrap_or_default()
-                }
-                StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                    self.writer.user_header().tx_len().unwrap_or_default()
-                }
+            let len = if segment.is_block_based() {
+                self.writer.user_header().block_len().unwrap_or_default()
+            } else {
+                self.writer.user_header().tx_len().unwrap_or_default()
             };
 
             if remaining_rows >= len {
@@ -398,12 +437,12 @@ impl StaticFileProviderRW {
                 let block_start = self.writer.user_header().expected_block_start();
 
                 // We only delete the file if it's NOT the first static file AND:
-                // * it's a Header segment  OR
+                // * it's a block-based segment  OR
                 // * it's a tx-based segment AND `last_block` is lower than the first block of this
                 //   file's block range. Otherwise, having no rows simply means that this block
                 //   range has no transactions, but the file should remain.
                 if block_start != 0 &&
-                    (segment.is_headers() || last_block.is_some_and(|b| b < block_start))
+                    (segment.is_block_based() || last_block.is_some_and(|b| b < block_start))
                 {
                     self.delete_current_and_open_previous()?;
                 } else {
@@ -631,6 +670,66 @@ impl StaticFileProviderRW {
         Ok(Some(tx_number))
     }
 
+    /// Appends the account changes of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since there is one row per block, even if the block has
+    /// no account changes.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_account_changeset(
+        &mut self,
+        block_number: BlockNumber,
+        changeset: &[AccountBeforeTx],
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::AccountChangeSets);
+
+        let block_number = self.increment_block(block_number)?;
+        self.append_column(changeset)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::AccountChangeSets,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
+    /// Appends the storage changes of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since there is one row per block, even if the block has
+    /// no storage changes.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_storage_changeset(
+        &mut self,
+        block_number: BlockNumber,
+        changeset: &[StorageBeforeTx],
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::StorageChangeSets);
+
+        let block_number = self.increment_block(block_number)?;
+        self.append_column(changeset)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::StorageChangeSets,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
     /// Adds an instruction to prune `to_delete`transactions during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at.
@@ -661,6 +760,12 @@ impl StaticFileProviderRW {
         self.queue_prune(to_delete, None)
     }
 
+    /// Adds an instruction to prune the changesets of the last `to_delete` blocks during commit.
+    pub fn prune_changesets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert!(self.writer.user_header().segment().is_changesets());
+        self.queue_prune(to_delete, None)
+    }
+
     /// Adds an instruction to prune `to_delete` elements during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at if dealing with transaction-based
@@ -750,6 +855,26 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Prunes the changesets of the last `to_delete` blocks from the data file.
+    fn prune_changeset_data(&mut self, to_delete: u64) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        let segment = self.writer.user_header().segment();
+        debug_assert!(segment.is_changesets());
+
+        self.truncate(to_delete, None)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     fn reader(&self) -> StaticFileProvider {
         Self::upgrade_provider_to_strong_reference(&self.reader)
     }
@@ -792,21 +917,99 @@ impl StaticFileProviderRW {
 }
 
 fn create_jar(
+    static_file_provider: &StaticFileProvider,
     segment: StaticFileSegment,
     path: &Path,
     expected_block_range: SegmentRangeInclusive,
-) -> NippyJar<SegmentHeader> {
-    let mut jar = NippyJar::new(
+) -> ProviderResult<NippyJar<SegmentHeader>> {
+    let jar = NippyJar::new(
         segment.columns(),
         path,
         SegmentHeader::new(expected_block_range, None, None, segment),
     );
 
-    // Transaction and Receipt already have the compression scheme used natively in its encoding.
-    // (zstd-dictionary)
-    if segment.is_headers() {
-        jar = jar.with_lz4();
+    let config = static_file_provider.compression();
+    let columns = config.columns(segment);
+
+    // Uniform compression is configured for the whole jar, as older versions expect.
+    if columns.iter().all(|compression| *compression == Compression::Uncompressed) {
+        return Ok(jar)
+    }
+    if columns.iter().all(|compression| *compression == Compression::Lz4) {
+        return Ok(jar.with_lz4())
+    }
+
+    let mut jar = jar.with_column_compression(
+        columns
+            .into_iter()
+            .map(|compression| match compression {
+                Compression::Lz4 => ColumnCompression::Lz4,
+                Compression::Zstd => ColumnCompression::Zstd { level: 0 },
+                Compression::ZstdWithDictionary => ColumnCompression::ZstdDictionary {
+                    level: 0,
+                    max_size: config.dictionary_max_size,
+                    dictionary: None,
+                },
+                Compression::Uncompressed => ColumnCompression::None,
+            })
+            .collect(),
+    );
+
+    if jar
+        .compressor()
+        .and_then(|compression| compression.columns())
+        .is_some_and(|columns| columns.iter().any(ColumnCompression::needs_training))
+    {
+        train_column_compression(static_file_provider, &mut jar, segment, expected_block_range)?;
+    }
+
+    Ok(jar)
+}
+
+/// Trains the column dictionaries of a new jar on samples of the previous static file of the
+/// segment. Columns which can't be trained, because there's no previous static file or it has no
+/// rows, fall back to zstd without a dictionary.
+fn train_column_compression(
+    static_file_provider: &StaticFileProvider,
+    jar: &mut NippyJar<SegmentHeader>,
+    segment: StaticFileSegment,
+    expected_block_range: SegmentRangeInclusive,
+) -> ProviderResult<()> {
+    let previous_path = expected_block_range
+        .start()
+        .checked_sub(1)
+        .map(|block| {
+            static_file_provider
+                .directory()
+                .join(segment.filename(&static_file_provider.find_fixed_range(block)))
+        })
+        .filter(|path| path.exists());
+
+    if let Some(previous_path) = previous_path {
+        let start = Instant::now();
+        let samples = NippyJar::<SegmentHeader>::load(&previous_path)
+            .and_then(|previous| {
+                previous.sample_columns(static_file_provider.compression().dictionary_samples)
+            })
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        if samples.iter().all(|column| !column.is_empty()) {
+            match jar.train_column_compression(&samples) {
+                Ok(()) => {
+                    debug!(target: "provider::static_file", %segment, ?expected_block_range, elapsed = ?start.elapsed(), "Trained static file column dictionaries");
+                }
+                Err(err) => {
+                    warn!(target: "provider::static_file", %segment, ?expected_block_range, %err, "Failed to train static file column dictionaries");
+                }
+            }
+        }
+    }
+
+    if let Some(Compressors::PerColumn(columns)) = jar.compressor_mut() {
+        for column in columns.iter_mut().filter(|column| column.needs_training()) {
+            *column = ColumnCompression::Zstd { level: 0 };
+        }
     }
 
-    jar
+    Ok(())
 }
@@ -2,7 +2,7 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
+    EvmEnvProvider, HeaderProvider, LogIndexReader, StageCheckpointReader, StateProviderFactory,
     StaticFileProviderFactory, TransactionsProvider,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
@@ -22,6 +22,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions
     + StageCheckpointReader
+    + LogIndexReader
     + Clone
     + Unpin
     + 'static
@@ -40,6 +41,7 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions
         + StageCheckpointReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static
@@ -56,6 +58,7 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + LogIndexReader
     + Clone
     + Unpin
     + 'static
@@ -70,6 +73,7 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static
@@ -0,0 +1,21 @@
+use alloy_primitives::{BlockNumber, Log};
+use auto_impl::auto_impl;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Log index writer.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Unwind and clear the log indices of the blocks in the given range.
+    ///
+    /// Returns the number of index keys that were unwound.
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Appends the given logs, ordered by block number, to the log indices.
+    ///
+    /// The blocks must be above the last indexed block.
+    fn insert_log_indices<'a>(
+        &self,
+        logs: impl IntoIterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<()>;
+}
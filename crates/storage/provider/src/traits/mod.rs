@@ -29,6 +29,9 @@ pub use trie::{StorageTrieWriter, TrieWriter};
 mod history;
 pub use history::HistoryWriter;
 
+mod log_index;
+pub use log_index::LogIndexWriter;
+
 mod static_file_provider;
 pub use static_file_provider::StaticFileProviderFactory;
 
@@ -43,3 +46,8 @@ pub use tree_viewer::TreeViewer;
 
 mod finalized_block;
 pub use finalized_block::{FinalizedBlockReader, FinalizedBlockWriter};
+
+#[cfg(feature = "telos")]
+mod telos;
+#[cfg(feature = "telos")]
+pub use telos::{TelosExtraFieldsReader, TelosExtraFieldsWriter};
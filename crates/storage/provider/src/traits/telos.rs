@@ -0,0 +1,46 @@
+use alloy_primitives::BlockHash;
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_errors::ProviderResult;
+use reth_storage_api::DBProvider;
+use reth_telos_rpc_engine_api::structs::TelosEngineAPIExtraFields;
+
+/// Functionality to read the Telos engine API extra fields of a block from the database.
+pub trait TelosExtraFieldsReader: Send + Sync {
+    /// Returns the extra fields the block with the given hash was executed with.
+    ///
+    /// If the fields of the block were never stored, this returns `None`.
+    fn telos_extra_fields(
+        &self,
+        hash: BlockHash,
+    ) -> ProviderResult<Option<TelosEngineAPIExtraFields>>;
+}
+
+/// Functionality to write the Telos engine API extra fields of a block to the database.
+pub trait TelosExtraFieldsWriter: Send + Sync {
+    /// Saves the extra fields of the block with the given hash in the DB.
+    fn insert_telos_extra_fields(
+        &self,
+        hash: BlockHash,
+        fields: &TelosEngineAPIExtraFields,
+    ) -> ProviderResult<()>;
+}
+
+impl<T: DBProvider> TelosExtraFieldsReader for T {
+    fn telos_extra_fields(
+        &self,
+        hash: BlockHash,
+    ) -> ProviderResult<Option<TelosEngineAPIExtraFields>> {
+        Ok(self.tx_ref().get::<tables::TelosExtraFields>(hash)?.map(Into::into))
+    }
+}
+
+impl<T: DBProvider<Tx: DbTxMut>> TelosExtraFieldsWriter for T {
+    fn insert_telos_extra_fields(
+        &self,
+        hash: BlockHash,
+        fields: &TelosEngineAPIExtraFields,
+    ) -> ProviderResult<()> {
+        Ok(self.tx_ref().put::<tables::TelosExtraFields>(hash, fields.clone().into())?)
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
-    providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter as SfWriter},
+    providers::{
+        is_snap_synced, StaticFileProvider, StaticFileProviderRWRefMut,
+        StaticFileWriter as SfWriter,
+    },
     writer::static_file::StaticFileWriter,
-    BlockExecutionWriter, BlockWriter, HistoryWriter, StateChangeWriter, StateWriter, TrieWriter,
+    BlockExecutionWriter, BlockWriter, HistoryWriter, LogIndexWriter, StateChangeWriter,
+    StateWriter, TrieWriter,
 };
 use alloy_primitives::{BlockNumber, B256, U256};
 use reth_chain_state::ExecutedBlock;
@@ -16,7 +20,8 @@ use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{Header, SealedBlock, StaticFileSegment, TransactionSignedNoHash};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    DBProvider, HeaderProvider, ReceiptWriter, StageCheckpointWriter, TransactionsProviderExt,
+    DBProvider, HeaderProvider, LogIndexReader, ReceiptWriter, StageCheckpointWriter,
+    TransactionsProviderExt,
 };
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm::db::OriginalValuesKnown;
@@ -157,8 +162,17 @@ where
         + HistoryWriter
         + StageCheckpointWriter
         + BlockExecutionWriter
+        + LogIndexReader
+        + LogIndexWriter
         + AsRef<ProviderDB>,
 {
+    /// Returns whether receipts are written to the database instead of static files, which is the
+    /// case with receipt pruning or if the database was snap synced.
+    fn receipts_in_database(&self) -> ProviderResult<bool> {
+        Ok(self.database().prune_modes_ref().has_receipts_pruning() ||
+            is_snap_synced(self.database().tx_ref())?)
+    }
+
     /// Writes executed blocks and receipts to storage.
     pub fn save_blocks(&self, blocks: &[ExecutedBlock]) -> ProviderResult<()> {
         if blocks.is_empty() {
@@ -174,8 +188,9 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
-        // Only write receipts to static files if there is no receipt pruning configured.
-        let mut state_writer = if self.database().prune_modes_ref().has_receipts_pruning() {
+        // Only write receipts to static files if there is no receipt pruning configured, and the
+        // receipts before the pivot block are not missing because of snap sync.
+        let mut state_writer = if self.receipts_in_database()? {
             UnifiedStorageWriter::from_database(self.database())
         } else {
             UnifiedStorageWriter::from(
@@ -216,6 +231,24 @@ where
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
 
+        // Extend the log index if it's maintained. It's only built by the optional `IndexLogs`
+        // stage, so its checkpoint isn't moved by `update_pipeline_stages`.
+        if self.database().log_index_tip()?.map(|tip| tip + 1) == Some(first_number) {
+            self.database().insert_log_indices(blocks.iter().flat_map(|block| {
+                let number = block.block().number;
+                block
+                    .execution_outcome()
+                    .logs(number)
+                    .into_iter()
+                    .flatten()
+                    .map(move |log| (number, log))
+            }))?;
+            self.database().save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
 
@@ -308,7 +341,7 @@ where
             .get_writer(block_number, StaticFileSegment::Transactions)?
             .prune_transactions(total_txs, block_number)?;
 
-        if !self.database().prune_modes_ref().has_receipts_pruning() {
+        if !self.receipts_in_database()? {
             self.static_file()
                 .get_writer(block_number, StaticFileSegment::Receipts)?
                 .prune_receipts(total_txs, block_number)?;
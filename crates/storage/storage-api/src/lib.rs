@@ -25,6 +25,9 @@ pub use block_hash::*;
 mod header;
 pub use header::*;
 
+mod log_index;
+pub use log_index::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
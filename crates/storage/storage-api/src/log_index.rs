@@ -0,0 +1,77 @@
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+/// Client trait for reading the log index built by the `IndexLogs` stage.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the highest block covered by the log index, or `None` if the index has not been
+    /// built.
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the sorted numbers of the blocks in `range` that contain at least one log emitted
+    /// by `address`.
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the sorted numbers of the blocks in `range` that contain at least one log with
+    /// `topic` in any topic position.
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the blocks in `range` that contain logs matching any of the `addresses` and, for
+    /// every topic position, any of its `topics`. Empty sets match all logs.
+    ///
+    /// The range is capped at the log index tip, which is returned together with the candidate
+    /// blocks. Returns `None` if the index doesn't cover the start of the range or the filter
+    /// constrains neither addresses nor topics.
+    ///
+    /// Implementations backed by a database read the tip and all shards from the same
+    /// transaction.
+    fn log_index_candidates(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, BTreeSet<BlockNumber>)>> {
+        if addresses.is_empty() && topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+        let Some(tip) = self.log_index_tip()?.filter(|tip| tip >= range.start()) else {
+            return Ok(None)
+        };
+        let range = *range.start()..=tip.min(*range.end());
+
+        let mut candidates: Option<BTreeSet<BlockNumber>> = None;
+        let mut intersect = |blocks: BTreeSet<BlockNumber>| {
+            candidates = Some(match candidates.take() {
+                Some(current) => current.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        };
+
+        if !addresses.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in addresses {
+                blocks.extend(self.blocks_with_log_address(*address, range.clone())?);
+            }
+            intersect(blocks);
+        }
+
+        for topics in topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics {
+                blocks.extend(self.blocks_with_log_topic(*topic, range.clone())?);
+            }
+            intersect(blocks);
+        }
+
+        Ok(candidates.map(|candidates| (*range.end(), candidates)))
+    }
+}
@@ -14,6 +14,18 @@ pub trait StageCheckpointReader: Send + Sync {
     /// Reads all stage checkpoints and returns a list with the name of the stage and the checkpoint
     /// data.
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>>;
+
+    /// Returns whether the state was downloaded at a pivot block by the
+    /// [`StageId::SnapSync`] stage.
+    ///
+    /// The state before the pivot block is not available then, and the plain state only contains
+    /// the accounts and storage slots changed after it. The rest of the state is only stored in
+    /// the hashed state tables.
+    fn is_snap_synced(&self) -> ProviderResult<bool> {
+        Ok(self
+            .get_stage_checkpoint(StageId::SnapSync)?
+            .is_some_and(|checkpoint| checkpoint.block_number > 0))
+    }
 }
 
 /// The trait for updating stage checkpoint related data.
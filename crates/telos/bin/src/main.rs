@@ -5,12 +5,17 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 
 use clap::Parser;
 use reth::args::utils::EthereumChainSpecParser;
-use reth_node_builder::{engine_tree_config::TreeConfig, EngineNodeLauncher};
+use reth_node_builder::{engine_tree_config::TreeConfig, EngineNodeLauncher, FullNodeComponents};
 use reth::cli::Cli;
+use reth::rpc::server_types::RethRpcModule;
 use reth_node_telos::{TelosArgs, TelosNode};
 use reth_node_telos::node::TelosAddOns;
-use reth_provider::providers::BlockchainProvider2;
+use reth_provider::{providers::BlockchainProvider2, CanonStateSubscriptions};
 use reth_telos_rpc::TelosClient;
+use reth_telos_rpc::eth::in_flight::{
+    maintain_in_flight_transactions, InFlightTransactions, DEFAULT_IN_FLIGHT_TTL,
+};
+use std::time::Duration;
 
 
 #[cfg(feature = "telos")]
@@ -48,6 +53,32 @@ fn main() {
                 let handle = builder
                     .node(TelosNode::new(telos_args.clone()))
                     .extend_rpc_modules(move |ctx| {
+                        if telos_args.inflight_pool {
+                            let ttl = telos_args
+                                .inflight_ttl_seconds
+                                .map_or(DEFAULT_IN_FLIGHT_TTL, Duration::from_secs);
+                            let in_flight = InFlightTransactions::new(ttl);
+                            ctx.node().task_executor().spawn_critical(
+                                "in-flight transactions maintenance",
+                                maintain_in_flight_transactions(
+                                    in_flight.clone(),
+                                    ctx.provider().canonical_state_stream(),
+                                ),
+                            );
+                            ctx.registry.eth_api().set_in_flight_transactions(in_flight);
+
+                            // serve the in-flight transactions as pending in `txpool_content`
+                            if let Some(txpool) =
+                                ctx.registry.eth_api().in_flight_txpool_api(ctx.pool().clone())
+                            {
+                                let txpool = txpool.into_rpc()?;
+                                ctx.modules.remove_method_from_configured("txpool_content");
+                                ctx.modules.remove_method_from_configured("txpool_contentFrom");
+                                ctx.modules
+                                    .merge_if_module_configured(RethRpcModule::Txpool, txpool)?;
+                            }
+                        }
+
                         if telos_args.telos_endpoint.is_some() {
                             ctx.registry
                                 .eth_api()
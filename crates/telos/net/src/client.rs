@@ -0,0 +1,143 @@
+//! Requesting the extra fields of blocks from peers over `telos/1`.
+
+use crate::message::{ExtraFields, GetExtraFields};
+use alloy_primitives::B256;
+use futures::{future::BoxFuture, FutureExt};
+use parking_lot::RwLock;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError, RequestResult},
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::trace;
+
+/// Time after which a request to a peer fails with [`RequestError::Timeout`].
+pub const TELOS_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The future of a `telos/1` request, which resolves to the response and the peer that sent it.
+pub type TelosResponseFut<T> = BoxFuture<'static, PeerRequestResult<T>>;
+
+/// A client requesting the extra fields of blocks from peers over `telos/1`.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait TelosClient: DownloadClient {
+    /// Requests the extra fields of the blocks with the given hashes.
+    fn get_extra_fields(&self, hashes: Vec<B256>) -> TelosResponseFut<ExtraFields>;
+}
+
+/// A request to a peer, sent to its [`TelosConnection`](crate::TelosConnection).
+#[derive(Debug)]
+pub(crate) struct TelosPeerRequest {
+    /// The request.
+    pub(crate) request: GetExtraFields,
+    /// The channel sender for the response.
+    pub(crate) response: oneshot::Sender<RequestResult<ExtraFields>>,
+}
+
+/// The connected peers supporting `telos/1`, which implements [`TelosClient`].
+///
+/// The [`TelosConnection`](crate::TelosConnection)s register themselves if the
+/// [`TelosProtocolHandler`](crate::TelosProtocolHandler) was created with
+/// [`TelosProtocolHandler::with_peers`](crate::TelosProtocolHandler::with_peers). Requests are
+/// sent to the peers in turn, peers reported for bad messages are skipped for the rest of their
+/// connection.
+#[derive(Debug, Clone, Default)]
+pub struct TelosPeers {
+    inner: Arc<TelosPeersInner>,
+}
+
+#[derive(Debug, Default)]
+struct TelosPeersInner {
+    /// Connected peers and the channels to their connections.
+    peers: RwLock<Vec<TelosPeer>>,
+    /// Index of the peer to send the next request to.
+    next_peer: AtomicUsize,
+    /// ID of the next request.
+    next_request_id: AtomicU64,
+}
+
+#[derive(Debug)]
+struct TelosPeer {
+    peer_id: PeerId,
+    to_connection: mpsc::UnboundedSender<TelosPeerRequest>,
+    /// Whether the peer sent a bad message.
+    bad: bool,
+}
+
+impl TelosPeers {
+    /// Registers the connection of a peer, replacing a previous connection of the peer.
+    pub(crate) fn register(
+        &self,
+        peer_id: PeerId,
+        to_connection: mpsc::UnboundedSender<TelosPeerRequest>,
+    ) {
+        let mut peers = self.inner.peers.write();
+        peers.retain(|peer| peer.peer_id != peer_id);
+        peers.push(TelosPeer { peer_id, to_connection, bad: false });
+    }
+
+    /// Removes the connection of a peer, if it wasn't replaced by a newer one.
+    pub(crate) fn unregister(
+        &self,
+        peer_id: PeerId,
+        to_connection: &mpsc::UnboundedSender<TelosPeerRequest>,
+    ) {
+        self.inner.peers.write().retain(|peer| {
+            peer.peer_id != peer_id || !peer.to_connection.same_channel(to_connection)
+        });
+    }
+}
+
+impl DownloadClient for TelosPeers {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        if let Some(peer) = self.inner.peers.write().iter_mut().find(|peer| peer.peer_id == peer_id)
+        {
+            trace!(target: "net::telos", %peer_id, "Skipping peer after bad telos response");
+            peer.bad = true;
+        }
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.inner.peers.read().iter().filter(|peer| !peer.bad).count()
+    }
+}
+
+impl TelosClient for TelosPeers {
+    fn get_extra_fields(&self, hashes: Vec<B256>) -> TelosResponseFut<ExtraFields> {
+        let request_id = self.inner.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let peers = self.inner.peers.read();
+        let start = self.inner.next_peer.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..peers.len() {
+            let peer = &peers[(start + offset) % peers.len()];
+            if peer.bad {
+                continue
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let request = GetExtraFields { request_id, hashes: hashes.clone() };
+            if peer.to_connection.send(TelosPeerRequest { request, response: tx }).is_err() {
+                // the connection is closing
+                continue
+            }
+            let peer_id = peer.peer_id;
+            return async move {
+                match tokio::time::timeout(TELOS_REQUEST_TIMEOUT, rx).await {
+                    Ok(Ok(response)) => response.map(|response| WithPeerId::new(peer_id, response)),
+                    Ok(Err(_)) => Err(RequestError::ConnectionDropped),
+                    Err(_) => Err(RequestError::Timeout),
+                }
+            }
+            .boxed()
+        }
+
+        trace!(target: "net::telos", "No peer to send the telos request to");
+        futures::future::ready(Err(RequestError::UnsupportedCapability)).boxed()
+    }
+}
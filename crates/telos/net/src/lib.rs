@@ -0,0 +1,51 @@
+//! Server and client of the `telos/1` protocol.
+//!
+//! Telos blocks are executed with extra fields that the consensus client sends along with the
+//! payload, but which are not part of the block and therefore not exchanged over `eth`. Nodes
+//! store the fields of the blocks they executed, and serve them to peers over this protocol, so
+//! that the peers can sync the blocks from the network.
+//!
+//! The [`TelosProtocolHandler`] is installed as an additional `RLPx` sub-protocol of the network
+//! and forwards the requests of peers to the [`TelosRequestHandler`], which answers them from the
+//! database:
+//!
+//! ```ignore
+//! let (tx, rx) = tokio::sync::mpsc::channel(TELOS_REQUEST_CHANNEL_CAPACITY);
+//! network.add_rlpx_sub_protocol(TelosProtocolHandler::new(tx).into_rlpx_sub_protocol());
+//! let request_handler = TelosRequestHandler::new(provider, rx);
+//! task_executor.spawn_critical_blocking("telos request handler", request_handler);
+//! ```
+//!
+//! To request the fields from peers, the connections are registered with [`TelosPeers`], which
+//! implements the [`TelosClient`]:
+//!
+//! ```ignore
+//! let peers = TelosPeers::default();
+//! network.add_rlpx_sub_protocol(
+//!     TelosProtocolHandler::default().with_peers(peers.clone()).into_rlpx_sub_protocol(),
+//! );
+//! let fields = peers.get_extra_fields(hashes).await?;
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub mod message;
+mod protocol;
+mod server;
+
+pub use client::{TelosClient, TelosPeers, TelosResponseFut, TELOS_REQUEST_TIMEOUT};
+pub use message::{telos_capability, telos_protocol, TelosMessage, TelosMessageId};
+pub use protocol::{TelosConnection, TelosConnectionHandler, TelosProtocolHandler};
+pub use server::{IncomingTelosRequest, TelosRequestHandler};
+
+/// Capacity of the channel between the [`TelosProtocolHandler`] and the [`TelosRequestHandler`].
+///
+/// Requests are dropped while the channel is full.
+pub const TELOS_REQUEST_CHANNEL_CAPACITY: usize = 256;
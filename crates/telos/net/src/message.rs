@@ -0,0 +1,166 @@
+//! Messages of the `telos/1` protocol.
+
+use alloy_primitives::{
+    bytes::{BufMut, BytesMut},
+    Bytes, B256,
+};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_eth_wire::{protocol::Protocol, Capability};
+use reth_telos_rpc_engine_api::structs::TelosEngineAPIExtraFields;
+
+/// Returns the `telos/1` capability.
+pub const fn telos_capability() -> Capability {
+    Capability::new_static("telos", 1)
+}
+
+/// Returns the `telos/1` protocol, which has 2 messages.
+pub const fn telos_protocol() -> Protocol {
+    Protocol::new(telos_capability(), 2)
+}
+
+/// Message IDs of the `telos/1` protocol, relative to the protocol's offset.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelosMessageId {
+    /// Requests the extra fields of blocks.
+    GetExtraFields = 0x00,
+    /// Response to [`TelosMessageId::GetExtraFields`].
+    ExtraFields = 0x01,
+}
+
+impl TryFrom<u8> for TelosMessageId {
+    type Error = u8;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Ok(match id {
+            0x00 => Self::GetExtraFields,
+            0x01 => Self::ExtraFields,
+            _ => return Err(id),
+        })
+    }
+}
+
+/// Requests the extra fields of the blocks with the given hashes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetExtraFields {
+    /// ID of the request, echoed in the response.
+    pub request_id: u64,
+    /// Hashes of the blocks.
+    pub hashes: Vec<B256>,
+}
+
+/// The requested extra fields, in request order.
+///
+/// The response may omit the fields of the last requested blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ExtraFields {
+    /// ID of the request.
+    pub request_id: u64,
+    /// JSON encoded [`TelosEngineAPIExtraFields`], empty for unavailable blocks.
+    pub fields: Vec<Bytes>,
+}
+
+impl ExtraFields {
+    /// Decodes the fields, with `None` for the unavailable blocks.
+    pub fn decode_fields(&self) -> serde_json::Result<Vec<Option<TelosEngineAPIExtraFields>>> {
+        self.fields
+            .iter()
+            .map(|fields| {
+                if fields.is_empty() {
+                    Ok(None)
+                } else {
+                    serde_json::from_slice(fields).map(Some)
+                }
+            })
+            .collect()
+    }
+}
+
+/// A message of the `telos/1` protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelosMessage {
+    /// Request for the extra fields of blocks.
+    GetExtraFields(GetExtraFields),
+    /// Response with the extra fields of blocks.
+    ExtraFields(ExtraFields),
+}
+
+impl TelosMessage {
+    /// Returns the ID of the message.
+    pub const fn message_id(&self) -> TelosMessageId {
+        match self {
+            Self::GetExtraFields(_) => TelosMessageId::GetExtraFields,
+            Self::ExtraFields(_) => TelosMessageId::ExtraFields,
+        }
+    }
+
+    /// Returns the ID of the request or response.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetExtraFields(msg) => msg.request_id,
+            Self::ExtraFields(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns the message ID followed by the RLP encoded message.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetExtraFields(msg) => msg.encode(&mut buf),
+            Self::ExtraFields(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from its message ID followed by the RLP encoded message.
+    pub fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        *buf = rest;
+        let id = TelosMessageId::try_from(id)
+            .map_err(|_| alloy_rlp::Error::Custom("unknown telos message id"))?;
+        Ok(match id {
+            TelosMessageId::GetExtraFields => Self::GetExtraFields(Decodable::decode(buf)?),
+            TelosMessageId::ExtraFields => Self::ExtraFields(Decodable::decode(buf)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn message_roundtrip() {
+        let fields = TelosEngineAPIExtraFields {
+            new_addresses_using_create: Some(vec![(0, U256::from(1))]),
+            ..Default::default()
+        };
+        let messages = [
+            TelosMessage::GetExtraFields(GetExtraFields {
+                request_id: 1,
+                hashes: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+            }),
+            TelosMessage::ExtraFields(ExtraFields {
+                request_id: 1,
+                fields: vec![serde_json::to_vec(&fields).unwrap().into(), Bytes::new()],
+            }),
+        ];
+
+        for (id, message) in messages.into_iter().enumerate() {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], id as u8);
+            assert_eq!(TelosMessage::decode(&mut &encoded[..]).unwrap(), message);
+        }
+
+        let response = ExtraFields {
+            request_id: 1,
+            fields: vec![serde_json::to_vec(&fields).unwrap().into(), Bytes::new()],
+        };
+        assert_eq!(response.decode_fields().unwrap(), vec![Some(fields), None]);
+
+        assert!(TelosMessage::decode(&mut &[0x02, 0xc0][..]).is_err());
+        assert!(TelosMessage::decode(&mut &[][..]).is_err());
+    }
+}
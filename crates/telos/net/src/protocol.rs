@@ -0,0 +1,273 @@
+//! The `telos/1` `RLPx` sub-protocol.
+
+use crate::{
+    client::{TelosPeerRequest, TelosPeers},
+    message::{telos_protocol, ExtraFields, TelosMessage},
+    server::IncomingTelosRequest,
+};
+use alloy_primitives::bytes::BytesMut;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::error::RequestResult;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, trace};
+
+/// Maximum number of requests of a peer that are served concurrently.
+///
+/// Further requests are dropped until a response was sent.
+const MAX_CONCURRENT_REQUESTS_PER_PEER: usize = 8;
+
+/// Announces the `telos/1` protocol on all connections, forwards the requests of peers to the
+/// [`TelosRequestHandler`](crate::TelosRequestHandler) and sends the requests of the
+/// [`TelosPeers`] client.
+///
+/// Without a request handler, requests of peers are answered with empty responses.
+#[derive(Debug, Clone, Default)]
+pub struct TelosProtocolHandler {
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingTelosRequest>>,
+    /// The client to register the connections with.
+    peers: Option<TelosPeers>,
+}
+
+impl TelosProtocolHandler {
+    /// Creates a new handler forwarding requests to the given channel.
+    pub const fn new(to_request_handler: mpsc::Sender<IncomingTelosRequest>) -> Self {
+        Self { to_request_handler: Some(to_request_handler), peers: None }
+    }
+
+    /// Registers all connections with the given client, so it can request extra fields from the
+    /// peers.
+    pub fn with_peers(mut self, peers: TelosPeers) -> Self {
+        self.peers = Some(peers);
+        self
+    }
+
+    fn connection_handler(&self) -> TelosConnectionHandler {
+        TelosConnectionHandler {
+            to_request_handler: self.to_request_handler.clone(),
+            peers: self.peers.clone(),
+        }
+    }
+}
+
+impl ProtocolHandler for TelosProtocolHandler {
+    type ConnectionHandler = TelosConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// Establishes the `telos/1` protocol on a connection.
+#[derive(Debug)]
+pub struct TelosConnectionHandler {
+    to_request_handler: Option<mpsc::Sender<IncomingTelosRequest>>,
+    peers: Option<TelosPeers>,
+}
+
+impl ConnectionHandler for TelosConnectionHandler {
+    type Connection = TelosConnection;
+
+    fn protocol(&self) -> Protocol {
+        telos_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let requests = self.peers.map(|peers| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            peers.register(peer_id, tx.clone());
+            OutgoingRequests {
+                peer_id,
+                peers,
+                to_connection: tx,
+                requests: rx,
+                inflight: HashMap::new(),
+            }
+        });
+        TelosConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: FuturesUnordered::new(),
+            requests,
+        }
+    }
+}
+
+/// A `telos/1` connection with a peer, which serves the requests of the peer and sends our
+/// requests to it.
+///
+/// The connection is closed if the peer sends an invalid message.
+pub struct TelosConnection {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// Messages received from the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingTelosRequest>>,
+    /// Responses to requests of the peer that are being served.
+    pending_responses: FuturesUnordered<BoxFuture<'static, Option<TelosMessage>>>,
+    /// Our requests to the peer, if the connection is registered with [`TelosPeers`].
+    requests: Option<OutgoingRequests>,
+}
+
+/// Our requests to a peer.
+#[derive(Debug)]
+struct OutgoingRequests {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// The client the connection is registered with.
+    peers: TelosPeers,
+    /// The sender half of `requests`, to unregister the connection.
+    to_connection: mpsc::UnboundedSender<TelosPeerRequest>,
+    /// Requests to send to the peer.
+    requests: mpsc::UnboundedReceiver<TelosPeerRequest>,
+    /// Channels for the responses of the requests sent to the peer, by request ID.
+    inflight: HashMap<u64, oneshot::Sender<RequestResult<ExtraFields>>>,
+}
+
+impl OutgoingRequests {
+    /// Tracks the request and returns the message to send to the peer.
+    fn on_request(&mut self, request: TelosPeerRequest) -> TelosMessage {
+        let TelosPeerRequest { request, response } = request;
+        // requests of timed out futures are never answered
+        self.inflight.retain(|_, inflight| !inflight.is_closed());
+        self.inflight.insert(request.request_id, response);
+        TelosMessage::GetExtraFields(request)
+    }
+
+    /// Sends the response to the request it answers.
+    fn on_response(&mut self, response: ExtraFields) {
+        let Some(inflight) = self.inflight.remove(&response.request_id) else {
+            trace!(target: "net::telos", peer_id=%self.peer_id, "Ignoring unrequested telos response");
+            return
+        };
+        let _ = inflight.send(Ok(response));
+    }
+}
+
+impl Drop for OutgoingRequests {
+    fn drop(&mut self) {
+        self.peers.unregister(self.peer_id, &self.to_connection);
+    }
+}
+
+impl TelosConnection {
+    /// Handles a message of the peer.
+    fn on_message(&mut self, message: TelosMessage) {
+        match message {
+            TelosMessage::ExtraFields(response) => {
+                if let Some(requests) = &mut self.requests {
+                    requests.on_response(response);
+                } else {
+                    trace!(target: "net::telos", peer_id=%self.peer_id, "Ignoring unrequested telos response");
+                }
+            }
+            TelosMessage::GetExtraFields(request) => {
+                if self.pending_responses.len() >= MAX_CONCURRENT_REQUESTS_PER_PEER {
+                    trace!(target: "net::telos", peer_id=%self.peer_id, "Dropping telos request, too many pending requests");
+                    return
+                }
+
+                let Some(to_request_handler) = &self.to_request_handler else {
+                    // not serving, so there is nothing to respond with
+                    let response = TelosMessage::ExtraFields(ExtraFields {
+                        request_id: request.request_id,
+                        ..Default::default()
+                    });
+                    self.pending_responses.push(futures::future::ready(Some(response)).boxed());
+                    return
+                };
+
+                let (response, rx) = oneshot::channel();
+                let request = IncomingTelosRequest::GetExtraFields {
+                    peer_id: self.peer_id,
+                    request,
+                    response,
+                };
+                if to_request_handler.try_send(request).is_err() {
+                    trace!(target: "net::telos", peer_id=%self.peer_id, "Dropping telos request, request handler is busy");
+                    return
+                }
+                self.pending_responses
+                    .push(rx.map(|res| res.ok().map(TelosMessage::ExtraFields)).boxed());
+            }
+        }
+    }
+}
+
+impl Stream for TelosConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(response.encoded()))
+                }
+                continue
+            }
+
+            if let Some(requests) = &mut this.requests {
+                // the channel can't close, the connection holds a sender itself
+                if let Poll::Ready(Some(request)) = requests.requests.poll_recv(cx) {
+                    return Poll::Ready(Some(requests.on_request(request).encoded()))
+                }
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+            match TelosMessage::decode(&mut &msg[..]) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    debug!(target: "net::telos", %err, peer_id=%this.peer_id, "Invalid telos message, closing the connection");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for TelosConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelosConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .field("inflight_requests", &self.requests.as_ref().map(|r| r.inflight.len()))
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,170 @@
+//! Serving `telos/1` requests from the database.
+
+use crate::message::{ExtraFields, GetExtraFields};
+use futures::StreamExt;
+use reth_db::tables;
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_network_api::PeerId;
+use reth_storage_api::{DBProvider, DatabaseProviderFactory};
+use reth_storage_errors::provider::ProviderResult;
+use reth_telos_rpc_engine_api::structs::TelosEngineAPIExtraFields;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+/// Maximum size of replies to data retrievals.
+const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Maximum number of blocks whose extra fields are served.
+///
+/// Used to limit lookups.
+const MAX_EXTRA_FIELDS_LOOKUPS: usize = 1024;
+
+/// Serves `telos/1` requests of peers from the stored extra fields of the executed blocks.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct TelosRequestHandler<F> {
+    /// The factory for database providers.
+    provider_factory: F,
+    /// Incoming requests from the [`TelosConnection`](crate::TelosConnection)s.
+    incoming_requests: ReceiverStream<IncomingTelosRequest>,
+}
+
+// === impl TelosRequestHandler ===
+
+impl<F> TelosRequestHandler<F> {
+    /// Create a new instance
+    pub fn new(provider_factory: F, incoming: Receiver<IncomingTelosRequest>) -> Self {
+        Self { provider_factory, incoming_requests: ReceiverStream::new(incoming) }
+    }
+}
+
+impl<F> TelosRequestHandler<F>
+where
+    F: DatabaseProviderFactory,
+{
+    /// Returns the requested extra fields, JSON encoded.
+    fn get_extra_fields(&self, request: GetExtraFields) -> ProviderResult<ExtraFields> {
+        let GetExtraFields { request_id, hashes } = request;
+        let mut response = ExtraFields { request_id, ..Default::default() };
+
+        let provider = self.provider_factory.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let mut size = 0;
+        for hash in hashes.into_iter().take(MAX_EXTRA_FIELDS_LOOKUPS) {
+            let fields = match tx.get::<tables::TelosExtraFields>(hash)? {
+                Some(fields) => serde_json::to_vec(&TelosEngineAPIExtraFields::from(fields))
+                    .map_err(|err| DatabaseError::Other(err.to_string()))?,
+                None => Vec::new(),
+            };
+            size += fields.len();
+            response.fields.push(fields.into());
+            if size >= SOFT_RESPONSE_LIMIT {
+                break
+            }
+        }
+        Ok(response)
+    }
+
+    /// Answers the request, or responds with an empty response if it can't be served.
+    fn on_request(&self, request: IncomingTelosRequest) {
+        match request {
+            IncomingTelosRequest::GetExtraFields { peer_id, request, response } => {
+                let request_id = request.request_id;
+                let fields = self.get_extra_fields(request).unwrap_or_else(|err| {
+                    debug!(target: "net::telos", %err, %peer_id, "Failed to serve extra fields");
+                    ExtraFields { request_id, ..Default::default() }
+                });
+                let _ = response.send(fields);
+            }
+        }
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<F> Future for TelosRequestHandler<F>
+where
+    F: DatabaseProviderFactory + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while let Poll::Ready(request) = this.incoming_requests.poll_next_unpin(cx) {
+            let Some(request) = request else {
+                // all connections and the protocol handler are gone
+                return Poll::Ready(())
+            };
+            this.on_request(request);
+        }
+
+        Poll::Pending
+    }
+}
+
+/// All `telos` requests delegated by the [`TelosConnection`](crate::TelosConnection)s.
+#[derive(Debug)]
+pub enum IncomingTelosRequest {
+    /// Request for the extra fields of blocks.
+    ///
+    /// The response should be sent through the channel.
+    GetExtraFields {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The request.
+        request: GetExtraFields,
+        /// The channel sender for the response.
+        response: oneshot::Sender<ExtraFields>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, B256, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn serve_extra_fields() {
+        let factory = create_test_provider_factory();
+        let fields = TelosEngineAPIExtraFields {
+            new_addresses_using_create: Some(vec![(0, U256::from(1))]),
+            ..Default::default()
+        };
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::TelosExtraFields>(B256::with_last_byte(1), fields.clone().into())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let (_, rx) = mpsc::channel(1);
+        let handler = TelosRequestHandler::new(factory, rx);
+        let response = handler
+            .get_extra_fields(GetExtraFields {
+                request_id: 1,
+                hashes: vec![B256::with_last_byte(2), B256::with_last_byte(1)],
+            })
+            .unwrap();
+        assert_eq!(
+            response,
+            ExtraFields {
+                request_id: 1,
+                fields: vec![Bytes::new(), serde_json::to_vec(&fields).unwrap().into()]
+            }
+        );
+        assert_eq!(response.decode_fields().unwrap(), vec![None, Some(fields)]);
+    }
+}
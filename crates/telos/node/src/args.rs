@@ -27,6 +27,22 @@ pub struct TelosArgs {
     #[arg(long = "telos.gas_cache_seconds")]
     pub gas_cache_seconds: Option<u32>,
 
+    /// Keep transactions forwarded to TelosZero out of the transaction pool, only tracking them
+    /// until they're included or expire. They're never gossiped to peers in this mode.
+    ///
+    /// Not supported by the experimental engine.
+    #[arg(
+        long = "telos.inflight_pool",
+        requires = "telos_endpoint",
+        conflicts_with = "experimental",
+        default_value = "false"
+    )]
+    pub inflight_pool: bool,
+
+    /// Seconds to keep a forwarded transaction in flight if it isn't included in a block
+    #[arg(long = "telos.inflight_ttl_seconds", requires = "inflight_pool")]
+    pub inflight_ttl_seconds: Option<u64>,
+
     /// Enable the engine2 experimental features on telos-reth binary
     #[arg(long = "engine.experimental", default_value = "false")]
     pub experimental: bool,
@@ -76,4 +92,31 @@ mod tests {
         let args = CommandParser::<TelosArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_inflight_pool_args() {
+        let args = CommandParser::<TelosArgs>::parse_from([
+            "reth",
+            "--telos.telos_endpoint",
+            "http://localhost:8888",
+            "--telos.inflight_pool",
+            "--telos.inflight_ttl_seconds",
+            "30",
+        ])
+        .args;
+        assert!(args.inflight_pool);
+        assert_eq!(args.inflight_ttl_seconds, Some(30));
+
+        assert!(
+            CommandParser::<TelosArgs>::try_parse_from(["reth", "--telos.inflight_pool"]).is_err()
+        );
+        assert!(CommandParser::<TelosArgs>::try_parse_from([
+            "reth",
+            "--telos.telos_endpoint",
+            "http://localhost:8888",
+            "--telos.inflight_pool",
+            "--engine.experimental",
+        ])
+        .is_err());
+    }
 }
@@ -11,6 +11,7 @@
 
 pub mod args;
 pub mod node;
+pub mod txpool;
 
 pub use crate::args::TelosArgs;
 pub use crate::node::TelosNode;
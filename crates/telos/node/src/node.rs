@@ -2,15 +2,17 @@
 
 use reth_chainspec::{ChainSpec};
 use crate::args::TelosArgs;
+use crate::txpool::{TelosTransactionPool, TelosTransactionValidator};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthEngineTypes, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
-use reth_node_builder::components::ComponentsBuilder;
-use reth_node_builder::{Node, PayloadTypes};
+use reth_node_builder::components::{ComponentsBuilder, PoolBuilder};
+use reth_node_builder::{BuilderContext, Node, PayloadTypes};
 use reth_node_ethereum::node::{EthereumConsensusBuilder, EthereumEngineValidatorBuilder, EthereumExecutorBuilder, EthereumNetworkBuilder, EthereumPayloadBuilder, EthereumPoolBuilder};
 use reth_node_types::NodeTypesWithEngine;
 use reth_telos_rpc::eth::TelosEthApi;
+use reth_transaction_pool::{blobstore::DiskFileBlobStore, ConfiguredOrdering};
 
 /// Type configuration for a regular Telos node.
 #[derive(Debug, Default, Clone)]
@@ -29,7 +31,7 @@ impl TelosNode {
     /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node.
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
-        EthereumPoolBuilder,
+        TelosPoolBuilder,
         EthereumPayloadBuilder,
         EthereumNetworkBuilder,
         EthereumExecutorBuilder,
@@ -46,7 +48,7 @@ impl TelosNode {
     {
         ComponentsBuilder::default()
             .node_types::<Node>()
-            .pool(EthereumPoolBuilder::default())
+            .pool(TelosPoolBuilder::default())
             .payload(EthereumPayloadBuilder::default())
             .network(EthereumNetworkBuilder::default())
             .executor(EthereumExecutorBuilder::default())
@@ -79,7 +81,7 @@ where
 {
     type ComponentsBuilder = ComponentsBuilder<
         N,
-        EthereumPoolBuilder,
+        TelosPoolBuilder,
         EthereumPayloadBuilder,
         EthereumNetworkBuilder,
         EthereumExecutorBuilder,
@@ -93,3 +95,30 @@ where
         Self::components()
     }
 }
+
+/// A basic Telos transaction pool.
+///
+/// Transactions are validated with the [`TelosTransactionValidator`], which rejects transactions
+/// priced below the native gas price.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct TelosPoolBuilder;
+
+impl<Types, Node> PoolBuilder<Node> for TelosPoolBuilder
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
+    Node: FullNodeTypes<Types = Types>,
+{
+    type Pool = TelosTransactionPool<Node::Provider, DiskFileBlobStore>;
+
+    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+        // the maintenance task also parks transactions priced below the native gas price of the
+        // new tip, through the validator
+        EthereumPoolBuilder::build_pool_with(
+            ctx,
+            TelosTransactionValidator::new,
+            ConfiguredOrdering::new(ctx.transaction_ordering()),
+        )
+        .await
+    }
+}
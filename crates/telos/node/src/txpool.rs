@@ -0,0 +1,237 @@
+//! Telos transaction pool types
+
+use alloy_primitives::U256;
+use parking_lot::RwLock;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{BlockNumberOrTag, Header, SealedBlock};
+use reth_provider::{BlockReaderIdExt, StateProviderFactory};
+use reth_telos_primitives_traits::TelosTxEnv;
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, ConfiguredOrdering, EthPoolTransaction,
+    EthPooledTransaction, EthTransactionValidator, Pool, PoolTransaction, TransactionOrigin,
+    TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
+};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Type alias for default Telos transaction pool
+///
+/// Pending transactions are ordered by the ordering configured with `--txpool.ordering`, since the
+/// coinbase tip is meaningless at Telos' fixed gas price.
+pub type TelosTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<TelosTransactionValidator<Client, EthPooledTransaction>>,
+    ConfiguredOrdering<EthPooledTransaction>,
+    S,
+>;
+
+/// Validator for Telos transactions.
+///
+/// Telos doesn't have an EIP-1559 base fee, every transaction is charged the native gas price
+/// recorded in the `TelosBlockExtension` of the header. Transactions priced below the gas price of
+/// the current tip are rejected, on top of the checks of [`EthTransactionValidator`].
+#[derive(Debug, Clone)]
+pub struct TelosTransactionValidator<Client, Tx> {
+    /// The type that performs the actual validation.
+    inner: EthTransactionValidator<Client, Tx>,
+    /// Native gas price and revision at the end of the current tip.
+    tx_env: Arc<RwLock<TelosTxEnv>>,
+}
+
+impl<Client, Tx> TelosTransactionValidator<Client, Tx> {
+    /// Returns the configured chain spec
+    pub fn chain_spec(&self) -> Arc<ChainSpec> {
+        self.inner.chain_spec()
+    }
+
+    /// Returns the configured client
+    pub fn client(&self) -> &Client {
+        self.inner.client()
+    }
+
+    /// Returns the native gas price and revision transactions are currently validated against.
+    pub fn tx_env(&self) -> TelosTxEnv {
+        self.tx_env.read().clone()
+    }
+
+    /// Updates the native gas price and revision from the given tip header.
+    fn update_tx_env(&self, header: &Header) {
+        let extension = &header.telos_block_extension;
+        let tx_env = TelosTxEnv {
+            gas_price: extension.get_last_gas_price(),
+            revision: extension.get_last_revision(),
+        };
+
+        let mut current = self.tx_env.write();
+        if *current != tx_env {
+            debug!(
+                target: "txpool",
+                block = header.number,
+                gas_price = %tx_env.gas_price,
+                revision = tx_env.revision,
+                "Updated Telos native gas price and revision"
+            );
+            *current = tx_env;
+        }
+    }
+}
+
+impl<Client, Tx> TelosTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Tx: EthPoolTransaction,
+{
+    /// Create a new [`TelosTransactionValidator`], initialized with the gas price of the latest
+    /// block.
+    pub fn new(inner: EthTransactionValidator<Client, Tx>) -> Self {
+        let this = Self { inner, tx_env: Default::default() };
+        if let Ok(Some(header)) =
+            this.inner.client().header_by_number_or_tag(BlockNumberOrTag::Latest)
+        {
+            this.update_tx_env(&header);
+        }
+
+        this
+    }
+
+    /// Validates a single transaction.
+    ///
+    /// See also [`TransactionValidator::validate_transaction`]
+    ///
+    /// This behaves the same as [`EthTransactionValidator::validate_one`], but in addition, ensures
+    /// that the transaction pays at least the current native gas price.
+    pub fn validate_one(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        let gas_price = self.tx_env.read().gas_price;
+        if U256::from(transaction.max_fee_per_gas()) < gas_price {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::Underpriced,
+            )
+        }
+
+        self.inner.validate_one(origin, transaction)
+    }
+
+    /// Validates all given transactions.
+    ///
+    /// Returns all outcomes for the given transactions in the same order.
+    ///
+    /// See also [`Self::validate_one`]
+    pub fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        transactions.into_iter().map(|(origin, tx)| self.validate_one(origin, tx)).collect()
+    }
+}
+
+impl<Client, Tx> TransactionValidator for TelosTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Tx: EthPoolTransaction,
+{
+    type Transaction = Tx;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_one(origin, transaction)
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        self.validate_all(transactions)
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.inner.on_new_head_block(new_tip_block);
+        self.update_tx_env(&new_tip_block.header);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{TxKind, B256};
+    use reth_chainspec::MAINNET;
+    use reth_primitives::{
+        SealedHeader, Signature, Transaction, TransactionSigned, TransactionSignedEcRecovered,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_telos_primitives_traits::TelosBlockExtension;
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+    };
+
+    fn transaction(gas_price: u128) -> EthPooledTransaction {
+        let signer = Default::default();
+        let tx = Transaction::Legacy(TxLegacy {
+            chain_id: Some(MAINNET.chain.id()),
+            nonce: 0,
+            gas_price,
+            gas_limit: 21_000,
+            to: TxKind::Call(Default::default()),
+            value: U256::ZERO,
+            input: Default::default(),
+        });
+        let signed_tx =
+            TransactionSigned::from_transaction_and_signature(tx, Signature::test_signature());
+        let signed_recovered =
+            TransactionSignedEcRecovered::from_signed_transaction(signed_tx, signer);
+        let len = signed_recovered.encode_2718_len();
+        EthPooledTransaction::new(signed_recovered, len)
+    }
+
+    #[test]
+    fn validate_native_gas_price() {
+        let client = MockEthProvider::default();
+        client.add_account(Default::default(), ExtendedAccount::new(0, U256::MAX));
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .no_shanghai()
+            .no_cancun()
+            .build(client, InMemoryBlobStore::default());
+        let validator = TelosTransactionValidator::new(validator);
+
+        let mut header = Header::default();
+        header.telos_block_extension = TelosBlockExtension {
+            starting_gas_price: U256::from(500_000_000_000u64),
+            ..Default::default()
+        };
+        validator.on_new_head_block(&SealedBlock {
+            header: SealedHeader::new(header.clone(), B256::ZERO),
+            ..Default::default()
+        });
+        assert_eq!(validator.tx_env().gas_price, U256::from(500_000_000_000u64));
+
+        let outcome =
+            validator.validate_one(TransactionOrigin::External, transaction(499_000_000_000));
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Underpriced)
+        ));
+
+        let outcome =
+            validator.validate_one(TransactionOrigin::External, transaction(500_000_000_000));
+        assert!(outcome.is_valid());
+
+        // a gas price decrease makes the transaction valid again
+        header.telos_block_extension = header.telos_block_extension.to_child();
+        header.telos_block_extension.starting_gas_price = U256::from(400_000_000_000u64);
+        validator.on_new_head_block(&SealedBlock {
+            header: SealedHeader::new(header, B256::ZERO),
+            ..Default::default()
+        });
+        let outcome =
+            validator.validate_one(TransactionOrigin::External, transaction(499_000_000_000));
+        assert!(outcome.is_valid());
+    }
+}
@@ -10,6 +10,10 @@
 mod header;
 
 use alloy_primitives::U256;
+use alloy_rlp::{
+    length_of_length, Buf, BufMut, Decodable, Encodable, RlpDecodable, RlpEncodable,
+    EMPTY_LIST_CODE,
+};
 use serde::{Deserialize, Serialize};
 use reth_codecs::Compact;
 pub use header::TelosHeader;
@@ -147,6 +151,74 @@ impl TelosBlockExtension {
     }
 }
 
+impl TelosBlockExtension {
+    fn rlp_payload_length(&self) -> usize {
+        self.starting_gas_price.length() +
+            self.starting_revision_number.length() +
+            self.gas_price_change.as_ref().map_or(1, Encodable::length) +
+            self.revision_change.as_ref().map_or(1, Encodable::length)
+    }
+}
+
+/// RLP encoding used to send the extension along with its header to other Telos peers.
+///
+/// The extension isn't part of the header RLP, which determines the block hash. Absent gas price
+/// and revision changes are encoded as empty lists.
+impl Encodable for TelosBlockExtension {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.rlp_payload_length() }.encode(out);
+        self.starting_gas_price.encode(out);
+        self.starting_revision_number.encode(out);
+        encode_optional(self.gas_price_change.as_ref(), out);
+        encode_optional(self.revision_change.as_ref(), out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_payload_length();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for TelosBlockExtension {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+        let this = Self {
+            starting_gas_price: Decodable::decode(buf)?,
+            starting_revision_number: Decodable::decode(buf)?,
+            gas_price_change: decode_optional(buf)?,
+            revision_change: decode_optional(buf)?,
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(this)
+    }
+}
+
+fn encode_optional<T: Encodable>(value: Option<&T>, out: &mut dyn BufMut) {
+    match value {
+        Some(value) => value.encode(out),
+        None => out.put_u8(EMPTY_LIST_CODE),
+    }
+}
+
+fn decode_optional<T: Decodable>(buf: &mut &[u8]) -> alloy_rlp::Result<Option<T>> {
+    if buf.first() == Some(&EMPTY_LIST_CODE) {
+        buf.advance(1);
+        return Ok(None);
+    }
+    T::decode(buf).map(Some)
+}
+
 /// Telos transaction environment data
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Compact)]
@@ -159,7 +231,10 @@ pub struct TelosTxEnv {
 
 /// Telos gas price
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
-#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Compact)]
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Compact, RlpEncodable,
+    RlpDecodable,
+)]
 pub struct GasPrice {
     /// Transaction height
     pub height: u64,
@@ -169,10 +244,42 @@ pub struct GasPrice {
 
 /// Telos revision number
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
-#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Compact)]
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Compact, RlpEncodable,
+    RlpDecodable,
+)]
 pub struct Revision {
     /// Transaction height
     pub height: u64,
     /// Revision
     pub revision: u64,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_extension_rlp_roundtrip() {
+        for extension in [
+            TelosBlockExtension::default(),
+            TelosBlockExtension {
+                starting_gas_price: U256::from(500_000_000_000u64),
+                starting_revision_number: 1,
+                gas_price_change: Some(GasPrice { height: 2, price: U256::from(1) }),
+                revision_change: None,
+            },
+            TelosBlockExtension {
+                starting_gas_price: U256::ZERO,
+                starting_revision_number: 0,
+                gas_price_change: None,
+                revision_change: Some(Revision { height: 3, revision: 2 }),
+            },
+        ] {
+            let mut buf = Vec::new();
+            extension.encode(&mut buf);
+            assert_eq!(buf.len(), extension.length());
+            assert_eq!(TelosBlockExtension::decode(&mut buf.as_slice()).unwrap(), extension);
+        }
+    }
+}
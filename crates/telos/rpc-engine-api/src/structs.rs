@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 use alloy_primitives::{Address, Bytes, U256};
+use reth_db_models::{
+    StoredTelosAccountRow, StoredTelosAccountStateRow, StoredTelosExtraFields,
+    StoredTelosRevisionChange, StoredTelosTxValue,
+};
 use reth_primitives::Receipt;
 
 /// Telos EVM Account Table Row
@@ -15,10 +19,24 @@ pub struct TelosAccountTableRow {
     pub nonce: u64,
     /// Code
     pub code: Bytes,
+    /// Index of the row in the `accountcode` table holding the code, if not inlined in `code`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_id: Option<u64>,
     /// Balance
     pub balance: U256
 }
 
+/// Telos EVM Account Code Table Row
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelosAccountCodeTableRow {
+    /// Removed - if true, this row was removed from storage
+    pub removed: bool,
+    /// Index, referenced by `code_id` of account rows
+    pub index: u64,
+    /// Code
+    pub code: Bytes
+}
+
 /// Telos EVM Account State Table Row
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TelosAccountStateTableRow {
@@ -51,3 +69,88 @@ pub struct TelosEngineAPIExtraFields {
     /// Receipts produced by telos.evm contract
     pub receipts: Option<Vec<Receipt>>
 }
+
+impl From<TelosEngineAPIExtraFields> for StoredTelosExtraFields {
+    fn from(fields: TelosEngineAPIExtraFields) -> Self {
+        let tx_values = |values: Vec<(u64, U256)>| {
+            values
+                .into_iter()
+                .map(|(tx_index, value)| StoredTelosTxValue { tx_index, value })
+                .collect()
+        };
+        Self {
+            statediffs_account: fields.statediffs_account.map(|rows| {
+                rows.into_iter()
+                    .map(|row| StoredTelosAccountRow {
+                        removed: row.removed,
+                        nonce: row.nonce,
+                        code_id: row.code_id,
+                        balance: row.balance,
+                        address: row.address,
+                        account: row.account.into_bytes(),
+                        code: row.code,
+                    })
+                    .collect()
+            }),
+            statediffs_accountstate: fields.statediffs_accountstate.map(|rows| {
+                rows.into_iter()
+                    .map(|row| StoredTelosAccountStateRow {
+                        removed: row.removed,
+                        key: row.key,
+                        value: row.value,
+                        address: row.address,
+                    })
+                    .collect()
+            }),
+            revision_change: fields
+                .revision_changes
+                .map(|(tx_index, revision)| StoredTelosRevisionChange { tx_index, revision }),
+            gas_price_change: fields
+                .gasprice_changes
+                .map(|(tx_index, value)| StoredTelosTxValue { tx_index, value }),
+            new_addresses_using_create: fields.new_addresses_using_create.map(tx_values),
+            new_addresses_using_openwallet: fields.new_addresses_using_openwallet.map(tx_values),
+            receipts: fields.receipts,
+        }
+    }
+}
+
+impl From<StoredTelosExtraFields> for TelosEngineAPIExtraFields {
+    fn from(fields: StoredTelosExtraFields) -> Self {
+        let tx_values = |values: Vec<StoredTelosTxValue>| {
+            values.into_iter().map(|value| (value.tx_index, value.value)).collect()
+        };
+        Self {
+            statediffs_account: fields.statediffs_account.map(|rows| {
+                rows.into_iter()
+                    .map(|row| TelosAccountTableRow {
+                        removed: row.removed,
+                        address: row.address,
+                        account: String::from_utf8_lossy(&row.account).into_owned(),
+                        nonce: row.nonce,
+                        code: row.code,
+                        code_id: row.code_id,
+                        balance: row.balance,
+                    })
+                    .collect()
+            }),
+            statediffs_accountstate: fields.statediffs_accountstate.map(|rows| {
+                rows.into_iter()
+                    .map(|row| TelosAccountStateTableRow {
+                        removed: row.removed,
+                        address: row.address,
+                        key: row.key,
+                        value: row.value,
+                    })
+                    .collect()
+            }),
+            revision_changes: fields
+                .revision_change
+                .map(|change| (change.tx_index, change.revision)),
+            gasprice_changes: fields.gas_price_change.map(|change| (change.tx_index, change.value)),
+            new_addresses_using_create: fields.new_addresses_using_create.map(tx_values),
+            new_addresses_using_openwallet: fields.new_addresses_using_openwallet.map(tx_values),
+            receipts: fields.receipts,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! In-flight view of transactions forwarded to Telos native.
+
+use alloy_primitives::{Address, TxHash};
+use futures_util::{Stream, StreamExt};
+use parking_lot::RwLock;
+use reth_primitives::TransactionSignedEcRecovered;
+use reth_provider::CanonStateNotification;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::trace;
+
+/// Default time a forwarded transaction is kept in the [`InFlightTransactions`] if it isn't
+/// included in a block.
+pub const DEFAULT_IN_FLIGHT_TTL: Duration = Duration::from_secs(60);
+
+/// Transactions forwarded to Telos native which aren't included in a block yet.
+///
+/// Telos replicas never build blocks, so in this mode forwarded transactions are kept here instead
+/// of the transaction pool, where they'd never be mined. Transactions are keyed by sender and
+/// nonce, a transaction replaces an in-flight one with the same sender and nonce. They're removed
+/// once a transaction of the sender with the same or a higher nonce is included in a block, or
+/// after the configured time to live. Transactions of reorged blocks that aren't included in the
+/// new chain are in flight again.
+///
+/// Transactions in here are never gossiped to peers.
+#[derive(Debug, Clone)]
+pub struct InFlightTransactions {
+    inner: Arc<RwLock<InFlightTransactionsInner>>,
+    ttl: Duration,
+}
+
+#[derive(Debug, Default)]
+struct InFlightTransactionsInner {
+    by_sender: HashMap<Address, BTreeMap<u64, InFlightTransaction>>,
+    by_hash: HashMap<TxHash, (Address, u64)>,
+}
+
+#[derive(Debug)]
+struct InFlightTransaction {
+    transaction: TransactionSignedEcRecovered,
+    forwarded_at: Instant,
+}
+
+impl InFlightTransactions {
+    /// Creates an empty [`InFlightTransactions`] keeping transactions for at most `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self { inner: Default::default(), ttl }
+    }
+
+    /// Returns the time a transaction is kept if it isn't included in a block.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the number of in-flight transactions, including expired ones which weren't evicted
+    /// yet.
+    pub fn len(&self) -> usize {
+        self.inner.read().by_hash.len()
+    }
+
+    /// Returns `true` if there are no in-flight transactions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a transaction which was accepted by Telos native, replacing any in-flight transaction
+    /// with the same sender and nonce.
+    pub fn insert(&self, transaction: TransactionSignedEcRecovered) {
+        let mut inner = self.inner.write();
+        inner.evict_expired(self.ttl);
+
+        let (sender, nonce, hash) = (transaction.signer(), transaction.nonce(), transaction.hash);
+        let replaced = inner
+            .by_sender
+            .entry(sender)
+            .or_default()
+            .insert(nonce, InFlightTransaction { transaction, forwarded_at: Instant::now() });
+        if let Some(replaced) = replaced {
+            inner.by_hash.remove(&replaced.transaction.hash);
+        }
+        inner.by_hash.insert(hash, (sender, nonce));
+    }
+
+    /// Removes a transaction.
+    pub fn remove(&self, hash: &TxHash) -> Option<TransactionSignedEcRecovered> {
+        let mut inner = self.inner.write();
+        let (sender, nonce) = inner.by_hash.remove(hash)?;
+        let transactions = inner.by_sender.get_mut(&sender)?;
+        let removed = transactions.remove(&nonce);
+        if transactions.is_empty() {
+            inner.by_sender.remove(&sender);
+        }
+        removed.map(|removed| removed.transaction)
+    }
+
+    /// Returns the in-flight transaction with the given hash, if it hasn't expired.
+    pub fn get(&self, hash: &TxHash) -> Option<TransactionSignedEcRecovered> {
+        let inner = self.inner.read();
+        let (sender, nonce) = inner.by_hash.get(hash)?;
+        inner
+            .by_sender
+            .get(sender)?
+            .get(nonce)
+            .filter(|transaction| transaction.forwarded_at.elapsed() < self.ttl)
+            .map(|transaction| transaction.transaction.clone())
+    }
+
+    /// Returns all unexpired in-flight transactions, ordered by nonce for each sender.
+    pub fn transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        self.inner
+            .read()
+            .by_sender
+            .values()
+            .flat_map(BTreeMap::values)
+            .filter(|transaction| transaction.forwarded_at.elapsed() < self.ttl)
+            .map(|transaction| transaction.transaction.clone())
+            .collect()
+    }
+
+    /// Returns the highest nonce of the unexpired in-flight transactions of the sender.
+    pub fn highest_nonce(&self, sender: &Address) -> Option<u64> {
+        self.inner
+            .read()
+            .by_sender
+            .get(sender)?
+            .iter()
+            .rev()
+            .find(|(_, transaction)| transaction.forwarded_at.elapsed() < self.ttl)
+            .map(|(nonce, _)| *nonce)
+    }
+
+    /// Removes all in-flight transactions made obsolete by the included transactions, given as
+    /// sender and nonce. These are the included transactions themselves, and any transaction of
+    /// the same sender with a lower nonce.
+    pub fn on_included(&self, included: impl IntoIterator<Item = (Address, u64)>) {
+        let mut inner = self.inner.write();
+        for (sender, nonce) in included {
+            let Some(transactions) = inner.by_sender.get_mut(&sender) else { continue };
+            let remaining = transactions.split_off(&(nonce + 1));
+            let removed = std::mem::replace(transactions, remaining);
+            if transactions.is_empty() {
+                inner.by_sender.remove(&sender);
+            }
+            for transaction in removed.into_values() {
+                inner.by_hash.remove(&transaction.transaction.hash);
+            }
+        }
+
+        inner.evict_expired(self.ttl);
+    }
+
+    /// Adds back the transactions of reverted blocks, which aren't included anymore.
+    ///
+    /// Their time to live starts over. Transactions that are included again in the new chain have
+    /// to be removed with [`Self::on_included`] afterwards.
+    pub fn on_reverted(&self, reverted: impl IntoIterator<Item = TransactionSignedEcRecovered>) {
+        for transaction in reverted {
+            trace!(
+                target: "rpc::eth",
+                hash = %transaction.hash,
+                "Adding back reverted in-flight transaction"
+            );
+            self.insert(transaction);
+        }
+    }
+}
+
+impl InFlightTransactionsInner {
+    fn evict_expired(&mut self, ttl: Duration) {
+        let by_hash = &mut self.by_hash;
+        self.by_sender.retain(|_, transactions| {
+            transactions.retain(|_, transaction| {
+                let expired = transaction.forwarded_at.elapsed() >= ttl;
+                if expired {
+                    trace!(
+                        target: "rpc::eth",
+                        hash = %transaction.transaction.hash,
+                        "Evicting expired in-flight transaction"
+                    );
+                    by_hash.remove(&transaction.transaction.hash);
+                }
+                !expired
+            });
+            !transactions.is_empty()
+        });
+    }
+}
+
+/// Removes transactions from the [`InFlightTransactions`] once they're included in a canonical
+/// block, and adds back the transactions of reverted blocks.
+pub async fn maintain_in_flight_transactions<St>(in_flight: InFlightTransactions, events: St)
+where
+    St: Stream<Item = CanonStateNotification>,
+{
+    let mut events = std::pin::pin!(events);
+    while let Some(event) = events.next().await {
+        if let CanonStateNotification::Reorg { old, .. } = &event {
+            in_flight.on_reverted(old.transactions_with_sender().map(|(sender, transaction)| {
+                TransactionSignedEcRecovered::from_signed_transaction(transaction.clone(), *sender)
+            }));
+        }
+
+        let chain = event.committed();
+        in_flight.on_included(
+            chain
+                .transactions_with_sender()
+                .map(|(sender, transaction)| (*sender, transaction.nonce())),
+        );
+    }
+}
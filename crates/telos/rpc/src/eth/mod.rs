@@ -1,5 +1,6 @@
 //! Telos `eth_` endpoint implementation.
 
+pub mod in_flight;
 pub mod receipt;
 pub mod transaction;
 
@@ -14,14 +15,14 @@ use std::{fmt, sync::Arc};
 
 use crate::TelosClient;
 use alloy_network::AnyNetwork;
-use alloy_primitives::U256;
+use alloy_primitives::{Address, U256};
 use derive_more::Deref;
 use reth_chainspec::EthereumHardforks;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_node_api::{BuilderProvider, FullNodeComponents, FullNodeTypes, NodeTypes};
 use reth_node_builder::EthApiBuilderCtx;
-use reth_primitives::Header;
+use reth_primitives::{BlockId, Header};
 use reth_provider::{
     BlockIdReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider,
     StageCheckpointReader, StateProviderFactory,
@@ -32,17 +33,20 @@ use reth_rpc_eth_api::{
         AddDevSigners, EthApiSpec, EthFees, EthSigner, EthState, LoadBlock, LoadFee, LoadState,
         SpawnBlocking, Trace,
     },
-    EthApiTypes,
+    EthApiTypes, FromEthApiError,
+};
+use reth_rpc_eth_types::{
+    EthApiError, EthStateCache, FeeHistoryCache, GasPriceOracle, RpcInvalidTransactionError,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
 };
 use reth_transaction_pool::TransactionPool;
+use std::future::Future;
 use tokio::sync::OnceCell;
 
-use crate::error::TelosEthApiError;
+use crate::{error::TelosEthApiError, eth::in_flight::InFlightTransactions};
 
 /// Adapter for [`EthApiInner`], which holds all the data required to serve core `eth_` API.
 pub type EthApiNodeBackend<N> = EthApiInner<
@@ -68,6 +72,8 @@ pub struct TelosEthApi<N: FullNodeComponents> {
     #[deref]
     inner: Arc<EthApiNodeBackend<N>>,
     telos_client: Arc<OnceCell<TelosClient>>,
+    /// Forwarded transactions, if they're kept out of the transaction pool.
+    in_flight: Arc<OnceCell<InFlightTransactions>>,
 }
 
 impl<N: FullNodeComponents> TelosEthApi<N> {
@@ -93,7 +99,11 @@ impl<N: FullNodeComponents> TelosEthApi<N> {
             ctx.config.proof_permits,
         );
 
-        Self { inner: Arc::new(inner), telos_client: Arc::new(OnceCell::new()) }
+        Self {
+            inner: Arc::new(inner),
+            telos_client: Arc::new(OnceCell::new()),
+            in_flight: Arc::new(OnceCell::new()),
+        }
     }
 }
 
@@ -217,6 +227,38 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    /// Returns the number of transactions sent from an address at the given block identifier.
+    ///
+    /// For the pending tag, in-flight transactions forwarded to Telos native are taken into
+    /// account as well.
+    fn transaction_count(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
+        async move {
+            let count = LoadState::transaction_count(self, address, block_id).await?;
+            if block_id != Some(BlockId::pending()) {
+                return Ok(count)
+            }
+
+            let Some(highest_nonce) = self
+                .in_flight_transactions()
+                .and_then(|in_flight| in_flight.highest_nonce(&address))
+            else {
+                return Ok(count)
+            };
+
+            // the corresponding txcount is nonce + 1
+            let next_nonce = highest_nonce.checked_add(1).ok_or_else(|| {
+                Self::Error::from_eth_err(EthApiError::InvalidTransaction(
+                    RpcInvalidTransactionError::NonceMaxValue,
+                ))
+            })?;
+            Ok(count.max(U256::from(next_nonce)))
+        }
+    }
 }
 
 impl<N> EthFees for TelosEthApi<N>
@@ -1,17 +1,23 @@
 //! Loads and formats OP transaction RPC response.
 
-use alloy_primitives::{Bytes, B256};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{BlockNumber, Bytes, B256};
 use reth_node_api::FullNodeComponents;
 use reth_provider::{BlockReaderIdExt, TransactionsProvider};
 use reth_rpc_eth_api::{
-    helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
+    helpers::{
+        transaction::add_private_raw_transaction, EthSigner, EthTransactions, LoadTransaction,
+        SpawnBlocking,
+    },
     FromEthApiError, FullEthApiTypes,
 };
-use reth_rpc_eth_types::{utils::recover_raw_transaction, EthStateCache};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthStateCache, TransactionSource};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 
+use crate::eth::in_flight::InFlightTransactions;
 use crate::eth::TelosClient;
 use crate::eth::TelosEthApi;
+use crate::txpool::TelosTxPoolApi;
 
 impl<N> EthTransactions for TelosEthApi<N>
 where
@@ -28,9 +34,14 @@ where
 
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
+    /// If in-flight transactions are enabled, the transaction is only forwarded to Telos native
+    /// and kept in the [`InFlightTransactions`] instead of the pool.
+    ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
         let recovered = recover_raw_transaction(tx.clone())?;
+        let in_flight =
+            self.in_flight_transactions().map(|in_flight| (in_flight, recovered.clone()));
         let pool_transaction = <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered);
 
         // On Telos, transactions are forwarded directly to the native network to be included in a block.
@@ -46,6 +57,13 @@ where
             }
         }
 
+        if let Some((in_flight, recovered)) = in_flight {
+            let transaction = recovered.into_ecrecovered_transaction();
+            let hash = transaction.hash();
+            in_flight.insert(transaction);
+            return Ok(hash);
+        }
+
         // submit the transaction to the pool with a `Local` origin
         let hash = self
             .pool()
@@ -55,6 +73,68 @@ where
 
         Ok(hash)
     }
+
+    /// Decodes and recovers the transaction and submits it to the pool as a private transaction,
+    /// which is never propagated to peers.
+    ///
+    /// Telos replicas don't produce blocks, so the transaction is also forwarded to Telos native
+    /// once the pool accepted it. It's removed from the pool again if forwarding fails.
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block: Option<BlockNumber>,
+    ) -> Result<B256, Self::Error> {
+        let hash = add_private_raw_transaction(self.pool(), tx.clone(), max_block).await?;
+
+        if let Some(client) = self.raw_tx_forwarder().as_ref() {
+            tracing::debug!(target: "rpc::eth", "forwarding private raw transaction to Telos native");
+            if let Err(err) = client.send_to_telos(&tx).await {
+                self.pool().remove_transactions(vec![hash]);
+                return Err(Self::Error::from_eth_err(err));
+            }
+        }
+
+        Ok(hash)
+    }
+
+    /// Returns the transaction by hash, including in-flight transactions forwarded to Telos
+    /// native, which are returned as pending.
+    async fn transaction_by_hash(
+        &self,
+        hash: B256,
+    ) -> Result<Option<TransactionSource>, Self::Error> {
+        if let Some(tx) = LoadTransaction::transaction_by_hash(self, hash).await? {
+            return Ok(Some(tx));
+        }
+
+        Ok(self
+            .in_flight_transactions()
+            .and_then(|in_flight| in_flight.get(&hash))
+            .map(TransactionSource::Pool))
+    }
+
+    /// Returns the EIP-2718 encoded transaction by hash, including in-flight transactions
+    /// forwarded to Telos native.
+    async fn raw_transaction_by_hash(&self, hash: B256) -> Result<Option<Bytes>, Self::Error> {
+        if let Some(tx) = self.in_flight_transactions().and_then(|in_flight| in_flight.get(&hash)) {
+            return Ok(Some(tx.encoded_2718().into()));
+        }
+
+        // Note: this is mostly used to fetch pooled transactions so we check the pool first
+        if let Some(tx) =
+            self.pool().get_pooled_transaction_element(hash).map(|tx| tx.envelope_encoded())
+        {
+            return Ok(Some(tx));
+        }
+
+        self.spawn_blocking_io(move |ref this| {
+            Ok(LoadTransaction::provider(this)
+                .transaction_by_hash(hash)
+                .map_err(Self::Error::from_eth_err)?
+                .map(|tx| tx.encoded_2718().into()))
+        })
+        .await
+    }
 }
 
 impl<N> LoadTransaction for TelosEthApi<N>
@@ -93,4 +173,26 @@ where
     pub fn raw_tx_forwarder(&self) -> Option<TelosClient> {
         self.telos_client.get().cloned()
     }
-}
\ No newline at end of file
+
+    /// Sets the [`InFlightTransactions`] to keep forwarded transactions in, instead of the pool.
+    ///
+    /// Forwarded transactions then never enter the pool, so they're never gossiped to peers.
+    pub fn set_in_flight_transactions(&self, in_flight: InFlightTransactions) {
+        self.in_flight.set(in_flight).expect("In-flight transactions can be set only once");
+    }
+
+    /// Returns the [`InFlightTransactions`] if forwarded transactions are kept out of the pool.
+    pub fn in_flight_transactions(&self) -> Option<InFlightTransactions> {
+        self.in_flight.get().cloned()
+    }
+
+    /// Returns the `txpool` API serving the given pool and the [`InFlightTransactions`], if
+    /// forwarded transactions are kept out of the pool.
+    pub fn in_flight_txpool_api<Pool>(&self, pool: Pool) -> Option<TelosTxPoolApi<Pool, Self>>
+    where
+        Self: FullEthApiTypes + 'static,
+        Pool: TransactionPool + 'static,
+    {
+        self.in_flight_transactions().map(|in_flight| TelosTxPoolApi::new(pool, in_flight))
+    }
+}
@@ -2,5 +2,6 @@
 
 pub mod error;
 pub mod eth;
+pub mod txpool;
 
 pub use eth::telos_client::TelosClient;
\ No newline at end of file
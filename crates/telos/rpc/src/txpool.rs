@@ -0,0 +1,74 @@
+//! `txpool` namespace overrides serving the in-flight transactions.
+
+use alloy_primitives::Address;
+use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom};
+use jsonrpsee::{core::RegisterMethodError, RpcModule};
+use reth_rpc::TxPoolApi;
+use reth_rpc_eth_api::{FullEthApiTypes, RpcTransaction};
+use reth_rpc_types_compat::transaction::from_recovered;
+use reth_transaction_pool::TransactionPool;
+use tracing::trace;
+
+use crate::eth::in_flight::InFlightTransactions;
+
+/// `txpool` API that includes the [`InFlightTransactions`], which never enter the pool, as pending
+/// transactions.
+///
+/// Only overrides `txpool_content` and `txpool_contentFrom`, see [`Self::into_rpc`].
+#[derive(Debug)]
+pub struct TelosTxPoolApi<Pool, Eth> {
+    inner: TxPoolApi<Pool, Eth>,
+    in_flight: InFlightTransactions,
+}
+
+impl<Pool, Eth> TelosTxPoolApi<Pool, Eth>
+where
+    Pool: TransactionPool + 'static,
+    Eth: FullEthApiTypes + 'static,
+{
+    /// Creates a new instance serving the pool and in-flight transactions.
+    pub fn new(pool: Pool, in_flight: InFlightTransactions) -> Self {
+        Self { inner: TxPoolApi::new(pool), in_flight }
+    }
+
+    /// Returns the pool content, with the in-flight transactions added to the pending ones.
+    ///
+    /// An in-flight transaction takes precedence over a pool transaction with the same sender and
+    /// nonce.
+    pub fn content(&self) -> TxpoolContent<RpcTransaction<Eth::NetworkTypes>> {
+        let mut content = self.inner.content();
+        for transaction in self.in_flight.transactions() {
+            let (sender, nonce) = (transaction.signer(), transaction.nonce());
+            if let Some(queued) = content.queued.get_mut(&sender) {
+                queued.remove(&nonce.to_string());
+                if queued.is_empty() {
+                    content.queued.remove(&sender);
+                }
+            }
+            content
+                .pending
+                .entry(sender)
+                .or_default()
+                .insert(nonce.to_string(), from_recovered::<Eth::TransactionCompat>(transaction));
+        }
+        content
+    }
+
+    /// Returns the `txpool_content` and `txpool_contentFrom` methods, to replace the ones of the
+    /// configured `txpool` namespace.
+    pub fn into_rpc(self) -> Result<RpcModule<Self>, RegisterMethodError> {
+        let mut module = RpcModule::new(self);
+        module.register_method("txpool_content", |_, this, _| {
+            trace!(target: "rpc::eth", "Serving txpool_content");
+            this.content()
+        })?;
+        module.register_method("txpool_contentFrom", |params, this, _| {
+            let from = params.one::<Address>()?;
+            trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
+            Ok::<TxpoolContentFrom<_>, jsonrpsee::types::ErrorObjectOwned>(
+                this.content().remove_from(&from),
+            )
+        })?;
+        Ok(module)
+    }
+}
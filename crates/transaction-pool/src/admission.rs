@@ -0,0 +1,398 @@
+//! Admission policies deciding which transactions may enter the pool.
+//!
+//! An [`AdmissionPolicy`] is checked for every transaction added to the pool, regardless of its
+//! origin, before the transaction is validated. It can deny transactions by sender, by recipient
+//! and by the function selector of contract calls, and cap the number of transactions a sender may
+//! submit within a period.
+//!
+//! With the `serde` feature, the [`AdmissionRules`] of a policy can be loaded from a TOML file and
+//! reloaded while the node is running:
+//!
+//! ```toml
+//! denied_senders = ["0x0000000000000000000000000000000000000001"]
+//! denied_recipients = ["0x0000000000000000000000000000000000000002"]
+//! denied_selectors = ["0xa9059cbb"]
+//!
+//! [sender_rate_limit]
+//! max_transactions = 10
+//! period_secs = 60
+//! ```
+
+use crate::{error::PoolErrorKind, metrics::AdmissionMetrics, PoolTransaction};
+use alloy_primitives::{Address, Selector};
+use parking_lot::{Mutex, RwLock};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Rules deciding which transactions are admitted to the pool.
+///
+/// The default rules admit all transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct AdmissionRules {
+    /// If set, only transactions of these senders are admitted.
+    pub allowed_senders: Option<HashSet<Address>>,
+    /// Senders whose transactions are never admitted.
+    pub denied_senders: HashSet<Address>,
+    /// If set, only calls to these recipients are admitted. Contract creations are not affected.
+    pub allowed_recipients: Option<HashSet<Address>>,
+    /// Recipients which may not be called.
+    pub denied_recipients: HashSet<Address>,
+    /// Function selectors which may not be called on any contract.
+    pub denied_selectors: HashSet<Selector>,
+    /// Maximum number of transactions a single sender may submit per period.
+    pub sender_rate_limit: Option<SenderRateLimit>,
+}
+
+/// Maximum number of transactions a single sender may submit per period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SenderRateLimit {
+    /// Maximum number of transactions per period.
+    pub max_transactions: u32,
+    /// Length of the period in seconds.
+    pub period_secs: u64,
+}
+
+impl SenderRateLimit {
+    /// Returns the length of the period.
+    pub const fn period(&self) -> Duration {
+        Duration::from_secs(self.period_secs)
+    }
+}
+
+/// Errors loading an admission policy file.
+#[cfg(feature = "serde")]
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionPolicyError {
+    /// The policy wasn't loaded from a file, so it can't be reloaded.
+    #[error("no admission policy file configured")]
+    NoFile,
+    /// Error reading the policy file.
+    #[error(transparent)]
+    Io(#[from] reth_fs_util::FsPathError),
+    /// Error parsing the policy file.
+    #[error("failed to parse admission policy {path:?}: {err}")]
+    Parse {
+        /// Path of the policy file.
+        path: PathBuf,
+        /// The parse error.
+        err: toml::de::Error,
+    },
+}
+
+/// Shared handle to the admission policy of the pool.
+///
+/// Clones share the same rules, so updating the rules through any handle takes effect for the
+/// pool immediately.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionPolicy {
+    inner: Arc<AdmissionPolicyInner>,
+}
+
+#[derive(Debug, Default)]
+struct AdmissionPolicyInner {
+    /// The file the rules were loaded from, if any.
+    path: Option<PathBuf>,
+    rules: RwLock<AdmissionRules>,
+    rate_limiter: Mutex<RateLimiter>,
+    metrics: AdmissionMetrics,
+}
+
+impl AdmissionPolicy {
+    /// Creates a new policy with the given rules.
+    pub fn new(rules: AdmissionRules) -> Self {
+        Self {
+            inner: Arc::new(AdmissionPolicyInner {
+                rules: RwLock::new(rules),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Loads the policy from the TOML file at the given path, which can be reloaded later with
+    /// [`Self::reload`].
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, AdmissionPolicyError> {
+        let path = path.into();
+        let rules = read_rules(&path)?;
+        Ok(Self {
+            inner: Arc::new(AdmissionPolicyInner {
+                path: Some(path),
+                rules: RwLock::new(rules),
+                ..Default::default()
+            }),
+        })
+    }
+
+    /// Reloads the rules from the policy file and returns them.
+    ///
+    /// The current rules are kept if the file can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn reload(&self) -> Result<AdmissionRules, AdmissionPolicyError> {
+        let path = self.path().ok_or(AdmissionPolicyError::NoFile)?;
+        let rules = read_rules(path)?;
+        self.set_rules(rules.clone());
+        self.inner.metrics.admission_policy_reloads.increment(1);
+        tracing::info!(target: "txpool", ?path, "Reloaded transaction admission policy");
+        Ok(rules)
+    }
+
+    /// Returns the path of the policy file, if the policy was loaded from one.
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    /// Returns the current rules.
+    pub fn rules(&self) -> AdmissionRules {
+        self.inner.rules.read().clone()
+    }
+
+    /// Replaces the rules, which also resets the rate limits of all senders.
+    pub fn set_rules(&self, rules: AdmissionRules) {
+        *self.inner.rules.write() = rules;
+        self.inner.rate_limiter.lock().clear();
+    }
+
+    /// Checks whether the transaction may enter the pool.
+    ///
+    /// An admitted transaction counts towards the rate limit of its sender, unless it's refunded
+    /// with [`Self::refund`] because it didn't enter the pool after all, e.g. because it's invalid
+    /// or already known.
+    pub fn check<T: PoolTransaction>(&self, transaction: &T) -> Result<(), PoolErrorKind> {
+        let rules = self.inner.rules.read();
+        let metrics = &self.inner.metrics;
+
+        let sender = transaction.sender();
+        if rules.denied_senders.contains(&sender) ||
+            rules.allowed_senders.as_ref().is_some_and(|allowed| !allowed.contains(&sender))
+        {
+            metrics.admission_denied_sender.increment(1);
+            return Err(PoolErrorKind::SenderDenied(sender))
+        }
+
+        if let Some(recipient) = transaction.to() {
+            if rules.denied_recipients.contains(&recipient) ||
+                rules
+                    .allowed_recipients
+                    .as_ref()
+                    .is_some_and(|allowed| !allowed.contains(&recipient))
+            {
+                metrics.admission_denied_recipient.increment(1);
+                return Err(PoolErrorKind::RecipientDenied(recipient))
+            }
+
+            if let Some(selector) = transaction.input().get(..4) {
+                let selector = Selector::from_slice(selector);
+                if rules.denied_selectors.contains(&selector) {
+                    metrics.admission_denied_selector.increment(1);
+                    return Err(PoolErrorKind::SelectorDenied(selector))
+                }
+            }
+        }
+
+        if let Some(limit) = &rules.sender_rate_limit {
+            if !self.inner.rate_limiter.lock().try_acquire(sender, limit) {
+                metrics.admission_rate_limited.increment(1);
+                return Err(PoolErrorKind::SenderRateLimited(sender))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Refunds the admission of a transaction of the sender that didn't enter the pool, so it
+    /// doesn't count towards the rate limit of the sender.
+    pub fn refund(&self, sender: Address) {
+        if self.inner.rules.read().sender_rate_limit.is_some() {
+            self.inner.rate_limiter.lock().release(sender);
+        }
+    }
+}
+
+/// Reads the rules from a TOML file.
+#[cfg(feature = "serde")]
+fn read_rules(path: &Path) -> Result<AdmissionRules, AdmissionPolicyError> {
+    let contents = reth_fs_util::read_to_string(path)?;
+    toml::from_str(&contents)
+        .map_err(|err| AdmissionPolicyError::Parse { path: path.to_path_buf(), err })
+}
+
+/// Counts the transactions of each sender in the current period of the [`SenderRateLimit`].
+#[derive(Debug, Default)]
+struct RateLimiter {
+    /// Start of the current period and the number of transactions in it, by sender.
+    periods: HashMap<Address, (Instant, u32)>,
+    /// When expired periods were last removed.
+    last_pruned: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Counts a transaction of the sender, returns `false` if the sender exceeded its limit.
+    fn try_acquire(&mut self, sender: Address, limit: &SenderRateLimit) -> bool {
+        let now = Instant::now();
+        let period = limit.period();
+
+        // remove the expired periods of senders which stopped sending
+        if self.last_pruned.map_or(true, |pruned| now.duration_since(pruned) >= period) {
+            self.periods.retain(|_, (start, _)| now.duration_since(*start) < period);
+            self.last_pruned = Some(now);
+        }
+
+        let (start, count) = self.periods.entry(sender).or_insert((now, 0));
+        if now.duration_since(*start) >= period {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= limit.max_transactions {
+            return false
+        }
+        *count += 1;
+        true
+    }
+
+    /// Uncounts a transaction of the sender in the current period.
+    fn release(&mut self, sender: Address) {
+        if let Some((_, count)) = self.periods.get_mut(&sender) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.periods.clear();
+        self.last_pruned = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::Bytes;
+
+    #[test]
+    fn deny_by_sender_and_recipient() {
+        let tx = MockTransaction::eip1559();
+        let recipient = tx.to().unwrap();
+        let policy = AdmissionPolicy::default();
+        assert!(policy.check(&tx).is_ok());
+
+        policy.set_rules(AdmissionRules {
+            denied_senders: HashSet::from([tx.sender()]),
+            ..Default::default()
+        });
+        assert!(matches!(policy.check(&tx), Err(PoolErrorKind::SenderDenied(_))));
+
+        policy.set_rules(AdmissionRules {
+            allowed_senders: Some(HashSet::from([Address::random()])),
+            ..Default::default()
+        });
+        assert!(matches!(policy.check(&tx), Err(PoolErrorKind::SenderDenied(_))));
+
+        policy.set_rules(AdmissionRules {
+            allowed_senders: Some(HashSet::from([tx.sender()])),
+            denied_recipients: HashSet::from([recipient]),
+            ..Default::default()
+        });
+        assert!(
+            matches!(policy.check(&tx), Err(PoolErrorKind::RecipientDenied(to)) if to == recipient)
+        );
+
+        policy.set_rules(AdmissionRules {
+            allowed_recipients: Some(HashSet::from([recipient])),
+            ..Default::default()
+        });
+        assert!(policy.check(&tx).is_ok());
+    }
+
+    #[test]
+    fn deny_by_selector() {
+        let selector = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+        let policy = AdmissionPolicy::new(AdmissionRules {
+            denied_selectors: HashSet::from([selector]),
+            ..Default::default()
+        });
+
+        let tx =
+            MockTransaction::eip1559().with_input(Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb, 1]));
+        assert!(
+            matches!(policy.check(&tx), Err(PoolErrorKind::SelectorDenied(s)) if s == selector)
+        );
+
+        let tx = MockTransaction::eip1559().with_input(Bytes::from(vec![0xa9, 0x05, 0x9c]));
+        assert!(policy.check(&tx).is_ok());
+    }
+
+    #[test]
+    fn sender_rate_limit() {
+        let policy = AdmissionPolicy::new(AdmissionRules {
+            sender_rate_limit: Some(SenderRateLimit { max_transactions: 2, period_secs: 60 }),
+            ..Default::default()
+        });
+
+        let tx = MockTransaction::eip1559();
+        assert!(policy.check(&tx).is_ok());
+        assert!(policy.check(&tx.next()).is_ok());
+        assert!(matches!(policy.check(&tx.next()), Err(PoolErrorKind::SenderRateLimited(_))));
+
+        // other senders have their own limit
+        assert!(policy.check(&MockTransaction::eip1559()).is_ok());
+
+        // refunded transactions don't count
+        policy.refund(tx.sender());
+        assert!(policy.check(&tx.next()).is_ok());
+        assert!(matches!(policy.check(&tx.next()), Err(PoolErrorKind::SenderRateLimited(_))));
+
+        // new rules reset the limits
+        policy.set_rules(policy.rules());
+        assert!(policy.check(&tx).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn load_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(
+            &path,
+            r#"
+denied_senders = ["0x0000000000000000000000000000000000000001"]
+denied_selectors = ["0xa9059cbb"]
+
+[sender_rate_limit]
+max_transactions = 10
+period_secs = 60
+"#,
+        )
+        .unwrap();
+
+        let policy = AdmissionPolicy::load(&path).unwrap();
+        let rules = policy.rules();
+        assert_eq!(rules.denied_senders, HashSet::from([Address::with_last_byte(1)]));
+        assert_eq!(
+            rules.denied_selectors,
+            HashSet::from([Selector::from([0xa9, 0x05, 0x9c, 0xbb])])
+        );
+        assert_eq!(
+            rules.sender_rate_limit,
+            Some(SenderRateLimit { max_transactions: 10, period_secs: 60 })
+        );
+
+        // invalid files keep the current rules
+        std::fs::write(&path, "denied_sender = []").unwrap();
+        assert!(matches!(policy.reload(), Err(AdmissionPolicyError::Parse { .. })));
+        assert_eq!(policy.rules(), rules);
+
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(policy.reload().unwrap(), AdmissionRules::default());
+
+        assert!(matches!(AdmissionPolicy::default().reload(), Err(AdmissionPolicyError::NoFile)));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    admission::AdmissionPolicy,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
 };
@@ -54,6 +55,8 @@ pub struct PoolConfig {
     pub pending_tx_listener_buffer_size: usize,
     /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
     pub new_tx_listener_buffer_size: usize,
+    /// Policy deciding which transactions may enter the pool, checked before validation.
+    pub admission_policy: AdmissionPolicy,
 }
 
 impl PoolConfig {
@@ -81,6 +84,7 @@ impl Default for PoolConfig {
             local_transactions_config: Default::default(),
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            admission_policy: Default::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
 //! Transaction pool errors
 
-use alloy_primitives::{Address, TxHash, U256};
+use alloy_primitives::{Address, Selector, TxHash, U256};
 use reth_primitives::{BlobTransactionValidationError, InvalidTransactionError};
 
 /// Transaction pool result type.
@@ -60,6 +60,18 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if the admission policy of the pool doesn't admit transactions of the sender.
+    #[error("sender {0} is denied by the pool admission policy")]
+    SenderDenied(Address),
+    /// Thrown if the admission policy of the pool doesn't admit calls to the recipient.
+    #[error("recipient {0} is denied by the pool admission policy")]
+    RecipientDenied(Address),
+    /// Thrown if the admission policy of the pool blocks the function selector of the call.
+    #[error("function selector {0} is denied by the pool admission policy")]
+    SelectorDenied(Selector),
+    /// Thrown if the sender exceeded its rate limit of the pool admission policy.
+    #[error("sender {0} exceeded its rate limit")]
+    SenderRateLimited(Address),
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -137,6 +149,13 @@ impl PoolError {
                 // exclusivity (blob vs normal tx) for all senders
                 false
             }
+            PoolErrorKind::SenderDenied(_) |
+            PoolErrorKind::RecipientDenied(_) |
+            PoolErrorKind::SelectorDenied(_) |
+            PoolErrorKind::SenderRateLimited(_) => {
+                // rejected by the local policy of this node, not by the protocol
+                false
+            }
         }
     }
 }
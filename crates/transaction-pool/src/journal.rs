@@ -0,0 +1,742 @@
+//! A crash-safe journal of all transactions in the pool.
+//!
+//! The journal is an append-only file, starting with [`JOURNAL_MAGIC`] and a version byte,
+//! followed by records framed as:
+//!
+//! ```text
+//! | length: u32 LE | checksum: 4 bytes | kind: u8 | payload: RLP |
+//! ```
+//!
+//! The checksum is the first four bytes of the keccak256 hash of the kind and payload. An
+//! [`Add`](JournalRecordKind::Add) record carries a [`JournalEntry`], a
+//! [`Remove`](JournalRecordKind::Remove) record the hash of a transaction which left the pool.
+//!
+//! Records are appended and synced to disk as transactions enter and leave the pool, so an unclean
+//! shutdown loses at most the record being written. A torn or corrupted record ends the journal
+//! when it's loaded, all records before it are kept. The journal is compacted by rewriting it from
+//! the transactions currently in the pool into a temporary file, which atomically replaces the
+//! journal.
+
+use crate::{
+    pool::AllTransactionsEvents, AllPoolTransactions, FullTransactionEvent, PoolTransaction,
+    TransactionOrigin, TransactionPool,
+};
+use alloy_primitives::{keccak256, BlockNumber, TxHash};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use futures_util::{future::Either, FutureExt, StreamExt};
+use reth_primitives::{PooledTransactionsElement, PooledTransactionsElementEcRecovered};
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, error, info, trace, warn};
+
+/// Magic bytes at the start of a journal file.
+pub const JOURNAL_MAGIC: [u8; 4] = *b"RTXJ";
+
+/// Version of the journal format.
+pub const JOURNAL_VERSION: u8 = 1;
+
+/// Default maximum age of journaled transactions reinserted on startup: 3 hours.
+pub const DEFAULT_JOURNAL_MAX_AGE: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// Default minimum number of records appended to the journal before it's compacted.
+pub const DEFAULT_JOURNAL_COMPACTION_THRESHOLD: usize = 10_000;
+
+/// Size of the framing of a record: length and checksum.
+const RECORD_HEADER_SIZE: usize = 8;
+
+/// Settings of the transaction journal, see [`journal_transactions_task`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionJournalConfig {
+    /// Path to the journal file.
+    pub path: PathBuf,
+    /// Journaled transactions which entered the pool longer ago than this are discarded on
+    /// startup.
+    pub max_age: Duration,
+    /// Minimum number of records appended to the journal before it's compacted. The journal is
+    /// only compacted once it holds at least twice as many records as there are transactions in
+    /// the pool.
+    pub compaction_threshold: usize,
+}
+
+impl TransactionJournalConfig {
+    /// Creates a config for the journal at the given path, with default settings.
+    pub const fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_age: DEFAULT_JOURNAL_MAX_AGE,
+            compaction_threshold: DEFAULT_JOURNAL_COMPACTION_THRESHOLD,
+        }
+    }
+
+    /// Sets the maximum age of transactions reinserted on startup.
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+/// Errors of the transaction journal.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionJournalError {
+    /// The file doesn't start with the journal header.
+    #[error("not a transaction journal")]
+    InvalidHeader,
+    /// The journal was written by an unsupported version.
+    #[error("unsupported transaction journal version {0}")]
+    UnsupportedVersion(u8),
+    /// Error reading or writing the journal.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Kind of a journal record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum JournalRecordKind {
+    /// A transaction entered the pool.
+    Add = 0,
+    /// A transaction left the pool.
+    Remove = 1,
+}
+
+/// A journaled transaction.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[rlp(trailing)]
+pub struct JournalEntry {
+    /// Origin of the transaction, see [`encode_origin`].
+    pub origin: u8,
+    /// Milliseconds since the unix epoch when the transaction entered the pool.
+    pub timestamp: u64,
+    /// The transaction, including the blob sidecar of blob transactions.
+    pub transaction: PooledTransactionsElement,
+    /// The last block a private transaction may be included in, see
+    /// [`TransactionPool::add_private_transaction`].
+    pub max_block: Option<BlockNumber>,
+}
+
+impl JournalEntry {
+    /// Returns the origin of the transaction, if it's known.
+    pub const fn origin(&self) -> Option<TransactionOrigin> {
+        decode_origin(self.origin)
+    }
+
+    /// Returns the hash of the transaction.
+    pub const fn hash(&self) -> &TxHash {
+        self.transaction.hash()
+    }
+}
+
+/// Returns the journal representation of the origin.
+pub const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Returns the origin of its journal representation.
+pub const fn decode_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// An open transaction journal, appending records to the journal file.
+#[derive(Debug)]
+pub struct TransactionJournal {
+    path: PathBuf,
+    file: File,
+    /// Journaled transactions and when they entered the pool, in milliseconds since the unix
+    /// epoch.
+    entries: HashMap<TxHash, (TransactionOrigin, u64)>,
+    /// Number of records in the journal file.
+    records: usize,
+}
+
+impl TransactionJournal {
+    /// Loads the live entries of the journal at the given path, in the order they were added.
+    ///
+    /// Returns an empty list if there's no journal. Loading stops at the first torn or corrupted
+    /// record, e.g. one that was partially written when the node crashed.
+    pub fn load(path: &Path) -> Result<Vec<JournalEntry>, TransactionJournalError> {
+        let mut data = Vec::new();
+        match File::open(path) {
+            Ok(mut file) => file.read_to_end(&mut data)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        if data.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let Some(mut records) = data.strip_prefix(&JOURNAL_MAGIC) else {
+            return Err(TransactionJournalError::InvalidHeader)
+        };
+        match records.split_first() {
+            Some((&JOURNAL_VERSION, rest)) => records = rest,
+            Some((&version, _)) => return Err(TransactionJournalError::UnsupportedVersion(version)),
+            None => return Err(TransactionJournalError::InvalidHeader),
+        }
+
+        let mut entries = Vec::new();
+        let mut index_by_hash = HashMap::new();
+        let mut offset = JOURNAL_MAGIC.len() + 1;
+        while !records.is_empty() {
+            let Some((kind, payload, len)) = decode_record(records) else {
+                warn!(target: "txpool::journal", ?path, offset, "Transaction journal ends with a torn or corrupted record, discarding it");
+                break
+            };
+            records = &records[len..];
+            offset += len;
+
+            match kind {
+                JournalRecordKind::Add => {
+                    let Ok(entry) = JournalEntry::decode(&mut &payload[..]) else {
+                        warn!(target: "txpool::journal", ?path, offset, "Skipping undecodable transaction journal entry");
+                        continue
+                    };
+                    if let Some(index) = index_by_hash.insert(*entry.hash(), entries.len()) {
+                        entries[index] = None;
+                    }
+                    entries.push(Some(entry));
+                }
+                JournalRecordKind::Remove => {
+                    if let Some(index) = TxHash::decode(&mut &payload[..])
+                        .ok()
+                        .and_then(|hash| index_by_hash.remove(&hash))
+                    {
+                        entries[index] = None;
+                    }
+                }
+            }
+        }
+
+        Ok(entries.into_iter().flatten().collect())
+    }
+
+    /// Creates a new journal at the given path containing the given entries, atomically replacing
+    /// any existing journal.
+    pub fn create(
+        path: impl Into<PathBuf>,
+        entries: impl IntoIterator<Item = JournalEntry>,
+    ) -> Result<Self, TransactionJournalError> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        writer.write_all(&JOURNAL_MAGIC)?;
+        writer.write_all(&[JOURNAL_VERSION])?;
+
+        let mut journal_entries = HashMap::new();
+        for entry in entries {
+            let Some(origin) = entry.origin() else { continue };
+            writer.write_all(&encode_record(JournalRecordKind::Add, &entry))?;
+            journal_entries.insert(*entry.hash(), (origin, entry.timestamp));
+        }
+
+        let file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp_path, &path)?;
+        sync_parent_dir(&path);
+
+        let file = OpenOptions::new().append(true).open(&path)?;
+        let records = journal_entries.len();
+        Ok(Self { path, file, entries: journal_entries, records })
+    }
+
+    /// Returns the path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of journaled transactions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no transactions are journaled.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the transaction is journaled.
+    pub fn contains(&self, hash: &TxHash) -> bool {
+        self.entries.contains_key(hash)
+    }
+
+    /// Returns the number of records in the journal file.
+    pub const fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Appends an [`Add`](JournalRecordKind::Add) record for the entry.
+    ///
+    /// The record isn't guaranteed to be on disk before [`Self::sync`].
+    pub fn add(&mut self, entry: &JournalEntry) -> io::Result<()> {
+        let Some(origin) = entry.origin() else { return Ok(()) };
+        self.file.write_all(&encode_record(JournalRecordKind::Add, entry))?;
+        self.entries.insert(*entry.hash(), (origin, entry.timestamp));
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Appends a [`Remove`](JournalRecordKind::Remove) record for the transaction, if it's
+    /// journaled.
+    ///
+    /// The record isn't guaranteed to be on disk before [`Self::sync`].
+    pub fn remove(&mut self, hash: &TxHash) -> io::Result<()> {
+        if self.entries.remove(hash).is_none() {
+            return Ok(())
+        }
+        self.file.write_all(&encode_record(JournalRecordKind::Remove, hash))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Syncs all appended records to disk.
+    pub fn sync(&self) -> io::Result<()> {
+        self.file.sync_data()
+    }
+
+    /// Returns when the journaled transaction entered the pool, in milliseconds since the unix
+    /// epoch.
+    pub fn timestamp(&self, hash: &TxHash) -> Option<u64> {
+        self.entries.get(hash).map(|(_, timestamp)| *timestamp)
+    }
+}
+
+/// Encodes a record, including its framing.
+fn encode_record(kind: JournalRecordKind, payload: &impl Encodable) -> Vec<u8> {
+    encode_frame(kind as u8, payload)
+}
+
+/// Decodes the record at the start of `data`.
+///
+/// Returns the kind and payload of the record and the length of the whole record, or `None` if
+/// the record is torn or corrupted.
+fn decode_record(data: &[u8]) -> Option<(JournalRecordKind, &[u8], usize)> {
+    let (kind, payload, len) = decode_frame(data)?;
+    let kind = match kind {
+        0 => JournalRecordKind::Add,
+        1 => JournalRecordKind::Remove,
+        _ => return None,
+    };
+    Some((kind, payload, len))
+}
+
+/// Frames the record of the given kind and payload.
+///
+/// Also used for the records of [workload](crate::workload) files.
+pub(crate) fn encode_frame(kind: u8, payload: &impl Encodable) -> Vec<u8> {
+    let mut body = Vec::with_capacity(1 + payload.length());
+    body.push(kind);
+    payload.encode(&mut body);
+
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + body.len());
+    record.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    record.extend_from_slice(&keccak256(&body)[..4]);
+    record.extend_from_slice(&body);
+    record
+}
+
+/// Decodes the framing of the record at the start of `data`.
+///
+/// Returns the kind byte and payload of the record and the length of the whole record, or `None`
+/// if the record is torn or corrupted.
+pub(crate) fn decode_frame(data: &[u8]) -> Option<(u8, &[u8], usize)> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let checksum = data.get(4..RECORD_HEADER_SIZE)?;
+    let body = data.get(RECORD_HEADER_SIZE..RECORD_HEADER_SIZE + len)?;
+    if keccak256(body)[..4] != *checksum {
+        return None
+    }
+
+    let (kind, payload) = body.split_first()?;
+    Some((*kind, payload, RECORD_HEADER_SIZE + len))
+}
+
+/// Syncs the directory of the file, so a rename of the file is persisted.
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Err(err) = File::open(parent).and_then(|dir| dir.sync_all()) {
+            debug!(target: "txpool::journal", %err, ?parent, "Failed to sync transaction journal directory");
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+/// Returns the current time in milliseconds since the unix epoch.
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// Returns the journal entry of a transaction in the pool.
+fn pool_entry<P: TransactionPool>(
+    pool: &P,
+    hash: TxHash,
+    timestamp: Option<u64>,
+) -> Option<JournalEntry> {
+    let tx = pool.get(&hash)?;
+    let transaction = pool.get_pooled_transaction_element(hash)?;
+    let timestamp = timestamp
+        .unwrap_or_else(|| now_millis().saturating_sub(tx.timestamp.elapsed().as_millis() as u64));
+    Some(JournalEntry {
+        origin: encode_origin(tx.origin),
+        timestamp,
+        transaction,
+        max_block: pool.inclusion_deadline(&hash),
+    })
+}
+
+/// Reinserts the journaled transactions into the pool, oldest first.
+///
+/// Transactions older than `max_age` and private transactions past their inclusion deadline are
+/// discarded, all others are validated against the current state like new transactions, which
+/// discards transactions that were mined or became invalid while the node was down.
+async fn reinsert_transactions<P>(pool: &P, entries: Vec<JournalEntry>, max_age: Duration)
+where
+    P: TransactionPool,
+    P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+{
+    let min_timestamp = now_millis().saturating_sub(max_age.as_millis() as u64);
+    let tip = pool.block_info().last_seen_block_number;
+    let total = entries.len();
+    let mut entries = entries
+        .into_iter()
+        .filter(|entry| {
+            entry.timestamp >= min_timestamp &&
+                entry.origin().is_some() &&
+                entry.max_block.map_or(true, |max_block| max_block > tip)
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.timestamp);
+    let expired = total - entries.len();
+
+    let mut reinserted = 0;
+    // transactions are added in batches of the same origin, preserving their order
+    for batch in entries.chunk_by(|a, b| a.origin == b.origin) {
+        let origin = batch[0].origin().expect("filtered");
+        let transactions = batch.iter().filter_map(|entry| {
+            let transaction = entry.transaction.clone().try_into_ecrecovered().ok()?;
+            Some((<P::Transaction as PoolTransaction>::from_pooled(transaction), entry.max_block))
+        });
+
+        // private transactions are added one by one, restoring their inclusion deadline
+        if origin == TransactionOrigin::Private {
+            for (transaction, max_block) in transactions {
+                if pool.add_private_transaction(transaction, max_block).await.is_ok() {
+                    reinserted += 1;
+                }
+            }
+            continue
+        }
+
+        reinserted += pool
+            .add_transactions(origin, transactions.map(|(transaction, _)| transaction).collect())
+            .await
+            .into_iter()
+            .filter(Result::is_ok)
+            .count();
+    }
+
+    info!(
+        target: "txpool::journal",
+        reinserted,
+        expired,
+        invalid = total - expired - reinserted,
+        "Reinserted transactions from the journal"
+    );
+}
+
+/// Task which journals all transactions in the pool, see the [module docs](self).
+///
+/// On startup, the transactions of an existing journal are reinserted into the pool, and the
+/// journal is compacted. The journal is compacted again on shutdown.
+///
+/// The journal follows the pool through its [`AllTransactionsEvents`], which drops events if the
+/// task falls behind. Compaction rewrites the journal from the pool, so such gaps are closed
+/// eventually.
+pub async fn journal_transactions_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionJournalConfig,
+) where
+    P: TransactionPool,
+    P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+{
+    // subscribe before reinserting, so the reinserted transactions are journaled
+    let mut events = pool.all_transactions_event_listener();
+
+    let Some(mut journal) = restore_journal(&pool, &config).await else { return };
+
+    let mut shutdown = std::pin::pin!(shutdown);
+    loop {
+        let event = match futures_util::future::select(events.next(), shutdown.as_mut()).await {
+            Either::Left((Some(event), _)) => event,
+            Either::Left((None, _)) => break,
+            Either::Right((guard, _)) => {
+                compact(&pool, |hash| journal.timestamp(hash), &config.path);
+                drop(guard);
+                return
+            }
+        };
+
+        // apply all ready events, then sync them to disk at once
+        if let Err(err) = on_event(&pool, &mut journal, event)
+            .and_then(|_| apply_ready_events(&pool, &mut journal, &mut events))
+            .and_then(|_| journal.sync())
+        {
+            error!(target: "txpool::journal", %err, path = ?config.path, "Failed to write transaction journal");
+        }
+
+        if journal.records() >= config.compaction_threshold.max(journal.len() * 2) {
+            match compact(&pool, |hash| journal.timestamp(hash), &config.path) {
+                Some(compacted) => journal = compacted,
+                None => return,
+            }
+        }
+    }
+}
+
+/// Reinserts the transactions of the journal into the pool, then compacts the journal.
+///
+/// The reinserted transactions keep the timestamps they were journaled with.
+async fn restore_journal<P>(
+    pool: &P,
+    config: &TransactionJournalConfig,
+) -> Option<TransactionJournal>
+where
+    P: TransactionPool,
+    P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+{
+    let mut timestamps = HashMap::new();
+    match TransactionJournal::load(&config.path) {
+        Ok(entries) => {
+            debug!(target: "txpool::journal", path = ?config.path, entries = entries.len(), "Loaded transaction journal");
+            timestamps.extend(entries.iter().map(|entry| (*entry.hash(), entry.timestamp)));
+            reinsert_transactions(pool, entries, config.max_age).await;
+        }
+        Err(err) => {
+            error!(target: "txpool::journal", %err, path = ?config.path, "Failed to load transaction journal, starting a new one");
+        }
+    }
+
+    compact(pool, |hash| timestamps.get(hash).copied(), &config.path)
+}
+
+/// Applies all events which are ready without waiting.
+fn apply_ready_events<P: TransactionPool>(
+    pool: &P,
+    journal: &mut TransactionJournal,
+    events: &mut AllTransactionsEvents<P::Transaction>,
+) -> io::Result<()> {
+    while let Some(Some(event)) = events.next().now_or_never() {
+        on_event(pool, journal, event)?;
+    }
+    Ok(())
+}
+
+/// Appends the record for the pool event to the journal.
+fn on_event<P: TransactionPool>(
+    pool: &P,
+    journal: &mut TransactionJournal,
+    event: FullTransactionEvent<P::Transaction>,
+) -> io::Result<()> {
+    match event {
+        FullTransactionEvent::Pending(hash) | FullTransactionEvent::Queued(hash) => {
+            // also emitted when a transaction moves between sub-pools
+            if !journal.contains(&hash) {
+                if let Some(entry) = pool_entry(pool, hash, None) {
+                    trace!(target: "txpool::journal", %hash, "Journaling transaction");
+                    journal.add(&entry)?;
+                }
+            }
+        }
+        FullTransactionEvent::Mined { tx_hash: hash, .. } |
+        FullTransactionEvent::Discarded(hash) |
+        FullTransactionEvent::Expired(hash) |
+        FullTransactionEvent::Invalid(hash) => journal.remove(&hash)?,
+        FullTransactionEvent::Replaced { transaction, .. } => journal.remove(transaction.hash())?,
+        FullTransactionEvent::Propagated(_) => {}
+    }
+    Ok(())
+}
+
+/// Rewrites the journal from the transactions currently in the pool, including blob transactions
+/// parked in the blob sub-pool.
+///
+/// Transactions keep the timestamp returned by `timestamp`, if any, e.g. the one they were
+/// journaled with.
+fn compact<P: TransactionPool>(
+    pool: &P,
+    timestamp: impl Fn(&TxHash) -> Option<u64>,
+    path: &Path,
+) -> Option<TransactionJournal> {
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let mut transactions = pending.into_iter().chain(queued).collect::<Vec<_>>();
+    // oldest first, in the order they entered the pool
+    transactions.sort_by_key(|tx| tx.timestamp);
+    let entries = transactions.into_iter().filter_map(|tx| {
+        let hash = *tx.hash();
+        pool_entry(pool, hash, timestamp(&hash))
+    });
+
+    match TransactionJournal::create(path, entries) {
+        Ok(journal) => {
+            debug!(target: "txpool::journal", ?path, entries = journal.len(), "Compacted transaction journal");
+            Some(journal)
+        }
+        Err(err) => {
+            error!(target: "txpool::journal", %err, ?path, "Failed to write transaction journal, stopping the journal");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blobstore::{BlobStore, InMemoryBlobStore},
+        noop::MockTransactionValidator,
+        test_utils::TransactionGenerator,
+        BlockInfo, CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPoolExt,
+    };
+    use alloy_primitives::hex;
+    use reth_primitives::BlobTransactionSidecar;
+
+    type EthTestPool = Pool<
+        MockTransactionValidator<EthPooledTransaction>,
+        CoinbaseTipOrdering<EthPooledTransaction>,
+        InMemoryBlobStore,
+    >;
+
+    fn eth_test_pool() -> EthTestPool {
+        Pool::new(
+            MockTransactionValidator::default(),
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            Default::default(),
+        )
+    }
+
+    fn entry(origin: TransactionOrigin, timestamp: u64) -> JournalEntry {
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let transaction = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        JournalEntry { origin: encode_origin(origin), timestamp, transaction, max_block: None }
+    }
+
+    #[test]
+    fn journal_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal");
+        assert!(TransactionJournal::load(&path).unwrap().is_empty());
+
+        let local = entry(TransactionOrigin::Local, 1);
+        let mut journal = TransactionJournal::create(&path, [local.clone()]).unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(TransactionJournal::load(&path).unwrap(), vec![local.clone()]);
+
+        // removal and re-adding with a different origin
+        journal.remove(local.hash()).unwrap();
+        assert!(TransactionJournal::load(&path).unwrap().is_empty());
+        let external = entry(TransactionOrigin::External, 2);
+        journal.add(&external).unwrap();
+        journal.sync().unwrap();
+        assert_eq!(journal.records(), 3);
+        assert_eq!(TransactionJournal::load(&path).unwrap(), vec![external.clone()]);
+
+        // the inclusion deadline of private transactions is kept
+        let private = JournalEntry { max_block: Some(10), ..entry(TransactionOrigin::Private, 3) };
+        journal.add(&private).unwrap();
+        assert_eq!(TransactionJournal::load(&path).unwrap(), vec![private.clone()]);
+        journal.remove(private.hash()).unwrap();
+        journal.add(&external).unwrap();
+        journal.sync().unwrap();
+        assert_eq!(journal.records(), 6);
+
+        // a torn record at the end is discarded
+        let valid_len = std::fs::metadata(&path).unwrap().len();
+        journal.add(&entry(TransactionOrigin::Local, 3)).unwrap();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(std::fs::metadata(&path).unwrap().len() - 5).unwrap();
+        assert_eq!(TransactionJournal::load(&path).unwrap(), vec![external.clone()]);
+
+        // so is a corrupted one
+        file.set_len(valid_len).unwrap();
+        let mut record = encode_record(JournalRecordKind::Remove, external.hash());
+        *record.last_mut().unwrap() ^= 1;
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&record).unwrap();
+        assert_eq!(TransactionJournal::load(&path).unwrap(), vec![external]);
+    }
+
+    #[test]
+    fn invalid_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal");
+
+        std::fs::write(&path, b"RLP").unwrap();
+        assert!(matches!(
+            TransactionJournal::load(&path),
+            Err(TransactionJournalError::InvalidHeader)
+        ));
+
+        std::fs::write(&path, [&JOURNAL_MAGIC[..], &[JOURNAL_VERSION + 1]].concat()).unwrap();
+        assert!(matches!(
+            TransactionJournal::load(&path),
+            Err(TransactionJournalError::UnsupportedVersion(version)) if version == JOURNAL_VERSION + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn restore_keeps_journaled_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TransactionJournalConfig::new(dir.path().join("journal"));
+        let journaled = entry(TransactionOrigin::Local, now_millis() - 60_000);
+        TransactionJournal::create(&config.path, [journaled.clone()]).unwrap();
+
+        let pool = eth_test_pool();
+        let journal = restore_journal(&pool, &config).await.unwrap();
+        assert!(pool.contains(journaled.hash()));
+        assert_eq!(journal.timestamp(journaled.hash()), Some(journaled.timestamp));
+        assert_eq!(TransactionJournal::load(&config.path).unwrap(), vec![journaled]);
+    }
+
+    #[tokio::test]
+    async fn compact_blob_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal");
+
+        // the transaction doesn't pay the blob fee, so it's parked in the blob sub-pool
+        let pool = eth_test_pool();
+        pool.set_block_info(BlockInfo { pending_blob_fee: Some(1), ..pool.block_info() });
+        let tx = TransactionGenerator::new(rand::thread_rng()).gen_eip4844_pooled();
+        let hash = *tx.hash();
+        pool.add_transaction(TransactionOrigin::Local, tx).await.unwrap();
+        pool.blob_store().insert(hash, BlobTransactionSidecar::default()).unwrap();
+        assert_eq!(pool.pool_size().blob, 1);
+
+        let journal = compact(&pool, |_| Some(1), &path).unwrap();
+        assert!(journal.contains(&hash));
+        let entries = TransactionJournal::load(&path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].hash(), &hash);
+        assert_eq!(entries[0].timestamp, 1);
+        assert!(matches!(entries[0].transaction, PooledTransactionsElement::BlobTransaction(_)));
+    }
+}
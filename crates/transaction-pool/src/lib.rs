@@ -139,7 +139,7 @@
 //!
 //! ## Feature Flags
 //!
-//! - `serde` (default): Enable serde support
+//! - `serde` (default): Enable serde support and loading [admission policies](admission) from files
 //! - `test-utils`: Export utilities for testing
 
 #![doc(
@@ -152,7 +152,7 @@
 
 use crate::{identifier::TransactionId, pool::PoolInner};
 use alloy_eips::eip4844::BlobAndProofV1;
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -163,6 +163,7 @@ use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
+    admission::{AdmissionPolicy, AdmissionRules},
     blobstore::{BlobStore, BlobStoreError},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
@@ -171,7 +172,11 @@ pub use crate::{
         TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        CoinbaseTipOrdering, ConfiguredOrdering, FifoOrdering, Priority, PriorityInputs,
+        PriorityScript, PriorityScriptError, ScriptOrdering, SenderFairOrdering,
+        TransactionOrdering, TransactionOrderingKind,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         TransactionEvent, TransactionEvents,
@@ -183,6 +188,7 @@ pub use crate::{
     },
 };
 
+pub mod admission;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
@@ -193,8 +199,10 @@ pub mod validate;
 pub mod blobstore;
 mod config;
 pub mod identifier;
+pub mod journal;
 mod ordering;
 mod traits;
+pub mod workload;
 
 #[cfg(any(test, feature = "test-utils"))]
 /// Common test helpers for mocking a pool
@@ -203,7 +211,7 @@ pub mod test_utils;
 /// Type alias for default ethereum transaction pool
 pub type EthTransactionPool<Client, S> = Pool<
     TransactionValidationTaskExecutor<EthTransactionValidator<Client, EthPooledTransaction>>,
-    CoinbaseTipOrdering<EthPooledTransaction>,
+    ConfiguredOrdering<EthPooledTransaction>,
     S,
 >;
 
@@ -284,7 +292,8 @@ where
     S: BlobStore,
 {
     /// Returns a new [`Pool`] that uses the default [`TransactionValidationTaskExecutor`] when
-    /// validating [`EthPooledTransaction`]s and ords via [`CoinbaseTipOrdering`]
+    /// validating [`EthPooledTransaction`]s and ords via the default [`ConfiguredOrdering`], which
+    /// is [`CoinbaseTipOrdering`]
     ///
     /// # Example
     ///
@@ -316,7 +325,7 @@ where
         blob_store: S,
         config: PoolConfig,
     ) -> Self {
-        Self::new(validator, CoinbaseTipOrdering::default(), blob_store, config)
+        Self::new(validator, ConfiguredOrdering::default(), blob_store, config)
     }
 }
 
@@ -342,8 +351,12 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        self.pool.check_admission(&transaction)?;
+        let sender = transaction.sender();
         let (_, tx) = self.validate(origin, transaction).await;
-        self.pool.add_transaction_and_subscribe(origin, tx)
+        self.pool
+            .add_transaction_and_subscribe(origin, tx)
+            .inspect_err(|_| self.pool.refund_admission(sender))
     }
 
     async fn add_transaction(
@@ -351,9 +364,32 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
+        self.pool.check_admission(&transaction)?;
+        let sender = transaction.sender();
         let (_, tx) = self.validate(origin, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
-        results.pop().expect("result length is the same as the input")
+        results
+            .pop()
+            .expect("result length is the same as the input")
+            .inspect_err(|_| self.pool.refund_admission(sender))
+    }
+
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        // the deadline is set first, so it's known once the transaction is in the pool, e.g. to
+        // the journal
+        let hash = *transaction.hash();
+        if let Some(max_block) = max_block {
+            self.pool.set_inclusion_deadline(hash, max_block);
+        }
+        self.add_transaction(TransactionOrigin::Private, transaction).await.inspect_err(|_| {
+            if let Some(max_block) = max_block {
+                self.pool.remove_inclusion_deadline(&hash, max_block);
+            }
+        })
     }
 
     async fn add_transactions(
@@ -364,9 +400,39 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
 
-        self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+        // only admitted transactions are validated, denied ones are put back in place afterwards
+        let mut denied = Vec::new();
+        let admitted = transactions
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, tx)| match self.pool.check_admission(&tx) {
+                Ok(()) => Some(tx),
+                Err(err) => {
+                    denied.push((idx, err));
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let senders = admitted.iter().map(|tx| tx.sender()).collect::<Vec<_>>();
+
+        let mut results = if admitted.is_empty() {
+            Vec::new()
+        } else {
+            let validated = self.validate_all(origin, admitted).await;
+            self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+        };
+        // transactions which didn't make it into the pool don't count towards the rate limit
+        for (result, sender) in results.iter().zip(senders) {
+            if result.is_err() {
+                self.pool.refund_admission(sender);
+            }
+        }
+        for (idx, err) in denied {
+            results.insert(idx, Err(err));
+        }
+        results
     }
 
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
@@ -541,6 +607,14 @@ where
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
     }
+
+    fn admission_policy(&self) -> Option<AdmissionPolicy> {
+        Some(self.pool.config().admission_policy.clone())
+    }
+
+    fn inclusion_deadline(&self, tx_hash: &TxHash) -> Option<BlockNumber> {
+        self.pool.inclusion_deadline(tx_hash)
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
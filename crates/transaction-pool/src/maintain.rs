@@ -17,7 +17,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_execution_types::ChangedAccount;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
-    BlockNumberOrTag, PooledTransactionsElementEcRecovered, SealedHeader, TransactionSigned,
+    BlockNumberOrTag, Header, PooledTransactionsElementEcRecovered, SealedHeader, TransactionSigned,
 };
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -111,9 +111,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             block_gas_limit: latest.gas_limit,
             last_seen_block_hash: latest.hash(),
             last_seen_block_number: latest.number,
-            pending_basefee: latest
-                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(latest.timestamp + 12))
-                .unwrap_or_default(),
+            pending_basefee: pending_block_base_fee(&latest, &*chain_spec),
             pending_blob_fee: latest.next_block_blob_fee(),
         };
         pool.set_block_info(info);
@@ -266,11 +264,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 let chain_spec = client.chain_spec();
 
                 // fees for the next block: `new_tip+1`
-                let pending_block_base_fee = new_tip
-                    .next_block_base_fee(
-                        chain_spec.base_fee_params_at_timestamp(new_tip.timestamp + 12),
-                    )
-                    .unwrap_or_default();
+                let pending_block_base_fee = pending_block_base_fee(&new_tip.header, &*chain_spec);
                 let pending_block_blob_fee = new_tip.next_block_blob_fee();
 
                 // we know all changed account in the new chain
@@ -372,11 +366,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 let chain_spec = client.chain_spec();
 
                 // fees for the next block: `tip+1`
-                let pending_block_base_fee = tip
-                    .next_block_base_fee(
-                        chain_spec.base_fee_params_at_timestamp(tip.timestamp + 12),
-                    )
-                    .unwrap_or_default();
+                let pending_block_base_fee = pending_block_base_fee(&tip.header, &*chain_spec);
                 let pending_block_blob_fee = tip.next_block_blob_fee();
 
                 let first_block = blocks.first();
@@ -443,6 +433,23 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     }
 }
 
+/// Returns the base fee of the block after `tip`.
+///
+/// Telos charges the native gas price recorded in the header instead of the EIP-1559 base fee, so
+/// it's used as the pending base fee if higher. This parks transactions priced below it in the
+/// base fee sub-pool after a gas price increase, until the price decreases again.
+pub(crate) fn pending_block_base_fee(tip: &Header, chain_spec: &impl EthChainSpec) -> u64 {
+    let base_fee = tip
+        .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(tip.timestamp + 12))
+        .unwrap_or_default();
+
+    #[cfg(feature = "telos")]
+    let base_fee = base_fee
+        .max(u64::try_from(tip.telos_block_extension.get_last_gas_price()).unwrap_or(u64::MAX));
+
+    base_fee
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -96,6 +96,22 @@ impl MaintainPoolMetrics {
     }
 }
 
+/// Transaction pool admission policy metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct AdmissionMetrics {
+    /// Number of transactions rejected because of their sender
+    pub(crate) admission_denied_sender: Counter,
+    /// Number of transactions rejected because of their recipient
+    pub(crate) admission_denied_recipient: Counter,
+    /// Number of transactions rejected because of the function selector they call
+    pub(crate) admission_denied_selector: Counter,
+    /// Number of transactions rejected because their sender exceeded its rate limit
+    pub(crate) admission_rate_limited: Counter,
+    /// Number of reloads of the admission policy
+    pub(crate) admission_policy_reloads: Counter,
+}
+
 /// All Transactions metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -17,7 +17,7 @@ use crate::{
     TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use alloy_eips::eip4844::BlobAndProofV1;
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, BlobTransactionSidecar};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
@@ -66,6 +66,15 @@ impl TransactionPool for NoopTransactionPool {
         Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        _max_block: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     async fn add_transactions(
         &self,
         _origin: TransactionOrigin,
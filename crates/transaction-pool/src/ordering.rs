@@ -1,88 +0,0 @@
-use crate::traits::PoolTransaction;
-use alloy_primitives::U256;
-use reth_primitives::{PooledTransactionsElementEcRecovered, TransactionSignedEcRecovered};
-use std::{fmt, marker::PhantomData};
-
-/// Priority of the transaction that can be missing.
-///
-/// Transactions with missing priorities are ranked lower.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
-pub enum Priority<T: Ord + Clone> {
-    /// The value of the priority of the transaction.
-    Value(T),
-    /// Missing priority due to ordering internals.
-    None,
-}
-
-impl<T: Ord + Clone> From<Option<T>> for Priority<T> {
-    fn from(value: Option<T>) -> Self {
-        value.map_or(Self::None, Priority::Value)
-    }
-}
-
-/// Transaction ordering trait to determine the order of transactions.
-///
-/// Decides how transactions should be ordered within the pool, depending on a `Priority` value.
-///
-/// The returned priority must reflect [total order](https://en.wikipedia.org/wiki/Total_order).
-pub trait TransactionOrdering: Send + Sync + 'static {
-    /// Priority of a transaction.
-    ///
-    /// Higher is better.
-    type PriorityValue: Ord + Clone + Default + fmt::Debug + Send + Sync;
-
-    /// The transaction type to determine the priority of.
-    type Transaction: PoolTransaction<
-        Pooled = PooledTransactionsElementEcRecovered,
-        Consensus = TransactionSignedEcRecovered,
-    >;
-
-    /// Returns the priority score for the given transaction.
-    fn priority(
-        &self,
-        transaction: &Self::Transaction,
-        base_fee: u64,
-    ) -> Priority<Self::PriorityValue>;
-}
-
-/// Default ordering for the pool.
-///
-/// The transactions are ordered by their coinbase tip.
-/// The higher the coinbase tip is, the higher the priority of the transaction.
-#[derive(Debug)]
-#[non_exhaustive]
-pub struct CoinbaseTipOrdering<T>(PhantomData<T>);
-
-impl<T> TransactionOrdering for CoinbaseTipOrdering<T>
-where
-    T: PoolTransaction<
-            Pooled = PooledTransactionsElementEcRecovered,
-            Consensus = TransactionSignedEcRecovered,
-        > + 'static,
-{
-    type PriorityValue = U256;
-    type Transaction = T;
-
-    /// Source: <https://github.com/ethereum/go-ethereum/blob/7f756dc1185d7f1eeeacb1d12341606b7135f9ea/core/txpool/legacypool/list.go#L469-L482>.
-    ///
-    /// NOTE: The implementation is incomplete for missing base fee.
-    fn priority(
-        &self,
-        transaction: &Self::Transaction,
-        base_fee: u64,
-    ) -> Priority<Self::PriorityValue> {
-        transaction.effective_tip_per_gas(base_fee).map(U256::from).into()
-    }
-}
-
-impl<T> Default for CoinbaseTipOrdering<T> {
-    fn default() -> Self {
-        Self(Default::default())
-    }
-}
-
-impl<T> Clone for CoinbaseTipOrdering<T> {
-    fn clone(&self) -> Self {
-        Self::default()
-    }
-}
@@ -0,0 +1,480 @@
+use crate::{traits::PoolTransaction, ValidPoolTransaction};
+use alloy_primitives::U256;
+use reth_primitives::{PooledTransactionsElementEcRecovered, TransactionSignedEcRecovered};
+use std::{
+    cmp::Reverse,
+    fmt,
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+mod script;
+pub use script::{PriorityInputs, PriorityScript, PriorityScriptError};
+
+/// Priority of the transaction that can be missing.
+///
+/// Transactions with missing priorities are ranked lower.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub enum Priority<T: Ord + Clone> {
+    /// The value of the priority of the transaction.
+    Value(T),
+    /// Missing priority due to ordering internals.
+    None,
+}
+
+impl<T: Ord + Clone> From<Option<T>> for Priority<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::None, Priority::Value)
+    }
+}
+
+impl<T: Ord + Clone> Priority<T> {
+    /// Maps the value of the priority, keeping a missing priority missing.
+    pub fn map<U: Ord + Clone>(self, f: impl FnOnce(T) -> U) -> Priority<U> {
+        match self {
+            Self::Value(value) => Priority::Value(f(value)),
+            Self::None => Priority::None,
+        }
+    }
+}
+
+/// Transaction ordering trait to determine the order of transactions.
+///
+/// Decides how transactions should be ordered within the pool, depending on a `Priority` value.
+///
+/// The returned priority must reflect [total order](https://en.wikipedia.org/wiki/Total_order).
+pub trait TransactionOrdering: Send + Sync + 'static {
+    /// Priority of a transaction.
+    ///
+    /// Higher is better.
+    type PriorityValue: Ord + Clone + Default + fmt::Debug + Send + Sync;
+
+    /// The transaction type to determine the priority of.
+    type Transaction: PoolTransaction<
+        Pooled = PooledTransactionsElementEcRecovered,
+        Consensus = TransactionSignedEcRecovered,
+    >;
+
+    /// Returns the priority score for the given transaction.
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue>;
+
+    /// Returns the priority score for the given transaction in the pending pool.
+    ///
+    /// Unlike [`Self::priority`], this has access to the pool's metadata of the transaction, like
+    /// its arrival time, and its `lane_position`: the number of pending transactions of the same
+    /// sender with a lower nonce.
+    ///
+    /// Defaults to [`Self::priority`].
+    fn pending_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        lane_position: u64,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let _ = lane_position;
+        self.priority(&transaction.transaction, base_fee)
+    }
+}
+
+/// Default ordering for the pool.
+///
+/// The transactions are ordered by their coinbase tip.
+/// The higher the coinbase tip is, the higher the priority of the transaction.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CoinbaseTipOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for CoinbaseTipOrdering<T>
+where
+    T: PoolTransaction<
+            Pooled = PooledTransactionsElementEcRecovered,
+            Consensus = TransactionSignedEcRecovered,
+        > + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    /// Source: <https://github.com/ethereum/go-ethereum/blob/7f756dc1185d7f1eeeacb1d12341606b7135f9ea/core/txpool/legacypool/list.go#L469-L482>.
+    ///
+    /// NOTE: The implementation is incomplete for missing base fee.
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        transaction.effective_tip_per_gas(base_fee).map(U256::from).into()
+    }
+}
+
+impl<T> Default for CoinbaseTipOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for CoinbaseTipOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders transactions by the time they arrived in the pool, regardless of their fees.
+///
+/// The earlier a transaction arrived, the higher its priority. Since transactions of a sender are
+/// always executed in nonce order, this is first-in-first-out per nonce lane.
+#[derive(Debug)]
+pub struct FifoOrdering<T> {
+    /// Arrival times are measured relative to this instant.
+    epoch: Instant,
+    _tx: PhantomData<T>,
+}
+
+impl<T> FifoOrdering<T> {
+    /// Returns the time between the creation of the ordering and the given instant.
+    fn since_epoch(&self, instant: Instant) -> Duration {
+        instant.saturating_duration_since(self.epoch)
+    }
+}
+
+impl<T> TransactionOrdering for FifoOrdering<T>
+where
+    T: PoolTransaction<
+            Pooled = PooledTransactionsElementEcRecovered,
+            Consensus = TransactionSignedEcRecovered,
+        > + 'static,
+{
+    type PriorityValue = Reverse<u64>;
+    type Transaction = T;
+
+    /// Without pool metadata the arrival time is unknown, so the transaction is treated as if it
+    /// arrived now.
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(Reverse(self.since_epoch(Instant::now()).as_nanos() as u64))
+    }
+
+    fn pending_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        _lane_position: u64,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(Reverse(self.since_epoch(transaction.timestamp).as_nanos() as u64))
+    }
+}
+
+impl<T> Default for FifoOrdering<T> {
+    fn default() -> Self {
+        Self { epoch: Instant::now(), _tx: PhantomData }
+    }
+}
+
+impl<T> Clone for FifoOrdering<T> {
+    fn clone(&self) -> Self {
+        Self { epoch: self.epoch, _tx: PhantomData }
+    }
+}
+
+/// Orders transactions round-robin over their senders, regardless of their fees.
+///
+/// The priority of a transaction is its position in the nonce lane of its sender: first the next
+/// transaction of every sender, then the transaction after that of every sender, and so on. Within
+/// a round, transactions are ordered by the time they entered the pending pool.
+///
+/// The position is assigned when the transaction enters the pending pool, and refreshed whenever
+/// the pending pool recomputes priorities on a base fee change or the next transaction of the
+/// sender leaves the pending pool.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SenderFairOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for SenderFairOrdering<T>
+where
+    T: PoolTransaction<
+            Pooled = PooledTransactionsElementEcRecovered,
+            Consensus = TransactionSignedEcRecovered,
+        > + 'static,
+{
+    type PriorityValue = Reverse<u64>;
+    type Transaction = T;
+
+    /// Without pool metadata the lane position is unknown, so the transaction is treated as the
+    /// next transaction of its sender.
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(Reverse(0))
+    }
+
+    fn pending_priority(
+        &self,
+        _transaction: &ValidPoolTransaction<Self::Transaction>,
+        lane_position: u64,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(Reverse(lane_position))
+    }
+}
+
+impl<T> Default for SenderFairOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for SenderFairOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders transactions by a [`PriorityScript`].
+///
+/// Transactions which don't pay the base fee have no priority.
+#[derive(Debug)]
+pub struct ScriptOrdering<T> {
+    script: Arc<PriorityScript>,
+    /// Arrival times are measured relative to this instant.
+    epoch: Instant,
+    _tx: PhantomData<T>,
+}
+
+impl<T> ScriptOrdering<T> {
+    /// Creates a new ordering evaluating the given [`PriorityScript`].
+    pub fn new(script: Arc<PriorityScript>) -> Self {
+        Self { script, epoch: Instant::now(), _tx: PhantomData }
+    }
+
+    /// Returns the [`PriorityScript`] transactions are ordered by.
+    pub fn script(&self) -> &PriorityScript {
+        &self.script
+    }
+}
+
+impl<T: PoolTransaction> ScriptOrdering<T> {
+    fn evaluate(
+        &self,
+        transaction: &T,
+        lane: u64,
+        arrival: Instant,
+        local: bool,
+        base_fee: u64,
+    ) -> Priority<i128> {
+        let Some(tip) = transaction.effective_tip_per_gas(base_fee) else { return Priority::None };
+        Priority::Value(self.script.evaluate(&PriorityInputs {
+            tip,
+            max_fee: transaction.max_fee_per_gas(),
+            priority_fee: transaction.priority_fee_or_price(),
+            gas_limit: transaction.gas_limit(),
+            size: transaction.size(),
+            nonce: transaction.nonce(),
+            lane,
+            arrival: arrival.saturating_duration_since(self.epoch).as_millis() as u64,
+            local,
+        }))
+    }
+}
+
+impl<T> TransactionOrdering for ScriptOrdering<T>
+where
+    T: PoolTransaction<
+            Pooled = PooledTransactionsElementEcRecovered,
+            Consensus = TransactionSignedEcRecovered,
+        > + 'static,
+{
+    type PriorityValue = i128;
+    type Transaction = T;
+
+    /// Without pool metadata, the transaction is treated as the next external transaction of its
+    /// sender, arriving now.
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.evaluate(transaction, 0, Instant::now(), false, base_fee)
+    }
+
+    fn pending_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        lane_position: u64,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.evaluate(
+            &transaction.transaction,
+            lane_position,
+            transaction.timestamp,
+            transaction.origin.is_local(),
+            base_fee,
+        )
+    }
+}
+
+impl<T> Clone for ScriptOrdering<T> {
+    fn clone(&self) -> Self {
+        Self { script: Arc::clone(&self.script), epoch: self.epoch, _tx: PhantomData }
+    }
+}
+
+/// The built-in orderings, see [`ConfiguredOrdering`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TransactionOrderingKind {
+    /// [`CoinbaseTipOrdering`].
+    #[default]
+    CoinbaseTip,
+    /// [`FifoOrdering`].
+    Fifo,
+    /// [`SenderFairOrdering`].
+    SenderFair,
+    /// [`ScriptOrdering`] with the given priority function.
+    Script(Arc<PriorityScript>),
+}
+
+/// A built-in ordering selected at runtime, e.g. from the node's configuration.
+///
+/// Priorities of all orderings are mapped to `i128`, preserving their order.
+#[derive(Debug)]
+pub enum ConfiguredOrdering<T> {
+    /// [`CoinbaseTipOrdering`].
+    CoinbaseTip(CoinbaseTipOrdering<T>),
+    /// [`FifoOrdering`].
+    Fifo(FifoOrdering<T>),
+    /// [`SenderFairOrdering`].
+    SenderFair(SenderFairOrdering<T>),
+    /// [`ScriptOrdering`].
+    Script(ScriptOrdering<T>),
+}
+
+impl<T> ConfiguredOrdering<T> {
+    /// Creates the ordering of the given kind.
+    pub fn new(kind: TransactionOrderingKind) -> Self {
+        match kind {
+            TransactionOrderingKind::CoinbaseTip => Self::CoinbaseTip(Default::default()),
+            TransactionOrderingKind::Fifo => Self::Fifo(Default::default()),
+            TransactionOrderingKind::SenderFair => Self::SenderFair(Default::default()),
+            TransactionOrderingKind::Script(script) => Self::Script(ScriptOrdering::new(script)),
+        }
+    }
+}
+
+impl<T> TransactionOrdering for ConfiguredOrdering<T>
+where
+    T: PoolTransaction<
+            Pooled = PooledTransactionsElementEcRecovered,
+            Consensus = TransactionSignedEcRecovered,
+        > + 'static,
+{
+    type PriorityValue = i128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self {
+            Self::CoinbaseTip(ordering) => {
+                ordering.priority(transaction, base_fee).map(tip_to_priority)
+            }
+            Self::Fifo(ordering) => {
+                ordering.priority(transaction, base_fee).map(|Reverse(arrival)| -(arrival as i128))
+            }
+            Self::SenderFair(ordering) => {
+                ordering.priority(transaction, base_fee).map(|Reverse(lane)| -(lane as i128))
+            }
+            Self::Script(ordering) => ordering.priority(transaction, base_fee),
+        }
+    }
+
+    fn pending_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        lane_position: u64,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self {
+            Self::CoinbaseTip(ordering) => {
+                ordering.pending_priority(transaction, lane_position, base_fee).map(tip_to_priority)
+            }
+            Self::Fifo(ordering) => ordering
+                .pending_priority(transaction, lane_position, base_fee)
+                .map(|Reverse(arrival)| -(arrival as i128)),
+            Self::SenderFair(ordering) => ordering
+                .pending_priority(transaction, lane_position, base_fee)
+                .map(|Reverse(lane)| -(lane as i128)),
+            Self::Script(ordering) => {
+                ordering.pending_priority(transaction, lane_position, base_fee)
+            }
+        }
+    }
+}
+
+impl<T> Clone for ConfiguredOrdering<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::CoinbaseTip(ordering) => Self::CoinbaseTip(ordering.clone()),
+            Self::Fifo(ordering) => Self::Fifo(ordering.clone()),
+            Self::SenderFair(ordering) => Self::SenderFair(ordering.clone()),
+            Self::Script(ordering) => Self::Script(ordering.clone()),
+        }
+    }
+}
+
+impl<T> Default for ConfiguredOrdering<T> {
+    fn default() -> Self {
+        Self::new(TransactionOrderingKind::default())
+    }
+}
+
+/// Maps a coinbase tip to an `i128` priority, saturating tips that don't fit.
+fn tip_to_priority(tip: U256) -> i128 {
+    i128::try_from(tip).unwrap_or(i128::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn script_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let script = Arc::new("tip * 10 - lane + local".parse::<PriorityScript>().unwrap());
+        let ordering = ScriptOrdering::<MockTransaction>::new(script);
+
+        let tx = f.validated(MockTransaction::eip1559().with_max_fee(100).with_priority_fee(5));
+        assert_eq!(ordering.pending_priority(&tx, 2, 95), Priority::Value(48));
+        assert_eq!(ordering.pending_priority(&tx, 0, 0), Priority::Value(50));
+        // doesn't pay the base fee
+        assert_eq!(ordering.pending_priority(&tx, 0, 101), Priority::None);
+    }
+
+    #[test]
+    fn configured_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let cheap = f.validated(MockTransaction::eip1559().with_priority_fee(1));
+        let mut expensive = f.validated(MockTransaction::eip1559().with_priority_fee(10));
+        expensive.timestamp = cheap.timestamp + Duration::from_secs(1);
+
+        let tip = ConfiguredOrdering::<MockTransaction>::new(TransactionOrderingKind::CoinbaseTip);
+        assert!(tip.pending_priority(&expensive, 0, 0) > tip.pending_priority(&cheap, 0, 0));
+
+        let fifo = ConfiguredOrdering::<MockTransaction>::new(TransactionOrderingKind::Fifo);
+        assert!(fifo.pending_priority(&cheap, 0, 0) > fifo.pending_priority(&expensive, 0, 0));
+
+        let fair = ConfiguredOrdering::<MockTransaction>::new(TransactionOrderingKind::SenderFair);
+        assert!(fair.pending_priority(&expensive, 0, 0) > fair.pending_priority(&cheap, 1, 0));
+        assert_eq!(fair.pending_priority(&expensive, 0, 0), fair.pending_priority(&cheap, 0, 0));
+    }
+}
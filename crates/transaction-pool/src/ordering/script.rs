@@ -0,0 +1,421 @@
+//! A priority function parsed from an arithmetic expression.
+
+use std::{
+    fmt,
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+};
+
+/// A priority function, evaluated for every pending transaction by the
+/// [`ScriptOrdering`](super::ScriptOrdering).
+///
+/// The function is an integer arithmetic expression, e.g. `tip * 1000 - arrival`. It supports the
+/// operators `+`, `-`, `*`, `/` and `%`, parentheses, and the functions `min(a, b)` and
+/// `max(a, b)`. Arithmetic saturates, and dividing by zero yields zero.
+///
+/// The following variables describe the transaction, see [`PriorityInputs`]:
+///
+/// - `tip`: the effective tip per gas at the current base fee
+/// - `max_fee`: the max fee per gas
+/// - `priority_fee`: the max priority fee per gas, or the gas price of legacy transactions
+/// - `gas_limit`: the gas limit
+/// - `size`: the encoded size in bytes
+/// - `nonce`: the nonce
+/// - `lane`: the number of transactions of the same sender ahead of the transaction
+/// - `arrival`: milliseconds between the creation of the ordering and the arrival of the
+///   transaction in the pool
+/// - `local`: `1` for local transactions, `0` otherwise
+///
+/// A higher value means a higher priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityScript {
+    source: String,
+    expr: Expr,
+}
+
+impl PriorityScript {
+    /// Returns the source of the priority function.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the priority function for the given transaction properties.
+    pub fn evaluate(&self, inputs: &PriorityInputs) -> i128 {
+        self.expr.evaluate(inputs)
+    }
+}
+
+impl FromStr for PriorityScript {
+    type Err = PriorityScriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { tokens: tokenize(s)?.into_iter().peekable() };
+        let expr = parser.expr()?;
+        if let Some(token) = parser.tokens.next() {
+            return Err(PriorityScriptError::UnexpectedToken(token.to_string()))
+        }
+
+        Ok(Self { source: s.to_string(), expr })
+    }
+}
+
+impl fmt::Display for PriorityScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Properties of a transaction a [`PriorityScript`] is evaluated on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PriorityInputs {
+    /// Effective tip per gas at the current base fee.
+    pub tip: u128,
+    /// Max fee per gas.
+    pub max_fee: u128,
+    /// Max priority fee per gas, or the gas price of legacy transactions.
+    pub priority_fee: u128,
+    /// Gas limit.
+    pub gas_limit: u64,
+    /// Encoded size in bytes.
+    pub size: usize,
+    /// Nonce.
+    pub nonce: u64,
+    /// Number of transactions of the same sender ahead of the transaction.
+    pub lane: u64,
+    /// Milliseconds between the creation of the ordering and the arrival of the transaction.
+    pub arrival: u64,
+    /// Whether the transaction is local.
+    pub local: bool,
+}
+
+/// Errors when parsing a [`PriorityScript`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PriorityScriptError {
+    /// A character which isn't part of any token.
+    #[error("unexpected character {0:?} at position {1}")]
+    UnexpectedCharacter(char, usize),
+    /// A number which doesn't fit into 128 bits.
+    #[error("invalid number {0}")]
+    InvalidNumber(String),
+    /// An unknown variable.
+    #[error("unknown variable {0}")]
+    UnknownVariable(String),
+    /// An unknown function.
+    #[error("unknown function {0}")]
+    UnknownFunction(String),
+    /// A token at a position where it isn't allowed.
+    #[error("unexpected {0}")]
+    UnexpectedToken(String),
+    /// The expression ended where more tokens were expected.
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    Tip,
+    MaxFee,
+    PriorityFee,
+    GasLimit,
+    Size,
+    Nonce,
+    Lane,
+    Arrival,
+    Local,
+}
+
+impl Variable {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "tip" => Self::Tip,
+            "max_fee" => Self::MaxFee,
+            "priority_fee" => Self::PriorityFee,
+            "gas_limit" => Self::GasLimit,
+            "size" => Self::Size,
+            "nonce" => Self::Nonce,
+            "lane" => Self::Lane,
+            "arrival" => Self::Arrival,
+            "local" => Self::Local,
+            _ => return None,
+        })
+    }
+
+    fn value(self, inputs: &PriorityInputs) -> i128 {
+        let value = match self {
+            Self::Tip => inputs.tip,
+            Self::MaxFee => inputs.max_fee,
+            Self::PriorityFee => inputs.priority_fee,
+            Self::GasLimit => inputs.gas_limit as u128,
+            Self::Size => inputs.size as u128,
+            Self::Nonce => inputs.nonce as u128,
+            Self::Lane => inputs.lane as u128,
+            Self::Arrival => inputs.arrival as u128,
+            Self::Local => inputs.local as u128,
+        };
+        i128::try_from(value).unwrap_or(i128::MAX)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(i128),
+    Variable(Variable),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self, inputs: &PriorityInputs) -> i128 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(variable) => variable.value(inputs),
+            Self::Neg(expr) => expr.evaluate(inputs).saturating_neg(),
+            Self::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(inputs), rhs.evaluate(inputs));
+                match op {
+                    BinaryOp::Add => lhs.saturating_add(rhs),
+                    BinaryOp::Sub => lhs.saturating_sub(rhs),
+                    BinaryOp::Mul => lhs.saturating_mul(rhs),
+                    BinaryOp::Div => lhs.checked_div(rhs).unwrap_or_default(),
+                    BinaryOp::Rem => lhs.checked_rem(rhs).unwrap_or_default(),
+                    BinaryOp::Min => lhs.min(rhs),
+                    BinaryOp::Max => lhs.max(rhs),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i128),
+    Ident(String),
+    Op(char),
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "number {value}"),
+            Self::Ident(name) => write!(f, "identifier {name}"),
+            Self::Op(op) => write!(f, "operator {op}"),
+            Self::OpenParen => f.write_str("("),
+            Self::CloseParen => f.write_str(")"),
+            Self::Comma => f.write_str(","),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, PriorityScriptError> {
+    fn take_while(
+        chars: &mut Peekable<CharIndices<'_>>,
+        mut predicate: impl FnMut(char) -> bool,
+    ) -> String {
+        let mut taken = String::new();
+        while let Some((_, c)) = chars.next_if(|(_, c)| predicate(*c)) {
+            taken.push(c);
+        }
+        taken
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue
+            }
+            '0'..='9' => {
+                let number = take_while(&mut chars, |c| c.is_ascii_digit() || c == '_');
+                Token::Number(
+                    number
+                        .replace('_', "")
+                        .parse()
+                        .map_err(|_| PriorityScriptError::InvalidNumber(number))?,
+                )
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                Token::Ident(take_while(&mut chars, |c| c.is_ascii_alphanumeric() || c == '_'))
+            }
+            '+' | '-' | '*' | '/' | '%' | '(' | ')' | ',' => {
+                chars.next();
+                match c {
+                    '(' => Token::OpenParen,
+                    ')' => Token::CloseParen,
+                    ',' => Token::Comma,
+                    op => Token::Op(op),
+                }
+            }
+            c => return Err(PriorityScriptError::UnexpectedCharacter(c, position)),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser of the grammar:
+///
+/// ```text
+/// expr  := term (("+" | "-") term)*
+/// term  := unary (("*" | "/" | "%") unary)*
+/// unary := "-" unary | atom
+/// atom  := number | variable | function "(" expr "," expr ")" | "(" expr ")"
+/// ```
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn next(&mut self) -> Result<Token, PriorityScriptError> {
+        self.tokens.next().ok_or(PriorityScriptError::UnexpectedEnd)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), PriorityScriptError> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(PriorityScriptError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, PriorityScriptError> {
+        let mut expr = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.tokens.peek() {
+            let op = if *op == '+' { BinaryOp::Add } else { BinaryOp::Sub };
+            self.tokens.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, PriorityScriptError> {
+        let mut expr = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/' | '%'))) = self.tokens.peek() {
+            let op = match op {
+                '*' => BinaryOp::Mul,
+                '/' => BinaryOp::Div,
+                _ => BinaryOp::Rem,
+            };
+            self.tokens.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, PriorityScriptError> {
+        if self.tokens.next_if_eq(&Token::Op('-')).is_some() {
+            return Ok(Expr::Neg(Box::new(self.unary()?)))
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, PriorityScriptError> {
+        match self.next()? {
+            Token::Number(value) => Ok(Expr::Number(value)),
+            Token::Ident(name) if self.tokens.peek() == Some(&Token::OpenParen) => {
+                let op = match name.as_str() {
+                    "min" => BinaryOp::Min,
+                    "max" => BinaryOp::Max,
+                    _ => return Err(PriorityScriptError::UnknownFunction(name)),
+                };
+                self.tokens.next();
+                let lhs = self.expr()?;
+                self.expect(Token::Comma)?;
+                let rhs = self.expr()?;
+                self.expect(Token::CloseParen)?;
+                Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)))
+            }
+            Token::Ident(name) => Variable::from_name(&name)
+                .map(Expr::Variable)
+                .ok_or(PriorityScriptError::UnknownVariable(name)),
+            Token::OpenParen => {
+                let expr = self.expr()?;
+                self.expect(Token::CloseParen)?;
+                Ok(expr)
+            }
+            token => Err(PriorityScriptError::UnexpectedToken(token.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_script() {
+        let inputs = PriorityInputs {
+            tip: 7,
+            max_fee: 100,
+            priority_fee: 10,
+            gas_limit: 21_000,
+            size: 110,
+            nonce: 3,
+            lane: 2,
+            arrival: 5_000,
+            local: true,
+        };
+
+        for (source, expected) in [
+            ("42", 42),
+            ("1_000", 1_000),
+            ("tip", 7),
+            ("tip * 1000 - arrival", 2_000),
+            ("-arrival", -5_000),
+            ("1 + 2 * 3", 7),
+            ("(1 + 2) * 3", 9),
+            ("10 - 4 - 3", 3),
+            ("max_fee / 3 % 5", 3),
+            ("tip / 0", 0),
+            ("max(tip, priority_fee) + min(lane, nonce)", 12),
+            ("local * 1000 + size", 1_110),
+            ("gas_limit", 21_000),
+            ("- - 2", 2),
+        ] {
+            let script = source.parse::<PriorityScript>().unwrap();
+            assert_eq!(script.evaluate(&inputs), expected, "{source}");
+            assert_eq!(script.to_string(), source);
+        }
+
+        let max = PriorityInputs { tip: u128::MAX, ..Default::default() };
+        assert_eq!("tip * 2".parse::<PriorityScript>().unwrap().evaluate(&max), i128::MAX);
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (source, err) in [
+            ("", PriorityScriptError::UnexpectedEnd),
+            ("tip +", PriorityScriptError::UnexpectedEnd),
+            ("gas", PriorityScriptError::UnknownVariable("gas".to_string())),
+            ("avg(tip, 1)", PriorityScriptError::UnknownFunction("avg".to_string())),
+            ("tip $ 1", PriorityScriptError::UnexpectedCharacter('$', 4)),
+            ("(tip", PriorityScriptError::UnexpectedEnd),
+            ("tip)", PriorityScriptError::UnexpectedToken(")".to_string())),
+            ("max(tip)", PriorityScriptError::UnexpectedToken(")".to_string())),
+            ("tip tip", PriorityScriptError::UnexpectedToken("identifier tip".to_string())),
+        ] {
+            assert_eq!(source.parse::<PriorityScript>(), Err(err), "{source}");
+        }
+
+        let too_large = "1".repeat(40);
+        assert_eq!(
+            too_large.parse::<PriorityScript>(),
+            Err(PriorityScriptError::InvalidNumber(too_large))
+        );
+    }
+}
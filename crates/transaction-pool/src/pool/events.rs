@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 pub enum FullTransactionEvent<T: PoolTransaction> {
     /// Transaction has been added to the pending pool.
     Pending(TxHash),
-    /// Transaction has been added to the queued pool.
+    /// Transaction has been added to the queued pool, or was moved there from the pending pool.
     Queued(TxHash),
     /// Transaction has been included in the block belonging to this hash.
     Mined {
@@ -30,6 +30,8 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
     },
     /// Transaction was dropped due to configured limits.
     Discarded(TxHash),
+    /// Private transaction was dropped because it wasn't included before its inclusion deadline.
+    Expired(TxHash),
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -48,6 +50,7 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
             Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Expired(hash) => Self::Expired(*hash),
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
@@ -60,7 +63,7 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
 pub enum TransactionEvent {
     /// Transaction has been added to the pending pool.
     Pending,
-    /// Transaction has been added to the queued pool.
+    /// Transaction has been added to the queued pool, or was moved there from the pending pool.
     Queued,
     /// Transaction has been included in the block belonging to this hash.
     Mined(B256),
@@ -80,7 +80,7 @@ use crate::{
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, PoolConfig, TransactionOrdering, TransactionValidator,
 };
-use alloy_primitives::{Address, TxHash, B256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire_types::HandleMempoolData;
@@ -90,7 +90,7 @@ use reth_primitives::{
     BlobTransaction, BlobTransactionSidecar, PooledTransactionsElement, TransactionSigned,
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     sync::Arc,
     time::Instant,
@@ -154,6 +154,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Hashes of private transactions by the last block they may be included in.
+    inclusion_deadlines: Mutex<BTreeMap<BlockNumber, Vec<TxHash>>>,
 }
 
 // === impl PoolInner ===
@@ -177,6 +179,7 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            inclusion_deadlines: Default::default(),
         }
     }
 
@@ -230,6 +233,19 @@ where
         &self.config
     }
 
+    /// Checks the transaction against the admission policy of the pool.
+    pub(crate) fn check_admission(&self, transaction: &T::Transaction) -> PoolResult<()> {
+        self.config
+            .admission_policy
+            .check(transaction)
+            .map_err(|kind| PoolError::new(*transaction.hash(), kind))
+    }
+
+    /// Refunds the admission of a transaction of the sender which wasn't added to the pool.
+    pub(crate) fn refund_admission(&self, sender: Address) {
+        self.config.admission_policy.refund(sender)
+    }
+
     /// Get the validator reference.
     pub const fn validator(&self) -> &V {
         &self.validator
@@ -322,7 +338,8 @@ where
         let transactions = self.get_all(tx_hashes);
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
-        for transaction in transactions {
+        // transactions that may not be propagated are never served to peers either
+        for transaction in transactions.into_iter().filter(|tx| tx.propagate) {
             let encoded_len = transaction.encoded_length();
             let tx = transaction.to_recovered_transaction().into_signed();
             let pooled = if tx.is_eip4844() {
@@ -393,6 +410,61 @@ where
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+
+        self.remove_expired_transactions(block_info.last_seen_block_number);
+    }
+
+    /// Drops the transaction from the pool unless it's included in a block up to and including
+    /// `max_block`.
+    pub(crate) fn set_inclusion_deadline(&self, hash: TxHash, max_block: BlockNumber) {
+        self.inclusion_deadlines.lock().entry(max_block).or_default().push(hash);
+    }
+
+    /// Removes an inclusion deadline set with [`Self::set_inclusion_deadline`].
+    pub(crate) fn remove_inclusion_deadline(&self, hash: &TxHash, max_block: BlockNumber) {
+        let mut deadlines = self.inclusion_deadlines.lock();
+        if let Some(hashes) = deadlines.get_mut(&max_block) {
+            if let Some(idx) = hashes.iter().position(|deadline| deadline == hash) {
+                hashes.swap_remove(idx);
+            }
+            if hashes.is_empty() {
+                deadlines.remove(&max_block);
+            }
+        }
+    }
+
+    /// Returns the last block the transaction may be included in, if it has an inclusion deadline.
+    pub(crate) fn inclusion_deadline(&self, hash: &TxHash) -> Option<BlockNumber> {
+        self.inclusion_deadlines
+            .lock()
+            .iter()
+            .find(|(_, hashes)| hashes.contains(hash))
+            .map(|(max_block, _)| *max_block)
+    }
+
+    /// Removes the transactions whose inclusion deadline passed with the given block.
+    ///
+    /// Transactions which were included in time are no longer in the pool at this point.
+    fn remove_expired_transactions(&self, block_number: BlockNumber) {
+        let expired = {
+            let mut deadlines = self.inclusion_deadlines.lock();
+            let remaining = deadlines.split_off(&(block_number + 1));
+            std::mem::replace(&mut *deadlines, remaining)
+        };
+        if expired.is_empty() {
+            return
+        }
+
+        let removed =
+            self.pool.write().remove_transactions(expired.into_values().flatten().collect());
+        let mut listener = self.event_listener.write();
+        removed.iter().for_each(|tx| listener.expired(tx.hash()));
+        drop(listener);
+
+        if !removed.is_empty() {
+            debug!(target: "txpool", count = removed.len(), block_number, "Dropped transactions past their inclusion deadline");
+        }
+        self.delete_discarded_blobs(removed.iter());
     }
 
     /// Performs account updates on the pool.
@@ -400,11 +472,12 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub(crate) fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
+        let UpdateOutcome { promoted, discarded, demoted } =
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        demoted.iter().for_each(|tx| listener.queued(tx.hash()));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
@@ -620,13 +693,15 @@ where
             listener.send_all(outcome.full_pending_transactions(listener.kind))
         });
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, discarded, demoted, block_hash } =
+            outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        demoted.iter().for_each(|tx| listener.queued(tx.hash()));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
     }
 
@@ -636,17 +711,20 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction { transaction, promoted, discarded, replaced, demoted } =
+                    tx;
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+                demoted.iter().for_each(|tx| listener.queued(tx.hash()));
                 discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
+            AddedTransaction::Parked { transaction, replaced, demoted, .. } => {
                 listener.queued(transaction.hash());
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
                 }
+                demoted.iter().for_each(|tx| listener.queued(tx.hash()));
             }
         }
     }
@@ -945,6 +1023,8 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transactions that failed and became discarded
     discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved from the pending queue to a parked pool
+    demoted: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> AddedPendingTransaction<T> {
@@ -1031,6 +1111,8 @@ pub enum AddedTransaction<T: PoolTransaction> {
         replaced: Option<Arc<ValidPoolTransaction<T>>>,
         /// The subpool it was moved to.
         subpool: SubPool,
+        /// Transactions moved from the pending pool to a parked pool.
+        demoted: Vec<Arc<ValidPoolTransaction<T>>>,
     },
 }
 
@@ -1114,6 +1196,8 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions moved from the pending pool to a parked pool.
+    pub(crate) demoted: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
@@ -1234,4 +1318,39 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn private_transactions_are_not_propagated_and_expire() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let add = |origin, propagate| {
+            let tx = MockTransaction::eip1559();
+            test_pool
+                .add_transaction(
+                    origin,
+                    TransactionValidationOutcome::Valid {
+                        balance: U256::from(1_000),
+                        state_nonce: 0,
+                        transaction: ValidTransaction::Valid(tx),
+                        propagate,
+                    },
+                )
+                .unwrap()
+        };
+        let public = add(TransactionOrigin::External, true);
+        let private = add(TransactionOrigin::Private, false);
+        test_pool.set_inclusion_deadline(private, 10);
+
+        assert_eq!(test_pool.pooled_transactions_hashes(), vec![public]);
+        assert_eq!(test_pool.inclusion_deadline(&private), Some(10));
+        assert_eq!(test_pool.inclusion_deadline(&public), None);
+
+        // the transaction may still be included in the deadline block
+        test_pool.remove_expired_transactions(9);
+        assert!(test_pool.get(&private).is_some());
+        test_pool.remove_expired_transactions(10);
+        assert!(test_pool.get(&private).is_none());
+        assert!(test_pool.get(&public).is_some());
+        assert!(test_pool.inclusion_deadlines.lock().is_empty());
+    }
 }
@@ -32,6 +32,8 @@ pub struct PendingPool<T: TransactionOrdering> {
     ///
     /// This way we can determine when transactions were submitted to the pool.
     submission_id: u64,
+    /// The base fee the priorities of the transactions were last computed with.
+    base_fee: u64,
     /// _All_ Transactions that are currently inside the pool grouped by their identifier.
     by_id: BTreeMap<TransactionId, PendingTransaction<T>>,
     /// _All_ transactions sorted by priority
@@ -64,6 +66,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         Self {
             ordering,
             submission_id: 0,
+            base_fee: 0,
             by_id: Default::default(),
             all: Default::default(),
             independent_transactions: Default::default(),
@@ -144,8 +147,9 @@ impl<T: TransactionOrdering> PendingPool<T> {
         for tx in unlocked {
             submission_id += 1;
             debug_assert!(!best.all.contains_key(tx.id()), "transaction already included");
-            let priority = self.ordering.priority(&tx.transaction, base_fee);
             let tx_id = *tx.id();
+            let priority =
+                self.ordering.pending_priority(&tx, lane_position(&best.all, &tx_id), base_fee);
             let transaction = PendingTransaction { submission_id, transaction: tx, priority };
             if best.ancestor(&tx_id).is_none() {
                 best.independent.insert(transaction.clone());
@@ -176,6 +180,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
         &mut self,
         blob_fee: u128,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.base_fee = base_fee;
+
         // Create a collection for removed transactions.
         let mut removed = Vec::new();
 
@@ -239,8 +245,13 @@ impl<T: TransactionOrdering> PendingPool<T> {
                     transactions_iter.next();
                 }
             } else {
-                // Re-insert the transaction with new priority.
-                tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
+                // Re-insert the transaction with new priority. Transactions are iterated in nonce
+                // order, so the sender's transactions ahead of it are already re-inserted.
+                tx.priority = self.ordering.pending_priority(
+                    &tx.transaction,
+                    lane_position(&self.by_id, &id),
+                    base_fee,
+                );
 
                 self.size_of += tx.transaction.size();
                 self.update_independents_and_highest_nonces(&tx, &id);
@@ -298,11 +309,13 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        self.base_fee = base_fee;
 
         let tx_id = *tx.id();
 
         let submission_id = self.next_id();
-        let priority = self.ordering.priority(&tx.transaction, base_fee);
+        let priority =
+            self.ordering.pending_priority(&tx, lane_position(&self.by_id, &tx_id), base_fee);
         let tx = PendingTransaction { submission_id, transaction: tx, priority };
 
         self.update_independents_and_highest_nonces(&tx, &tx_id);
@@ -331,7 +344,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         let tx = self.by_id.remove(id)?;
         self.size_of -= tx.transaction.size();
         self.all.remove(&tx);
-        self.independent_transactions.remove(&tx);
+        let was_independent = self.independent_transactions.remove(&tx);
 
         // switch out for the next ancestor if there is one
         if self.highest_nonces.remove(&tx) {
@@ -339,9 +352,55 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 self.highest_nonces.insert(ancestor.clone());
             }
         }
+
+        // the lane positions of the sender's remaining transactions moved up by one
+        if was_independent {
+            self.update_lane_priorities(id.sender);
+        }
+
         Some(tx.transaction)
     }
 
+    /// Recomputes the priorities of the sender's transactions with their current lane position
+    /// and the last known base fee.
+    ///
+    /// This is required whenever the lowest pending nonce of the sender changes without a base
+    /// fee update, e.g. once its independent transaction was mined.
+    fn update_lane_priorities(&mut self, sender: SenderId) {
+        let ids = self
+            .by_id
+            .range((sender.start_bound(), Unbounded))
+            .take_while(|(id, _)| id.sender == sender)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in ids {
+            let Some(tx) = self.by_id.get(&id) else { continue };
+            let priority = self.ordering.pending_priority(
+                &tx.transaction,
+                lane_position(&self.by_id, &id),
+                self.base_fee,
+            );
+            if priority == tx.priority {
+                continue
+            }
+
+            // the sets are ordered by priority, so the transaction must be removed with its old
+            // priority before it is re-inserted
+            let mut updated = tx.clone();
+            updated.priority = priority;
+            self.all.remove(tx);
+            self.all.insert(updated.clone());
+            if self.independent_transactions.remove(tx) {
+                self.independent_transactions.insert(updated.clone());
+            }
+            if self.highest_nonces.remove(tx) {
+                self.highest_nonces.insert(updated.clone());
+            }
+            self.by_id.insert(id, updated);
+        }
+    }
+
     fn next_id(&mut self) -> u64 {
         let id = self.submission_id;
         self.submission_id = self.submission_id.wrapping_add(1);
@@ -537,6 +596,21 @@ impl<T: TransactionOrdering> PendingPool<T> {
     }
 }
 
+/// Returns the number of pending transactions of the sender with a lower nonce than the given
+/// transaction.
+///
+/// The pending pool is gapless, so this is the distance to the sender's lowest pending nonce.
+fn lane_position<T: TransactionOrdering>(
+    by_id: &BTreeMap<TransactionId, PendingTransaction<T>>,
+    id: &TransactionId,
+) -> u64 {
+    by_id
+        .range((id.sender.start_bound(), Unbounded))
+        .next()
+        .filter(|(lowest, _)| lowest.sender == id.sender)
+        .map_or(0, |(lowest, _)| id.nonce.saturating_sub(lowest.nonce))
+}
+
 /// A transaction that is ready to be included in a block.
 #[derive(Debug)]
 pub(crate) struct PendingTransaction<T: TransactionOrdering> {
@@ -595,11 +669,14 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
-        PoolTransaction,
+        FifoOrdering, PoolTransaction, SenderFairOrdering,
     };
     use alloy_primitives::address;
     use reth_primitives::TxType;
-    use std::collections::HashSet;
+    use std::{
+        collections::HashSet,
+        time::{Duration, Instant},
+    };
 
     #[test]
     fn test_enforce_basefee() {
@@ -659,6 +736,62 @@ mod tests {
         pool.assert_invariants();
     }
 
+    #[test]
+    fn best_fifo_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(FifoOrdering::<MockTransaction>::default());
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+
+        // the cheap transaction of B arrived first, but is added to the pending pool last
+        let arrival = Instant::now();
+        let mut validated = |tx: MockTransaction, arrived_after: u64| {
+            let mut tx = f.validated(tx);
+            tx.timestamp = arrival + Duration::from_secs(arrived_after);
+            Arc::new(tx)
+        };
+        let b0 =
+            validated(MockTransaction::eip1559().with_sender(b_sender).with_priority_fee(1), 0);
+        let a0 =
+            validated(MockTransaction::eip1559().with_sender(a_sender).with_priority_fee(9), 1);
+        let a1 = validated(a0.transaction.next(), 2);
+        for tx in [&a0, &a1, &b0] {
+            pool.add_transaction(tx.clone(), 0);
+        }
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*b0.hash(), *a0.hash(), *a1.hash()]);
+    }
+
+    #[test]
+    fn best_sender_fair_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(SenderFairOrdering::<MockTransaction>::default());
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+        let c_sender = address!("000000000000000000000000000000000000000c");
+
+        let a = MockTransactionSet::dependent(a_sender, 0, 3, TxType::Eip1559).into_vec();
+        let b = MockTransaction::eip1559().with_sender(b_sender);
+        let c = MockTransaction::eip1559().with_sender(c_sender);
+        for tx in a.iter().cloned().chain([b.clone(), c.clone()]) {
+            pool.add_transaction(f.validated_arc(tx), 0);
+        }
+
+        // the next transaction of every sender first, then the remaining transactions of A
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*a[0].hash(), *b.hash(), *c.hash(), *a[1].hash(), *a[2].hash()]);
+
+        // lane positions of A are recomputed on removal, A's next transaction entered the pending
+        // pool first
+        pool.remove_transaction(&f.tx_id(&a[0]));
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*a[1].hash(), *b.hash(), *c.hash(), *a[2].hash()]);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn evict_worst() {
         let mut f = MockTransactionFactory::default();
@@ -330,9 +330,10 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.all()
     }
 
-    /// Returns all transactions from parked pools
+    /// Returns all transactions from parked pools, including blob transactions parked in the blob
+    /// sub-pool
     pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.basefee_pool.all().chain(self.queued_pool.all()).collect()
+        self.basefee_pool.all().chain(self.queued_pool.all()).chain(self.blob_pool.all()).collect()
     }
 
     /// Returns queued and pending transactions for the specified sender
@@ -427,11 +428,17 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, discarded, demoted } = self.update_accounts(changed_senders);
 
         self.metrics.performed_state_updates.increment(1);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            discarded,
+            demoted,
+        }
     }
 
     /// Update sub-pools size metrics.
@@ -495,7 +502,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, discarded, demoted } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
 
@@ -506,9 +513,10 @@ impl<T: TransactionOrdering> TxPool<T> {
                         promoted,
                         discarded,
                         replaced,
+                        demoted,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to, replaced }
+                    AddedTransaction::Parked { transaction, subpool: move_to, replaced, demoted }
                 };
 
                 // Update size metrics after adding and potentially moving transactions.
@@ -591,10 +599,13 @@ impl<T: TransactionOrdering> TxPool<T> {
                 Destination::Pool(move_to) => {
                     debug_assert_ne!(&move_to, &current, "destination must be different");
                     let moved = self.move_transaction(current, move_to, &id);
-                    if matches!(move_to, SubPool::Pending) {
-                        if let Some(tx) = moved {
+                    if let Some(tx) = moved {
+                        if move_to.is_pending() {
                             trace!(target: "txpool", hash=%tx.transaction.hash(), "Promoted transaction to pending");
                             outcome.promoted.push(tx);
+                        } else if current.is_pending() {
+                            trace!(target: "txpool", hash=%tx.transaction.hash(), ?move_to, "Demoted pending transaction");
+                            outcome.demoted.push(tx);
                         }
                     }
                 }
@@ -1830,11 +1841,13 @@ pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that failed and were discarded
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved from the pending pool to a parked pool
+    pub(crate) demoted: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], discarded: vec![], demoted: vec![] }
     }
 }
 
@@ -1865,7 +1878,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        SenderFairOrdering, SubPoolLimit,
     };
 
     #[test]
@@ -2718,6 +2731,26 @@ mod tests {
         assert_eq!(pool.pending_pool.len(), 1);
     }
 
+    #[test]
+    fn demote_unaffordable_pending() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx = MockTransaction::eip1559().inc_price_by(10).inc_limit();
+        let validated = f.validated(tx.clone());
+        let id = *validated.id();
+        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        let mut changed_senders = HashMap::default();
+        changed_senders.insert(id.sender, SenderInfo { state_nonce: 0, balance: U256::ZERO });
+        let outcome = pool.update_accounts(changed_senders);
+        assert_eq!(outcome.demoted.len(), 1);
+        assert!(outcome.promoted.is_empty());
+        assert!(pool.pending_pool.is_empty());
+        assert_eq!(pool.queued_pool.len(), 1);
+    }
+
     #[test]
     fn discard_with_large_blob_txs() {
         // init tracing
@@ -2992,4 +3025,32 @@ mod tests {
             vec![1, 2, 3]
         );
     }
+
+    #[test]
+    fn sender_fair_lanes_after_mined_transaction() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(SenderFairOrdering::default(), Default::default());
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+        let a = MockTransactionSet::dependent(a_sender, 0, 3, TxType::Eip1559).into_vec();
+        let b = MockTransaction::eip1559().with_sender(b_sender);
+        for tx in a.iter().cloned().chain([b.clone()]) {
+            pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        }
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*a[0].hash(), *b.hash(), *a[1].hash(), *a[2].hash()]);
+
+        // A's first transaction is mined, the base fee stays the same
+        let block_info = pool.block_info();
+        let mut changed_senders = HashMap::default();
+        changed_senders
+            .insert(f.tx_id(&a[0]).sender, SenderInfo { state_nonce: 1, balance: U256::MAX });
+        pool.on_canonical_state_change(block_info, vec![*a[0].hash()], changed_senders);
+
+        // A's next transaction moved into the first round, and entered the pending pool before B
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*a[1].hash(), *b.hash(), *a[2].hash()]);
+        pool.pending_pool.assert_invariants();
+    }
 }
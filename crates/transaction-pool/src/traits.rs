@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 use crate::{
+    admission::AdmissionPolicy,
     blobstore::BlobStoreError,
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
@@ -8,7 +9,7 @@ use crate::{
     AllTransactionsEvents,
 };
 use alloy_eips::{eip2718::Encodable2718, eip2930::AccessList, eip4844::BlobAndProofV1};
-use alloy_primitives::{Address, TxHash, TxKind, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxKind, B256, U256};
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -102,6 +103,19 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> impl Future<Output = PoolResult<TxHash>> + Send;
 
+    /// Adds an _unvalidated_ [`TransactionOrigin::Private`] transaction into the pool.
+    ///
+    /// The transaction is available to the local payload builder, but never announced or served
+    /// to peers. If `max_block` is set, the transaction is dropped if it isn't included in a block
+    /// up to and including `max_block`.
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block: Option<BlockNumber>,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -225,6 +239,9 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// If the transaction is a blob transaction, the sidecar will be included.
     ///
+    /// Transactions that may not be propagated, like [`TransactionOrigin::Private`] transactions,
+    /// are skipped.
+    ///
     /// Consumer: P2P
     fn get_pooled_transaction_elements(
         &self,
@@ -421,6 +438,18 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
+
+    /// Returns the [`AdmissionPolicy`] deciding which transactions may enter the pool, if the pool
+    /// enforces one.
+    fn admission_policy(&self) -> Option<AdmissionPolicy> {
+        None
+    }
+
+    /// Returns the last block the private transaction may be included in, if it was added with
+    /// one, see [`Self::add_private_transaction`].
+    fn inclusion_deadline(&self, _tx_hash: &TxHash) -> Option<BlockNumber> {
+        None
+    }
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
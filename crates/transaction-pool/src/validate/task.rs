@@ -99,9 +99,9 @@ impl TransactionValidationTaskExecutor<()> {
 
 impl<V> TransactionValidationTaskExecutor<V> {
     /// Maps the given validator to a new type.
-    pub fn map<F, T>(self, mut f: F) -> TransactionValidationTaskExecutor<T>
+    pub fn map<F, T>(self, f: F) -> TransactionValidationTaskExecutor<T>
     where
-        F: FnMut(V) -> T,
+        F: FnOnce(V) -> T,
     {
         TransactionValidationTaskExecutor {
             validator: f(self.validator),
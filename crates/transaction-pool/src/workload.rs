@@ -0,0 +1,568 @@
+//! Recording of transaction pool workloads, for replaying them offline.
+//!
+//! [`record_workload_task`] follows a running pool and the canonical chain and records every
+//! transaction entering and leaving the pool and every canonical update into a workload file. The
+//! `replay` benchmark of this crate drives a [`Pool`](crate::Pool) with a recorded workload against
+//! a mocked state provider.
+//!
+//! A workload file starts with [`WORKLOAD_MAGIC`], a version byte and the chain id as `u64` LE,
+//! followed by [`WorkloadRecord`]s framed like the records of the [journal](crate::journal):
+//!
+//! ```text
+//! | length: u32 LE | checksum: 4 bytes | kind: u8 | payload: RLP |
+//! ```
+//!
+//! The state of every sender is recorded before its first transaction, and the changed state of
+//! recorded senders with every canonical update, so the replay starts from the same state the
+//! node validated the transactions against.
+
+use crate::{
+    journal::{decode_frame, decode_origin, encode_frame, encode_origin},
+    maintain::pending_block_base_fee,
+    AllPoolTransactions, FullTransactionEvent, PoolTransaction, TransactionOrigin, TransactionPool,
+};
+use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use futures_util::{
+    future::{self, Either},
+    FutureExt, Stream, StreamExt,
+};
+use reth_chain_state::CanonStateNotification;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_primitives::{PooledTransactionsElement, PooledTransactionsElementEcRecovered};
+use reth_storage_api::{AccountReader, StateProviderFactory};
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tracing::{debug, error, info, warn};
+
+/// Magic bytes at the start of a workload file.
+pub const WORKLOAD_MAGIC: [u8; 4] = *b"RTXW";
+
+/// Version of the workload format.
+pub const WORKLOAD_VERSION: u8 = 1;
+
+/// Size of the workload file header: magic, version and chain id.
+const WORKLOAD_HEADER_SIZE: usize = WORKLOAD_MAGIC.len() + 1 + 8;
+
+/// Errors of workload files.
+#[derive(Debug, thiserror::Error)]
+pub enum WorkloadError {
+    /// The file doesn't start with the workload header.
+    #[error("not a transaction pool workload")]
+    InvalidHeader,
+    /// The workload was written by an unsupported version.
+    #[error("unsupported transaction pool workload version {0}")]
+    UnsupportedVersion(u8),
+    /// Error reading or writing the workload.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// State of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct WorkloadAccount {
+    /// Address of the account.
+    pub address: Address,
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Balance of the account.
+    pub balance: U256,
+}
+
+/// A transaction that entered the pool.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct WorkloadTransaction {
+    /// Milliseconds since the recording started.
+    pub elapsed: u64,
+    /// Origin of the transaction, see [`encode_origin`].
+    pub origin: u8,
+    /// The transaction, including the blob sidecar of blob transactions.
+    pub transaction: PooledTransactionsElement,
+}
+
+impl WorkloadTransaction {
+    /// Returns the origin of the transaction, if it's known.
+    pub const fn origin(&self) -> Option<TransactionOrigin> {
+        decode_origin(self.origin)
+    }
+}
+
+/// A transaction that was replaced by another transaction of the same sender and nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct WorkloadReplacement {
+    /// Milliseconds since the recording started.
+    pub elapsed: u64,
+    /// Hash of the replaced transaction.
+    pub hash: TxHash,
+    /// Hash of the replacement transaction.
+    pub replaced_by: TxHash,
+}
+
+/// A transaction that left the pool without being mined or replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct WorkloadRemoval {
+    /// Milliseconds since the recording started.
+    pub elapsed: u64,
+    /// Hash of the removed transaction.
+    pub hash: TxHash,
+}
+
+/// A new canonical tip.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct WorkloadCanonicalUpdate {
+    /// Milliseconds since the recording started.
+    pub elapsed: u64,
+    /// Number of the tip block.
+    pub number: u64,
+    /// Hash of the tip block.
+    pub hash: B256,
+    /// Timestamp of the tip block.
+    pub timestamp: u64,
+    /// Gas limit of the tip block.
+    pub gas_limit: u64,
+    /// Base fee of the next block.
+    pub pending_basefee: u64,
+    /// Blob fee of the next block, zero before Cancun.
+    pub pending_blob_fee: u128,
+    /// Transactions mined in the new blocks.
+    pub mined_transactions: Vec<TxHash>,
+    /// Changed state of the recorded senders.
+    pub changed_accounts: Vec<WorkloadAccount>,
+}
+
+/// A record of a workload file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkloadRecord {
+    /// State of a sender, recorded before its first transaction.
+    Account(WorkloadAccount),
+    /// A transaction entered the pool.
+    Transaction(WorkloadTransaction),
+    /// A transaction was replaced.
+    Replacement(WorkloadReplacement),
+    /// A transaction was removed from the pool.
+    Removal(WorkloadRemoval),
+    /// The canonical chain was updated.
+    CanonicalUpdate(WorkloadCanonicalUpdate),
+}
+
+impl WorkloadRecord {
+    /// Encodes the record, including its framing.
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Account(account) => encode_frame(0, account),
+            Self::Transaction(transaction) => encode_frame(1, transaction),
+            Self::Replacement(replacement) => encode_frame(2, replacement),
+            Self::Removal(removal) => encode_frame(3, removal),
+            Self::CanonicalUpdate(update) => encode_frame(4, update),
+        }
+    }
+
+    /// Decodes the payload of a record of the given kind.
+    fn decode(kind: u8, mut payload: &[u8]) -> Option<Self> {
+        let buf = &mut payload;
+        let record = match kind {
+            0 => Self::Account(Decodable::decode(buf).ok()?),
+            1 => Self::Transaction(Decodable::decode(buf).ok()?),
+            2 => Self::Replacement(Decodable::decode(buf).ok()?),
+            3 => Self::Removal(Decodable::decode(buf).ok()?),
+            4 => Self::CanonicalUpdate(Decodable::decode(buf).ok()?),
+            _ => return None,
+        };
+        Some(record)
+    }
+}
+
+/// A recorded workload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workload {
+    /// Chain id of the recorded node.
+    pub chain_id: u64,
+    /// The records, in the order they were recorded.
+    pub records: Vec<WorkloadRecord>,
+}
+
+impl Workload {
+    /// Loads the workload file at the given path.
+    ///
+    /// Loading stops at the first torn or corrupted record, e.g. one that was partially written
+    /// when the recording node stopped.
+    pub fn load(path: &Path) -> Result<Self, WorkloadError> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+
+        let Some(rest) = data.strip_prefix(&WORKLOAD_MAGIC) else {
+            return Err(WorkloadError::InvalidHeader)
+        };
+        let (chain_id, mut records) = match rest.split_first() {
+            Some((&WORKLOAD_VERSION, rest)) if rest.len() >= 8 => {
+                let (chain_id, records) = rest.split_at(8);
+                (u64::from_le_bytes(chain_id.try_into().expect("8 bytes")), records)
+            }
+            Some((&WORKLOAD_VERSION, _)) | None => return Err(WorkloadError::InvalidHeader),
+            Some((&version, _)) => return Err(WorkloadError::UnsupportedVersion(version)),
+        };
+
+        let mut workload = Self { chain_id, records: Vec::new() };
+        let mut offset = WORKLOAD_HEADER_SIZE;
+        while !records.is_empty() {
+            let Some(record) = decode_frame(records).and_then(|(kind, payload, len)| {
+                Some((WorkloadRecord::decode(kind, payload)?, len))
+            }) else {
+                warn!(target: "txpool::workload", ?path, offset, "Workload ends with a torn or corrupted record, discarding it");
+                break
+            };
+            records = &records[record.1..];
+            offset += record.1;
+            workload.records.push(record.0);
+        }
+
+        Ok(workload)
+    }
+}
+
+/// Writes records to a workload file.
+#[derive(Debug)]
+pub struct WorkloadRecorder {
+    writer: BufWriter<File>,
+    /// When the recording started.
+    started: Instant,
+    /// Number of written records.
+    records: usize,
+}
+
+impl WorkloadRecorder {
+    /// Creates a new workload file at the given path, replacing any existing file.
+    pub fn create(path: &Path, chain_id: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&WORKLOAD_MAGIC)?;
+        writer.write_all(&[WORKLOAD_VERSION])?;
+        writer.write_all(&chain_id.to_le_bytes())?;
+        Ok(Self { writer, started: Instant::now(), records: 0 })
+    }
+
+    /// Returns the milliseconds since the recording started.
+    pub fn elapsed(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// Returns the number of written records.
+    pub const fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Writes the record.
+    ///
+    /// The record isn't guaranteed to be written to the file before [`Self::flush`].
+    pub fn record(&mut self, record: &WorkloadRecord) -> io::Result<()> {
+        self.writer.write_all(&record.encode())?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Flushes all written records to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Task which records the workload of the pool into a workload file, see the
+/// [module docs](self).
+///
+/// The transactions already in the pool are recorded first. Transactions rejected by the pool are
+/// not recorded.
+pub async fn record_workload_task<Client, P, St>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    client: Client,
+    pool: P,
+    canonical_events: St,
+    path: PathBuf,
+) where
+    Client: StateProviderFactory + ChainSpecProvider,
+    P: TransactionPool,
+    P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+    St: Stream<Item = CanonStateNotification> + Unpin,
+{
+    // subscribe before recording the pool, so no transaction is missed
+    let pool_events = pool.all_transactions_event_listener();
+
+    let chain_id = client.chain_spec().chain().id();
+    let recorder = match WorkloadRecorder::create(&path, chain_id) {
+        Ok(recorder) => recorder,
+        Err(err) => {
+            error!(target: "txpool::workload", %err, ?path, "Failed to create workload file");
+            return
+        }
+    };
+    info!(target: "txpool::workload", ?path, "Recording transaction pool workload");
+
+    let mut state =
+        RecorderState { client, recorder, transactions: HashSet::new(), senders: HashSet::new() };
+
+    // start with the pool's view of the chain, so the replay validates against the same fees
+    let block_info = pool.block_info();
+    let mut result =
+        state.recorder.record(&WorkloadRecord::CanonicalUpdate(WorkloadCanonicalUpdate {
+            elapsed: 0,
+            number: block_info.last_seen_block_number,
+            hash: block_info.last_seen_block_hash,
+            timestamp: 0,
+            gas_limit: block_info.block_gas_limit,
+            pending_basefee: block_info.pending_basefee,
+            pending_blob_fee: block_info.pending_blob_fee.unwrap_or_default(),
+            mined_transactions: Vec::new(),
+            changed_accounts: Vec::new(),
+        }));
+
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let mut transactions = pending.into_iter().chain(queued).collect::<Vec<_>>();
+    // oldest first, in the order they entered the pool
+    transactions.sort_by_key(|tx| tx.timestamp);
+    for tx in transactions {
+        result = result.and_then(|_| state.on_added(&pool, *tx.hash()));
+    }
+
+    let mut events = futures_util::stream::select(
+        pool_events.map(Either::Left),
+        canonical_events.map(Either::Right),
+    );
+    let mut shutdown = std::pin::pin!(shutdown);
+    loop {
+        if let Err(err) = result.and_then(|_| state.recorder.flush()) {
+            error!(target: "txpool::workload", %err, ?path, "Failed to write workload, stopping the recording");
+            return
+        }
+
+        let event = match future::select(events.next(), shutdown.as_mut()).await {
+            Either::Left((Some(event), _)) => event,
+            Either::Left((None, _)) => break,
+            Either::Right((guard, _)) => {
+                let _ = state.recorder.flush();
+                drop(guard);
+                break
+            }
+        };
+
+        // record all ready events, then flush them at once
+        result = state.on_event(&pool, event);
+        while let Some(Some(event)) = events.next().now_or_never() {
+            result = result.and_then(|_| state.on_event(&pool, event));
+        }
+    }
+
+    debug!(target: "txpool::workload", ?path, records = state.recorder.records(), "Stopped recording transaction pool workload");
+}
+
+/// State of [`record_workload_task`].
+struct RecorderState<Client> {
+    client: Client,
+    recorder: WorkloadRecorder,
+    /// Recorded transactions which are still in the pool.
+    transactions: HashSet<TxHash>,
+    /// Senders whose state was recorded.
+    senders: HashSet<Address>,
+}
+
+impl<Client> RecorderState<Client>
+where
+    Client: StateProviderFactory + ChainSpecProvider,
+{
+    /// Records the pool event or canonical update.
+    fn on_event<P>(
+        &mut self,
+        pool: &P,
+        event: Either<FullTransactionEvent<P::Transaction>, CanonStateNotification>,
+    ) -> io::Result<()>
+    where
+        P: TransactionPool,
+        P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+    {
+        let elapsed = self.recorder.elapsed();
+        let record = match event {
+            Either::Left(event) => match event {
+                // also emitted when a transaction moves between sub-pools
+                FullTransactionEvent::Pending(hash) | FullTransactionEvent::Queued(hash) => {
+                    return self.on_added(pool, hash)
+                }
+                FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                    let hash = *transaction.hash();
+                    if !self.transactions.remove(&hash) {
+                        return Ok(())
+                    }
+                    WorkloadRecord::Replacement(WorkloadReplacement { elapsed, hash, replaced_by })
+                }
+                FullTransactionEvent::Discarded(hash) |
+                FullTransactionEvent::Expired(hash) |
+                FullTransactionEvent::Invalid(hash) => {
+                    if !self.transactions.remove(&hash) {
+                        return Ok(())
+                    }
+                    WorkloadRecord::Removal(WorkloadRemoval { elapsed, hash })
+                }
+                FullTransactionEvent::Mined { tx_hash, .. } => {
+                    // recorded with the canonical update
+                    self.transactions.remove(&tx_hash);
+                    return Ok(())
+                }
+                FullTransactionEvent::Propagated(_) => return Ok(()),
+            },
+            Either::Right(notification) => {
+                WorkloadRecord::CanonicalUpdate(self.canonical_update(elapsed, &notification))
+            }
+        };
+        self.recorder.record(&record)
+    }
+
+    /// Records the transaction if it's new, preceded by the state of its sender if it's unknown.
+    fn on_added<P>(&mut self, pool: &P, hash: TxHash) -> io::Result<()>
+    where
+        P: TransactionPool,
+        P::Transaction: PoolTransaction<Pooled = PooledTransactionsElementEcRecovered>,
+    {
+        if self.transactions.contains(&hash) {
+            return Ok(())
+        }
+        let Some(tx) = pool.get(&hash) else { return Ok(()) };
+        let Some(transaction) = pool.get_pooled_transaction_element(hash) else { return Ok(()) };
+
+        let sender = tx.sender();
+        if self.senders.insert(sender) {
+            let account = self
+                .client
+                .latest()
+                .and_then(|state| state.basic_account(sender))
+                .inspect_err(|err| {
+                    debug!(target: "txpool::workload", %err, %sender, "Failed to load sender state");
+                })
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            self.recorder.record(&WorkloadRecord::Account(WorkloadAccount {
+                address: sender,
+                nonce: account.nonce,
+                balance: account.balance,
+            }))?;
+        }
+
+        self.transactions.insert(hash);
+        self.recorder.record(&WorkloadRecord::Transaction(WorkloadTransaction {
+            elapsed: self.recorder.elapsed(),
+            origin: encode_origin(tx.origin),
+            transaction,
+        }))
+    }
+
+    /// Returns the canonical update of the new canonical chain.
+    fn canonical_update(
+        &self,
+        elapsed: u64,
+        notification: &CanonStateNotification,
+    ) -> WorkloadCanonicalUpdate {
+        let chain = notification.committed();
+        let (blocks, state) = chain.inner();
+        let tip = blocks.tip();
+
+        WorkloadCanonicalUpdate {
+            elapsed,
+            number: tip.number,
+            hash: tip.hash(),
+            timestamp: tip.timestamp,
+            gas_limit: tip.gas_limit,
+            pending_basefee: pending_block_base_fee(&tip.header, &*self.client.chain_spec()),
+            pending_blob_fee: tip.next_block_blob_fee().unwrap_or_default(),
+            mined_transactions: blocks.transaction_hashes().collect(),
+            changed_accounts: state
+                .changed_accounts()
+                .filter(|account| self.senders.contains(&account.address))
+                .map(|account| WorkloadAccount {
+                    address: account.address,
+                    nonce: account.nonce,
+                    balance: account.balance,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn workload_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workload.bin");
+
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let transaction = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let hash = *transaction.hash();
+        let records = vec![
+            WorkloadRecord::Account(WorkloadAccount {
+                address: Address::with_last_byte(1),
+                nonce: 3,
+                balance: U256::from(1_000),
+            }),
+            WorkloadRecord::Transaction(WorkloadTransaction {
+                elapsed: 1,
+                origin: encode_origin(TransactionOrigin::External),
+                transaction,
+            }),
+            WorkloadRecord::Replacement(WorkloadReplacement {
+                elapsed: 2,
+                hash,
+                replaced_by: B256::with_last_byte(2),
+            }),
+            WorkloadRecord::CanonicalUpdate(WorkloadCanonicalUpdate {
+                elapsed: 3,
+                number: 10,
+                hash: B256::with_last_byte(10),
+                timestamp: 1_700_000_000,
+                gas_limit: 30_000_000,
+                pending_basefee: 7,
+                pending_blob_fee: 0,
+                mined_transactions: vec![B256::with_last_byte(2)],
+                changed_accounts: vec![],
+            }),
+        ];
+
+        let mut recorder = WorkloadRecorder::create(&path, 40).unwrap();
+        for record in &records {
+            recorder.record(record).unwrap();
+        }
+        recorder.flush().unwrap();
+        drop(recorder);
+
+        let workload = Workload::load(&path).unwrap();
+        assert_eq!(workload, Workload { chain_id: 40, records: records.clone() });
+
+        // a torn record at the end is discarded
+        let mut data = std::fs::read(&path).unwrap();
+        data.extend_from_slice(&[16, 0, 0, 0, 1, 2]);
+        std::fs::write(&path, data).unwrap();
+        assert_eq!(Workload::load(&path).unwrap().records, records);
+    }
+
+    #[test]
+    fn invalid_workload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workload.bin");
+
+        std::fs::write(&path, b"RTXJ\x01").unwrap();
+        assert!(matches!(Workload::load(&path), Err(WorkloadError::InvalidHeader)));
+
+        std::fs::write(&path, b"RTXW\x02").unwrap();
+        assert!(matches!(Workload::load(&path), Err(WorkloadError::UnsupportedVersion(2))));
+
+        std::fs::write(&path, b"RTXW\x01\x28").unwrap();
+        assert!(matches!(Workload::load(&path), Err(WorkloadError::InvalidHeader)));
+    }
+}
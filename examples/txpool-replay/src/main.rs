@@ -0,0 +1,237 @@
+//! Replays a transaction pool workload recorded with `--txpool.record-workload`.
+//!
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --release -p example-txpool-replay -- workload.bin --txpool.pending-max-count 20000
+//! ```
+//!
+//! The workload drives a [`Pool`] with the Ethereum validator against a mocked state provider that
+//! is kept up to date with the recorded sender states. The pool is configured with the same
+//! `--txpool.*` arguments as the node. Records are replayed back to back, ignoring the recorded
+//! timing, and the throughput, latency percentiles and memory usage are reported.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{Chain, ChainSpecBuilder};
+use reth_execution_types::ChangedAccount;
+use reth_node_core::{args::TxPoolArgs, cli::config::RethTransactionPoolConfig};
+use reth_primitives::{Header, SealedBlock, SealedHeader};
+use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+use reth_tasks::TokioTaskExecutor;
+use reth_tracing::{
+    tracing::info, tracing_subscriber::filter::LevelFilter, LayerInfo, LogFormat, RethTracer,
+    Tracer,
+};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    validate::EthTransactionValidatorBuilder,
+    workload::{Workload, WorkloadAccount, WorkloadRecord},
+    CanonicalStateUpdate, EthPooledTransaction, Pool, PoolConfig, PoolTransaction, TransactionPool,
+    TransactionPoolExt,
+};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let args = ReplayArgs::parse();
+
+    let _guard = RethTracer::new()
+        .with_stdout(LayerInfo::new(
+            LogFormat::Terminal,
+            LevelFilter::INFO.to_string(),
+            "".to_string(),
+            Some("always".to_string()),
+        ))
+        .init()?;
+
+    let workload = Workload::load(&args.workload)
+        .wrap_err_with(|| format!("failed to load workload {}", args.workload.display()))?;
+    info!(records = workload.records.len(), chain_id = workload.chain_id, "Replaying workload");
+
+    replay(workload, args.txpool.pool_config()).await.report();
+    Ok(())
+}
+
+/// Arguments of the replay.
+#[derive(Debug, Parser)]
+struct ReplayArgs {
+    /// Path to the workload recorded with `--txpool.record-workload`.
+    #[arg(value_name = "FILE")]
+    workload: PathBuf,
+
+    /// Configuration of the replayed pool.
+    #[command(flatten)]
+    txpool: TxPoolArgs,
+}
+
+/// Replays the workload and returns the collected stats.
+async fn replay(workload: Workload, config: PoolConfig) -> ReplayStats {
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::mainnet()
+            .chain(Chain::from_id(workload.chain_id))
+            .cancun_activated()
+            .build(),
+    );
+    let provider = MockEthProvider { chain_spec: chain_spec.clone(), ..Default::default() };
+    let blob_store = InMemoryBlobStore::default();
+    let validator = EthTransactionValidatorBuilder::new(chain_spec).build_with_tasks(
+        provider.clone(),
+        TokioTaskExecutor::default(),
+        blob_store.clone(),
+    );
+    let pool = Pool::eth_pool(validator, blob_store, config);
+
+    let mut stats = ReplayStats::default();
+    let start = Instant::now();
+    for record in workload.records {
+        match record {
+            WorkloadRecord::Account(account) => {
+                provider.add_account(
+                    account.address,
+                    ExtendedAccount::new(account.nonce, account.balance),
+                );
+            }
+            WorkloadRecord::Transaction(tx) => {
+                let Some(origin) = tx.origin() else { continue };
+                let Ok(transaction) = tx.transaction.try_into_ecrecovered() else {
+                    stats.rejected += 1;
+                    continue
+                };
+                let transaction = EthPooledTransaction::from_pooled(transaction);
+
+                let started = Instant::now();
+                let result = pool.add_transaction(origin, transaction).await;
+                stats.add_latencies.push(started.elapsed());
+                if result.is_ok() {
+                    stats.accepted += 1;
+                } else {
+                    stats.rejected += 1;
+                }
+            }
+            // replacements happen on their own when the replacement is added
+            WorkloadRecord::Replacement(_) => {}
+            WorkloadRecord::Removal(removal) => {
+                pool.remove_transactions(vec![removal.hash]);
+            }
+            WorkloadRecord::CanonicalUpdate(update) => {
+                provider.extend_accounts(update.changed_accounts.iter().map(|account| {
+                    (account.address, ExtendedAccount::new(account.nonce, account.balance))
+                }));
+                let header = Header {
+                    number: update.number,
+                    timestamp: update.timestamp,
+                    gas_limit: update.gas_limit,
+                    ..Default::default()
+                };
+                let tip =
+                    SealedBlock::new(SealedHeader::new(header, update.hash), Default::default());
+
+                let started = Instant::now();
+                pool.on_canonical_state_change(CanonicalStateUpdate {
+                    new_tip: &tip,
+                    pending_block_base_fee: update.pending_basefee,
+                    pending_block_blob_fee: (update.pending_blob_fee > 0)
+                        .then_some(update.pending_blob_fee),
+                    changed_accounts: update
+                        .changed_accounts
+                        .into_iter()
+                        .map(|WorkloadAccount { address, nonce, balance }| ChangedAccount {
+                            address,
+                            nonce,
+                            balance,
+                        })
+                        .collect(),
+                    mined_transactions: update.mined_transactions,
+                });
+                stats.update_latencies.push(started.elapsed());
+            }
+        }
+
+        let size = pool.pool_size();
+        stats.peak_transactions = stats.peak_transactions.max(size.total);
+        stats.peak_size = stats
+            .peak_size
+            .max(size.pending_size + size.basefee_size + size.queued_size + size.blob_size);
+    }
+    stats.elapsed = start.elapsed();
+    stats
+}
+
+/// Stats collected while replaying a workload.
+#[derive(Debug, Default)]
+struct ReplayStats {
+    /// Transactions accepted by the pool.
+    accepted: usize,
+    /// Transactions rejected by the pool.
+    rejected: usize,
+    /// Latencies of adding transactions.
+    add_latencies: Vec<Duration>,
+    /// Latencies of processing canonical updates.
+    update_latencies: Vec<Duration>,
+    /// Highest number of transactions in the pool.
+    peak_transactions: usize,
+    /// Highest size of all transactions in the pool in bytes.
+    peak_size: usize,
+    /// Total time of the replay.
+    elapsed: Duration,
+}
+
+impl ReplayStats {
+    /// Logs the collected stats.
+    fn report(mut self) {
+        let added = self.add_latencies.len();
+        let add_time = self.add_latencies.iter().sum::<Duration>();
+        info!(
+            transactions = added,
+            accepted = self.accepted,
+            rejected = self.rejected,
+            canonical_updates = self.update_latencies.len(),
+            elapsed = ?self.elapsed,
+            "Replayed workload"
+        );
+        if !add_time.is_zero() {
+            let throughput = (added as f64 / add_time.as_secs_f64()).round();
+            info!(throughput, "Transactions added per second");
+        }
+        log_latencies("add transaction", &mut self.add_latencies);
+        log_latencies("canonical update", &mut self.update_latencies);
+        info!(
+            transactions = self.peak_transactions,
+            bytes = self.peak_size,
+            resident_memory = peak_rss().as_deref().unwrap_or("unknown"),
+            "Peak pool size"
+        );
+    }
+}
+
+/// Logs the latency percentiles.
+fn log_latencies(name: &str, latencies: &mut [Duration]) {
+    if latencies.is_empty() {
+        return
+    }
+    latencies.sort_unstable();
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+    info!(
+        p50 = ?percentile(50),
+        p90 = ?percentile(90),
+        p99 = ?percentile(99),
+        max = ?percentile(100),
+        "{name} latency"
+    );
+}
+
+/// Returns the peak resident set size of the process, only available on Linux.
+fn peak_rss() -> Option<String> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .map(|value| value.trim().to_string())
+}